@@ -0,0 +1,215 @@
+//! `quickcheck::Arbitrary` implementations, behind the `arbitrary`
+//! feature. They only generate values that are written and parsed back
+//! unchanged, so that round-trip properties hold:
+//!
+//! ```
+//! use libsip::SipMessage;
+//! use quickcheck::{quickcheck, TestResult};
+//!
+//! fn round_trip(msg: SipMessage) -> TestResult {
+//!     TestResult::from_bool(SipMessage::parse(&msg.to_bytes()) == Ok(msg))
+//! }
+//! quickcheck(round_trip as fn(SipMessage) -> TestResult);
+//! ```
+
+use quickcheck::{Arbitrary, Gen};
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::{
+    headers::via::ViaHeader, Domain, GenericParams, Header, Headers, Method, NamedHeader,
+    SipMessage, Transport, Uri, UriAuth, UriParam, UriSchema, Version,
+};
+
+const TOKEN_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Status codes of every class, with and without a reason phrase.
+const STATUS_CODES: &[u32] = &[
+    100, 180, 183, 200, 202, 302, 401, 404, 486, 499, 500, 603, 699,
+];
+
+/// A lowercase token of 1 to 8 characters starting with a letter, so
+/// that it can't be taken for a number or an address.
+fn token(g: &mut Gen) -> String {
+    let len = 1 + usize::arbitrary(g) % 8;
+    let mut token = String::with_capacity(len);
+    token.push(*g.choose(&TOKEN_CHARS[..26]).unwrap() as char);
+    while token.len() < len {
+        token.push(*g.choose(TOKEN_CHARS).unwrap() as char);
+    }
+    token
+}
+
+/// A token with a prefix no known parameter or header name starts with.
+fn extension(g: &mut Gen) -> String {
+    format!("x-{}", token(g))
+}
+
+/// Words separated by single spaces, e.g. a User-Agent.
+fn text(g: &mut Gen) -> String {
+    let words: Vec<String> = (0..=usize::arbitrary(g) % 3).map(|_| token(g)).collect();
+    words.join(" ")
+}
+
+/// A list of 1 to 3 values.
+fn list<T, F: Fn(&mut Gen) -> T>(g: &mut Gen, value: F) -> Vec<T> {
+    (0..=usize::arbitrary(g) % 2).map(|_| value(g)).collect()
+}
+
+fn params(g: &mut Gen) -> GenericParams {
+    let len = usize::arbitrary(g) % 3;
+    (0..len)
+        .map(|_| (extension(g), Option::<()>::arbitrary(g).map(|_| token(g))))
+        .collect()
+}
+
+impl Arbitrary for Transport {
+    fn arbitrary(g: &mut Gen) -> Transport {
+        *g.choose(&[
+            Transport::Udp,
+            Transport::Tcp,
+            Transport::Tls,
+            Transport::Ws,
+            Transport::Wss,
+            Transport::Sctp,
+            Transport::TlsSctp,
+        ])
+        .unwrap()
+    }
+}
+
+impl Arbitrary for Method {
+    fn arbitrary(g: &mut Gen) -> Method {
+        if u8::arbitrary(g) % 8 == 0 {
+            Method::Extension(token(g).to_uppercase())
+        } else {
+            g.choose(&Method::all()).unwrap().clone()
+        }
+    }
+}
+
+impl Arbitrary for Domain {
+    fn arbitrary(g: &mut Gen) -> Domain {
+        let port = Option::<u16>::arbitrary(g);
+        match u8::arbitrary(g) % 3 {
+            0 => Domain::Ipv4(Ipv4Addr::from(u32::arbitrary(g)), port),
+            1 => Domain::Ipv6(Ipv6Addr::from(u128::arbitrary(g)), port),
+            _ => {
+                let labels: Vec<String> = list(g, token);
+                Domain::Domain(format!("{}.com", labels.join(".")), port)
+            },
+        }
+    }
+}
+
+impl Arbitrary for Uri {
+    fn arbitrary(g: &mut Gen) -> Uri {
+        let schema = if bool::arbitrary(g) {
+            UriSchema::Sip
+        } else {
+            UriSchema::Sips
+        };
+        let mut uri = Uri::new(schema, Domain::arbitrary(g));
+        if bool::arbitrary(g) {
+            uri = uri.auth(UriAuth::new(token(g)));
+        }
+        for _ in 0..usize::arbitrary(g) % 3 {
+            let param = match u8::arbitrary(g) % 6 {
+                0 => UriParam::Transport(Transport::arbitrary(g)),
+                1 => UriParam::Lr,
+                2 => UriParam::User(token(g)),
+                3 => UriParam::Ttl(u8::arbitrary(g)),
+                4 => UriParam::Maddr(Domain::new(Domain::arbitrary(g).host(), None)),
+                _ => UriParam::Other(extension(g), Option::<()>::arbitrary(g).map(|_| token(g))),
+            };
+            uri = uri.parameter(param);
+        }
+        uri
+    }
+}
+
+impl Arbitrary for NamedHeader {
+    fn arbitrary(g: &mut Gen) -> NamedHeader {
+        let mut header = NamedHeader::new(Uri::arbitrary(g));
+        if bool::arbitrary(g) {
+            header.display_name = Some(text(g));
+        }
+        header.parameters = params(g);
+        header
+    }
+}
+
+impl Arbitrary for ViaHeader {
+    fn arbitrary(g: &mut Gen) -> ViaHeader {
+        let mut via = ViaHeader::new(Domain::arbitrary(g), Transport::arbitrary(g));
+        via.parameters
+            .push("branch", Some(format!("z9hG4bK{}", token(g))));
+        for (name, value) in params(g) {
+            via.parameters.push(name, value);
+        }
+        via
+    }
+}
+
+impl Arbitrary for Header {
+    fn arbitrary(g: &mut Gen) -> Header {
+        match u8::arbitrary(g) % 20 {
+            0 => Header::To(NamedHeader::arbitrary(g)),
+            1 => Header::From(NamedHeader::arbitrary(g)),
+            2 => Header::Contact(list(g, NamedHeader::arbitrary)),
+            3 => Header::CallId(format!("{}@{}", token(g), token(g))),
+            4 => Header::CSeq(u32::arbitrary(g), Method::arbitrary(g)),
+            5 => Header::MaxForwards(u32::arbitrary(g)),
+            6 => Header::Expires(u32::arbitrary(g)),
+            7 => Header::MinExpires(u32::arbitrary(g)),
+            8 => Header::Via(list(g, ViaHeader::arbitrary)),
+            9 => Header::Route(list(g, NamedHeader::arbitrary)),
+            10 => Header::RecordRoute(list(g, NamedHeader::arbitrary)),
+            11 => Header::Allow(list(g, Method::arbitrary)),
+            12 => Header::Supported(list(g, token)),
+            13 => Header::Require(list(g, token)),
+            14 => Header::UserAgent(text(g)),
+            15 => Header::Server(text(g)),
+            16 => Header::Subject(text(g)),
+            17 => Header::Organization(text(g)),
+            18 => Header::Timestamp(u32::arbitrary(g)),
+            _ => Header::Other(extension(g), text(g)),
+        }
+    }
+}
+
+impl Arbitrary for Headers {
+    fn arbitrary(g: &mut Gen) -> Headers {
+        Headers(
+            (0..usize::arbitrary(g) % 8)
+                .map(|_| Header::arbitrary(g))
+                .collect(),
+        )
+    }
+}
+
+/// Messages have a Content-Length matching their body, written after
+/// the other headers.
+impl Arbitrary for SipMessage {
+    fn arbitrary(g: &mut Gen) -> SipMessage {
+        let mut headers = Headers::arbitrary(g);
+        let body = Vec::<u8>::arbitrary(g);
+        headers.push(Header::ContentLength(body.len() as u32));
+        if bool::arbitrary(g) {
+            SipMessage::Request {
+                method: Method::arbitrary(g),
+                uri: Uri::arbitrary(g),
+                version: Version::default(),
+                headers,
+                body,
+            }
+        } else {
+            SipMessage::Response {
+                code: *g.choose(STATUS_CODES).unwrap(),
+                version: Version::default(),
+                headers,
+                body,
+            }
+        }
+    }
+}
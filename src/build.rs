@@ -0,0 +1,89 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{Error as IoError, ErrorKind as IoErrorKind},
+};
+
+use crate::headers::{HeaderKind, Headers};
+
+/// Headers that every request must contain.
+/// ([RFC3261: Section 8.1.1, Generating the Request](https://tools.ietf.org/html/rfc3261#section-8.1.1))
+pub(crate) const REQUEST_HEADERS: [HeaderKind; 6] = [
+    HeaderKind::Via,
+    HeaderKind::MaxForwards,
+    HeaderKind::From,
+    HeaderKind::To,
+    HeaderKind::CallId,
+    HeaderKind::CSeq,
+];
+
+/// Headers that every response must contain.
+/// ([RFC3261: Section 8.2.6.2, Headers and Tags](https://tools.ietf.org/html/rfc3261#section-8.2.6.2))
+pub(crate) const RESPONSE_HEADERS: [HeaderKind; 5] = [
+    HeaderKind::Via,
+    HeaderKind::From,
+    HeaderKind::To,
+    HeaderKind::CallId,
+    HeaderKind::CSeq,
+];
+
+/// Error returned by the `build` method of `RequestGenerator` and
+/// `ResponseGenerator` when the message would be incomplete.
+#[derive(Debug, PartialEq, Clone)]
+pub enum BuildError {
+    /// The `method` of a request wasn't set.
+    MissingMethod,
+    /// The `uri` of a request wasn't set.
+    MissingUri,
+    /// The `code` of a response wasn't set.
+    MissingCode,
+    /// Mandatory headers are missing, listed in the order they are
+    /// usually written.
+    MissingHeaders(Vec<HeaderKind>),
+}
+
+impl BuildError {
+    /// Check that `headers` contain every one of `kinds`.
+    pub(crate) fn check_headers(headers: &Headers, kinds: &[HeaderKind]) -> Result<(), BuildError> {
+        let missing: Vec<HeaderKind> = kinds
+            .iter()
+            .filter(|kind| headers.get((*kind).clone()).is_none())
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(BuildError::MissingHeaders(missing))
+        }
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::MissingMethod => write!(f, "`method` method call required"),
+            BuildError::MissingUri => write!(f, "`uri` method call required"),
+            BuildError::MissingCode => {
+                write!(f, "ResponseGenerator requires `code` method be called.")
+            },
+            BuildError::MissingHeaders(kinds) => {
+                write!(f, "missing mandatory headers: ")?;
+                for (index, kind) in kinds.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", kind)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+impl From<BuildError> for IoError {
+    fn from(error: BuildError) -> IoError {
+        IoError::new(IoErrorKind::InvalidInput, error)
+    }
+}
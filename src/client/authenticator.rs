@@ -0,0 +1,159 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+};
+
+use crate::{
+    headers::{ChallengeOutcome, DigestCredentials},
+    *,
+};
+
+/// Resends requests challenged by a 401 (Unauthorized) or 407 (Proxy
+/// Authentication Required) response with the credentials they ask for.
+/// ([RFC3261: Section 22.3, Proxy-to-User Authentication](https://tools.ietf.org/html/rfc3261#section-22.3))
+///
+/// A response can carry challenges of several realms, e.g. when a
+/// forking proxy aggregates the responses of its branches, every one of
+/// them is answered. The credentials of a realm are kept between requests
+/// so that its nonce count keeps increasing.
+/// # Examples
+///
+/// ```
+/// use libsip::{Authenticator, SipMessage};
+///
+/// let request = SipMessage::parse(
+///     b"INVITE sip:bob@biloxi.com SIP/2.0\r\n\
+///     Via: SIP/2.0/UDP pc33.atlanta.com;branch=z9hG4bK776asdhds\r\n\
+///     CSeq: 1 INVITE\r\n\r\n",
+/// )
+/// .unwrap();
+/// let response = SipMessage::parse(
+///     b"SIP/2.0 407 Proxy Authentication Required\r\n\
+///     Via: SIP/2.0/UDP pc33.atlanta.com;branch=z9hG4bK776asdhds\r\n\
+///     CSeq: 1 INVITE\r\n\
+///     Proxy-Authenticate: Digest realm=\"atlanta.com\", nonce=\"abc\", qop=\"auth\"\r\n\r\n",
+/// )
+/// .unwrap();
+///
+/// let mut authenticator = Authenticator::new().credentials("alice", "secret");
+/// let resend = authenticator.resend(&request, &response).unwrap();
+/// assert_eq!(Some(2), resend.cseq().map(|(seq, _)| seq));
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Authenticator {
+    default: Option<(String, String)>,
+    realms: HashMap<String, (String, String)>,
+    credentials: HashMap<String, DigestCredentials>,
+}
+
+impl Authenticator {
+    /// Create an authenticator without any credentials.
+    pub fn new() -> Authenticator {
+        Authenticator::default()
+    }
+
+    /// Answer the challenges of realms without their own credentials as
+    /// `user` with the password `pass`.
+    pub fn credentials<U: Into<String>, P: Into<String>>(mut self, user: U, pass: P) -> Self {
+        self.default = Some((user.into(), pass.into()));
+        self
+    }
+
+    /// Answer the challenges of `realm` as `user` with the password `pass`.
+    pub fn realm_credentials<R, U, P>(mut self, realm: R, user: U, pass: P) -> Self
+    where
+        R: Into<String>,
+        U: Into<String>,
+        P: Into<String>,
+    {
+        self.realms.insert(realm.into(), (user.into(), pass.into()));
+        self
+    }
+
+    /// Generate the request resending `request` after it was challenged
+    /// by `response`. The resend has the next CSeq, a new branch and
+    /// an Authorization or Proxy-Authorization header for every challenge,
+    /// replacing the credentials previously sent for the same realms.
+    ///
+    /// Fails if `response` has no challenge, if a realm has no credentials
+    /// or if `request` already carried credentials for a challenged realm,
+    /// meaning they were rejected.
+    pub fn resend(&mut self, request: &SipMessage, response: &SipMessage) -> IoResult<SipMessage> {
+        let (method, uri) = match (request.method(), request.uri()) {
+            (Some(method), Some(uri)) => (method, uri),
+            _ => return Err(invalid("the challenged message is not a request")),
+        };
+        if response.headers().challenges().is_empty() {
+            return Err(invalid("the response doesn't contain a challenge"));
+        }
+
+        let mut answers = vec![];
+        for header in response.headers().iter() {
+            let (challenge, proxy) = match header {
+                Header::WwwAuthenticate(challenge) => (challenge, false),
+                Header::ProxyAuthenticate(challenge) => (challenge, true),
+                _ => continue,
+            };
+            let realm = challenge
+                .realm()
+                .ok_or_else(|| invalid("challenge doesn't contain a realm"))?;
+            let credentials = match self.credentials.entry(realm.to_string()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let (user, pass) = self
+                        .realms
+                        .get(realm)
+                        .or(self.default.as_ref())
+                        .ok_or_else(|| {
+                            IoError::new(
+                                IoErrorKind::PermissionDenied,
+                                format!("no credentials for realm {}", realm),
+                            )
+                        })?;
+                    entry.insert(DigestCredentials::new(user.clone(), pass.clone()))
+                },
+            };
+            let sent = request.headers().iter().find_map(|header| match header {
+                Header::Authorization(auth) if !proxy && auth.realm() == Some(realm) => Some(auth),
+                Header::ProxyAuthorization(auth) if proxy && auth.realm() == Some(realm) => {
+                    Some(auth)
+                },
+                _ => None,
+            });
+            if credentials.on_challenge(challenge, sent) == ChallengeOutcome::Rejected {
+                return Err(IoError::new(
+                    IoErrorKind::PermissionDenied,
+                    format!("credentials rejected for realm {}", realm),
+                ));
+            }
+            let auth = credentials.authorize(method, uri, request.body())?;
+            answers.push((realm.to_string(), proxy, auth));
+        }
+
+        let mut resend = request.clone();
+        resend.headers_mut().0.retain(|header| match header {
+            Header::Authorization(auth) | Header::ProxyAuthorization(auth) => !answers
+                .iter()
+                .any(|(realm, _, _)| auth.realm() == Some(realm.as_str())),
+            _ => true,
+        });
+        for (_, proxy, auth) in answers {
+            resend.headers_mut().push(if proxy {
+                Header::ProxyAuthorization(auth)
+            } else {
+                Header::Authorization(auth)
+            });
+        }
+        if let Some((seq, method)) = request.cseq() {
+            resend.set_header(Header::CSeq(seq + 1, method.clone()));
+        }
+        if let Some(via) = resend.top_via_mut() {
+            via.set_param("branch", Some(generate_branch()));
+        }
+        Ok(resend)
+    }
+}
+
+fn invalid(message: &str) -> IoError {
+    IoError::new(IoErrorKind::InvalidInput, message)
+}
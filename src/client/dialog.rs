@@ -0,0 +1,321 @@
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
+
+use crate::{client::refer_to, headers::ReplacesHeader, transaction::DialogId, *};
+
+/// Whether a dialog was created by a provisional or a final response.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DialogState {
+    /// Created by a provisional response with a To tag.
+    Early,
+    /// Created or confirmed by a 2xx response.
+    Confirmed,
+}
+
+/// State of a dialog established by an INVITE, used to generate
+/// requests within it. ([RFC3261: Section 12, Dialogs](https://tools.ietf.org/html/rfc3261#section-12))
+#[derive(Debug, PartialEq, Clone)]
+pub struct Dialog {
+    pub state: DialogState,
+    pub call_id: String,
+    /// The local party, with the local tag, used as the From header.
+    pub local: NamedHeader,
+    /// The remote party, with the remote tag, used as the To header.
+    pub remote: NamedHeader,
+    /// The CSeq number of the last request sent in this dialog.
+    pub local_seq: u32,
+    /// The CSeq number of the last request received in this dialog.
+    pub remote_seq: Option<u32>,
+    /// The Contact of the remote party, where requests are sent.
+    pub remote_target: Uri,
+    /// The proxies requests have to be routed through.
+    pub route_set: Vec<NamedHeader>,
+    /// The Via of the local party, sent with a new branch in every request.
+    /// Requests can't be built without it, as Via is mandatory.
+    pub via: Option<ViaHeader>,
+}
+
+impl Dialog {
+    /// Create the dialog of the UAC that sent `invite` from a 2xx (or 1xx
+    /// with a To tag) `response`.
+    /// ([RFC3261: Section 12.1.2, UAC Behavior](https://tools.ietf.org/html/rfc3261#section-12.1.2))
+    pub fn from_uac(invite: &SipMessage, response: &SipMessage) -> IoResult<Dialog> {
+        let seq = cseq(invite)?;
+        Ok(Dialog {
+            state: state(response),
+            call_id: call_id(invite)?,
+            local: required(invite.from(), "From")?.clone(),
+            remote: required(response.to(), "To")?.clone(),
+            local_seq: seq,
+            remote_seq: None,
+            remote_target: contact(response)?,
+            route_set: uac_route_set(response),
+            via: invite.top_via().cloned(),
+        })
+    }
+
+    /// Create the dialog of the UAS that received `invite` and sent the
+    /// `response`, whose To header has the local tag. The local CSeq
+    /// number starts at zero. The Via of the requests sent within the
+    /// dialog is made from `via`, the address of the UAS, as the Via
+    /// headers of `invite` belong to the remote party and its proxies.
+    /// ([RFC3261: Section 12.1.1, UAS behavior](https://tools.ietf.org/html/rfc3261#section-12.1.1))
+    pub fn from_uas(
+        invite: &SipMessage,
+        response: &SipMessage,
+        via: ViaHeader,
+    ) -> IoResult<Dialog> {
+        let seq = cseq(invite)?;
+        Ok(Dialog {
+            state: state(response),
+            call_id: call_id(invite)?,
+            local: required(response.to(), "To")?.clone(),
+            remote: required(invite.from(), "From")?.clone(),
+            local_seq: 0,
+            remote_seq: Some(seq),
+            remote_target: contact(invite)?,
+            route_set: uas_route_set(invite),
+            via: Some(via),
+        })
+    }
+
+    /// Update the dialog of the UAC with a later `response` to the INVITE,
+    /// e.g. the 2xx confirming an early dialog. A 2xx replaces the route
+    /// set and every response with a Contact the remote target.
+    /// ([RFC3261: Section 12.2.1.2, Processing the Responses](https://tools.ietf.org/html/rfc3261#section-12.2.1.2))
+    pub fn on_response(&mut self, response: &SipMessage) {
+        if let Ok(target) = contact(response) {
+            self.remote_target = target;
+        }
+        if self.state == DialogState::Early && state(response) == DialogState::Confirmed {
+            self.state = DialogState::Confirmed;
+            self.route_set = uac_route_set(response);
+        }
+    }
+
+    /// The ID of this dialog, see `DialogId`. It is `None` until both
+    /// tags are known.
+    pub fn id(&self) -> Option<DialogId> {
+        Some(DialogId {
+            call_id: self.call_id.clone(),
+            local_tag: self.local.tag()?.to_string(),
+            remote_tag: self.remote.tag()?.to_string(),
+        })
+    }
+
+    /// Determine if `request` was sent within this dialog: its Call-ID,
+    /// From tag and To tag match the Call-ID, remote tag and local tag.
+    pub fn matches(&self, request: &SipMessage) -> bool {
+        request.call_id() == Some(&self.call_id)
+            && request.from().and_then(|from| from.tag()) == self.remote.tag()
+            && request.to().and_then(|to| to.tag()) == self.local.tag()
+    }
+
+    /// Handle a `request` received within this dialog, see `matches`.
+    /// Returns false if its CSeq number is lower than the previous one,
+    /// the request being out of order and to be rejected with a 500.
+    /// Otherwise the remote CSeq number is updated, as well as the remote
+    /// target when a target refresh request has a Contact.
+    /// ([RFC3261: Section 12.2.2, UAS Behavior](https://tools.ietf.org/html/rfc3261#section-12.2.2))
+    pub fn on_request(&mut self, request: &SipMessage) -> bool {
+        let seq = match request.cseq() {
+            Some((seq, _)) => seq,
+            None => return false,
+        };
+        if matches!(self.remote_seq, Some(remote_seq) if seq < remote_seq) {
+            return false;
+        }
+        if request.method() != Some(&Method::Ack) {
+            self.remote_seq = Some(seq);
+        }
+        if request.method() == Some(&Method::Invite) || request.method() == Some(&Method::Update) {
+            if let Ok(target) = contact(request) {
+                self.remote_target = target;
+            }
+        }
+        true
+    }
+
+    /// Set the Via of the local party, see `via`.
+    pub fn via(mut self, via: ViaHeader) -> Self {
+        self.via = Some(via);
+        self
+    }
+
+    /// The Request-URI, Route headers and next hop of the requests within
+    /// this dialog, see `route_target`.
+    pub fn route_target(&self) -> RouteTarget {
+        route_target(&self.route_set, &self.remote_target)
+    }
+
+    /// Create a generator for a request within this dialog, incrementing
+    /// the local CSeq number. The Request-URI and Route headers are set
+    /// from the remote target and route set, see `route_target`. When the
+    /// dialog has no `via` the generator has no Via header and building
+    /// it fails with a missing Via header error.
+    /// ([RFC3261: Section 12.2.1.1, Generating the Request](https://tools.ietf.org/html/rfc3261#section-12.2.1.1))
+    pub fn new_request(&mut self, method: Method) -> RequestGenerator {
+        self.local_seq += 1;
+        let RouteTarget { uri, routes, .. } = self.route_target();
+
+        let mut generator = RequestGenerator::new().method(method.clone()).uri(uri);
+        if let Some(via) = &self.via {
+            generator = generator.header(Header::Via(vec![ViaHeader::new(
+                via.host.clone(),
+                via.transport,
+            )
+            .branch_param(generate_branch())]));
+        }
+        generator = generator
+            .header(Header::MaxForwards(70))
+            .header(Header::From(self.local.clone()))
+            .header(Header::To(self.remote.clone()))
+            .header(Header::CallId(self.call_id.clone()))
+            .header(Header::CSeq(self.local_seq, method));
+        if !routes.is_empty() {
+            generator = generator.header(Header::Route(routes));
+        }
+        generator
+    }
+
+    /// Generate the BYE ending this dialog.
+    pub fn bye(&mut self, header_cfg: &HeaderWriteConfig) -> IoResult<SipMessage> {
+        let mut req = self.new_request(Method::Bye);
+        header_cfg.write_headers(req.headers_ref_mut());
+        Ok(req.header(Header::ContentLength(0)).build()?)
+    }
+
+    /// Generate a REFER asking the remote party to call `target`, see
+    /// `refer_to`. The local party is given as the Referred-By.
+    /// ([RFC3515: Section 2.4.1, Sending a REFER](https://tools.ietf.org/html/rfc3515#section-2.4.1))
+    pub fn refer(
+        &mut self,
+        target: Uri,
+        replaces: Option<&ReplacesHeader>,
+        header_cfg: &HeaderWriteConfig,
+    ) -> IoResult<SipMessage> {
+        let referred_by = NamedHeader::new(self.local.uri.clone());
+        let mut req = self
+            .new_request(Method::Refer)
+            .header(Header::ReferTo(refer_to(target, replaces)))
+            .header(Header::ReferredBy(referred_by));
+        header_cfg.write_headers(req.headers_ref_mut());
+        Ok(req.header(Header::ContentLength(0)).build()?)
+    }
+
+    /// The Replaces value identifying this dialog to the remote party,
+    /// for an attended transfer to it. `None` until both tags are known.
+    pub fn replaces(&self) -> Option<ReplacesHeader> {
+        Some(ReplacesHeader::new(
+            self.call_id.clone(),
+            self.remote.tag()?,
+            self.local.tag()?,
+        ))
+    }
+}
+
+/// Where a request within a dialog is sent, as determined by
+/// `route_target`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RouteTarget {
+    /// The Request-URI of the request.
+    pub uri: Uri,
+    /// The Route headers of the request, in order.
+    pub routes: Vec<NamedHeader>,
+    /// The URI the request is sent to, whose servers are located with
+    /// `resolve::resolve`.
+    pub next_hop: Uri,
+}
+
+/// The route set of the dialog of a UAC, from the Record-Route headers
+/// of the `response` creating it, in reverse order.
+/// ([RFC3261: Section 12.1.2, UAC Behavior](https://tools.ietf.org/html/rfc3261#section-12.1.2))
+pub fn uac_route_set(response: &SipMessage) -> Vec<NamedHeader> {
+    let mut route_set = response.headers().record_route_set();
+    route_set.reverse();
+    route_set
+}
+
+/// The route set of the dialog of a UAS, from the Record-Route headers
+/// of the `request` creating it, in order.
+/// ([RFC3261: Section 12.1.1, UAS behavior](https://tools.ietf.org/html/rfc3261#section-12.1.1))
+pub fn uas_route_set(request: &SipMessage) -> Vec<NamedHeader> {
+    request.headers().record_route_set()
+}
+
+/// Determine where a request to `remote_target` is sent through the
+/// proxies of `route_set`.
+/// ([RFC3261: Section 12.2.1.1, Generating the Request](https://tools.ietf.org/html/rfc3261#section-12.2.1.1))
+///
+/// - Without routes, the request is sent to the remote target.
+/// - When the first route has the `lr` parameter, the request is loose
+///   routed: the remote target is the Request-URI and the routes are
+///   the Route headers.
+/// - Otherwise the first route is a strict router, which expects to be
+///   the Request-URI. The remote target is added as the last Route.
+///
+/// Either way, the request is sent to the first route.
+/// # Examples
+///
+/// ```
+/// use libsip::{route_target, uri::parse_uri, NamedHeader};
+/// use nom::error::VerboseError;
+///
+/// let uri = |uri: &[u8]| parse_uri::<VerboseError<&[u8]>>(uri).unwrap().1;
+/// let route_set = vec![NamedHeader::new(uri(b"sip:p1.example.com"))];
+/// let target = route_target(&route_set, &uri(b"sip:bob@192.0.2.4"));
+/// assert_eq!(uri(b"sip:p1.example.com"), target.uri);
+/// assert_eq!(vec![NamedHeader::new(uri(b"sip:bob@192.0.2.4"))], target.routes);
+/// assert_eq!(uri(b"sip:p1.example.com"), target.next_hop);
+/// ```
+pub fn route_target(route_set: &[NamedHeader], remote_target: &Uri) -> RouteTarget {
+    let mut routes = route_set.to_vec();
+    let next_hop = match routes.first() {
+        Some(route) => route.uri.clone(),
+        None => remote_target.clone(),
+    };
+    let uri = if next_hop.is_loose_route() || routes.is_empty() {
+        remote_target.clone()
+    } else {
+        routes.push(NamedHeader::new(remote_target.clone()));
+        routes.remove(0).uri
+    };
+    RouteTarget {
+        uri,
+        routes,
+        next_hop,
+    }
+}
+
+fn required<T>(value: Option<T>, name: &str) -> IoResult<T> {
+    value.ok_or_else(|| {
+        IoError::new(
+            IoErrorKind::InvalidInput,
+            format!("message doesn't contain a {} header", name),
+        )
+    })
+}
+
+fn state(response: &SipMessage) -> DialogState {
+    match response.status_code() {
+        Some(code) if code < 200 => DialogState::Early,
+        _ => DialogState::Confirmed,
+    }
+}
+
+fn call_id(msg: &SipMessage) -> IoResult<String> {
+    required(msg.call_id().cloned(), "Call-ID")
+}
+
+fn cseq(msg: &SipMessage) -> IoResult<u32> {
+    required(msg.cseq(), "CSeq").map(|(seq, _)| seq)
+}
+
+fn contact(msg: &SipMessage) -> IoResult<Uri> {
+    required(
+        msg.headers().iter().find_map(|header| match header {
+            Header::Contact(contacts) => contacts.first().map(|contact| contact.uri.clone()),
+            _ => None,
+        }),
+        "Contact",
+    )
+}
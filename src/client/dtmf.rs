@@ -0,0 +1,81 @@
+use std::{
+    fmt,
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+};
+
+/// The `application/dtmf-relay` body of an INFO request, sending
+/// a key press in the signaling path when the media can't carry
+/// telephone-events (RFC4733).
+/// # Examples
+///
+/// ```
+/// use libsip::DtmfRelay;
+///
+/// let dtmf = DtmfRelay::new('5', 160).unwrap();
+/// assert_eq!("Signal=5\r\nDuration=160\r\n", dtmf.to_string());
+/// assert_eq!(dtmf, DtmfRelay::parse(b"Signal= 5\r\nDuration= 160\r\n").unwrap());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DtmfRelay {
+    /// The key pressed: a digit, `*`, `#` or `A` to `D`.
+    pub signal: char,
+    /// The duration of the key press in milliseconds.
+    pub duration: u32,
+}
+
+impl DtmfRelay {
+    /// Create the body sending `signal`, failing if it isn't a DTMF key.
+    pub fn new(signal: char, duration: u32) -> IoResult<DtmfRelay> {
+        let signal = signal.to_ascii_uppercase();
+        if signal.is_ascii_digit() || "*#ABCD".contains(signal) {
+            Ok(DtmfRelay { signal, duration })
+        } else {
+            Err(invalid(&format!("invalid DTMF signal: {}", signal)))
+        }
+    }
+
+    /// Parse a body made of `Signal=` and `Duration=` lines. The names are
+    /// case insensitive and other lines are ignored. The duration defaults
+    /// to 250ms when missing.
+    pub fn parse(body: &[u8]) -> IoResult<DtmfRelay> {
+        let body = std::str::from_utf8(body).map_err(|_| invalid("dtmf-relay body isn't utf8"))?;
+        let mut signal = None;
+        let mut duration = 250;
+        for line in body.lines() {
+            let mut parts = line.splitn(2, '=');
+            let (name, value) = match (parts.next(), parts.next()) {
+                (Some(name), Some(value)) => (name.trim(), value.trim()),
+                _ => continue,
+            };
+            if name.eq_ignore_ascii_case("Signal") {
+                let mut chars = value.chars();
+                signal = match (chars.next(), chars.next()) {
+                    (Some(signal), None) => Some(signal),
+                    _ => return Err(invalid(&format!("invalid DTMF signal: {}", value))),
+                };
+            } else if name.eq_ignore_ascii_case("Duration") {
+                duration = value
+                    .parse()
+                    .map_err(|_| invalid(&format!("invalid DTMF duration: {}", value)))?;
+            }
+        }
+        match signal {
+            Some(signal) => DtmfRelay::new(signal, duration),
+            None => Err(invalid("dtmf-relay body doesn't contain a Signal")),
+        }
+    }
+}
+
+impl fmt::Display for DtmfRelay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Signal={}\r\nDuration={}\r\n",
+            self.signal, self.duration
+        )
+    }
+}
+
+fn invalid(message: &str) -> IoError {
+    IoError::new(IoErrorKind::InvalidInput, message)
+}
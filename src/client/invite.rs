@@ -3,7 +3,7 @@ use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
 use crate::*;
 
 macro_rules! impl_simple_header_method {
-    ($name:ident, $variant:ident, $ty:ident) => {
+    ($name:ident, $variant:ident, $ty:ty) => {
         /// Retrieve value of the $variant header.
         pub fn $name(&self) -> IoResult<$ty> {
             if let Some(Header::$variant(header)) = self.headers.$name() {
@@ -38,7 +38,7 @@ impl InviteHelper {
 
     impl_simple_header_method!(call_id, CallId, String);
 
-    impl_simple_header_method!(via, Via, ViaHeader);
+    impl_simple_header_method!(via, Via, Vec<ViaHeader>);
 
     /// Create a InviteHelper from the given SipMessage.
     pub fn new(msg: SipMessage) -> IoResult<InviteHelper> {
@@ -67,29 +67,29 @@ impl InviteHelper {
     pub fn ringing(&self, header_cfg: &HeaderWriteConfig) -> IoResult<SipMessage> {
         let mut req = ResponseGenerator::new()
             .code(180)
-            .header(self.headers.from().unwrap())
-            .header(self.headers.to().unwrap())
-            .header(self.headers.call_id().unwrap())
-            .header(self.headers.cseq().unwrap())
-            .header(self.headers.via().unwrap())
+            .header_opt(self.headers.from())
+            .header_opt(self.headers.to())
+            .header_opt(self.headers.call_id())
+            .header_opt(self.headers.cseq())
+            .header_opt(self.headers.via())
             .header(Header::ContentLength(0));
         header_cfg.write_headers(req.headers_ref_mut());
-        req.build()
+        Ok(req.build()?)
     }
 
     /// Generate a response that will accept the invite with the sdp as the body.
     pub fn accept(&self, sdp: Vec<u8>, header_cfg: &HeaderWriteConfig) -> IoResult<SipMessage> {
         let mut req = ResponseGenerator::new()
             .code(200)
-            .header(self.headers.cseq().unwrap())
-            .header(self.headers.via().unwrap())
-            .header(self.headers.to().unwrap())
-            .header(self.headers.from().unwrap())
-            .header(self.headers.call_id().unwrap())
+            .header_opt(self.headers.cseq())
+            .header_opt(self.headers.via())
+            .header_opt(self.headers.to())
+            .header_opt(self.headers.from())
+            .header_opt(self.headers.call_id())
             .header(Header::ContentLength(sdp.len() as u32))
             .body(sdp);
         header_cfg.write_headers(req.headers_ref_mut());
-        req.build()
+        Ok(req.build()?)
     }
 
     /// Generate a Bye response for this Invite Request.
@@ -97,13 +97,14 @@ impl InviteHelper {
         let mut req = RequestGenerator::new()
             .method(Method::Bye)
             .uri(self.uri.clone())
-            .header(self.headers.cseq().unwrap())
-            .header(self.headers.via().unwrap())
-            .header(self.headers.to().unwrap())
-            .header(self.headers.from().unwrap())
-            .header(self.headers.call_id().unwrap());
+            .header(Header::MaxForwards(70))
+            .header_opt(self.headers.cseq())
+            .header_opt(self.headers.via())
+            .header_opt(self.headers.to())
+            .header_opt(self.headers.from())
+            .header_opt(self.headers.call_id());
         header_cfg.write_headers(req.headers_ref_mut());
-        req.build()
+        Ok(req.build()?)
     }
 
     /// Verify the CSeq header is equal to `cseq`.
@@ -123,7 +124,7 @@ impl InviteHelper {
         let mut out_headers = vec![];
         for header in self.headers.iter() {
             match header {
-                Header::CSeq(a, b) => out_headers.push(Header::CSeq(*a, *b)),
+                Header::CSeq(a, b) => out_headers.push(Header::CSeq(*a, b.clone())),
                 Header::CallId(call) => out_headers.push(Header::CallId(call.clone())),
                 Header::From(from) => out_headers.push(Header::From(from.clone())),
                 Header::To(to) => out_headers.push(Header::To(to.clone())),
@@ -164,19 +165,21 @@ impl InviteWriter {
         InviteWriter { cseq: 0, uri }
     }
 
-    /// Generate a Invite Request.
+    /// Generate a Invite Request. The request has no Via header, which
+    /// has to be added before it is sent.
     pub fn generate_invite(&mut self, uri: Uri, sdp: Vec<u8>) -> IoResult<SipMessage> {
         self.cseq += 1;
         let me_uri = self.uri.clone();
-        RequestGenerator::new()
+        Ok(RequestGenerator::new()
             .method(Method::Invite)
             .uri(uri.clone())
+            .header(Header::MaxForwards(70))
             .header(self.cseq()?)
             .header(Header::From(__named_header!(me_uri)))
             .header(Header::To(__named_header!(uri)))
             .header(Header::CallId(InviteWriter::generate_call_id()))
             .body(sdp)
-            .build()
+            .build_unchecked()?)
     }
 
     /// Generate a CSeq header.
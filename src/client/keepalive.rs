@@ -0,0 +1,145 @@
+use rand::Rng;
+
+use std::time::{Duration, Instant};
+
+use crate::{core::ParsedPacket, Transport};
+
+/// The time a pong or STUN response has to be received in before the
+/// flow is considered failed.
+const PONG_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The magic cookie of STUN messages.
+/// ([RFC5389: Section 6, STUN Message Structure](https://tools.ietf.org/html/rfc5389#section-6))
+const STUN_MAGIC_COOKIE: [u8; 4] = [0x21, 0x12, 0xa4, 0x42];
+
+/// Schedules the keep-alives maintaining an outbound flow, the
+/// connection or NAT binding over which a client registered so that it
+/// receives its inbound requests.
+/// ([RFC5626: Section 4.4.1, Keep-Alive with CRLF](https://tools.ietf.org/html/rfc5626#section-4.4.1)
+/// and [Section 4.4.2, Keep-Alive with STUN](https://tools.ietf.org/html/rfc5626#section-4.4.2))
+///
+/// Keep-alives are a CRLF ping (`\r\n\r\n`) answered by a CRLF pong on
+/// stream transports and a STUN binding request on UDP. They are sent
+/// at a random interval between 80% and 100% of the Flow-Timer given
+/// by the registrar, or else between 95 and 120 seconds on stream
+/// transports and 24 and 29 seconds on UDP. A flow whose keep-alive
+/// isn't answered within 10 seconds failed, and the client has to
+/// register again over a new flow.
+/// # Examples
+///
+/// ```
+/// use libsip::{core::ParsedPacket, FlowKeepAlive, Transport};
+/// use std::time::{Duration, Instant};
+///
+/// let now = Instant::now();
+/// let mut keep_alive = FlowKeepAlive::new(Transport::Tcp).flow_timer(30);
+/// keep_alive.start(now);
+/// assert_eq!(None, keep_alive.poll(now));
+///
+/// let later = now + Duration::from_secs(30);
+/// assert_eq!(Some(b"\r\n\r\n".to_vec()), keep_alive.poll(later));
+/// assert!(keep_alive.on_packet(&ParsedPacket::KeepAlive));
+/// assert!(!keep_alive.is_failed(later + Duration::from_secs(10)));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct FlowKeepAlive {
+    transport: Transport,
+    flow_timer: Option<u32>,
+    next_at: Option<Instant>,
+    /// When the unanswered keep-alive was sent, with its STUN
+    /// transaction id on UDP.
+    pending: Option<(Instant, Option<[u8; 12]>)>,
+}
+
+impl FlowKeepAlive {
+    /// Create the keep-alive schedule of a flow over `transport`.
+    pub fn new(transport: Transport) -> FlowKeepAlive {
+        FlowKeepAlive {
+            transport,
+            flow_timer: None,
+            next_at: None,
+            pending: None,
+        }
+    }
+
+    /// Set the seconds between keep-alives expected by the registrar,
+    /// from the Flow-Timer header of its 2xx response.
+    pub fn flow_timer(mut self, seconds: u32) -> Self {
+        self.flow_timer = Some(seconds);
+        self
+    }
+
+    /// Start sending keep-alives, the first one after an interval from `now`.
+    pub fn start(&mut self, now: Instant) {
+        self.pending = None;
+        self.next_at = Some(now + self.interval());
+    }
+
+    /// Stop sending keep-alives, e.g. after the flow is unregistered.
+    pub fn stop(&mut self) {
+        self.pending = None;
+        self.next_at = None;
+    }
+
+    /// A random interval until the next keep-alive.
+    pub fn interval(&self) -> Duration {
+        let (min, max) = match self.flow_timer {
+            Some(seconds) => (u64::from(seconds) * 800, u64::from(seconds) * 1000),
+            None if self.transport == Transport::Udp => (24_000, 29_000),
+            None => (95_000, 120_000),
+        };
+        Duration::from_millis(rand::thread_rng().gen_range(min, max + 1))
+    }
+
+    /// The time at which the next keep-alive has to be sent.
+    pub fn next_at(&self) -> Option<Instant> {
+        self.next_at
+    }
+
+    /// Return the keep-alive to send on the flow if it is due at `now`,
+    /// scheduling the next one.
+    pub fn poll(&mut self, now: Instant) -> Option<Vec<u8>> {
+        if self.next_at? > now {
+            return None;
+        }
+        self.next_at = Some(now + self.interval());
+        if self.transport == Transport::Udp {
+            let transaction_id = rand::random::<[u8; 12]>();
+            self.pending = Some((now, Some(transaction_id)));
+            let mut request = vec![0x00, 0x01, 0x00, 0x00];
+            request.extend_from_slice(&STUN_MAGIC_COOKIE);
+            request.extend_from_slice(&transaction_id);
+            Some(request)
+        } else {
+            self.pending = Some((now, None));
+            Some(b"\r\n\r\n".to_vec())
+        }
+    }
+
+    /// Handle a packet received on the flow. Returns true if it answers
+    /// the last keep-alive: a CRLF pong on stream transports, the STUN
+    /// binding response to the request sent on UDP.
+    pub fn on_packet(&mut self, packet: &ParsedPacket) -> bool {
+        let answered = match (packet, &self.pending) {
+            (ParsedPacket::KeepAlive, Some((_, None))) => true,
+            (ParsedPacket::MaybeStun(data), Some((_, Some(transaction_id)))) => {
+                data.len() >= 20
+                    && data[..2] == [0x01, 0x01]
+                    && data[4..8] == STUN_MAGIC_COOKIE
+                    && data[8..20] == transaction_id[..]
+            },
+            _ => false,
+        };
+        if answered {
+            self.pending = None;
+        }
+        answered
+    }
+
+    /// Determine if the flow failed at `now`, its last keep-alive not
+    /// having been answered in time.
+    pub fn is_failed(&self, now: Instant) -> bool {
+        self.pending
+            .is_some_and(|(sent_at, _)| sent_at + PONG_TIMEOUT <= now)
+    }
+}
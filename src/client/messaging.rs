@@ -1,9 +1,16 @@
-use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
+use std::{
+    collections::HashMap,
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+};
 
 use crate::*;
 
+/// The option tags of the extensions supported by libsip, advertised
+/// in OPTIONS requests and responses.
+pub const SUPPORTED_EXTENSIONS: &[&str] = &["replaces", "timer"];
+
 macro_rules! impl_simple_header_method {
-    ($name:ident, $variant:ident, $ty:ident) => {
+    ($name:ident, $variant:ident, $ty:ty) => {
         /// Retrieve value of the $variant header.
         pub fn $name(&self) -> IoResult<$ty> {
             if let Some(Header::$variant(header)) = self.headers.$name() {
@@ -30,13 +37,13 @@ impl MessageHelper {
 
     impl_simple_header_method!(to, To, NamedHeader);
 
-    impl_simple_header_method!(contact, Contact, NamedHeader);
+    impl_simple_header_method!(contact, Contact, Vec<NamedHeader>);
 
     impl_simple_header_method!(call_id, CallId, String);
 
     impl_simple_header_method!(xfs_sending_message, XFsSendingMessage, String);
 
-    impl_simple_header_method!(via, Via, ViaHeader);
+    impl_simple_header_method!(via, Via, Vec<ViaHeader>);
 
     /// Create a message helper from a SipMessage
     pub fn new(msg: SipMessage) -> IoResult<MessageHelper> {
@@ -57,8 +64,8 @@ impl MessageHelper {
         Ok(MessageHelper { uri, headers, body })
     }
 
-    /// Retrieve the data of this message, currently this
-    /// just clone's the message body.
+    /// Retrieve the data of this message, the body as delimited
+    /// by its Content-Length header.
     pub fn data(&self) -> Vec<u8> {
         self.body.clone()
     }
@@ -69,23 +76,24 @@ impl MessageHelper {
     pub fn received(&self, header_cfg: &HeaderWriteConfig) -> IoResult<SipMessage> {
         let mut req = ResponseGenerator::new()
             .code(200)
-            .header(self.headers.from().unwrap())
-            .header(self.headers.to().unwrap())
-            .header(self.headers.call_id().unwrap())
-            .header(self.headers.cseq().unwrap())
-            .header(self.headers.via().unwrap())
+            .header_opt(self.headers.from())
+            .header_opt(self.headers.to())
+            .header_opt(self.headers.call_id())
+            .header_opt(self.headers.cseq())
+            .header_opt(self.headers.via())
             .header(Header::ContentLength(0));
         header_cfg.write_headers(req.headers_ref_mut());
-        req.build()
+        Ok(req.build()?)
     }
 }
 
 /// Structure to help when sending Sip messages. Handles the message CSeq,
-/// Call-Id and User-Agent headers.
+/// Call-Id and User-Agent headers. A CSeq counter is kept for every method.
 pub struct MessageWriter {
-    cseq: u32,
+    cseq: HashMap<Method, u32>,
     uri: Uri,
     call_id: String,
+    via: Option<ViaHeader>,
 }
 
 impl MessageWriter {
@@ -95,26 +103,43 @@ impl MessageWriter {
         let _call_id = md5::compute(rand::random::<[u8; 16]>());
         let call_id = format!("{:x}@{}", _call_id, uri.host);
         MessageWriter {
-            cseq: 0,
+            cseq: HashMap::new(),
             uri,
             call_id,
+            via: None,
         }
     }
 
+    /// Set the Via of the local listening socket, inserted with a new
+    /// branch in every request written without a `via_header`.
+    pub fn via(mut self, via: ViaHeader) -> MessageWriter {
+        self.via = Some(via);
+        self
+    }
+
+    /// Set the Via of the local listening socket, see `via`.
+    pub fn set_via(&mut self, via: ViaHeader) {
+        self.via = Some(via);
+    }
+
     /// Actually produce the SIP Message Request with
-    /// the given `body` text.
+    /// the given `body` text. The request uri keeps the schema of `to`,
+    /// defaulting to `sip` when it has none. A Via with a new branch is
+    /// generated from `via` when `via_header` is `None`.
     pub fn write_message(
         &mut self,
         body: Vec<u8>,
         to: Uri,
-        via_header: Header,
+        via_header: Option<Header>,
         header_cfg: &HeaderWriteConfig,
     ) -> IoResult<SipMessage> {
-        self.cseq += 1;
-        let mut req = RequestGenerator::new()
+        self.next_cseq(Method::Message);
+        let schema = to.schema.unwrap_or(UriSchema::Sip);
+        let mut req = self
+            .generator()
             .method(Method::Message)
-            .uri(to.clone().schema(UriSchema::Sip))
-            .header(via_header)
+            .uri(to.clone().schema(schema))
+            .header_opt(via_header)
             .header(Header::To(NamedHeader::new(to)))
             .header(self.from())
             .header(self.cseq())
@@ -125,17 +150,156 @@ impl MessageWriter {
 
         header_cfg.write_headers(req.headers_ref_mut());
 
-        req.body(body).build()
+        Ok(req.body(body).build()?)
+    }
+
+    /// Produce an INVITE request to `to` with the SDP offer `sdp_body`.
+    /// The INVITE starts a new dialog, so it gets a new Call-ID and a From
+    /// tag. The Contact is the account user at the host of `via_header`,
+    /// or of `via` when it is `None`.
+    pub fn write_invite(
+        &mut self,
+        to: Uri,
+        sdp_body: Vec<u8>,
+        via_header: Option<Header>,
+        header_cfg: &HeaderWriteConfig,
+    ) -> IoResult<SipMessage> {
+        self.next_cseq(Method::Invite);
+        let schema = to.schema.unwrap_or(UriSchema::Sip);
+        let mut contact = self.uri.clone();
+        let via = match &via_header {
+            Some(Header::Via(vias)) => vias.first(),
+            _ => self.via.as_ref(),
+        };
+        if let Some(via) = via {
+            contact.host = via.host.clone();
+        }
+        let mut from = NamedHeader::new(self.uri.clone());
+        from.set_tag(generate_tag());
+        let call_id = format!(
+            "{:x}@{}",
+            md5::compute(rand::random::<[u8; 16]>()),
+            self.uri.host
+        );
+        let mut req = self
+            .generator()
+            .method(Method::Invite)
+            .uri(to.clone().schema(schema))
+            .header_opt(via_header)
+            .header(self.max_forwards())
+            .header(Header::To(NamedHeader::new(to)))
+            .header(Header::From(from))
+            .header(Header::CallId(call_id))
+            .header(self.cseq_for(Method::Invite))
+            .header(Header::Contact(vec![NamedHeader::new(contact)]))
+            .header(Header::Supported(vec!["replaces".into()]))
+            .header(Header::ContentType(ContentType::sdp()))
+            .header(Header::ContentLength(sdp_body.len() as u32));
+
+        header_cfg.write_headers(req.headers_ref_mut());
+
+        Ok(req.body(sdp_body).build()?)
+    }
+
+    /// Produce an OPTIONS request to `to`, querying its capabilities or
+    /// checking that it is reachable. The request advertises the local
+    /// capabilities: `application/sdp` bodies in Accept, the
+    /// `SUPPORTED_EXTENSIONS` and the Allow of `header_cfg`.
+    /// ([RFC3261: Section 11, Querying for Capabilities](https://tools.ietf.org/html/rfc3261#section-11))
+    pub fn write_options(
+        &mut self,
+        to: Uri,
+        via_header: Option<Header>,
+        header_cfg: &HeaderWriteConfig,
+    ) -> IoResult<SipMessage> {
+        self.next_cseq(Method::Options);
+        let schema = to.schema.unwrap_or(UriSchema::Sip);
+        let mut req = self
+            .generator()
+            .method(Method::Options)
+            .uri(to.clone().schema(schema))
+            .header_opt(via_header)
+            .header(self.max_forwards())
+            .header(Header::To(NamedHeader::new(to)))
+            .header(self.from())
+            .header(self.call_id())
+            .header(self.cseq_for(Method::Options))
+            .header(Header::Accept(vec![ContentType::sdp()]))
+            .header(supported())
+            .header(Header::ContentLength(0));
+
+        header_cfg.write_headers(req.headers_ref_mut());
+
+        Ok(req.build()?)
+    }
+
+    /// Produce an INFO request to `to` sending the DTMF key `digit`,
+    /// pressed for `duration` milliseconds, as an `application/dtmf-relay`
+    /// body. Fails if `digit` isn't a DTMF key.
+    pub fn write_info_dtmf(
+        &mut self,
+        to: Uri,
+        digit: char,
+        duration: u32,
+        via_header: Option<Header>,
+        header_cfg: &HeaderWriteConfig,
+    ) -> IoResult<SipMessage> {
+        let body = DtmfRelay::new(digit, duration)?.to_string().into_bytes();
+        self.next_cseq(Method::Info);
+        let schema = to.schema.unwrap_or(UriSchema::Sip);
+        let mut req = self
+            .generator()
+            .method(Method::Info)
+            .uri(to.clone().schema(schema))
+            .header_opt(via_header)
+            .header(self.max_forwards())
+            .header(Header::To(NamedHeader::new(to)))
+            .header(self.from())
+            .header(self.call_id())
+            .header(self.cseq_for(Method::Info))
+            .header(Header::ContentType(ContentType::dtmf_relay()))
+            .header(Header::ContentLength(body.len() as u32));
+
+        header_cfg.write_headers(req.headers_ref_mut());
+
+        Ok(req.body(body).build()?)
+    }
+
+    /// Increment the CSeq counter of `method`, returning the number
+    /// to use for the next request with that method.
+    pub fn next_cseq(&mut self, method: Method) -> u32 {
+        let cseq = self.cseq.entry(method).or_insert(0);
+        *cseq += 1;
+        *cseq
     }
 
-    /// Get a new CSeq header.
+    /// Get the CSeq header of the last MESSAGE request.
     pub fn cseq(&self) -> Header {
-        Header::CSeq(self.cseq, Method::Message)
+        self.cseq_for(Method::Message)
+    }
+
+    /// Get the CSeq header of the last request with `method`.
+    pub fn cseq_for(&self, method: Method) -> Header {
+        let cseq = self.cseq.get(&method).copied().unwrap_or(0);
+        Header::CSeq(cseq, method)
+    }
+
+    /// Get a RequestGenerator inserting the local Via when none is given,
+    /// with an `rport` parameter asking for symmetric response routing.
+    /// ([RFC3581: Section 3, Client Behavior](https://tools.ietf.org/html/rfc3581#section-3))
+    fn generator(&self) -> RequestGenerator {
+        match &self.via {
+            Some(via) if via.has_rport() => RequestGenerator::new().default_via(via.clone()),
+            Some(via) => {
+                RequestGenerator::new().default_via(via.clone().param("rport", None::<&str>))
+            },
+            None => RequestGenerator::new(),
+        }
     }
 
     /// Get a new Content-Type header.
     pub fn content_type(&self) -> Header {
-        Header::ContentType(ContentType::PlainText)
+        Header::ContentType(ContentType::plain_text())
     }
 
     /// Get a new Max-Forwards header.
@@ -153,3 +317,46 @@ impl MessageWriter {
         Header::From(NamedHeader::new(self.uri.clone()))
     }
 }
+
+/// Generate the 200 OK answering the OPTIONS request `options` with the
+/// local capabilities, the same ones `MessageWriter::write_options`
+/// advertises.
+/// # Examples
+///
+/// ```
+/// use libsip::*;
+///
+/// let options = SipMessage::parse(
+///     b"OPTIONS sip:bob@example.com SIP/2.0\r\n\
+///     Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+///     Max-Forwards: 70\r\n\
+///     From: <sip:alice@example.com>;tag=1928301774\r\n\
+///     To: <sip:bob@example.com>\r\n\
+///     Call-ID: a84b4c76e66710\r\n\
+///     CSeq: 63104 OPTIONS\r\n\r\n",
+/// )
+/// .unwrap();
+/// let response = options_response(&options, &HeaderWriteConfig::default()).unwrap();
+/// assert_eq!(Some(200), response.status_code());
+/// assert!(response.headers().supports("replaces"));
+/// ```
+pub fn options_response(
+    options: &SipMessage,
+    header_cfg: &HeaderWriteConfig,
+) -> IoResult<SipMessage> {
+    let mut res = ResponseGenerator::reply_to(options, 200)
+        .header(Header::Accept(vec![ContentType::sdp()]))
+        .header(supported())
+        .header(Header::ContentLength(0));
+    header_cfg.write_headers(res.headers_ref_mut());
+    Ok(res.build()?)
+}
+
+fn supported() -> Header {
+    Header::Supported(
+        SUPPORTED_EXTENSIONS
+            .iter()
+            .map(|tag| tag.to_string())
+            .collect(),
+    )
+}
@@ -3,15 +3,41 @@
 //! all this functionality is the SoftPhone struct.
 
 mod registration;
-pub use self::registration::RegistrationManager;
+pub use self::registration::{RegistrationManager, RegistrationState};
 
 mod messaging;
-pub use self::messaging::{MessageHelper, MessageWriter};
+pub use self::messaging::{options_response, MessageHelper, MessageWriter, SUPPORTED_EXTENSIONS};
+
+mod authenticator;
+pub use self::authenticator::Authenticator;
+
+mod keepalive;
+pub use self::keepalive::FlowKeepAlive;
+
+mod dtmf;
+pub use self::dtmf::DtmfRelay;
+
+mod dialog;
+pub use self::dialog::{
+    route_target, uac_route_set, uas_route_set, Dialog, DialogState, RouteTarget,
+};
 
 mod invite;
 pub use self::invite::{InviteHelper, InviteWriter};
 
-use crate::{Header, Headers, Method, SipMessage, Uri};
+mod publication;
+pub use self::publication::{PublicationManager, PublicationStatus};
+
+mod session_timer;
+pub use self::session_timer::SessionTimer;
+
+mod subscription;
+pub use self::subscription::{SubscriptionManager, SubscriptionStatus};
+
+mod transfer;
+pub use self::transfer::{parse_sipfrag, refer_to, TransferProgress};
+
+use crate::{Header, Headers, Method, SipMessage, Transport, Uri, ViaHeader};
 
 use std::{
     collections::HashMap,
@@ -94,7 +120,8 @@ impl SoftPhone {
     pub fn new(local_uri: Uri, account_uri: Uri) -> SoftPhone {
         SoftPhone {
             header_cfg: HeaderWriteConfig::default(),
-            msg: MessageWriter::new(account_uri.clone()),
+            msg: MessageWriter::new(account_uri.clone())
+                .via(ViaHeader::new(local_uri.host.clone(), Transport::Udp)),
             invite: InviteWriter::new(account_uri.clone()),
             reg: RegistrationManager::new(account_uri, local_uri),
             calls: HashMap::new(),
@@ -143,7 +170,18 @@ impl SoftPhone {
 
     /// Simple pass through method to get a registration request.
     pub fn get_register_request(&mut self) -> IoResult<SipMessage> {
-        Ok(self.reg.get_request(&self.header_cfg)?)
+        self.reg.get_request(&self.header_cfg)
+    }
+
+    /// Simple pass through method to get a request removing the registration.
+    pub fn get_unregister_request(&mut self) -> IoResult<SipMessage> {
+        self.reg.get_unregister_request(&self.header_cfg)
+    }
+
+    /// Simple pass through method to get a register request with the expires
+    /// value required by a 423 (Interval Too Brief) response.
+    pub fn handle_interval_too_brief(&mut self, msg: &SipMessage) -> IoResult<SipMessage> {
+        self.reg.handle_interval_too_brief(&self.header_cfg, msg)
     }
 
     /// Set the received auth challenge request.
@@ -154,9 +192,7 @@ impl SoftPhone {
 
     /// Send a new Message to `uri`.
     pub fn write_message(&mut self, b: Vec<u8>, uri: Uri) -> IoResult<SipMessage> {
-        Ok(self
-            .msg
-            .write_message(b, uri, self.reg.via_header(), &self.header_cfg)?)
+        self.msg.write_message(b, uri, None, &self.header_cfg)
     }
 
     /// Send a new Invite Request to `uri`.
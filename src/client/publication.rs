@@ -0,0 +1,196 @@
+use std::{
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+    time::{Duration, Instant},
+};
+
+use crate::{headers::EventType, *};
+
+/// The state of the event state published by a `PublicationManager`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PublicationStatus {
+    /// Nothing was published yet, or the publication was removed.
+    Unpublished,
+    /// A PUBLISH was sent and no final response received yet.
+    Publishing,
+    /// The event state compositor accepted the publication.
+    Published,
+    /// The last PUBLISH was rejected with the status `code`.
+    Failed(u32),
+}
+
+/// Publishes event state, e.g. the `presence` of an account, to an
+/// event state compositor.
+/// ([RFC3903: Section 4, Event State Publication](https://tools.ietf.org/html/rfc3903#section-4))
+///
+/// The first PUBLISH carries the state and is answered with the entity-tag
+/// identifying the publication, given as the SIP-If-Match of the requests
+/// that refresh, modify or remove it. A 412 response means the compositor
+/// lost the publication, the state has to be published again.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PublicationManager {
+    account_uri: Uri,
+    event: EventType,
+    via: ViaHeader,
+    expires: u32,
+    call_id: String,
+    local: NamedHeader,
+    cseq: u32,
+    etag: Option<String>,
+    status: PublicationStatus,
+    refresh_at: Option<Instant>,
+}
+
+impl PublicationManager {
+    /// Create a publication of the `event` state of `account_uri`.
+    /// `via` represents the local listening socket.
+    pub fn new(account_uri: Uri, event: EventType, via: ViaHeader) -> Self {
+        let call_id = format!(
+            "{:x}@{}",
+            md5::compute(rand::random::<[u8; 16]>()),
+            account_uri.host
+        );
+        let mut local = NamedHeader::new(account_uri.clone());
+        local.set_tag(generate_tag());
+        PublicationManager {
+            account_uri,
+            event,
+            via,
+            expires: 3600,
+            call_id,
+            local,
+            cseq: 0,
+            etag: None,
+            status: PublicationStatus::Unpublished,
+            refresh_at: None,
+        }
+    }
+
+    /// Set the duration of the publication requested in seconds,
+    /// 3600 by default.
+    pub fn expires(mut self, expires: u32) -> Self {
+        self.expires = expires;
+        self
+    }
+
+    /// The current state of the publication.
+    pub fn status(&self) -> PublicationStatus {
+        self.status
+    }
+
+    /// The entity-tag of the publication given by the compositor.
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Generate a PUBLISH with the event state `body`, the initial
+    /// publication or a modification of the existing one.
+    pub fn publish(
+        &mut self,
+        content_type: ContentType,
+        body: Vec<u8>,
+        header_cfg: &HeaderWriteConfig,
+    ) -> IoResult<SipMessage> {
+        let req = self
+            .request(self.expires, header_cfg)
+            .header(Header::ContentType(content_type))
+            .body(body);
+        Ok(req.build()?)
+    }
+
+    /// Generate a PUBLISH extending the duration of the publication,
+    /// without a body. Fails if nothing was published.
+    pub fn refresh(&mut self, header_cfg: &HeaderWriteConfig) -> IoResult<SipMessage> {
+        self.require_etag()?;
+        Ok(self.request(self.expires, header_cfg).build()?)
+    }
+
+    /// Generate a PUBLISH removing the publication (`Expires: 0`).
+    /// Fails if nothing was published.
+    pub fn remove(&mut self, header_cfg: &HeaderWriteConfig) -> IoResult<SipMessage> {
+        self.require_etag()?;
+        self.refresh_at = None;
+        Ok(self.request(0, header_cfg).build()?)
+    }
+
+    fn require_etag(&self) -> IoResult<()> {
+        if self.etag.is_none() {
+            return Err(IoError::new(
+                IoErrorKind::InvalidInput,
+                "no entity-tag, the event state wasn't published",
+            ));
+        }
+        Ok(())
+    }
+
+    fn request(&mut self, expires: u32, header_cfg: &HeaderWriteConfig) -> RequestGenerator {
+        self.cseq += 1;
+        self.status = PublicationStatus::Publishing;
+        let mut req = RequestGenerator::new()
+            .method(Method::Publish)
+            .uri(self.account_uri.clone())
+            .header(Header::Via(vec![self
+                .via
+                .clone()
+                .branch_param(generate_branch())]))
+            .header(Header::MaxForwards(70))
+            .header(Header::From(self.local.clone()))
+            .header(Header::To(NamedHeader::new(self.account_uri.clone())))
+            .header(Header::CallId(self.call_id.clone()))
+            .header(Header::CSeq(self.cseq, Method::Publish))
+            .header(Header::Event(self.event.clone(), GenericParams::new()))
+            .header(Header::Expires(expires));
+        if let Some(etag) = &self.etag {
+            req = req.header(Header::SipIfMatch(etag.clone()));
+        }
+        header_cfg.write_headers(req.headers_ref_mut());
+        req
+    }
+
+    /// Handle the response to the last PUBLISH, received at `now`.
+    /// A 2xx stores the entity-tag and the expiration time, a 412 forgets
+    /// the entity-tag and a 423 raises the requested duration to the
+    /// Min-Expires of the response, the state having to be published again.
+    pub fn handle_response(&mut self, response: &SipMessage, now: Instant) {
+        let code = match response.status_code() {
+            Some(code) if code >= 200 => code,
+            _ => return,
+        };
+        if code >= 300 {
+            self.status = PublicationStatus::Failed(code);
+            self.refresh_at = None;
+            if code == 412 {
+                self.etag = None;
+            }
+            if let Some(Header::MinExpires(min)) = response.headers().min_expires() {
+                self.expires = std::cmp::max(self.expires, min);
+            }
+            return;
+        }
+        let expires = match response.headers().expires() {
+            Some(Header::Expires(expires)) => expires,
+            _ => self.expires,
+        };
+        if expires == 0 {
+            self.status = PublicationStatus::Unpublished;
+            self.etag = None;
+            self.refresh_at = None;
+        } else {
+            self.status = PublicationStatus::Published;
+            if let Some(Header::SipETag(etag)) = response.headers().sip_etag() {
+                self.etag = Some(etag);
+            }
+            self.refresh_at = Some(now + Duration::from_secs(u64::from(expires)) / 2);
+        }
+    }
+
+    /// The time at which the publication has to be refreshed, half
+    /// of the duration last granted by the compositor.
+    pub fn refresh_at(&self) -> Option<Instant> {
+        self.refresh_at
+    }
+
+    /// Determine if the publication has to be refreshed at `now`.
+    pub fn needs_refresh(&self, now: Instant) -> bool {
+        self.refresh_at.is_some_and(|at| at <= now)
+    }
+}
@@ -1,6 +1,24 @@
 use crate::*;
 
-use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
+use std::{
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+    time::{Duration, Instant},
+};
+
+/// The state of the registration of an account.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RegistrationState {
+    /// No registration was attempted, or it was removed.
+    Unregistered,
+    /// A REGISTER request was sent and no final response received yet.
+    Registering,
+    /// The registrar accepted the binding for `expires` seconds.
+    Registered { expires: u32 },
+    /// A request removing the registration was sent.
+    Unregistering,
+    /// The registrar rejected the request with the status `code`.
+    Failed(u32),
+}
 
 /// Handle's the SIP registration process.
 /// This structure is designed to handle the authentication
@@ -21,6 +39,15 @@ pub struct RegistrationManager {
     c_nonce: Option<String>,
     /// The Finished computed auth header.
     auth_header: Option<AuthHeader>,
+    /// The challenge of a proxy, from a 407 response.
+    proxy_auth_header: Option<AuthHeader>,
+    /// The realms the last request carried credentials for, along with
+    /// whether they answered a 407 rather than a 401.
+    answered: Vec<(bool, Option<String>)>,
+    /// The state of the registration.
+    state: RegistrationState,
+    /// When the last successful registration was accepted.
+    registered_at: Option<Instant>,
     /// The branch to use for registration.
     branch: String,
     /// The Call Id to use for register requests.
@@ -35,6 +62,12 @@ pub struct RegistrationManager {
     realm: Option<String>,
     /// Authentication nonce
     nonce: Option<String>,
+    /// The instance id and reg-id of the outbound flow registered.
+    outbound: Option<(String, u32)>,
+    /// Whether the registrar accepted the flow (`Require: outbound`).
+    outbound_active: bool,
+    /// The Flow-Timer value of the last successful registration.
+    flow_timer: Option<u32>,
 }
 
 impl RegistrationManager {
@@ -47,15 +80,22 @@ impl RegistrationManager {
             local_uri,
             cseq_counter: 444,
             auth_header: None,
+            proxy_auth_header: None,
+            answered: vec![],
+            state: RegistrationState::Unregistered,
+            registered_at: None,
             nonce_c: 1,
             c_nonce: None,
-            branch: format!("{:x}", md5::compute(rand::random::<[u8; 16]>())),
+            branch: generate_branch(),
             call_id: format!("{:x}", md5::compute(rand::random::<[u8; 16]>())),
             expires_header: None,
             user: None,
             pass: None,
             realm: None,
             nonce: None,
+            outbound: None,
+            outbound_active: false,
+            flow_timer: None,
         }
     }
 
@@ -69,45 +109,92 @@ impl RegistrationManager {
         self.pass = Some(p.into());
     }
 
+    /// Register an outbound flow identified by the `+sip.instance` id
+    /// `instance` and `reg_id`, so that inbound requests are sent over the
+    /// connection or NAT binding the registration was sent on. Once
+    /// registered the flow is kept alive with the `keep_alive` schedule.
+    /// ([RFC5626: Section 4.2, Registrations](https://tools.ietf.org/html/rfc5626#section-4.2))
+    pub fn outbound<S: Into<String>>(&mut self, instance: S, reg_id: u32) {
+        self.outbound = Some((instance.into(), reg_id));
+    }
+
     /// Get the register request. if this method is called before `set_challenge`
     /// then no authentication header will be set, if called after `set_challenge`
     /// then the Authorization header will be set.
     pub fn get_request(&mut self, cfg: &HeaderWriteConfig) -> IoResult<SipMessage> {
+        let mut contact_header = self.local_uri.clone();
+        if let Some(name) = &self.user {
+            contact_header = contact_header.auth(UriAuth::new(name));
+        }
+        let mut contact = NamedHeader::new(contact_header);
+        if let Some((instance, reg_id)) = &self.outbound {
+            contact.set_instance(instance.clone());
+            contact.set_reg_id(*reg_id);
+        }
+        let expires = self.expires_header;
+        self.state = RegistrationState::Registering;
+        self.build_request(cfg, Header::Contact(vec![contact]), expires)
+    }
+
+    /// Get a register request removing all bindings of the account
+    /// (`Contact: *` with `Expires: 0`). The Authorization header is set
+    /// the same way as in `get_request`.
+    pub fn get_unregister_request(&mut self, cfg: &HeaderWriteConfig) -> IoResult<SipMessage> {
+        self.state = RegistrationState::Unregistering;
+        self.build_request(cfg, Header::ContactWildcard, Some(0))
+    }
+
+    fn build_request(
+        &mut self,
+        cfg: &HeaderWriteConfig,
+        contact: Header,
+        expires: Option<u32>,
+    ) -> IoResult<SipMessage> {
         self.cseq_counter += 1;
         self.nonce_c += 1;
         let to_header = self.account_uri.clone();
         let from_header = self.account_uri.clone();
-        let mut contact_header = self.local_uri.clone();
         let mut headers = vec![];
 
-        if let Some(name) = &self.user {
-            contact_header = contact_header.auth(UriAuth::new(name));
+        self.branch = generate_branch();
+        self.answered.clear();
+        if let (Some(name), Some(pass)) = (&self.user, &self.pass) {
+            let (nc, uri) = (self.nonce_c, &self.account_uri);
+            let ctx = || AuthContext {
+                user: name,
+                pass,
+                nc,
+                uri,
+            };
             if let Some(auth_header) = &self.auth_header {
-                if let Some(pass) = &self.pass {
-                    let ctx = AuthContext {
-                        user: &name,
-                        pass,
-                        nc: self.nonce_c,
-                        uri: &self.account_uri,
-                    };
-                    headers.push(Header::Authorization(auth_header.authenticate(ctx)?));
-                }
+                headers.push(Header::Authorization(auth_header.authenticate(ctx())?));
+                self.answered
+                    .push((false, auth_header.realm().map(String::from)));
+            }
+            if let Some(auth_header) = &self.proxy_auth_header {
+                headers.push(Header::ProxyAuthorization(auth_header.authenticate(ctx())?));
+                self.answered
+                    .push((true, auth_header.realm().map(String::from)));
             }
         }
         headers.push(Header::ContentLength(0));
         headers.push(Header::To(NamedHeader::new(to_header)));
         headers.push(Header::From(NamedHeader::new(from_header)));
-        headers.push(Header::Contact(NamedHeader::new(contact_header)));
+        headers.push(contact);
         headers.push(Header::CSeq(self.cseq_counter, Method::Register));
         headers.push(Header::CallId(format!(
             "{}@{}",
             self.call_id,
-            self.account_uri.host()
+            self.account_uri.host().unwrap_or_default()
         )));
         headers.push(self.via_header());
+        headers.push(Header::MaxForwards(70));
+        if self.outbound.is_some() {
+            headers.push(Header::Supported(vec!["outbound".into()]));
+        }
         cfg.write_headers_vec(&mut headers);
 
-        if let Some(exp) = self.expires_header {
+        if let Some(exp) = expires {
             headers.push(Header::Expires(exp));
         }
         Ok(RequestGenerator::new()
@@ -121,6 +208,7 @@ impl RegistrationManager {
     /// to this function to perform compute the hashed password.
     pub fn set_challenge(&mut self, msg: SipMessage) -> IoResult<()> {
         if let SipMessage::Response { headers, .. } = msg {
+            let mut contact_expires = None;
             for item in headers.into_iter() {
                 match item {
                     Header::WwwAuthenticate(auth) => {
@@ -129,9 +217,20 @@ impl RegistrationManager {
                     Header::Expires(expire) => {
                         self.expires_header = Some(expire);
                     },
+                    Header::Contact(contacts) => {
+                        contact_expires = contacts
+                            .iter()
+                            .filter(|contact| self.is_local_contact(contact))
+                            .find_map(NamedHeader::expires)
+                            .or(contact_expires);
+                    },
                     _ => {},
                 }
             }
+            // The expires parameter of a contact takes precedence over the Expires header.
+            if contact_expires.is_some() {
+                self.expires_header = contact_expires;
+            }
             Ok(())
         } else {
             Err(IoError::new(
@@ -141,6 +240,187 @@ impl RegistrationManager {
         }
     }
 
+    /// Handle a response to the last REGISTER request, updating the
+    /// registration state, and return the request to send next if any.
+    ///
+    /// A 2xx registers the account for the expires value the registrar
+    /// granted, from the Contact matching the local uri or the Expires
+    /// header, or unregisters it after `get_unregister_request`.
+    /// 401 and 407 challenges are answered, unless the request carried
+    /// credentials for the same kind of challenge and realm already, in
+    /// which case they were wrong and the registration fails, unless the
+    /// nonce is stale. A 423 is handled with `handle_interval_too_brief`.
+    /// `now` is the time the response was received.
+    pub fn handle_response(
+        &mut self,
+        cfg: &HeaderWriteConfig,
+        msg: &SipMessage,
+        now: Instant,
+    ) -> IoResult<Option<SipMessage>> {
+        let code = msg.status_code().ok_or_else(|| {
+            IoError::new(
+                IoErrorKind::InvalidInput,
+                "REGISTER response was not a SIP response",
+            )
+        })?;
+        let unregistering = self.state == RegistrationState::Unregistering;
+        match code {
+            100..=199 => Ok(None),
+            200..=299 if unregistering => {
+                self.state = RegistrationState::Unregistered;
+                self.registered_at = None;
+                Ok(None)
+            },
+            200..=299 => {
+                let expires = self.granted_expires(msg);
+                self.state = RegistrationState::Registered { expires };
+                self.registered_at = Some(now);
+                self.outbound_active =
+                    self.outbound.is_some() && msg.headers().requires("outbound");
+                self.flow_timer = match msg.headers().flow_timer() {
+                    Some(Header::FlowTimer(seconds)) => Some(seconds),
+                    _ => None,
+                };
+                Ok(None)
+            },
+            401 | 407 => {
+                let challenge = self.challenge(msg, code);
+                let answered = challenge.as_ref().is_some_and(|challenge| {
+                    self.answered.iter().any(|(proxy, realm)| {
+                        *proxy == (code == 407) && realm.as_deref() == challenge.realm()
+                    })
+                });
+                match challenge {
+                    Some(challenge) if !answered || challenge.stale() => {
+                        if code == 401 {
+                            self.auth_header = Some(challenge);
+                        } else {
+                            self.proxy_auth_header = Some(challenge);
+                        }
+                        let request = if unregistering {
+                            self.get_unregister_request(cfg)?
+                        } else {
+                            self.get_request(cfg)?
+                        };
+                        Ok(Some(request))
+                    },
+                    _ => {
+                        self.state = RegistrationState::Failed(code);
+                        Ok(None)
+                    },
+                }
+            },
+            423 => self.handle_interval_too_brief(cfg, msg).map(Some),
+            _ => {
+                self.state = RegistrationState::Failed(code);
+                Ok(None)
+            },
+        }
+    }
+
+    fn challenge(&self, msg: &SipMessage, code: u32) -> Option<AuthHeader> {
+        msg.headers().iter().find_map(|header| match header {
+            Header::WwwAuthenticate(auth) if code == 401 => Some(auth.clone()),
+            Header::ProxyAuthenticate(auth) if code == 407 => Some(auth.clone()),
+            _ => None,
+        })
+    }
+
+    /// The expiration granted to the binding of the local contact, among
+    /// the bindings of the account listed in a 2xx `msg`.
+    fn granted_expires(&self, msg: &SipMessage) -> u32 {
+        let contact = msg
+            .headers()
+            .contacts()
+            .iter()
+            .filter(|contact| self.is_local_contact(contact))
+            .find_map(NamedHeader::expires);
+        let header = match msg.headers().expires() {
+            Some(Header::Expires(expires)) => Some(expires),
+            _ => None,
+        };
+        contact.or(header).unwrap_or_else(|| self.expires())
+    }
+
+    /// Determine if `contact` is the binding registered by `get_request`,
+    /// by its `+sip.instance` for outbound registrations or else by its
+    /// user and host.
+    fn is_local_contact(&self, contact: &NamedHeader) -> bool {
+        if let Some((instance, _)) = &self.outbound {
+            return contact.instance() == Some(instance.as_str());
+        }
+        let user = contact.uri.auth.as_ref().map(|auth| &auth.username);
+        contact.uri.host == self.local_uri.host && user == self.user.as_ref()
+    }
+
+    /// The current state of the registration.
+    pub fn state(&self) -> RegistrationState {
+        self.state
+    }
+
+    /// The time to wait after a successful registration before refreshing
+    /// it, half of the granted expires value.
+    pub fn refresh_interval(&self) -> Option<Duration> {
+        match self.state {
+            RegistrationState::Registered { expires } => {
+                Some(Duration::from_secs(u64::from(expires)) / 2)
+            },
+            _ => None,
+        }
+    }
+
+    /// The time at which the registration has to be refreshed with
+    /// `get_request`, see `refresh_interval`.
+    pub fn refresh_at(&self) -> Option<Instant> {
+        Some(self.registered_at? + self.refresh_interval()?)
+    }
+
+    /// Determine if the registration has to be refreshed at `now`.
+    pub fn needs_refresh(&self, now: Instant) -> bool {
+        self.refresh_at().is_some_and(|at| at <= now)
+    }
+
+    /// Determine if the registrar accepted the outbound flow of the
+    /// last successful registration.
+    pub fn is_outbound(&self) -> bool {
+        self.outbound_active
+    }
+
+    /// The keep-alive schedule of the flow registered over `transport`,
+    /// using the Flow-Timer of the registrar if it gave one.
+    pub fn keep_alive(&self, transport: Transport) -> FlowKeepAlive {
+        let keep_alive = FlowKeepAlive::new(transport);
+        match self.flow_timer {
+            Some(seconds) => keep_alive.flow_timer(seconds),
+            None => keep_alive,
+        }
+    }
+
+    /// Handle a 423 (Interval Too Brief) response to a REGISTER request.
+    /// The expires value is raised to the Min-Expires value of the response
+    /// and a new register request using it is returned.
+    pub fn handle_interval_too_brief(
+        &mut self,
+        cfg: &HeaderWriteConfig,
+        msg: &SipMessage,
+    ) -> IoResult<SipMessage> {
+        if msg.status_code() != Some(423) {
+            return Err(IoError::new(
+                IoErrorKind::InvalidInput,
+                "Response was not a 423 Interval Too Brief response",
+            ));
+        }
+        if let Some(Header::MinExpires(min)) = msg.headers().min_expires() {
+            self.expires_header = Some(self.expires_header.unwrap_or(0).max(min));
+            self.get_request(cfg)
+        } else {
+            Err(IoError::new(
+                IoErrorKind::InvalidInput,
+                "Interval Too Brief response is missing the Min-Expires header",
+            ))
+        }
+    }
+
     /// Retreive the expires header value.
     pub fn expires(&self) -> u32 {
         self.expires_header.unwrap_or(60)
@@ -154,16 +434,10 @@ impl RegistrationManager {
     /// Retreive the via header being used to represent the local
     /// listening socket.
     pub fn via_header(&self) -> Header {
-        let via_uri = self
-            .local_uri
-            .clone()
-            .parameter(UriParam::Branch(self.branch.clone()))
-            .authless()
-            .schemaless();
-        Header::Via(ViaHeader {
-            uri: via_uri,
-            version: Default::default(),
-            transport: Transport::Udp,
-        })
+        Header::Via(vec![ViaHeader::new(
+            self.local_uri.host.clone(),
+            Transport::Udp,
+        )
+        .branch_param(self.branch.clone())])
     }
 }
@@ -0,0 +1,67 @@
+use crate::{
+    headers::{Refresher, SessionExpires},
+    *,
+};
+
+use std::time::Duration;
+
+/// Session timer negotiated for a call using the Session-Expires and
+/// Min-SE headers. ([RFC4028](https://tools.ietf.org/html/rfc4028))
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SessionTimer {
+    /// The session interval in seconds.
+    pub interval: u32,
+    /// The side responsible for refreshing the session.
+    pub refresher: Refresher,
+}
+
+impl SessionTimer {
+    /// Minimum session interval allowed by RFC 4028.
+    pub const MIN_INTERVAL: u32 = 90;
+
+    /// Get the session timer from the headers of a 2xx response to an INVITE or UPDATE.
+    /// The UAC refreshes the session if the response doesn't name a refresher.
+    pub fn from_headers(headers: &Headers) -> Option<SessionTimer> {
+        if let Some(Header::SessionExpires(value)) = headers.session_expires() {
+            Some(SessionTimer {
+                interval: value.interval,
+                refresher: value.refresher.unwrap_or(Refresher::Uac),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Get the Session-Expires header to send in a request, raising
+    /// `interval` to the Min-SE value of a 422 response if there is one.
+    pub fn request_header(interval: u32, response_headers: Option<&Headers>) -> Header {
+        let min_se = response_headers
+            .and_then(|headers| headers.min_se())
+            .and_then(|header| match header {
+                Header::MinSE(min_se) => Some(min_se),
+                _ => None,
+            })
+            .unwrap_or(SessionTimer::MIN_INTERVAL);
+        Header::SessionExpires(SessionExpires::new(interval.max(min_se)))
+    }
+
+    /// Time after which the refresher should send a refresh request,
+    /// half of the session interval.
+    pub fn refresh_interval(&self) -> Duration {
+        Duration::from_secs(u64::from(self.interval) / 2)
+    }
+
+    /// Time after which the other side should consider the session
+    /// expired if no refresh was received. This is the session interval
+    /// minus a third of it or 32 seconds, whichever is less.
+    pub fn expiration_interval(&self) -> Duration {
+        let interval = u64::from(self.interval);
+        Duration::from_secs(interval - (interval / 3).min(32))
+    }
+
+    /// Determine if this side is responsible for refreshing the session,
+    /// `is_uac` is true for the side that sent the INVITE.
+    pub fn is_refresher(&self, is_uac: bool) -> bool {
+        (self.refresher == Refresher::Uac) == is_uac
+    }
+}
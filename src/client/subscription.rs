@@ -0,0 +1,279 @@
+use std::{
+    io::Result as IoResult,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    headers::{EventType, SubscriptionState},
+    *,
+};
+
+/// The state of a subscription handled by a `SubscriptionManager`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SubscriptionStatus {
+    /// No SUBSCRIBE was sent yet.
+    Idle,
+    /// A SUBSCRIBE was sent and neither its 2xx nor a NOTIFY received.
+    Subscribing,
+    /// The notifier accepted the subscription but is waiting for it to
+    /// be authorized.
+    Pending,
+    /// Notifications are sent for the subscription.
+    Active,
+    /// The subscription ended, with the reason and the seconds after
+    /// which it may be retried given by the notifier if any.
+    Terminated {
+        reason: Option<String>,
+        retry_after: Option<u32>,
+    },
+}
+
+/// Handles a subscription to an event package, e.g. `message-summary`
+/// for voicemail notifications or `presence`.
+/// ([RFC6665: Section 4.1, Subscriber Behavior](https://tools.ietf.org/html/rfc6665#section-4.1))
+///
+/// The dialog of the subscription is created by the 2xx response to the
+/// SUBSCRIBE or the first NOTIFY, whichever arrives first. The expiration
+/// time is updated from both and the subscription has to be refreshed
+/// with `subscribe` once `needs_refresh` returns true.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SubscriptionManager {
+    account_uri: Uri,
+    target: Uri,
+    event: EventType,
+    via: ViaHeader,
+    expires: u32,
+    call_id: String,
+    local: NamedHeader,
+    cseq: u32,
+    dialog: Option<Dialog>,
+    status: SubscriptionStatus,
+    refresh_at: Option<Instant>,
+}
+
+impl SubscriptionManager {
+    /// Create a subscription of `account_uri` to the `event` package of
+    /// `target`. `via` represents the local listening socket.
+    pub fn new(account_uri: Uri, target: Uri, event: EventType, via: ViaHeader) -> Self {
+        let call_id = format!(
+            "{:x}@{}",
+            md5::compute(rand::random::<[u8; 16]>()),
+            account_uri.host
+        );
+        let mut local = NamedHeader::new(account_uri.clone());
+        local.set_tag(generate_tag());
+        SubscriptionManager {
+            account_uri,
+            target,
+            event,
+            via,
+            expires: 3600,
+            call_id,
+            local,
+            cseq: 0,
+            dialog: None,
+            status: SubscriptionStatus::Idle,
+            refresh_at: None,
+        }
+    }
+
+    /// Set the duration of the subscription requested in seconds,
+    /// 3600 by default.
+    pub fn expires(mut self, expires: u32) -> Self {
+        self.expires = expires;
+        self
+    }
+
+    /// The current state of the subscription.
+    pub fn status(&self) -> &SubscriptionStatus {
+        &self.status
+    }
+
+    /// The dialog of the subscription, once established.
+    pub fn dialog(&self) -> Option<&Dialog> {
+        self.dialog.as_ref()
+    }
+
+    /// Generate a SUBSCRIBE creating or refreshing the subscription.
+    pub fn subscribe(&mut self, header_cfg: &HeaderWriteConfig) -> IoResult<SipMessage> {
+        if self.status == SubscriptionStatus::Idle {
+            self.status = SubscriptionStatus::Subscribing;
+        }
+        self.request(self.expires, header_cfg)
+    }
+
+    /// Generate a SUBSCRIBE ending the subscription (`Expires: 0`).
+    /// The notifier answers it with a final NOTIFY.
+    pub fn unsubscribe(&mut self, header_cfg: &HeaderWriteConfig) -> IoResult<SipMessage> {
+        self.refresh_at = None;
+        self.request(0, header_cfg)
+    }
+
+    fn request(&mut self, expires: u32, header_cfg: &HeaderWriteConfig) -> IoResult<SipMessage> {
+        let mut req = match &mut self.dialog {
+            Some(dialog) => {
+                let req = dialog.new_request(Method::Subscribe);
+                self.cseq = dialog.local_seq;
+                req
+            },
+            None => {
+                self.cseq += 1;
+                RequestGenerator::new()
+                    .method(Method::Subscribe)
+                    .uri(self.target.clone())
+                    .header(Header::Via(vec![self
+                        .via
+                        .clone()
+                        .branch_param(generate_branch())]))
+                    .header(Header::MaxForwards(70))
+                    .header(Header::From(self.local.clone()))
+                    .header(Header::To(NamedHeader::new(self.target.clone())))
+                    .header(Header::CallId(self.call_id.clone()))
+                    .header(Header::CSeq(self.cseq, Method::Subscribe))
+            },
+        };
+        let mut contact = self.account_uri.clone();
+        contact.host = self.via.host.clone();
+        req = req
+            .header(Header::Contact(vec![NamedHeader::new(contact)]))
+            .header(Header::Event(self.event.clone(), GenericParams::new()))
+            .header(Header::Expires(expires));
+        header_cfg.write_headers(req.headers_ref_mut());
+        Ok(req.build()?)
+    }
+
+    /// Handle the response to the last SUBSCRIBE, received at `now`.
+    /// A 2xx establishes the dialog and sets the expiration time from its
+    /// Expires header, a failure terminates the subscription.
+    pub fn handle_response(&mut self, subscribe: &SipMessage, response: &SipMessage, now: Instant) {
+        match response.status_code() {
+            Some(200..=299) => {
+                if self.dialog.is_none() {
+                    if let Ok(dialog) = Dialog::from_uac(subscribe, response) {
+                        self.dialog = Some(dialog.via(self.via.clone()));
+                    }
+                }
+                if self.status == SubscriptionStatus::Subscribing {
+                    self.status = SubscriptionStatus::Pending;
+                }
+                if let Some(Header::Expires(expires)) = response.headers().expires() {
+                    self.set_expires(expires, now);
+                }
+            },
+            Some(code) if code >= 300 => {
+                self.status = SubscriptionStatus::Terminated {
+                    reason: None,
+                    retry_after: None,
+                };
+                self.refresh_at = None;
+            },
+            _ => {},
+        }
+    }
+
+    /// Handle a NOTIFY received at `now`, returning the response to send.
+    /// NOTIFYs that don't belong to the subscription are answered with
+    /// a 481, with another Event with a 489 and without a
+    /// Subscription-State header with a 400.
+    pub fn handle_notify(&mut self, notify: &SipMessage, now: Instant) -> IoResult<SipMessage> {
+        let matches = notify.call_id() == Some(&self.call_id)
+            && notify.to().and_then(|to| to.tag()) == self.local.tag()
+            && match &self.dialog {
+                Some(dialog) => dialog.matches(notify),
+                None => true,
+            };
+        if !matches {
+            return reply(notify, StatusCode::CALL_OR_TRANSACTION_DOES_NOT_EXIST);
+        }
+        match notify.headers().event() {
+            Some(Header::Event(event, _)) if event == self.event => {},
+            _ => return reply(notify, StatusCode::BAD_EVENT),
+        }
+        let state = match notify.headers().subscription_state() {
+            Some(Header::SubscriptionState(state)) => state,
+            _ => return reply(notify, StatusCode::BAD_REQUEST),
+        };
+
+        match &mut self.dialog {
+            Some(dialog) => {
+                dialog.on_request(notify);
+            },
+            None => {
+                self.dialog = Some(Dialog {
+                    state: DialogState::Confirmed,
+                    call_id: self.call_id.clone(),
+                    local: self.local.clone(),
+                    remote: notify
+                        .from()
+                        .cloned()
+                        .unwrap_or_else(|| NamedHeader::new(self.target.clone())),
+                    local_seq: self.cseq,
+                    remote_seq: notify.cseq().map(|(seq, _)| seq),
+                    remote_target: notify
+                        .headers()
+                        .iter()
+                        .find_map(|header| match header {
+                            Header::Contact(contacts) => {
+                                contacts.first().map(|contact| contact.uri.clone())
+                            },
+                            _ => None,
+                        })
+                        .unwrap_or_else(|| self.target.clone()),
+                    route_set: notify.headers().record_route_set(),
+                    via: Some(self.via.clone()),
+                })
+            },
+        }
+
+        match state {
+            SubscriptionState::Active { expires, .. } => {
+                self.status = SubscriptionStatus::Active;
+                if let Some(expires) = expires {
+                    self.set_expires(expires, now);
+                }
+            },
+            SubscriptionState::Pending { expires, .. } => {
+                self.status = SubscriptionStatus::Pending;
+                if let Some(expires) = expires {
+                    self.set_expires(expires, now);
+                }
+            },
+            SubscriptionState::Terminated {
+                reason,
+                retry_after,
+                ..
+            } => {
+                self.status = SubscriptionStatus::Terminated {
+                    reason,
+                    retry_after,
+                };
+                self.refresh_at = None;
+            },
+            SubscriptionState::Other { .. } => {},
+        }
+        reply(notify, StatusCode::OK)
+    }
+
+    fn set_expires(&mut self, expires: u32, now: Instant) {
+        self.refresh_at = if expires == 0 {
+            None
+        } else {
+            Some(now + Duration::from_secs(u64::from(expires)) / 2)
+        };
+    }
+
+    /// The time at which the subscription has to be refreshed, half
+    /// of the remaining duration last given by the notifier.
+    pub fn refresh_at(&self) -> Option<Instant> {
+        self.refresh_at
+    }
+
+    /// Determine if the subscription has to be refreshed at `now`.
+    pub fn needs_refresh(&self, now: Instant) -> bool {
+        self.refresh_at.is_some_and(|at| at <= now)
+    }
+}
+
+fn reply(request: &SipMessage, code: StatusCode) -> IoResult<SipMessage> {
+    Ok(ResponseGenerator::reply_to(request, code).build()?)
+}
@@ -0,0 +1,123 @@
+use nom::error::VerboseError;
+
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
+
+use crate::{
+    core::{parse_status_line, StatusLine},
+    headers::{EventType, HeaderKind, ReplacesHeader},
+    *,
+};
+
+/// Create the Refer-To value asking to call `target`. For an attended
+/// transfer `replaces` identifies the dialog with `target` that the new
+/// call replaces, it is embedded as a header of the uri.
+/// ([RFC3891: Section 5, Use of Replaces with REFER](https://tools.ietf.org/html/rfc3891#section-5))
+/// # Examples
+///
+/// ```
+/// use libsip::{headers::ReplacesHeader, *};
+///
+/// let target = Uri::sip(domain!("example.org")).auth(uri_auth!("carol"));
+/// let replaces = ReplacesHeader::new("12345@example.org", "1", "2");
+/// assert_eq!(
+///     "<sip:carol@example.org?Replaces=12345%40example.org%3Bto-tag%3D1%3Bfrom-tag%3D2>",
+///     format!("{}", refer_to(target, Some(&replaces)))
+/// );
+/// ```
+pub fn refer_to(target: Uri, replaces: Option<&ReplacesHeader>) -> NamedHeader {
+    let uri = match replaces {
+        Some(replaces) => target.header("Replaces", replaces.to_string()),
+        None => target,
+    };
+    NamedHeader::new(uri)
+}
+
+/// Progress of a transfer, reported by the transferee in the NOTIFYs
+/// of the implicit subscription created by the REFER.
+/// ([RFC3515: Section 2.4.5, The Body of the NOTIFY](https://tools.ietf.org/html/rfc3515#section-2.4.5))
+#[derive(Debug, PartialEq, Clone)]
+pub enum TransferProgress {
+    /// The call to the target is in progress, with its last provisional
+    /// response.
+    Trying(StatusLine),
+    /// The target accepted the call.
+    Succeeded(StatusLine),
+    /// The call to the target failed with the given final response.
+    Failed(StatusLine),
+}
+
+impl TransferProgress {
+    /// Read the progress from a NOTIFY of the `refer` event whose body
+    /// is a `message/sipfrag`.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::{SipMessage, TransferProgress};
+    ///
+    /// let notify = SipMessage::parse(
+    ///     b"NOTIFY sip:alice@example.com SIP/2.0\r\n\
+    ///     Event: refer\r\n\
+    ///     Subscription-State: terminated;reason=noresource\r\n\
+    ///     Content-Type: message/sipfrag\r\n\
+    ///     Content-Length: 16\r\n\r\n\
+    ///     SIP/2.0 200 OK\r\n",
+    /// )
+    /// .unwrap();
+    /// let progress = TransferProgress::from_notify(&notify).unwrap();
+    /// assert!(progress.is_final());
+    /// assert_eq!(200, progress.status_line().code);
+    /// ```
+    pub fn from_notify(notify: &SipMessage) -> IoResult<TransferProgress> {
+        if notify.method() != Some(&Method::Notify) {
+            return Err(invalid("Expected a NOTIFY request"));
+        }
+        match notify.headers().event() {
+            Some(Header::Event(EventType::Refer, _)) => {},
+            _ => return Err(invalid("NOTIFY isn't for the refer event")),
+        }
+        match notify.header(HeaderKind::ContentType) {
+            Some(Header::ContentType(ty)) if ty.is("message", "sipfrag") => {},
+            _ => return Err(invalid("NOTIFY body isn't a message/sipfrag")),
+        }
+        let line = parse_sipfrag(notify.body())?;
+        Ok(if line.code < 200 {
+            TransferProgress::Trying(line)
+        } else if line.code < 300 {
+            TransferProgress::Succeeded(line)
+        } else {
+            TransferProgress::Failed(line)
+        })
+    }
+
+    /// The status line of the sipfrag.
+    pub fn status_line(&self) -> &StatusLine {
+        match self {
+            TransferProgress::Trying(line)
+            | TransferProgress::Succeeded(line)
+            | TransferProgress::Failed(line) => line,
+        }
+    }
+
+    /// Determine if the transfer completed, successfully or not.
+    pub fn is_final(&self) -> bool {
+        !matches!(self, TransferProgress::Trying(_))
+    }
+}
+
+/// Parse the status line of a `message/sipfrag` body, the only part of
+/// the fragment a transfer report is required to contain. Any header
+/// following the status line is ignored.
+/// ([RFC3420: Section 2, Syntax](https://tools.ietf.org/html/rfc3420#section-2))
+pub fn parse_sipfrag(body: &[u8]) -> IoResult<StatusLine> {
+    let mut line = body.to_vec();
+    if !line.ends_with(b"\r\n") {
+        line.extend_from_slice(b"\r\n");
+    }
+    parse_status_line::<VerboseError<&[u8]>>(&line)
+        .map(|(_, line)| line)
+        .map_err(|_| invalid("Failed to parse sipfrag status line"))
+}
+
+fn invalid(message: &str) -> IoError {
+    IoError::new(IoErrorKind::InvalidInput, message)
+}
@@ -0,0 +1,199 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::error::VerboseError;
+
+use std::fmt;
+
+use crate::{
+    core::StatusLine,
+    headers::{parse_header, ContentType, HeaderKind},
+    sdp::SessionDescription,
+    Header, Headers,
+};
+
+/// The body of a message decoded according to its Content-Type, see
+/// `SipMessage::typed_body`. Bodies of an unknown type, or that fail
+/// to decode, are kept as raw bytes.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Body {
+    /// An `application/sdp` session description.
+    Sdp(Box<SessionDescription>),
+    /// The status line of a `message/sipfrag`, as sent in the NOTIFYs
+    /// of a transfer.
+    SipFrag(StatusLine),
+    /// A `multipart/*` body.
+    Multipart(Multipart),
+    /// A `text/*` body, given the `text/plain` type when written.
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// The parts of a `multipart/*` body, separated by the `boundary`.
+/// ([RFC2046: Section 5.1, Multipart Media Type](https://tools.ietf.org/html/rfc2046#section-5.1))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Multipart {
+    /// The subtype of the body, e.g. `mixed`.
+    pub subtype: String,
+    pub boundary: String,
+    pub parts: Vec<BodyPart>,
+}
+
+/// A part of a multipart body, with the headers describing it.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BodyPart {
+    pub headers: Headers,
+    pub body: Body,
+}
+
+impl Body {
+    /// Decode `raw` according to `content_type`.
+    pub fn decode(content_type: Option<&ContentType>, raw: &[u8]) -> Body {
+        let content_type = match content_type {
+            Some(content_type) => content_type,
+            None => return Body::Binary(raw.to_vec()),
+        };
+        let decoded = if content_type.is("application", "sdp") {
+            SessionDescription::parse(raw)
+                .ok()
+                .map(|sdp| Body::Sdp(Box::new(sdp)))
+        } else if content_type.is("message", "sipfrag") {
+            crate::parse_sipfrag(raw).ok().map(Body::SipFrag)
+        } else if content_type.media_type == "multipart" {
+            content_type.boundary().and_then(|boundary| {
+                Multipart::parse(&content_type.subtype, boundary, raw).map(Body::Multipart)
+            })
+        } else if content_type.media_type == "text" {
+            String::from_utf8(raw.to_vec()).ok().map(Body::Text)
+        } else {
+            None
+        };
+        decoded.unwrap_or_else(|| Body::Binary(raw.to_vec()))
+    }
+
+    /// The Content-Type of this body, `None` for binary bodies whose
+    /// type isn't known.
+    pub fn content_type(&self) -> Option<ContentType> {
+        match self {
+            Body::Sdp(_) => Some(ContentType::sdp()),
+            Body::SipFrag(_) => Some(ContentType::sipfrag()),
+            Body::Multipart(multipart) => Some(multipart.content_type()),
+            Body::Text(_) => Some(ContentType::plain_text()),
+            Body::Binary(_) => None,
+        }
+    }
+
+    /// Encode this body.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Body::Sdp(sdp) => sdp.to_string().into_bytes(),
+            Body::SipFrag(line) => format!("{}\r\n", line).into_bytes(),
+            Body::Multipart(multipart) => multipart.to_bytes(),
+            Body::Text(text) => text.clone().into_bytes(),
+            Body::Binary(raw) => raw.clone(),
+        }
+    }
+}
+
+impl Multipart {
+    /// Create a `multipart/mixed` body of `parts` with a random boundary.
+    pub fn mixed(parts: Vec<BodyPart>) -> Multipart {
+        Multipart {
+            subtype: "mixed".into(),
+            boundary: format!("{:x}", md5::compute(rand::random::<[u8; 16]>())),
+            parts,
+        }
+    }
+
+    /// Split `raw` at the delimiters made of `boundary`, decoding every
+    /// part according to its own Content-Type. Returns `None` if the
+    /// closing delimiter or the headers of a part are malformed.
+    pub fn parse(subtype: &str, boundary: &str, raw: &[u8]) -> Option<Multipart> {
+        let delimiter = format!("\r\n--{}", boundary).into_bytes();
+        let mut data = b"\r\n".to_vec();
+        data.extend_from_slice(raw);
+
+        let mut parts = vec![];
+        let mut pos = find(&data, &delimiter)? + delimiter.len();
+        while !data[pos..].starts_with(b"--") {
+            let start = pos + find(&data[pos..], b"\r\n")? + 2;
+            let end = start + find(&data[start..], &delimiter)?;
+            parts.push(BodyPart::parse(&data[start..end])?);
+            pos = end + delimiter.len();
+        }
+        Some(Multipart {
+            subtype: subtype.to_string(),
+            boundary: boundary.to_string(),
+            parts,
+        })
+    }
+
+    /// The Content-Type of this body, with its boundary.
+    pub fn content_type(&self) -> ContentType {
+        ContentType::new("multipart", self.subtype.clone())
+            .param("boundary", Some(self.boundary.clone()))
+    }
+
+    /// Encode every part, each preceded by a delimiter.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![];
+        for part in &self.parts {
+            out.extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+            for header in part.headers.iter() {
+                out.extend_from_slice(format!("{}\r\n", header).as_bytes());
+            }
+            out.extend_from_slice(b"\r\n");
+            out.extend_from_slice(&part.body.to_bytes());
+            out.extend_from_slice(b"\r\n");
+        }
+        out.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        out
+    }
+}
+
+impl BodyPart {
+    /// Create a part holding `body`, described by its Content-Type.
+    pub fn new(body: Body) -> BodyPart {
+        let mut headers = Headers::new();
+        if let Some(content_type) = body.content_type() {
+            headers.push(Header::ContentType(content_type));
+        }
+        BodyPart { headers, body }
+    }
+
+    fn parse(raw: &[u8]) -> Option<BodyPart> {
+        let (mut input, content) = if raw.starts_with(b"\r\n") {
+            (&raw[..0], &raw[2..])
+        } else {
+            let end = find(raw, b"\r\n\r\n")?;
+            (&raw[..end + 2], &raw[end + 4..])
+        };
+        let mut headers = Headers::new();
+        while !input.is_empty() {
+            let (rest, header) = parse_header::<VerboseError<&[u8]>>(input).ok()?;
+            headers.push(header);
+            input = rest;
+        }
+        let content_type = match headers.get(HeaderKind::ContentType) {
+            Some(Header::ContentType(content_type)) => Some(content_type),
+            _ => None,
+        };
+        let body = Body::decode(content_type, content);
+        Some(BodyPart { headers, body })
+    }
+}
+
+impl fmt::Display for Body {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.to_bytes()))
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
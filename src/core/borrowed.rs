@@ -0,0 +1,279 @@
+use nom::error::VerboseError;
+
+use std::str;
+
+use crate::{
+    core::{ParseError, SipMessage},
+    headers::{compact_form, parse_header, Header},
+};
+
+/// Start line of a message parsed by `parse_message_borrowed`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum StartLineRef<'a> {
+    Request {
+        method: &'a str,
+        uri: &'a str,
+        version: &'a str,
+    },
+    Response {
+        version: &'a str,
+        code: u32,
+        reason: &'a str,
+    },
+}
+
+/// A header line parsed by `parse_message_borrowed`. The value has the
+/// surrounding whitespace removed but is otherwise as received, including
+/// any folded lines.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct HeaderRef<'a> {
+    pub name: &'a str,
+    pub value: &'a str,
+    raw: &'a [u8],
+}
+
+impl<'a> HeaderRef<'a> {
+    /// Parse this header into the owned `Header` type.
+    pub fn to_owned(&self) -> Result<Header, ParseError> {
+        match parse_header::<VerboseError<&[u8]>>(self.raw) {
+            Ok((_, header)) => Ok(header),
+            Err(nom::Err::Error((remains, kind))) | Err(nom::Err::Failure((remains, kind))) => {
+                Err(ParseError::header(self.raw, 0, remains, kind))
+            },
+            Err(nom::Err::Incomplete(_)) => Err(ParseError::Incomplete { needed: None }),
+        }
+    }
+}
+
+/// A SIP message borrowing its start line, headers and body from the
+/// input it was parsed from, avoiding any copies or header parsing for
+/// workloads such as proxies that only look at a few headers.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SipMessageRef<'a> {
+    pub start_line: StartLineRef<'a>,
+    pub headers: Vec<HeaderRef<'a>>,
+    pub body: &'a [u8],
+    raw: &'a [u8],
+}
+
+impl<'a> SipMessageRef<'a> {
+    /// Determine if this message is a request.
+    pub fn is_request(&self) -> bool {
+        matches!(self.start_line, StartLineRef::Request { .. })
+    }
+
+    /// Determine if this message is a response.
+    pub fn is_response(&self) -> bool {
+        matches!(self.start_line, StartLineRef::Response { .. })
+    }
+
+    /// The request method, if this message is a request.
+    pub fn method(&self) -> Option<&'a str> {
+        match self.start_line {
+            StartLineRef::Request { method, .. } => Some(method),
+            StartLineRef::Response { .. } => None,
+        }
+    }
+
+    /// The request uri, if this message is a request.
+    pub fn uri(&self) -> Option<&'a str> {
+        match self.start_line {
+            StartLineRef::Request { uri, .. } => Some(uri),
+            StartLineRef::Response { .. } => None,
+        }
+    }
+
+    /// The status code, if this message is a response.
+    pub fn status_code(&self) -> Option<u32> {
+        match self.start_line {
+            StartLineRef::Response { code, .. } => Some(code),
+            StartLineRef::Request { .. } => None,
+        }
+    }
+
+    /// The value of the first header called `name`, ignoring case.
+    /// Headers sent in their compact form are matched as well when `name`
+    /// is the full header name, e.g. `Call-ID` matches `i`.
+    pub fn header(&self, name: &str) -> Option<&'a str> {
+        self.header_values(name).next()
+    }
+
+    /// The values of every header called `name`, in order. See `header`.
+    pub fn header_values<'s>(&'s self, name: &'s str) -> impl Iterator<Item = &'a str> + 's {
+        self.find_headers(name).map(|header| header.value)
+    }
+
+    fn find_headers<'s>(&'s self, name: &'s str) -> impl Iterator<Item = &'s HeaderRef<'a>> {
+        let compact = compact_form(name);
+        self.headers.iter().filter(move |header| {
+            header.name.eq_ignore_ascii_case(name)
+                || matches!(compact, Some(compact) if header.name.eq_ignore_ascii_case(compact))
+        })
+    }
+
+    /// The bytes this message was parsed from, including the body.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.raw
+    }
+
+    /// Parse this message into the owned `SipMessage` type.
+    pub fn to_owned(&self) -> Result<SipMessage, ParseError> {
+        SipMessage::parse(self.raw)
+    }
+}
+
+/// Parse a SIP message without copying it. Only the start line and the
+/// header names are checked, header values are left unparsed until
+/// converted with `to_owned`. The body is read according to
+/// Content-Length or is the rest of the input when there is none.
+/// # Examples
+///
+/// ```
+/// use libsip::core::borrowed::parse_message_borrowed;
+///
+/// let input = b"MESSAGE sip:bob@example.com SIP/2.0\r\nCall-ID: 1234\r\nl: 2\r\n\r\nhi";
+/// let msg = parse_message_borrowed(input).unwrap();
+/// assert_eq!(Some("sip:bob@example.com"), msg.uri());
+/// assert_eq!(Some("1234"), msg.header("call-id"));
+/// assert_eq!(Some("2"), msg.header("Content-Length"));
+/// assert_eq!(b"hi", msg.body);
+/// ```
+pub fn parse_message_borrowed(input: &[u8]) -> Result<SipMessageRef<'_>, ParseError> {
+    let line_end = find_crlf(input, 0).ok_or(ParseError::Incomplete { needed: None })?;
+    let start_line = parse_start_line(&input[..line_end])?;
+
+    let mut headers = vec![];
+    let mut position = line_end + 2;
+    while !input[position..].starts_with(b"\r\n") {
+        let line_start = position;
+        let mut end = find_crlf(input, position).ok_or(ParseError::Incomplete { needed: None })?;
+        while matches!(input.get(end + 2), Some(b' ') | Some(b'\t')) {
+            end = find_crlf(input, end + 2).ok_or(ParseError::Incomplete { needed: None })?;
+        }
+        position = end + 2;
+        headers.push(parse_header_line(input, line_start, end)?);
+    }
+    let body_start = position + 2;
+
+    let mut message = SipMessageRef {
+        start_line,
+        headers,
+        body: &input[body_start..],
+        raw: input,
+    };
+    let content_length = message.find_headers("Content-Length").next().copied();
+    if let Some(header) = content_length {
+        let length = header
+            .value
+            .parse::<usize>()
+            .map_err(|_| header_error(input, header.raw, "digit"))?;
+        if message.body.len() < length {
+            return Err(ParseError::Incomplete {
+                needed: Some(length - message.body.len()),
+            });
+        }
+        message.body = &input[body_start..body_start + length];
+        message.raw = &input[..body_start + length];
+    }
+    Ok(message)
+}
+
+fn find_crlf(input: &[u8], from: usize) -> Option<usize> {
+    input[from..]
+        .windows(2)
+        .position(|window| window == b"\r\n")
+        .map(|index| from + index)
+}
+
+fn parse_start_line(line: &[u8]) -> Result<StartLineRef<'_>, ParseError> {
+    let line = str::from_utf8(line).map_err(|error| ParseError::StartLine {
+        offset: error.valid_up_to(),
+        expected: String::from("UTF-8"),
+    })?;
+    let mut parts = line.splitn(3, ' ');
+    let first = parts.next().unwrap_or_default();
+    let second = parts.next().ok_or_else(|| ParseError::StartLine {
+        offset: line.len(),
+        expected: String::from("' '"),
+    })?;
+    let third = parts.next();
+    if first.starts_with("SIP/") {
+        if first != "SIP/2.0" {
+            return Err(ParseError::UnsupportedVersion { offset: 0 });
+        }
+        let offset = first.len() + 1;
+        let code = match second.parse::<u32>() {
+            Ok(code) if second.len() == 3 => code,
+            _ => {
+                return Err(ParseError::StartLine {
+                    offset,
+                    expected: String::from("status code"),
+                })
+            },
+        };
+        Ok(StartLineRef::Response {
+            version: first,
+            code,
+            reason: third.unwrap_or_default().trim(),
+        })
+    } else {
+        let offset = first.len() + second.len() + 2;
+        let version = third.ok_or_else(|| ParseError::StartLine {
+            offset: line.len(),
+            expected: String::from("' '"),
+        })?;
+        if !version.starts_with("SIP/") {
+            return Err(ParseError::StartLine {
+                offset,
+                expected: String::from("SIP version"),
+            });
+        }
+        if version != "SIP/2.0" {
+            return Err(ParseError::UnsupportedVersion { offset });
+        }
+        if first.is_empty() || second.is_empty() {
+            return Err(ParseError::StartLine {
+                offset: 0,
+                expected: String::from("method"),
+            });
+        }
+        Ok(StartLineRef::Request {
+            method: first,
+            uri: second,
+            version,
+        })
+    }
+}
+
+fn parse_header_line(input: &[u8], start: usize, end: usize) -> Result<HeaderRef<'_>, ParseError> {
+    let raw = &input[start..end + 2];
+    let line = str::from_utf8(&input[start..end]).map_err(|_| header_error(input, raw, "UTF-8"))?;
+    let colon = line
+        .find(':')
+        .ok_or_else(|| header_error(input, raw, "':'"))?;
+    let name = line[..colon].trim_end();
+    if name.is_empty() || name.starts_with(|chr: char| chr.is_ascii_whitespace()) {
+        return Err(header_error(input, raw, "header name"));
+    }
+    Ok(HeaderRef {
+        name,
+        value: line[colon + 1..].trim(),
+        raw,
+    })
+}
+
+fn header_error(input: &[u8], raw: &[u8], expected: &str) -> ParseError {
+    let start = raw.as_ptr() as usize - input.as_ptr() as usize;
+    let line = &input[start..start + raw.len() - 2];
+    let name = line.split(|byte| *byte == b':').next().unwrap_or(line);
+    ParseError::Header {
+        name: String::from_utf8_lossy(name).trim().to_string(),
+        line: input[..start]
+            .windows(2)
+            .filter(|window| *window == b"\r\n")
+            .count()
+            + 1,
+        offset: start,
+        expected: expected.to_string(),
+    }
+}
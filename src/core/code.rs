@@ -1,79 +1,180 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::fmt;
+
+macro_rules! status_codes {
+    ($($name:ident => ($code:tt, $reason:tt),)*) => {
+        impl StatusCode {
+            $(
+                #[doc = $reason]
+                pub const $name: StatusCode = StatusCode($code);
+            )*
+
+            /// The canonical reason phrase of this status code, if it is
+            /// a registered code.
+            pub fn reason_phrase(&self) -> Option<&'static str> {
+                match self.0 {
+                    $($code => Some($reason),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+/// A SIP response status code
+/// ([RFC3261: Section 21, Response Codes](https://tools.ietf.org/html/rfc3261#section-21)).
+///
+/// Registered codes are available as constants, e.g. `StatusCode::BUSY_HERE`,
+/// but any three digit code can be used.
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StatusCode(pub u32);
+
+status_codes! {
+    TRYING => (100, "Trying"),
+    RINGING => (180, "Ringing"),
+    CALL_IS_BEING_FORWARDED => (181, "Call is Being Forwarded"),
+    QUEUED => (182, "Queued"),
+    SESSION_PROGRESS => (183, "Session Progress"),
+    EARLY_DIALOG_TERMINATED => (199, "Early Dialog Terminated"),
+    OK => (200, "OK"),
+    ACCEPTED => (202, "Accepted"),
+    NO_NOTIFICATION => (204, "No Notification"),
+    MULTIPLE_CHOICES => (300, "Multiple Choices"),
+    MOVED_PERMANENTLY => (301, "Moved Permanently"),
+    MOVED_TEMPORARILY => (302, "Moved Temporarily"),
+    USE_PROXY => (305, "Use Proxy"),
+    ALTERNATIVE_SERVICE => (380, "Alternative Service"),
+    BAD_REQUEST => (400, "Bad Request"),
+    UNAUTHORIZED => (401, "Unauthorized"),
+    PAYMENT_REQUIRED => (402, "Payment Required"),
+    FORBIDDEN => (403, "Forbidden"),
+    NOT_FOUND => (404, "Not Found"),
+    METHOD_NOT_ALLOWED => (405, "Method Not Allowed"),
+    NOT_ACCEPTABLE => (406, "Not Acceptable"),
+    PROXY_AUTHENTICATION_REQUIRED => (407, "Proxy Authentication Required"),
+    REQUEST_TIMEOUT => (408, "Request Timeout"),
+    CONFLICT => (409, "Conflict"),
+    GONE => (410, "Gone"),
+    LENGTH_REQUIRED => (411, "Length Required"),
+    CONDITIONAL_REQUEST_FAILED => (412, "Conditional Request Failed"),
+    REQUEST_ENTITY_TOO_LARGE => (413, "Request Entity Too Large"),
+    REQUEST_URI_TOO_LONG => (414, "Request-URI Too Long"),
+    UNSUPPORTED_MEDIA_TYPE => (415, "Unsupported Media Type"),
+    UNSUPPORTED_URI_SCHEME => (416, "Unsupported URI Scheme"),
+    UNKNOWN_RESOURCE_PRIORITY => (417, "Unknown Resource-Priority"),
+    BAD_EXTENSION => (420, "Bad Extension"),
+    EXTENSION_REQUIRED => (421, "Extension Required"),
+    SESSION_INTERVAL_TOO_SMALL => (422, "Session Interval Too Small"),
+    INTERVAL_TOO_BRIEF => (423, "Interval Too Brief"),
+    BAD_LOCATION_INFORMATION => (424, "Bad Location Information"),
+    USE_IDENTITY_HEADER => (428, "Use Identity Header"),
+    PROVIDE_REFERRER_IDENTITY => (429, "Provide Referrer Identity"),
+    ANONYMITY_DISALLOWED => (433, "Anonymity Disallowed"),
+    BAD_IDENTITY_INFO => (436, "Bad Identity-Info"),
+    UNSUPPORTED_CERTIFICATE => (437, "Unsupported Certificate"),
+    INVALID_IDENTITY_HEADER => (438, "Invalid Identity Header"),
+    FIRST_HOP_LACKS_OUTBOUND_SUPPORT => (439, "First Hop Lacks Outbound Support"),
+    MAX_BREADTH_EXCEEDED => (440, "Max-Breadth Exceeded"),
+    BAD_INFO_PACKAGE => (469, "Bad Info Package"),
+    CONSENT_NEEDED => (470, "Consent Needed"),
+    TEMPORARILY_UNAVAILABLE => (480, "Temporarily Unavailable"),
+    CALL_OR_TRANSACTION_DOES_NOT_EXIST => (481, "Call/Transaction Does Not Exist"),
+    LOOP_DETECTED => (482, "Loop Detected"),
+    TOO_MANY_HOPS => (483, "Too Many Hops"),
+    ADDRESS_INCOMPLETE => (484, "Address Incomplete"),
+    AMBIGUOUS => (485, "Ambiguous"),
+    BUSY_HERE => (486, "Busy Here"),
+    REQUEST_TERMINATED => (487, "Request Terminated"),
+    NOT_ACCEPTABLE_HERE => (488, "Not Acceptable Here"),
+    BAD_EVENT => (489, "Bad Event"),
+    REQUEST_PENDING => (491, "Request Pending"),
+    UNDECIPHERABLE => (493, "Undecipherable"),
+    SECURITY_AGREEMENT_REQUIRED => (494, "Security Agreement Required"),
+    SERVER_INTERNAL_ERROR => (500, "Server Internal Error"),
+    NOT_IMPLEMENTED => (501, "Not Implemented"),
+    BAD_GATEWAY => (502, "Bad Gateway"),
+    SERVICE_UNAVAILABLE => (503, "Service Unavailable"),
+    SERVER_TIMEOUT => (504, "Server Time-out"),
+    VERSION_NOT_SUPPORTED => (505, "Version Not Supported"),
+    MESSAGE_TOO_LARGE => (513, "Message Too Large"),
+    PRECONDITION_FAILURE => (580, "Precondition Failure"),
+    BUSY_EVERYWHERE => (600, "Busy Everywhere"),
+    DECLINE => (603, "Decline"),
+    DOES_NOT_EXIST_ANYWHERE => (604, "Does Not Exist Anywhere"),
+    NOT_ACCEPTABLE_ANYWHERE => (606, "Not Acceptable"),
+    UNWANTED => (607, "Unwanted"),
+}
+
+impl StatusCode {
+    /// The numeric value of this status code.
+    pub fn as_u32(&self) -> u32 {
+        self.0
+    }
+
+    /// Determine if this is a provisional (1xx) response code.
+    pub fn is_provisional(&self) -> bool {
+        (100..200).contains(&self.0)
+    }
+
+    /// Determine if this is a success (2xx) response code.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.0)
+    }
+
+    /// Determine if this is a redirection (3xx) response code.
+    pub fn is_redirect(&self) -> bool {
+        (300..400).contains(&self.0)
+    }
+
+    /// Determine if this is a client failure (4xx) response code.
+    pub fn is_client_error(&self) -> bool {
+        (400..500).contains(&self.0)
+    }
+
+    /// Determine if this is a server failure (5xx) response code.
+    pub fn is_server_error(&self) -> bool {
+        (500..600).contains(&self.0)
+    }
+
+    /// Determine if this is a global failure (6xx) response code.
+    pub fn is_global_failure(&self) -> bool {
+        (600..700).contains(&self.0)
+    }
+
+    /// Determine if this is a final response code, i.e. not provisional.
+    pub fn is_final(&self) -> bool {
+        self.0 >= 200
+    }
+}
+
+impl From<u32> for StatusCode {
+    fn from(code: u32) -> StatusCode {
+        StatusCode(code)
+    }
+}
+
+impl From<StatusCode> for u32 {
+    fn from(code: StatusCode) -> u32 {
+        code.0
+    }
+}
+
+impl PartialEq<u32> for StatusCode {
+    fn eq(&self, other: &u32) -> bool {
+        self.0 == *other
+    }
+}
+
+impl fmt::Display for StatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Get the response code string for the given SIP response code.
 pub fn error_code_to_str(code: u32) -> Option<&'static str> {
-    match code {
-        100 => Some("Trying"),
-        180 => Some("Ringing"),
-        181 => Some("Call is Being Forwarded"),
-        182 => Some("Queued"),
-        183 => Some("Session Progress"),
-        199 => Some("Early Dialog Terminated"),
-        200 => Some("OK"),
-        202 => Some("Accepted"),
-        204 => Some("No Notification"),
-        300 => Some("Multiple Choices"),
-        301 => Some("Moved Permanently"),
-        302 => Some("Moved Temporarily"),
-        305 => Some("Use Proxy"),
-        380 => Some("Alternative Service"),
-        400 => Some("Bad Request"),
-        401 => Some("Unauthorized"),
-        402 => Some("Payment Required"),
-        403 => Some("Forbidden"),
-        404 => Some("Not Found"),
-        405 => Some("Method Not Allowed"),
-        406 => Some("Not Acceptable"),
-        407 => Some("Proxy Authentication Required"),
-        408 => Some("Request Timeout"),
-        409 => Some("Conflict"),
-        410 => Some("Gone"),
-        411 => Some("Length Required"),
-        412 => Some("Conditional Request Failed"),
-        413 => Some("Request Entity Too Large"),
-        414 => Some("Request-URI Too Long"),
-        415 => Some("Unsupported Media Type"),
-        416 => Some("Unsupported URI Scheme"),
-        417 => Some("Unknown Resource-Priority"),
-        420 => Some("Bad Extension"),
-        421 => Some("Extension Required"),
-        422 => Some("Session Interval Too Small"),
-        423 => Some("Interval Too Brief"),
-        424 => Some("Bad Location Information"),
-        428 => Some("Use Identity Header"),
-        429 => Some("Provide Referrer Identity"),
-        433 => Some("Anonymity Disallowed"),
-        436 => Some("Bad Identity-Info"),
-        437 => Some("Unsupported Certificate"),
-        438 => Some("Invalid Identity Header"),
-        439 => Some("First Hop Lacks Outbound Support"),
-        440 => Some("Max-Breadth Exceeded"),
-        469 => Some("Bad Info Package"),
-        470 => Some("Consent Needed"),
-        480 => Some("Temporarily Unavailable"),
-        481 => Some("Call/Transaction Does Not Exist"),
-        482 => Some("Loop Detected"),
-        483 => Some("Too Many Hops"),
-        484 => Some("Address Incomplete"),
-        485 => Some("Ambiguous"),
-        486 => Some("Busy Here"),
-        487 => Some("Request Terminated"),
-        488 => Some("Not Acceptable Here"),
-        489 => Some("Bad Event"),
-        491 => Some("Request Pending"),
-        493 => Some("Undecipherable"),
-        494 => Some("Security Agreement Required"),
-        500 => Some("Server Internal Error"),
-        501 => Some("Not Implemented"),
-        502 => Some("Bad Gateway"),
-        503 => Some("Service Unavailable"),
-        504 => Some("Server Time-out"),
-        505 => Some("Version Not Supported"),
-        513 => Some("Message Too Large"),
-        580 => Some("Precondition Failure"),
-        600 => Some("Busy Everywhere"),
-        603 => Some("Decline"),
-        604 => Some("Does Not Exist Anywhere"),
-        606 => Some("Not Acceptable"),
-        607 => Some("Unwanted"),
-        _ => None,
-    }
+    StatusCode(code).reason_phrase()
 }
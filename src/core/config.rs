@@ -0,0 +1,132 @@
+use crate::core::{ParseError, SipMessage};
+
+/// Deviations from RFC3261 that are accepted when parsing a message.
+/// The default configuration is strict and accepts none of them.
+///
+/// Lenient parsing works by rewriting the start line and headers of a
+/// message into their strict form before they are parsed, the body is
+/// never modified.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct ParseConfig {
+    /// Accept lines ending with `\n` instead of `\r\n`.
+    pub allow_bare_lf: bool,
+    /// Ignore spaces and tabs at the end of the start line and header lines.
+    pub allow_trailing_whitespace: bool,
+}
+
+impl ParseConfig {
+    /// Only accept messages following RFC3261.
+    pub fn strict() -> ParseConfig {
+        ParseConfig::default()
+    }
+
+    /// Accept bare `\n` line endings and trailing whitespace, as sent by
+    /// many embedded devices.
+    pub fn lenient() -> ParseConfig {
+        ParseConfig {
+            allow_bare_lf: true,
+            allow_trailing_whitespace: true,
+        }
+    }
+
+    /// Determine if no deviations are accepted.
+    pub fn is_strict(&self) -> bool {
+        *self == ParseConfig::strict()
+    }
+
+    /// Find the index just after the empty line that ends the start line
+    /// and headers of a message.
+    pub fn find_head_end(&self, data: &[u8]) -> Option<usize> {
+        if !self.allow_bare_lf {
+            return data
+                .windows(4)
+                .position(|window| window == b"\r\n\r\n")
+                .map(|index| index + 4);
+        }
+        data.iter().enumerate().find_map(|(index, byte)| {
+            if *byte != b'\n' {
+                None
+            } else if data[index + 1..].starts_with(b"\n") {
+                Some(index + 2)
+            } else if data[index + 1..].starts_with(b"\r\n") {
+                Some(index + 3)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Rewrite the start line and headers of a message (everything up to and
+    /// including the empty line) so they can be parsed by the strict parsers.
+    pub fn normalize_head(&self, head: &[u8]) -> Vec<u8> {
+        self.normalize_head_with_lines(head).0
+    }
+
+    /// Rewrite `head` like `normalize_head`, also returning the offset of
+    /// every line in the rewritten head along with its offset in `head`.
+    fn normalize_head_with_lines(&self, head: &[u8]) -> (Vec<u8>, Vec<(usize, usize)>) {
+        let mut out = Vec::with_capacity(head.len() + 16);
+        let mut lines = vec![];
+        let mut start = 0;
+        for piece in head.split_inclusive(|byte| *byte == b'\n') {
+            lines.push((out.len(), start));
+            start += piece.len();
+            let mut line = if piece.ends_with(b"\r\n") {
+                &piece[..piece.len() - 2]
+            } else if piece.ends_with(b"\n") && self.allow_bare_lf {
+                &piece[..piece.len() - 1]
+            } else {
+                out.extend_from_slice(piece);
+                continue;
+            };
+            if self.allow_trailing_whitespace {
+                while let [rest @ .., b' '] | [rest @ .., b'\t'] = line {
+                    line = rest;
+                }
+            }
+            out.extend_from_slice(line);
+            out.extend_from_slice(b"\r\n");
+        }
+        (out, lines)
+    }
+}
+
+/// Parse a complete SIP message, e.g. a received datagram, accepting the
+/// deviations allowed by `config`, see `SipMessage::parse_with_config`.
+/// The offsets of the errors returned refer to `input`.
+/// # Examples
+///
+/// ```
+/// use libsip::core::config::{parse_message_with_config, ParseConfig};
+///
+/// let input = b"SIP/2.0 200 OK \nContent-Length: 0\n\n";
+/// assert!(parse_message_with_config(input, &ParseConfig::strict()).is_err());
+/// let msg = parse_message_with_config(input, &ParseConfig::lenient()).unwrap();
+/// assert_eq!(Some(200), msg.status_code());
+/// ```
+pub fn parse_message_with_config(
+    input: &[u8],
+    config: &ParseConfig,
+) -> Result<SipMessage, ParseError> {
+    if config.is_strict() {
+        return SipMessage::parse(input);
+    }
+    let head_end = config.find_head_end(input).unwrap_or(input.len());
+    let (mut data, lines) = config.normalize_head_with_lines(&input[..head_end]);
+    let normalized_end = data.len();
+    data.extend_from_slice(&input[head_end..]);
+    SipMessage::parse(&data).map_err(|error| {
+        error.map_offset(|offset| {
+            if offset >= normalized_end {
+                return offset - normalized_end + head_end;
+            }
+            let (line_start, start) = lines
+                .iter()
+                .rev()
+                .find(|(line_start, _)| *line_start <= offset)
+                .copied()
+                .unwrap_or((0, 0));
+            (start + offset - line_start).min(head_end)
+        })
+    })
+}
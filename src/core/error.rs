@@ -0,0 +1,143 @@
+use nom::error::{ErrorKind, VerboseError, VerboseErrorKind};
+
+use std::{error::Error, fmt};
+
+/// Error returned by `SipMessage::parse`, describing where and why a
+/// message could not be parsed. Offsets are byte offsets from the start
+/// of the message.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParseError {
+    /// The request line or status line is malformed.
+    StartLine { offset: usize, expected: String },
+    /// The message uses a SIP version other than `SIP/2.0`.
+    UnsupportedVersion { offset: usize },
+    /// A header line is malformed. `line` is the line number within the
+    /// message, the start line being line 1.
+    Header {
+        name: String,
+        line: usize,
+        offset: usize,
+        expected: String,
+    },
+    /// The input ended before the empty line following the headers, or
+    /// before the number of body bytes given by Content-Length.
+    Incomplete { needed: Option<usize> },
+}
+
+impl ParseError {
+    /// The byte offset at which the error occurred, if known.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            ParseError::StartLine { offset, .. }
+            | ParseError::UnsupportedVersion { offset }
+            | ParseError::Header { offset, .. } => Some(*offset),
+            ParseError::Incomplete { .. } => None,
+        }
+    }
+
+    /// Replace the byte offset of this error with `map(offset)`, e.g. to
+    /// refer to the input before it was rewritten.
+    pub(crate) fn map_offset<F: Fn(usize) -> usize>(self, map: F) -> ParseError {
+        match self {
+            ParseError::StartLine { offset, expected } => ParseError::StartLine {
+                offset: map(offset),
+                expected,
+            },
+            ParseError::UnsupportedVersion { offset } => ParseError::UnsupportedVersion {
+                offset: map(offset),
+            },
+            ParseError::Header {
+                name,
+                line,
+                offset,
+                expected,
+            } => ParseError::Header {
+                name,
+                line,
+                offset: map(offset),
+                expected,
+            },
+            ParseError::Incomplete { needed } => ParseError::Incomplete { needed },
+        }
+    }
+
+    /// Create a StartLine error from the error of a nom parser run on `input`.
+    pub(crate) fn start_line(input: &[u8], error: &VerboseError<&[u8]>) -> ParseError {
+        let (offset, expected) = describe(input, error);
+        ParseError::StartLine { offset, expected }
+    }
+
+    /// Create a Header error for the header line starting at `line_start`,
+    /// `remains` being the input at which the header parser failed.
+    pub(crate) fn header(
+        input: &[u8],
+        line_start: usize,
+        remains: &[u8],
+        kind: ErrorKind,
+    ) -> ParseError {
+        let line = &input[line_start..];
+        let end = line.windows(2).position(|window| window == b"\r\n");
+        let line = &line[..end.unwrap_or(line.len())];
+        let name = line.split(|byte| *byte == b':').next().unwrap_or(line);
+        let offset = input.len().saturating_sub(remains.len());
+        ParseError::Header {
+            name: String::from_utf8_lossy(name).trim().to_string(),
+            line: input[..line_start]
+                .windows(2)
+                .filter(|window| *window == b"\r\n")
+                .count()
+                + 1,
+            offset: offset.max(line_start),
+            expected: kind.description().to_string(),
+        }
+    }
+}
+
+/// Get the offset and a hint of what was expected from the innermost
+/// error of a parser run on `input`.
+fn describe(input: &[u8], error: &VerboseError<&[u8]>) -> (usize, String) {
+    match error.errors.first() {
+        Some((remains, kind)) => {
+            let expected = match kind {
+                VerboseErrorKind::Context(context) => context.to_string(),
+                VerboseErrorKind::Char(chr) => format!("'{}'", chr.escape_default()),
+                VerboseErrorKind::Nom(kind) => kind.description().to_string(),
+            };
+            (input.len().saturating_sub(remains.len()), expected)
+        },
+        None => (0, String::from("valid input")),
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::StartLine { offset, expected } => write!(
+                f,
+                "invalid start line at byte {}: expected {}",
+                offset, expected
+            ),
+            ParseError::UnsupportedVersion { offset } => {
+                write!(f, "unsupported SIP version at byte {}", offset)
+            },
+            ParseError::Header {
+                name,
+                line,
+                offset,
+                expected,
+            } => write!(
+                f,
+                "invalid {} header on line {} at byte {}: expected {}",
+                name, line, offset, expected
+            ),
+            ParseError::Incomplete {
+                needed: Some(needed),
+            } => {
+                write!(f, "incomplete message: {} more bytes needed", needed)
+            },
+            ParseError::Incomplete { needed: None } => write!(f, "incomplete message"),
+        }
+    }
+}
+
+impl Error for ParseError {}
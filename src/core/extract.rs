@@ -1,14 +1,16 @@
-use std::{collections::HashMap, str::FromStr};
+use std::str::FromStr;
+
+use crate::headers::GenericParams;
 
 /// Extracts a parameter with a given name from parameters if this parameter has any value
 /// # Examples
 /// ```
 /// use libsip::core::extract_opt_param;
-/// use std::collections::HashMap;
+/// use libsip::headers::GenericParams;
 ///
-/// let mut params = HashMap::new();
-/// params.insert("param1".to_string(), Some("value".to_string()));
-/// params.insert("param2".to_string(), None);
+/// let mut params = GenericParams::new();
+/// params.insert("param1", Some("value"));
+/// params.insert("param2", None::<String>);
 ///
 /// let mut param1: Option<String> = None;
 /// extract_opt_param(&mut params, "param1", &mut param1);
@@ -21,7 +23,7 @@ use std::{collections::HashMap, str::FromStr};
 /// assert_eq!(params.get("param2"), Some(&None));
 /// ```
 pub fn extract_opt_param<V: FromStr>(
-    params: &mut HashMap<String, Option<String>>,
+    params: &mut GenericParams,
     param: &str,
     extracted_value: &mut Option<V>,
 ) {
@@ -0,0 +1,156 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while, take_while1},
+    character::{complete::char, is_digit},
+    combinator::{map_res, opt},
+    error::{ErrorKind, ParseError},
+    sequence::terminated,
+    IResult,
+};
+
+use std::fmt;
+
+use crate::{
+    core::{method::parse_method, version::parse_version, Method, Version},
+    parse::{parse_u32, slice_to_string_nullable},
+    uri::{parse_absolute_uri, parse_uri, Uri},
+};
+
+/// First line of a SIP request, e.g. `INVITE sip:bob@example.com SIP/2.0`.
+/// ([RFC3261: Section 7.1, Requests](https://tools.ietf.org/html/rfc3261#section-7.1))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RequestLine {
+    pub method: Method,
+    pub uri: Uri,
+    pub version: Version,
+}
+
+impl fmt::Display for RequestLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.method, self.uri, self.version)
+    }
+}
+
+/// First line of a SIP response, e.g. `SIP/2.0 180 Ringing`.
+/// ([RFC3261: Section 7.2, Responses](https://tools.ietf.org/html/rfc3261#section-7.2))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StatusLine {
+    pub version: Version,
+    pub code: u32,
+    /// The reason phrase as it was received, which may differ from the
+    /// default phrase of `code`.
+    pub reason: Option<String>,
+}
+
+impl fmt::Display for StatusLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.version, self.code)?;
+        if let Some(reason) = &self.reason {
+            write!(f, " {}", reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse the SIP version of a request or status line. Only `SIP/2.0` is
+/// supported, any other version results in a `nom::Err::Failure` with
+/// `ErrorKind::Verify` pointing at the version so it is not mistaken for
+/// a malformed line.
+pub fn parse_supported_version<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Version, E> {
+    let (rest, version) = parse_version(input)?;
+    if version != Version::default() {
+        return Err(nom::Err::Failure(E::from_error_kind(
+            input,
+            ErrorKind::Verify,
+        )));
+    }
+    Ok((rest, version))
+}
+
+/// Parse a request line including the trailing `\r\n`. Besides sip, sips
+/// and tel uris the Request-URI may be any absolute uri, as used for
+/// emergency calls to `urn:service:sos`.
+/// # Examples
+///
+/// ```
+/// use libsip::{core::line::parse_request_line, Method};
+/// use nom::error::VerboseError;
+///
+/// let (_, line) =
+///     parse_request_line::<VerboseError<_>>(b"INVITE sip:bob@example.com SIP/2.0\r\n").unwrap();
+/// assert_eq!(Method::Invite, line.method);
+/// assert!(matches!(
+///     parse_request_line::<VerboseError<_>>(b"INVITE sip:bob@example.com SIP/3.0\r\n"),
+///     Err(nom::Err::Failure(_))
+/// ));
+///
+/// let (_, line) =
+///     parse_request_line::<VerboseError<_>>(b"INVITE urn:service:sos SIP/2.0\r\n").unwrap();
+/// assert_eq!("urn:service:sos", line.uri.to_string());
+/// ```
+pub fn parse_request_line<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], RequestLine, E> {
+    let (input, method) = parse_method(input)?;
+    let (input, _) = char(' ')(input)?;
+    let (input, uri) = alt((
+        terminated(parse_uri, char(' ')),
+        terminated(parse_absolute_uri, char(' ')),
+    ))(input)?;
+    let (input, version) = parse_supported_version(input)?;
+    let (input, _) = opt(char(' '))(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((
+        input,
+        RequestLine {
+            method,
+            uri,
+            version,
+        },
+    ))
+}
+
+/// Parse a status line including the trailing `\r\n`.
+/// # Examples
+///
+/// ```
+/// use libsip::core::line::parse_status_line;
+/// use nom::error::VerboseError;
+///
+/// let (_, line) = parse_status_line::<VerboseError<_>>(b"SIP/2.0 404 Not Found\r\n").unwrap();
+/// assert_eq!(404, line.code);
+/// assert_eq!(Some("Not Found".to_string()), line.reason);
+/// ```
+pub fn parse_status_line<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], StatusLine, E> {
+    let (input, version) = parse_supported_version(input)?;
+    let (input, _) = char(' ')(input)?;
+    let (input, code) = map_res(take_while1(is_digit), parse_u32)(input)?;
+    let (input, _) = opt(char(' '))(input)?;
+    let (input, reason) = map_res(
+        take_while(|chr| chr != b'\r' && chr != b'\n'),
+        slice_to_string_nullable,
+    )(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    let reason = reason.trim_end();
+    Ok((
+        input,
+        StatusLine {
+            version,
+            code,
+            reason: if reason.is_empty() {
+                None
+            } else {
+                Some(reason.to_string())
+            },
+        },
+    ))
+}
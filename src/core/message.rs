@@ -1,17 +1,25 @@
-use nom::{branch::alt, character::*, IResult};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use std::fmt;
+use nom::{branch::alt, error::VerboseError, IResult, Needed};
+
+use std::{fmt, io, net::SocketAddr};
 
 use crate::{
-    core::{code::error_code_to_str, method::parse_method, version::parse_version},
-    headers::parse_header,
-    parse::{parse_byte_vec, parse_u32, slice_to_string},
-    uri::parse_uri,
+    core::{
+        body::Body,
+        code::error_code_to_str,
+        error,
+        line::{parse_request_line, parse_status_line, RequestLine, StatusLine},
+    },
+    headers::{parse_header, HeaderKind},
+    parse::parse_byte_vec,
     *,
 };
 
 /// Sip Protocol Message.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SipMessage {
     Request {
         method: Method,
@@ -31,20 +39,12 @@ pub enum SipMessage {
 impl SipMessage {
     /// Determine if this is a SIP request.
     pub fn is_request(&self) -> bool {
-        if let SipMessage::Request { .. } = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, SipMessage::Request { .. })
     }
 
     /// Determine if this is a SIP response.
     pub fn is_response(&self) -> bool {
-        if let SipMessage::Response { .. } = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, SipMessage::Response { .. })
     }
 
     /// Retreive the SIP response's status code.
@@ -73,6 +73,44 @@ impl SipMessage {
         }
     }
 
+    /// Decode the body according to the Content-Type header, `None` if
+    /// the message has no body. The raw bytes stay available from `body`.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::{core::Body, SipMessage};
+    ///
+    /// let msg = SipMessage::parse(
+    ///     b"MESSAGE sip:bob@example.com SIP/2.0\r\n\
+    ///     Content-Type: text/plain\r\n\
+    ///     Content-Length: 5\r\n\r\n\
+    ///     Hello",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(Some(Body::Text("Hello".into())), msg.typed_body());
+    /// ```
+    pub fn typed_body(&self) -> Option<Body> {
+        if self.body().is_empty() {
+            return None;
+        }
+        let content_type = match self.header(HeaderKind::ContentType) {
+            Some(Header::ContentType(content_type)) => Some(content_type),
+            _ => None,
+        };
+        Some(Body::decode(content_type, self.body()))
+    }
+
+    /// Replace the body with the encoded `body`, updating the
+    /// Content-Length header and the Content-Type unless `body` is binary.
+    pub fn set_body(&mut self, body: Body) {
+        if let Some(content_type) = body.content_type() {
+            self.set_header(Header::ContentType(content_type));
+        }
+        *self.body_mut() = body.to_bytes();
+        let length = self.body().len() as u32;
+        self.set_header(Header::ContentLength(length));
+    }
+
     /// Retreive headers from the SIP message.
     pub fn headers(&self) -> &Headers {
         match self {
@@ -88,8 +126,402 @@ impl SipMessage {
             SipMessage::Response { headers, .. } => headers,
         }
     }
+
+    /// Retreive the SIP request's method.
+    /// Returns None for responses.
+    pub fn method(&self) -> Option<&Method> {
+        if let SipMessage::Request { method, .. } = self {
+            Some(method)
+        } else {
+            None
+        }
+    }
+
+    /// Retreive the SIP request's uri.
+    /// Returns None for responses.
+    pub fn uri(&self) -> Option<&Uri> {
+        if let SipMessage::Request { uri, .. } = self {
+            Some(uri)
+        } else {
+            None
+        }
+    }
+
+    /// Retreive a mutable reference to the SIP request's uri.
+    /// Returns None for responses.
+    pub fn uri_mut(&mut self) -> Option<&mut Uri> {
+        if let SipMessage::Request { uri, .. } = self {
+            Some(uri)
+        } else {
+            None
+        }
+    }
+
+    /// Retreive the SIP response's status code as a `StatusCode`.
+    /// Returns None for requests.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.status_code().map(StatusCode)
+    }
+
+    /// Retreive the first header of the given kind.
+    pub fn header(&self, kind: HeaderKind) -> Option<&Header> {
+        self.headers().get(kind)
+    }
+
+    /// Replace every header of the same kind as `header` with it, adding
+    /// it at the end if there was none.
+    pub fn set_header(&mut self, header: Header) {
+        self.headers_mut().replace(header.kind(), header)
+    }
+
+    /// Remove every header of the given kind, returning the removed headers.
+    pub fn remove_header(&mut self, kind: HeaderKind) -> Vec<Header> {
+        self.headers_mut().remove(kind)
+    }
+
+    /// Retreive the value of the Call-ID header.
+    pub fn call_id(&self) -> Option<&String> {
+        self.headers().iter().find_map(|header| match header {
+            Header::CallId(call_id) => Some(call_id),
+            _ => None,
+        })
+    }
+
+    /// Retreive the sequence number and method of the CSeq header.
+    pub fn cseq(&self) -> Option<(u32, &Method)> {
+        self.headers().iter().find_map(|header| match header {
+            Header::CSeq(seq, method) => Some((*seq, method)),
+            _ => None,
+        })
+    }
+
+    /// Retreive the From header.
+    pub fn from(&self) -> Option<&NamedHeader> {
+        self.headers().iter().find_map(|header| match header {
+            Header::From(from) => Some(from),
+            _ => None,
+        })
+    }
+
+    /// Retreive the To header.
+    pub fn to(&self) -> Option<&NamedHeader> {
+        self.headers().iter().find_map(|header| match header {
+            Header::To(to) => Some(to),
+            _ => None,
+        })
+    }
+
+    /// Retreive every Via header, topmost first.
+    pub fn via_stack(&self) -> Vec<&ViaHeader> {
+        self.headers()
+            .iter()
+            .filter_map(|header| match header {
+                Header::Via(vias) => Some(vias),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Retreive the topmost Via header.
+    pub fn top_via(&self) -> Option<&ViaHeader> {
+        self.via_stack().into_iter().next()
+    }
+
+    /// Retreive the topmost Via header for modification.
+    pub fn top_via_mut(&mut self) -> Option<&mut ViaHeader> {
+        self.headers_mut()
+            .0
+            .iter_mut()
+            .find_map(|header| match header {
+                Header::Via(vias) => vias.first_mut(),
+                _ => None,
+            })
+    }
+
+    /// Record on the topmost Via of a received request the address it was
+    /// received from, see `ViaHeader::set_received`. Responses are left
+    /// unchanged.
+    pub fn set_received(&mut self, source: SocketAddr) {
+        if !self.is_request() {
+            return;
+        }
+        if let Some(via) = self.top_via_mut() {
+            via.set_received(source);
+        }
+    }
+}
+
+impl SipMessage {
+    /// Parse a complete SIP message, e.g. a received datagram, reporting
+    /// where the message is malformed when it can't be parsed. Any data
+    /// following the number of body bytes given by Content-Length is
+    /// silently discarded, as required for datagrams, use `parse_with_len`
+    /// to detect it.
+    /// ([RFC3261: Section 18.3, Framing](https://tools.ietf.org/html/rfc3261#section-18.3))
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::{ParseError, SipMessage};
+    ///
+    /// let msg = SipMessage::parse(b"SIP/2.0 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+    /// assert_eq!(Some(200), msg.status_code());
+    ///
+    /// let err = SipMessage::parse(b"SIP/2.0 200 OK\r\nBad Header\r\n\r\n").unwrap_err();
+    /// assert!(matches!(err, ParseError::Header { line: 2, .. }));
+    /// ```
+    pub fn parse(input: &[u8]) -> Result<SipMessage, error::ParseError> {
+        Ok(MessageParts::parse(input)?.into_message())
+    }
+
+    /// Parse a complete SIP message like `parse`, also returning the number
+    /// of bytes of `input` the message spans. Any bytes after them were
+    /// discarded.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::SipMessage;
+    ///
+    /// let input = b"SIP/2.0 200 OK\r\nContent-Length: 2\r\n\r\nhello";
+    /// let (msg, len) = SipMessage::parse_with_len(input).unwrap();
+    /// assert_eq!(b"he", msg.body().as_slice());
+    /// assert_eq!(3, input.len() - len);
+    /// ```
+    pub fn parse_with_len(input: &[u8]) -> Result<(SipMessage, usize), error::ParseError> {
+        let parts = MessageParts::parse(input)?;
+        let len = parts.len;
+        Ok((parts.into_message(), len))
+    }
+
+    /// Parse a complete SIP message like `parse`, accepting the deviations
+    /// from RFC3261 allowed by `config`. The offsets of the errors returned
+    /// refer to `input`, before the lenient parts were rewritten.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::{ParseConfig, ParseError, SipMessage};
+    ///
+    /// let config = ParseConfig::lenient();
+    /// let msg = SipMessage::parse_with_config(b"SIP/2.0 200 OK \n\n", &config).unwrap();
+    /// assert_eq!(Some(200), msg.status_code());
+    ///
+    /// let err = SipMessage::parse_with_config(b"SIP/2.0 200 OK  \nBad Header\n\n", &config)
+    ///     .unwrap_err();
+    /// assert!(matches!(err, ParseError::Header { line: 2, offset: 17, .. }));
+    /// ```
+    pub fn parse_with_config(
+        input: &[u8],
+        config: &ParseConfig,
+    ) -> Result<SipMessage, error::ParseError> {
+        crate::core::config::parse_message_with_config(input, config)
+    }
+}
+
+/// The parts of a message parsed by `SipMessage::parse`, along with
+/// the raw bytes of the start line and of each header line.
+pub(crate) struct MessageParts<'a> {
+    pub line: Result<RequestLine, StatusLine>,
+    pub raw_line: &'a [u8],
+    pub headers: Headers,
+    pub raw_headers: Vec<&'a [u8]>,
+    pub body: Vec<u8>,
+    /// The number of bytes of the input the message spans.
+    pub len: usize,
+}
+
+impl<'a> MessageParts<'a> {
+    pub fn parse(input: &'a [u8]) -> Result<MessageParts<'a>, error::ParseError> {
+        let start = if input.starts_with(b"SIP/") {
+            parse_status_line::<VerboseError<&[u8]>>(input).map(|(rest, line)| (rest, Err(line)))
+        } else {
+            parse_request_line::<VerboseError<&[u8]>>(input).map(|(rest, line)| (rest, Ok(line)))
+        };
+        let (mut rest, line) = match start {
+            Ok(value) => value,
+            Err(nom::Err::Failure(error)) => {
+                return Err(error::ParseError::UnsupportedVersion {
+                    offset: error
+                        .errors
+                        .first()
+                        .map(|(remains, _)| input.len() - remains.len())
+                        .unwrap_or(0),
+                })
+            },
+            Err(nom::Err::Error(error)) => {
+                return Err(error::ParseError::start_line(input, &error))
+            },
+            Err(nom::Err::Incomplete(_)) => {
+                return Err(error::ParseError::Incomplete { needed: None })
+            },
+        };
+
+        let raw_line = &input[..input.len() - rest.len()];
+        let mut headers = Headers::new();
+        let mut raw_headers = vec![];
+        while !rest.starts_with(b"\r\n") {
+            if rest.is_empty() {
+                return Err(error::ParseError::Incomplete { needed: None });
+            }
+            match parse_header::<VerboseError<&[u8]>>(rest) {
+                Ok((remains, header)) => {
+                    headers.push(header);
+                    raw_headers.push(&rest[..rest.len() - remains.len()]);
+                    rest = remains;
+                },
+                Err(nom::Err::Error((remains, kind))) | Err(nom::Err::Failure((remains, kind))) => {
+                    return Err(error::ParseError::header(
+                        input,
+                        input.len() - rest.len(),
+                        remains,
+                        kind,
+                    ))
+                },
+                Err(nom::Err::Incomplete(_)) => {
+                    return Err(error::ParseError::Incomplete { needed: None })
+                },
+            }
+        }
+
+        let (len, body) = match parse_body::<VerboseError<&[u8]>>(&headers, &rest[2..]) {
+            Ok((remains, body)) => (input.len() - remains.len(), body),
+            Err(nom::Err::Incomplete(Needed::Size(needed))) => {
+                return Err(error::ParseError::Incomplete {
+                    needed: Some(needed.get()),
+                })
+            },
+            Err(_) => return Err(error::ParseError::Incomplete { needed: None }),
+        };
+        Ok(MessageParts {
+            line,
+            raw_line,
+            headers,
+            raw_headers,
+            body,
+            len,
+        })
+    }
+
+    pub fn into_message(self) -> SipMessage {
+        let MessageParts {
+            line,
+            headers,
+            body,
+            ..
+        } = self;
+        match line {
+            Ok(RequestLine {
+                method,
+                uri,
+                version,
+            }) => SipMessage::Request {
+                method,
+                uri,
+                version,
+                headers,
+                body,
+            },
+            Err(StatusLine { version, code, .. }) => SipMessage::Response {
+                code,
+                version,
+                headers,
+                body,
+            },
+        }
+    }
+}
+
+impl SipMessage {
+    /// Write this message to `w`. Unlike the Display implementation
+    /// the body is written as is, so binary bodies are sent unchanged.
+    pub fn write_to(&self, w: &mut impl io::Write) -> io::Result<()> {
+        self.write_with(w, false)
+    }
+
+    /// Write this message to `w` like `write_to`, with the headers in
+    /// their compact form (`v:` for Via, `f:` for From...) to keep
+    /// messages sent over UDP small.
+    pub fn write_compact_to(&self, w: &mut impl io::Write) -> io::Result<()> {
+        self.write_with(w, true)
+    }
+
+    fn write_with(&self, w: &mut impl io::Write, compact: bool) -> io::Result<()> {
+        self.write_start_line(w)?;
+        self.headers().write_to(w, compact)?;
+        w.write_all(b"\r\n")?;
+        w.write_all(self.body())
+    }
+
+    /// Write the request line or status line of this message to `w`.
+    pub(crate) fn write_start_line(&self, w: &mut impl io::Write) -> io::Result<()> {
+        match self {
+            SipMessage::Request {
+                method,
+                uri,
+                version,
+                ..
+            } => write!(w, "{} {} {}\r\n", method, uri, version),
+            SipMessage::Response { code, version, .. } => {
+                if let Some(desc) = error_code_to_str(*code) {
+                    write!(w, "{} {} {}\r\n", version, code, desc)
+                } else {
+                    write!(w, "{} {}\r\n", version, code)
+                }
+            },
+        }
+    }
+
+    /// Serialize this message, see `write_to`.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::{Header, ResponseGenerator};
+    ///
+    /// let res = ResponseGenerator::new()
+    ///     .code(200)
+    ///     .header(Header::ContentLength(2))
+    ///     .body(vec![0xff, 0x00])
+    ///     .build_unchecked()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     b"SIP/2.0 200 OK\r\nContent-Length: 2\r\n\r\n\xff\x00".to_vec(),
+    ///     res.to_bytes()
+    /// );
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.body().len() + 256);
+        self.write_to(&mut out)
+            .expect("writing to a Vec can't fail");
+        out
+    }
+
+    /// Serialize this message with compact header names, see `write_compact_to`.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::{Header, ResponseGenerator};
+    ///
+    /// let res = ResponseGenerator::new()
+    ///     .code(200)
+    ///     .header(Header::CallId("a84b4c76e66710".into()))
+    ///     .header(Header::ContentLength(0))
+    ///     .build_unchecked()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     b"SIP/2.0 200 OK\r\ni: a84b4c76e66710\r\nl: 0\r\n\r\n".to_vec(),
+    ///     res.to_compact_bytes()
+    /// );
+    /// ```
+    pub fn to_compact_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.body().len() + 256);
+        self.write_compact_to(&mut out)
+            .expect("writing to a Vec can't fail");
+        out
+    }
 }
 
+/// Use the alternate flag (`format!("{:#}", msg)`) to write headers
+/// in their compact form. The body is written as text, use
+/// `to_compact_bytes` to serialize messages that are sent.
 impl fmt::Display for SipMessage {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -120,17 +552,22 @@ impl fmt::Display for SipMessage {
     }
 }
 
-/// Write the headers and body of a SIP message.
+/// Write the headers and body of a SIP message. Headers are
+/// written in their compact form when the alternate flag is set.
 pub fn display_headers_and_body(
     f: &mut fmt::Formatter,
     headers: &Headers,
     body: &[u8],
 ) -> Result<(), fmt::Error> {
     for header in headers.iter() {
-        writeln!(f, "{}\r", header)?;
+        if f.alternate() {
+            writeln!(f, "{:#}\r", header)?;
+        } else {
+            writeln!(f, "{}\r", header)?;
+        }
     }
     writeln!(f, "\r")?;
-    f.write_str(&String::from_utf8_lossy(&body))?;
+    f.write_str(&String::from_utf8_lossy(body))?;
     Ok(())
 }
 
@@ -147,30 +584,38 @@ pub fn parse_headers<'a, E: ParseError<&'a [u8]>>(
     Ok((input, headers))
 }
 
-use nom::{
-    bytes::complete::{tag, take_while},
-    character::complete::char,
-    combinator::{map_res, opt},
-    error::ParseError,
-};
+use nom::{bytes::complete::tag, error::ParseError};
+
+/// Parse the body of a message. When a Content-Length header is present
+/// exactly that many bytes are taken, any data after them is left unparsed,
+/// and `nom::Err::Incomplete` is returned if the input is too short. Without
+/// Content-Length the body extends to the end of the input, as is the case
+/// for datagram transports.
+pub fn parse_body<'a, E: ParseError<&'a [u8]>>(
+    headers: &Headers,
+    input: &'a [u8],
+) -> IResult<&'a [u8], Vec<u8>, E> {
+    match headers.content_length() {
+        Some(Header::ContentLength(length)) => {
+            let length = length as usize;
+            if input.len() < length {
+                Err(nom::Err::Incomplete(Needed::new(length - input.len())))
+            } else {
+                Ok((&input[length..], input[..length].to_vec()))
+            }
+        },
+        _ => parse_byte_vec(input),
+    }
+}
 
 /// Parse a SIP message assuming it is a SIP response.
 pub fn parse_response<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], SipMessage, E> {
-    let (input, version) = parse_version::<E>(input)?;
-    let (input, _) = char(' ')(input)?;
-    let (input, code) = map_res(take_while(is_digit), parse_u32)(input)?;
-    let (input, _) = char(' ')(input)?;
-    let (input, _) = opt(map_res::<_, _, _, _, E, _, _>(
-        take_while(|item| is_alphabetic(item) || is_space(item)),
-        slice_to_string,
-    ))(input)?;
-    let (input, _) = opt(char(' '))(input)?;
-    let (input, _) = tag("\r\n")(input)?;
+    let (input, StatusLine { version, code, .. }) = parse_status_line::<E>(input)?;
     let (input, headers) = parse_headers::<E>(input)?;
     let (input, _) = tag("\r\n")(input)?;
-    let (input, body) = parse_byte_vec::<E>(input)?;
+    let (input, body) = parse_body::<E>(&headers, input)?;
     Ok((
         input,
         SipMessage::Response {
@@ -186,16 +631,17 @@ pub fn parse_response<'a, E: ParseError<&'a [u8]>>(
 pub fn parse_request<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], SipMessage, E> {
-    let (input, method) = parse_method(input)?;
-    let (input, _) = char(' ')(input)?;
-    let (input, uri) = parse_uri(input)?;
-    let (input, _) = char(' ')(input)?;
-    let (input, version) = parse_version(input)?;
-    let (input, _) = opt(char(' '))(input)?;
-    let (input, _) = tag("\r\n")(input)?;
+    let (
+        input,
+        RequestLine {
+            method,
+            uri,
+            version,
+        },
+    ) = parse_request_line(input)?;
     let (input, headers) = parse_headers(input)?;
     let (input, _) = tag("\r\n")(input)?;
-    let (input, body) = parse_byte_vec(input)?;
+    let (input, body) = parse_body(&headers, input)?;
     Ok((
         input,
         SipMessage::Request {
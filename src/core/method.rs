@@ -1,8 +1,17 @@
-use nom::{branch::alt, bytes::complete::tag_no_case, combinator::map, error::ParseError, IResult};
-use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{bytes::complete::take_while1, combinator::map, error::ParseError, IResult};
+use std::{fmt, str::FromStr};
+
+use crate::parse::is_token;
 
 /// SIP protocol methods.
-#[derive(Debug, PartialEq, Clone, Copy)]
+///
+/// Methods without a variant, e.g. those of extensions this library
+/// doesn't know about, are kept as `Extension`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Method {
     Invite,
     Ack,
@@ -18,6 +27,7 @@ pub enum Method {
     Refer,
     Message,
     Update,
+    Extension(String),
 }
 
 impl Method {
@@ -41,6 +51,27 @@ impl Method {
             Method::Update,
         ]
     }
+
+    /// Get the method for a method token. Registered methods are
+    /// recognized regardless of case.
+    pub fn from_token(token: &str) -> Method {
+        Method::all()
+            .into_iter()
+            .find(|method| token.eq_ignore_ascii_case(&method.to_string()))
+            .unwrap_or_else(|| Method::Extension(token.to_string()))
+    }
+}
+
+impl FromStr for Method {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.is_empty() && s.bytes().all(is_token) {
+            Ok(Method::from_token(s))
+        } else {
+            Err(())
+        }
+    }
 }
 
 impl fmt::Display for Method {
@@ -60,26 +91,29 @@ impl fmt::Display for Method {
             Method::Refer => write!(f, "REFER"),
             Method::Message => write!(f, "MESSAGE"),
             Method::Update => write!(f, "UPDATE"),
+            Method::Extension(method) => write!(f, "{}", method),
         }
     }
 }
 
 /// Parse SIP request Method.
+/// # Examples
+///
+/// ```
+/// use libsip::core::{parse_method, Method};
+/// use nom::error::VerboseError;
+///
+/// assert_eq!(
+///     Ok(("".as_bytes(), Method::Invite)),
+///     parse_method::<VerboseError<_>>(b"INVITE")
+/// );
+/// assert_eq!(
+///     Ok(("".as_bytes(), Method::Extension("FOO-BAR".into()))),
+///     parse_method::<VerboseError<_>>(b"FOO-BAR")
+/// );
+/// ```
 pub fn parse_method<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Method, E> {
-    alt::<_, _, E, _>((
-        map(tag_no_case("INVITE"), |_| Method::Invite),
-        map(tag_no_case("ACK"), |_| Method::Ack),
-        map(tag_no_case("BYE"), |_| Method::Bye),
-        map(tag_no_case("CANCEL"), |_| Method::Cancel),
-        map(tag_no_case("REGISTER"), |_| Method::Register),
-        map(tag_no_case("OPTIONS"), |_| Method::Options),
-        map(tag_no_case("PRACK"), |_| Method::PRack),
-        map(tag_no_case("SUBSCRIBE"), |_| Method::Subscribe),
-        map(tag_no_case("NOTIFY"), |_| Method::Notify),
-        map(tag_no_case("PUBLISH"), |_| Method::Publish),
-        map(tag_no_case("INFO"), |_| Method::Info),
-        map(tag_no_case("REFER"), |_| Method::Refer),
-        map(tag_no_case("MESSAGE"), |_| Method::Message),
-        map(tag_no_case("UPDATE"), |_| Method::Update),
-    ))(input)
+    map(take_while1(is_token), |token| {
+        Method::from_token(&String::from_utf8_lossy(token))
+    })(input)
 }
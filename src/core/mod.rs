@@ -1,4 +1,5 @@
 pub mod code;
+pub use self::code::StatusCode;
 
 pub mod method;
 pub use self::method::{parse_method, Method};
@@ -9,8 +10,35 @@ pub use self::transport::{parse_transport, Transport};
 pub mod version;
 pub use self::version::{parse_version, Version};
 
+pub mod error;
+pub use self::error::ParseError;
+
+pub mod line;
+pub use self::line::{parse_request_line, parse_status_line, RequestLine, StatusLine};
+
+pub mod borrowed;
+pub use self::borrowed::{parse_message_borrowed, HeaderRef, SipMessageRef, StartLineRef};
+
+pub mod body;
+pub use self::body::{Body, BodyPart, Multipart};
+
 pub mod message;
 pub use self::message::{parse_message, parse_request, parse_response, SipMessage};
 
+pub mod config;
+pub use self::config::{parse_message_with_config, ParseConfig};
+
+pub mod packet;
+pub use self::packet::{parse_packet, ParsedPacket};
+
+pub mod preserve;
+pub use self::preserve::PreservedMessage;
+
+pub mod validate;
+pub use self::validate::{Role, ValidationError};
+
+pub mod stream;
+pub use self::stream::SipParser;
+
 pub mod extract;
 pub use self::extract::extract_opt_param;
@@ -0,0 +1,41 @@
+use crate::core::{ParseError, SipMessage};
+
+/// A datagram received on a SIP socket.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParsedPacket {
+    /// A CRLF keep-alive ping (`\r\n\r\n`) or pong (`\r\n`).
+    /// ([RFC5626: Section 3.5.1, CRLF Keep-Alive Technique](https://tools.ietf.org/html/rfc5626#section-3.5.1))
+    KeepAlive,
+    /// A packet that looks like a STUN message, e.g. a STUN keep-alive
+    /// multiplexed on the SIP port, to be handed to a STUN implementation.
+    MaybeStun(Vec<u8>),
+    Sip(Box<SipMessage>),
+}
+
+/// Classify a received datagram as a keep-alive, a STUN message or
+/// a SIP message, parsing the latter with `SipMessage::parse`.
+/// # Examples
+///
+/// ```
+/// use libsip::core::packet::{parse_packet, ParsedPacket};
+///
+/// assert_eq!(Ok(ParsedPacket::KeepAlive), parse_packet(b"\r\n\r\n"));
+/// assert!(matches!(parse_packet(b"SIP/2.0 200 OK\r\n\r\n"), Ok(ParsedPacket::Sip(_))));
+/// ```
+pub fn parse_packet(input: &[u8]) -> Result<ParsedPacket, ParseError> {
+    if !input.is_empty() && input.iter().all(|byte| *byte == b'\r' || *byte == b'\n') {
+        Ok(ParsedPacket::KeepAlive)
+    } else if is_stun(input) {
+        Ok(ParsedPacket::MaybeStun(input.to_vec()))
+    } else {
+        SipMessage::parse(input).map(|msg| ParsedPacket::Sip(Box::new(msg)))
+    }
+}
+
+/// Determine if `input` looks like a STUN message: it is at least as
+/// long as the STUN header and its first byte is in the range reserved
+/// for STUN, which no SIP message can start with.
+/// ([RFC7983: Section 7, Demultiplexing](https://tools.ietf.org/html/rfc7983#section-7))
+fn is_stun(input: &[u8]) -> bool {
+    input.len() >= 20 && input[0] <= 3
+}
@@ -0,0 +1,121 @@
+use std::io;
+
+use crate::{
+    core::{
+        error::ParseError,
+        line::{RequestLine, StatusLine},
+        message::MessageParts,
+        SipMessage,
+    },
+    headers::Header,
+};
+
+/// A parsed SIP message that remembers how it was received, so that
+/// writing it back out gives the original bytes. Parts of the message
+/// that were changed are written in the usual format while the start
+/// line and headers that were left alone keep their original order,
+/// casing, spacing and line folding, as needed by proxies and B2BUAs
+/// that must not reformat what they forward.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PreservedMessage {
+    message: SipMessage,
+    line: Result<RequestLine, StatusLine>,
+    raw_line: Vec<u8>,
+    raw_headers: Vec<(Header, Vec<u8>)>,
+}
+
+impl PreservedMessage {
+    /// Parse a complete SIP message, see `SipMessage::parse`.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::core::preserve::PreservedMessage;
+    ///
+    /// let input = b"SIP/2.0 200 Okay\r\ncall-id:  1234\r\nX-Custom: a,b\r\n\r\n";
+    /// let msg = PreservedMessage::parse(input).unwrap();
+    /// assert_eq!(Some(200), msg.message().status_code());
+    /// assert_eq!(input.to_vec(), msg.to_bytes());
+    /// ```
+    pub fn parse(input: &[u8]) -> Result<PreservedMessage, ParseError> {
+        let parts = MessageParts::parse(input)?;
+        let raw_line = parts.raw_line.to_vec();
+        let line = parts.line.clone();
+        let raw_headers = parts
+            .headers
+            .iter()
+            .cloned()
+            .zip(parts.raw_headers.iter().map(|raw| raw.to_vec()))
+            .collect();
+        Ok(PreservedMessage {
+            message: parts.into_message(),
+            line,
+            raw_line,
+            raw_headers,
+        })
+    }
+
+    /// The parsed message.
+    pub fn message(&self) -> &SipMessage {
+        &self.message
+    }
+
+    /// The parsed message, for making changes before it is written.
+    pub fn message_mut(&mut self) -> &mut SipMessage {
+        &mut self.message
+    }
+
+    /// Drop the original formatting, keeping only the parsed message.
+    pub fn into_message(self) -> SipMessage {
+        self.message
+    }
+
+    /// Write this message to `w`, using the original bytes of the start
+    /// line and of every header that is unchanged.
+    pub fn write_to(&self, w: &mut impl io::Write) -> io::Result<()> {
+        let unchanged = match (&self.line, &self.message) {
+            (
+                Ok(line),
+                SipMessage::Request {
+                    method,
+                    uri,
+                    version,
+                    ..
+                },
+            ) => &line.method == method && &line.uri == uri && &line.version == version,
+            (Err(line), SipMessage::Response { code, version, .. }) => {
+                &line.code == code && &line.version == version
+            },
+            _ => false,
+        };
+        if unchanged {
+            w.write_all(&self.raw_line)?;
+        } else {
+            self.message.write_start_line(w)?;
+        }
+        let mut used = vec![false; self.raw_headers.len()];
+        for header in self.message.headers().iter() {
+            let original = self
+                .raw_headers
+                .iter()
+                .zip(&used)
+                .position(|((original, _), used)| !used && original == header);
+            match original {
+                Some(index) => {
+                    used[index] = true;
+                    w.write_all(&self.raw_headers[index].1)?;
+                },
+                None => write!(w, "{}\r\n", header)?,
+            }
+        }
+        w.write_all(b"\r\n")?;
+        w.write_all(self.message.body())
+    }
+
+    /// Serialize this message, see `write_to`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.message.body().len() + 256);
+        self.write_to(&mut out)
+            .expect("writing to a Vec can't fail");
+        out
+    }
+}
@@ -0,0 +1,186 @@
+use nom::error::VerboseError;
+
+use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
+
+use crate::core::{parse_message, ParseConfig, SipMessage};
+
+/// The default size limit of a message, start line, headers and body,
+/// see `SipParser::set_max_message_size`.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 65535;
+
+/// Incremental parser for SIP messages received over a stream transport
+/// such as TCP, where reads don't line up with message boundaries.
+///
+/// Data is added with `feed` and complete messages are taken out with
+/// `next_message`, the end of each message body is determined by the
+/// Content-Length header. Messages without a Content-Length header are
+/// assumed to have no body. Use `with_config` to accept messages that
+/// deviate from RFC3261.
+/// # Examples
+///
+/// ```
+/// use libsip::core::stream::SipParser;
+///
+/// let mut parser = SipParser::new();
+/// parser.feed(b"SIP/2.0 200 OK\r\nContent-Length: 5\r\n\r\nhe");
+/// assert!(parser.next_message().unwrap().is_none());
+/// parser.feed(b"lloSIP/2.0 180 Ringing\r\n");
+/// let msg = parser.next_message().unwrap().unwrap();
+/// assert_eq!(b"hello", msg.body().as_slice());
+/// assert_eq!(b"SIP/2.0 180 Ringing\r\n", parser.remaining());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SipParser {
+    buffer: Vec<u8>,
+    config: ParseConfig,
+    max_message_size: usize,
+    keep_alive: bool,
+}
+
+impl SipParser {
+    pub fn new() -> SipParser {
+        SipParser::with_config(ParseConfig::strict())
+    }
+
+    /// Create a parser accepting the deviations allowed by `config`.
+    pub fn with_config(config: ParseConfig) -> SipParser {
+        SipParser {
+            buffer: vec![],
+            config,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            keep_alive: false,
+        }
+    }
+
+    /// Set the size limit of a message, 65535 bytes by default.
+    pub fn set_max_message_size(&mut self, size: usize) {
+        self.max_message_size = size;
+    }
+
+    /// Add received data to the end of the buffer.
+    pub fn feed(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// The bytes that are not part of a complete message yet.
+    pub fn remaining(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Remove every buffered byte, e.g. after a parse error when the
+    /// connection is going to be closed.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Determine if empty lines were skipped between messages by
+    /// `next_message` since the last call, i.e. a keep-alive ping or pong
+    /// was received. The line ending the headers of a message received in
+    /// several parts is never taken for one.
+    /// ([RFC5626: Section 3.5.1, Keep-Alive with CRLF](https://tools.ietf.org/html/rfc5626#section-3.5.1))
+    pub fn take_keep_alive(&mut self) -> bool {
+        std::mem::replace(&mut self.keep_alive, false)
+    }
+
+    /// Take the next complete message out of the buffer. Returns `Ok(None)`
+    /// when more data is needed. Empty lines before a message, such as the
+    /// `\r\n\r\n` keep-alives from [RFC5626](https://tools.ietf.org/html/rfc5626#section-3.5.1),
+    /// are skipped, see `take_keep_alive`.
+    ///
+    /// When a complete message can't be parsed it is removed from the
+    /// buffer, along with the body announced by its Content-Length, and an
+    /// error of kind `InvalidData` is returned, so the following messages
+    /// can still be read.
+    ///
+    /// When the end of the message can't be determined, because it is
+    /// invalid and has no Content-Length, or because it is longer than the
+    /// maximum message size, the start of the next message is lost: the
+    /// buffer is cleared and an error of kind `ConnectionAborted` is
+    /// returned, after which the connection has to be closed.
+    /// ([RFC3261: Section 18.3, Framing](https://tools.ietf.org/html/rfc3261#section-18.3))
+    pub fn next_message(&mut self) -> IoResult<Option<SipMessage>> {
+        let start = self
+            .buffer
+            .iter()
+            .position(|byte| *byte != b'\r' && *byte != b'\n')
+            .unwrap_or(self.buffer.len());
+        if start > 0 {
+            self.keep_alive = true;
+            self.buffer.drain(..start);
+        }
+
+        let mut head_end = match self.config.find_head_end(&self.buffer) {
+            Some(index) => index,
+            None if self.buffer.len() > self.max_message_size => {
+                return Err(self.abort("SIP message headers are too long"));
+            },
+            None => return Ok(None),
+        };
+        if !self.config.is_strict() {
+            let head = self.config.normalize_head(&self.buffer[..head_end]);
+            let length = head.len();
+            self.buffer.splice(..head_end, head);
+            head_end = length;
+        }
+        let content_length = find_content_length(&self.buffer[..head_end]);
+        let end = match head_end.checked_add(content_length.unwrap_or(0)) {
+            Some(end) if end <= self.max_message_size => end,
+            _ => return Err(self.abort("SIP message is too long")),
+        };
+        match parse_message::<VerboseError<&[u8]>>(&self.buffer) {
+            Ok((rest, mut msg)) => {
+                let end = if msg.headers().content_length().is_some() {
+                    self.buffer.len() - rest.len()
+                } else {
+                    msg.body_mut().clear();
+                    head_end
+                };
+                self.buffer.drain(..end);
+                Ok(Some(msg))
+            },
+            Err(nom::Err::Incomplete(_)) => Ok(None),
+            Err(_) => {
+                if content_length.is_none() {
+                    return Err(self.abort("Failed to parse SIP message"));
+                }
+                if self.buffer.len() < end {
+                    return Ok(None);
+                }
+                self.buffer.drain(..end);
+                Err(IoError::new(
+                    IoErrorKind::InvalidData,
+                    "Failed to parse SIP message",
+                ))
+            },
+        }
+    }
+
+    fn abort(&mut self, error: &str) -> IoError {
+        self.buffer.clear();
+        IoError::new(IoErrorKind::ConnectionAborted, error)
+    }
+}
+
+impl Default for SipParser {
+    fn default() -> SipParser {
+        SipParser::new()
+    }
+}
+
+/// Find the value of the Content-Length header in the start line and
+/// headers of a message without parsing them, to skip the body of a
+/// message that is otherwise invalid.
+fn find_content_length(head: &[u8]) -> Option<usize> {
+    head.split(|byte| *byte == b'\n').find_map(|line| {
+        let colon = line.iter().position(|byte| *byte == b':')?;
+        let name = String::from_utf8_lossy(&line[..colon]);
+        let name = name.trim();
+        if !name.eq_ignore_ascii_case("content-length") && !name.eq_ignore_ascii_case("l") {
+            return None;
+        }
+        String::from_utf8_lossy(&line[colon + 1..])
+            .trim()
+            .parse()
+            .ok()
+    })
+}
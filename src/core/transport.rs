@@ -1,18 +1,40 @@
 use nom::{branch::alt, bytes::complete::tag_no_case, combinator::map, error::ParseError, IResult};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use std::fmt;
 
+use crate::uri::{DEFAULT_SIPS_PORT, DEFAULT_SIP_PORT};
+
 /// SIP protocol transport.
-#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Transport {
+    #[default]
     Udp,
     Tcp,
+    Tls,
+    /// SIP over WebSocket.
+    /// ([RFC7118: The WebSocket Protocol as a Transport for SIP](https://tools.ietf.org/html/rfc7118))
+    Ws,
+    /// SIP over secure WebSocket.
+    Wss,
+    /// SIP over SCTP.
+    /// ([RFC4168: The SCTP as a Transport for SIP](https://tools.ietf.org/html/rfc4168))
+    Sctp,
+    /// SIP over TLS over SCTP.
+    TlsSctp,
 }
 
-impl Default for Transport {
-    fn default() -> Transport {
-        Transport::Udp
+impl Transport {
+    /// The port used when none is given for this transport: 5061 for
+    /// the transports secured with TLS and 5060 for the others.
+    /// ([RFC3261: Section 19.1.2](https://tools.ietf.org/html/rfc3261#section-19.1.2))
+    pub fn default_port(self) -> u16 {
+        match self {
+            Transport::Tls | Transport::TlsSctp | Transport::Wss => DEFAULT_SIPS_PORT,
+            _ => DEFAULT_SIP_PORT,
+        }
     }
 }
 
@@ -21,6 +43,11 @@ impl fmt::Display for Transport {
         match self {
             Transport::Udp => write!(f, "UDP"),
             Transport::Tcp => write!(f, "TCP"),
+            Transport::Tls => write!(f, "TLS"),
+            Transport::Ws => write!(f, "WS"),
+            Transport::Wss => write!(f, "WSS"),
+            Transport::Sctp => write!(f, "SCTP"),
+            Transport::TlsSctp => write!(f, "TLS-SCTP"),
         }
     }
 }
@@ -32,5 +59,10 @@ pub fn parse_transport<'a, E: ParseError<&'a [u8]>>(
     alt::<_, _, E, _>((
         map(tag_no_case::<_, _, E>("TCP"), |_| Transport::Tcp),
         map(tag_no_case::<_, _, E>("UDP"), |_| Transport::Udp),
+        map(tag_no_case::<_, _, E>("TLS-SCTP"), |_| Transport::TlsSctp),
+        map(tag_no_case::<_, _, E>("TLS"), |_| Transport::Tls),
+        map(tag_no_case::<_, _, E>("SCTP"), |_| Transport::Sctp),
+        map(tag_no_case::<_, _, E>("WSS"), |_| Transport::Wss),
+        map(tag_no_case::<_, _, E>("WS"), |_| Transport::Ws),
     ))(input)
 }
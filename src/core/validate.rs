@@ -0,0 +1,190 @@
+use std::{error::Error, fmt};
+
+use crate::{
+    core::{Method, SipMessage},
+    headers::{Header, HeaderKind},
+};
+
+/// The role of the element validating a received message, which decides
+/// the checks that apply.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Role {
+    /// A user agent client, receiving responses.
+    Uac,
+    /// A user agent server, receiving requests.
+    Uas,
+    /// A proxy, receiving both requests and responses.
+    Proxy,
+}
+
+/// A problem found by `SipMessage::validate`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ValidationError {
+    /// A mandatory header is missing.
+    MissingHeader(HeaderKind),
+    /// A header that may only appear once appears several times.
+    DuplicateHeader(HeaderKind),
+    /// The method of the CSeq header doesn't match the request method.
+    CSeqMethodMismatch { method: Method, cseq_method: Method },
+    /// The Content-Length header doesn't match the size of the body.
+    ContentLengthMismatch {
+        content_length: u32,
+        body_length: usize,
+    },
+    /// The request has a Max-Forwards of zero and can't be forwarded.
+    TooManyHops,
+    /// A response received by a UAC has more than one Via header.
+    /// ([RFC3261: Section 8.1.3.3, Vias](https://tools.ietf.org/html/rfc3261#section-8.1.3.3))
+    MultipleVias,
+    /// The request is a copy of a pending request that took another path.
+    /// ([RFC3261: Section 8.2.2.2, Merged Requests](https://tools.ietf.org/html/rfc3261#section-8.2.2.2))
+    MergedRequest,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::MissingHeader(kind) => write!(f, "missing {} header", kind),
+            ValidationError::DuplicateHeader(kind) => write!(f, "duplicate {} header", kind),
+            ValidationError::CSeqMethodMismatch {
+                method,
+                cseq_method,
+            } => write!(
+                f,
+                "CSeq method {} doesn't match request method {}",
+                cseq_method, method
+            ),
+            ValidationError::ContentLengthMismatch {
+                content_length,
+                body_length,
+            } => write!(
+                f,
+                "Content-Length {} doesn't match body length {}",
+                content_length, body_length
+            ),
+            ValidationError::TooManyHops => write!(f, "Max-Forwards reached zero"),
+            ValidationError::MultipleVias => write!(f, "response has more than one Via"),
+            ValidationError::MergedRequest => write!(f, "merged request"),
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+impl SipMessage {
+    /// Check that this received message is well formed enough to act
+    /// on, in the manner of [RFC3261: Section 16.3, Request Validation](https://tools.ietf.org/html/rfc3261#section-16.3).
+    /// Returns every problem found, an empty list meaning the message is valid.
+    ///
+    /// The To, From, Call-ID, CSeq and Via headers are required in every
+    /// message and Max-Forwards in every request. The CSeq method must
+    /// match the request method and Content-Length, when present, the
+    /// size of the body. Proxies also reject requests with a Max-Forwards
+    /// of zero and UACs responses with more than one Via.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::{
+    ///     core::validate::{Role, ValidationError},
+    ///     headers::HeaderKind,
+    ///     SipMessage,
+    /// };
+    ///
+    /// let msg = SipMessage::parse(b"SIP/2.0 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+    /// let errors = msg.validate(Role::Uac);
+    /// assert!(errors.contains(&ValidationError::MissingHeader(HeaderKind::CallId)));
+    /// ```
+    pub fn validate(&self, role: Role) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        let headers = self.headers();
+
+        let mut required = vec![
+            HeaderKind::To,
+            HeaderKind::From,
+            HeaderKind::CallId,
+            HeaderKind::CSeq,
+        ];
+        if self.is_request() {
+            required.push(HeaderKind::MaxForwards);
+        }
+        for kind in required {
+            match headers.get_all(kind.clone()).len() {
+                0 => errors.push(ValidationError::MissingHeader(kind)),
+                1 => {},
+                _ => errors.push(ValidationError::DuplicateHeader(kind)),
+            }
+        }
+        let vias = self.via_stack().len();
+        if vias == 0 {
+            errors.push(ValidationError::MissingHeader(HeaderKind::Via));
+        } else if vias > 1 && role == Role::Uac && self.is_response() {
+            errors.push(ValidationError::MultipleVias);
+        }
+
+        if let (Some(method), Some((_, cseq_method))) = (self.method(), self.cseq()) {
+            if method != cseq_method {
+                errors.push(ValidationError::CSeqMethodMismatch {
+                    method: method.clone(),
+                    cseq_method: cseq_method.clone(),
+                });
+            }
+        }
+
+        let content_lengths = headers.get_all(HeaderKind::ContentLength);
+        if content_lengths.len() > 1 {
+            errors.push(ValidationError::DuplicateHeader(HeaderKind::ContentLength));
+        }
+        if let Some(Header::ContentLength(content_length)) = content_lengths.first() {
+            if *content_length as usize != self.body().len() {
+                errors.push(ValidationError::ContentLengthMismatch {
+                    content_length: *content_length,
+                    body_length: self.body().len(),
+                });
+            }
+        }
+
+        if role == Role::Proxy && self.is_request() {
+            if let Some(Header::MaxForwards(0)) = headers.get(HeaderKind::MaxForwards) {
+                errors.push(ValidationError::TooManyHops);
+            }
+        }
+        errors
+    }
+
+    /// Validate this received request like `validate`, also reporting it
+    /// as merged if it matches one of the `pending` requests.
+    /// See `is_merged_with`.
+    pub fn validate_against<'a, I>(&self, role: Role, pending: I) -> Vec<ValidationError>
+    where
+        I: IntoIterator<Item = &'a SipMessage>,
+    {
+        let mut errors = self.validate(role);
+        if pending.into_iter().any(|other| self.is_merged_with(other)) {
+            errors.push(ValidationError::MergedRequest);
+        }
+        errors
+    }
+
+    /// Determine if this request, which has no To tag, has the same From
+    /// tag, Call-ID and CSeq as the `other` request but arrived through
+    /// a different path, i.e. with another topmost Via branch.
+    /// ([RFC3261: Section 8.2.2.2, Merged Requests](https://tools.ietf.org/html/rfc3261#section-8.2.2.2))
+    pub fn is_merged_with(&self, other: &SipMessage) -> bool {
+        if !self.is_request() || !other.is_request() {
+            return false;
+        }
+        if self.to().and_then(|to| to.tag()).is_some() {
+            return false;
+        }
+        let from_tag = |msg: &SipMessage| msg.from().and_then(|from| from.tag().map(String::from));
+        let branch =
+            |msg: &SipMessage| msg.top_via().and_then(|via| via.branch().map(String::from));
+        from_tag(self).is_some()
+            && from_tag(self) == from_tag(other)
+            && self.call_id().is_some()
+            && self.call_id() == other.call_id()
+            && self.cseq().is_some()
+            && self.cseq() == other.cseq()
+            && branch(self) != branch(other)
+    }
+}
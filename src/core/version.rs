@@ -1,3 +1,6 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::parse::parse_u8;
 use nom::{
     bytes::complete::{tag, take_while1},
@@ -12,6 +15,7 @@ use std::fmt;
 /// SIP Protocol version struct.
 /// default: 2.0
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Version(u8, u8);
 
 impl Default for Version {
@@ -1,54 +1,176 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use sha::{
     sha256::Sha256,
-    sha512::Sha512,
+    sha512256::Sha512256,
     utils::{Digest, DigestExt},
 };
 
-use std::{collections::HashMap, fmt, io::Result as IoResult};
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+};
 
-use crate::Uri;
+use crate::{Method, Uri};
 
 /// The SIP Authentication schema.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AuthSchema {
     Digest,
+    /// The user id and password encoded in base64.
+    /// ([RFC7617: The 'Basic' HTTP Authentication Scheme](https://tools.ietf.org/html/rfc7617))
+    Basic,
+    /// An access token obtained from an authorization server.
+    /// ([RFC6750: Section 2.1, Authorization Request Header Field](https://tools.ietf.org/html/rfc6750#section-2.1)
+    /// and [RFC8898: Third-Party Token-Based Authentication and Authorization for SIP](https://tools.ietf.org/html/rfc8898))
+    Bearer,
 }
 
 impl fmt::Display for AuthSchema {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             AuthSchema::Digest => write!(f, "Digest"),
+            AuthSchema::Basic => write!(f, "Basic"),
+            AuthSchema::Bearer => write!(f, "Bearer"),
         }
     }
 }
 
+/// The name of the parameter holding the token68 of Basic or Bearer
+/// credentials, written alone after the schema.
+pub const TOKEN_PARAM: &str = "token";
+
 /// AuthHeader used for headers such as Authorization
 /// or WWWAuthenticate.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AuthHeader(pub AuthSchema, pub HashMap<String, String>);
 
+/// The order in which the known parameters are written, the others
+/// following them sorted by name.
+const PARAM_ORDER: &[&str] = &[
+    "username",
+    "realm",
+    "domain",
+    "nonce",
+    "uri",
+    "response",
+    "algorithm",
+    "cnonce",
+    "opaque",
+    "stale",
+    "qop",
+    "nc",
+];
+
 impl fmt::Display for AuthHeader {
+    /// Parameters are written in a fixed order. `algorithm`, `stale`
+    /// and `nc` are tokens and `qop` is a token in credentials, every
+    /// other value is quoted.
+    /// ([RFC3261: Section 25.1, Basic Rules](https://tools.ietf.org/html/rfc3261#section-25.1))
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.0)?;
-        for (index, (key, value)) in self.1.iter().enumerate() {
-            if index == 0 && key == &"qop".to_string() {
-                write!(f, "{}={}", key, value)?;
-            } else if index == 0 && key == &"auth".to_string() {
-                write!(f, "{}={:08}", key, value)?;
-            } else if index == 0 {
-                write!(f, " {}=\"{}\"", key, value)?;
-            } else if key == &"qop".to_string() {
-                write!(f, ", {}={}", key, value)?;
-            } else if key == &"auth".to_string() {
-                write!(f, ", {}={:08}", key, value)?;
+        if let Some(token) = self.token() {
+            return write!(f, " {}", token);
+        }
+        let mut params: Vec<(&String, &String)> = self.1.iter().collect();
+        params.sort_by_key(|(key, _)| {
+            let position = PARAM_ORDER.iter().position(|known| known == key);
+            (position.unwrap_or(PARAM_ORDER.len()), key.as_str())
+        });
+        let credentials = self.1.contains_key("response");
+        for (index, (key, value)) in params.into_iter().enumerate() {
+            let separator = if index == 0 { " " } else { ", " };
+            let token = match key.as_str() {
+                "algorithm" | "stale" | "nc" => true,
+                "qop" => credentials,
+                _ => false,
+            };
+            if token {
+                write!(f, "{}{}={}", separator, key, value)?;
             } else {
-                write!(f, ", {}=\"{}\"", key, value)?;
+                write!(f, "{}{}=\"{}\"", separator, key, value)?;
             }
         }
         Ok(())
     }
 }
 
+/// The hash algorithms of Digest authentication. The `-sess` variants
+/// hash the credentials with the nonce and cnonce once more.
+/// ([RFC8760: Section 2, Updates to SIP Digest](https://tools.ietf.org/html/rfc8760#section-2))
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DigestAlgorithm {
+    Md5,
+    Md5Sess,
+    Sha256,
+    Sha256Sess,
+    Sha512_256,
+    Sha512_256Sess,
+}
+
+impl DigestAlgorithm {
+    /// Get the algorithm of an `algorithm` parameter, ignoring case.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::headers::DigestAlgorithm;
+    /// assert_eq!(Some(DigestAlgorithm::Sha256Sess), DigestAlgorithm::from_token("sha-256-SESS"));
+    /// assert_eq!(None, DigestAlgorithm::from_token("SHA-1"));
+    /// ```
+    pub fn from_token(token: &str) -> Option<DigestAlgorithm> {
+        match token.to_ascii_uppercase().as_str() {
+            "MD5" => Some(DigestAlgorithm::Md5),
+            "MD5-SESS" => Some(DigestAlgorithm::Md5Sess),
+            "SHA-256" => Some(DigestAlgorithm::Sha256),
+            "SHA-256-SESS" => Some(DigestAlgorithm::Sha256Sess),
+            "SHA-512-256" => Some(DigestAlgorithm::Sha512_256),
+            "SHA-512-256-SESS" => Some(DigestAlgorithm::Sha512_256Sess),
+            _ => None,
+        }
+    }
+
+    /// Determine if this is a `-sess` variant.
+    pub fn is_sess(self) -> bool {
+        matches!(
+            self,
+            DigestAlgorithm::Md5Sess
+                | DigestAlgorithm::Sha256Sess
+                | DigestAlgorithm::Sha512_256Sess
+        )
+    }
+
+    /// Hash `data`, returning the lowercase hex digest.
+    pub fn hash(self, data: &[u8]) -> String {
+        match self {
+            DigestAlgorithm::Md5 | DigestAlgorithm::Md5Sess => format!("{:x}", md5::compute(data)),
+            DigestAlgorithm::Sha256 | DigestAlgorithm::Sha256Sess => {
+                Sha256::default().digest(data).to_hex()
+            },
+            DigestAlgorithm::Sha512_256 | DigestAlgorithm::Sha512_256Sess => {
+                Sha512256::default().digest(data).to_hex()
+            },
+        }
+    }
+}
+
+impl fmt::Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DigestAlgorithm::Md5 => write!(f, "MD5"),
+            DigestAlgorithm::Md5Sess => write!(f, "MD5-sess"),
+            DigestAlgorithm::Sha256 => write!(f, "SHA-256"),
+            DigestAlgorithm::Sha256Sess => write!(f, "SHA-256-sess"),
+            DigestAlgorithm::Sha512_256 => write!(f, "SHA-512-256"),
+            DigestAlgorithm::Sha512_256Sess => write!(f, "SHA-512-256-sess"),
+        }
+    }
+}
+
 /// Context struct used when calculating the Auth Headers.
 pub struct AuthContext<'a> {
     pub user: &'a str,
@@ -57,158 +179,520 @@ pub struct AuthContext<'a> {
     pub uri: &'a Uri,
 }
 
+impl<'a> AuthContext<'a> {
+    /// Compute the credentials answering the Digest `challenge` of a 401
+    /// or 407 response, for a request with `method` and `body` sent to
+    /// the `uri` of this context. The result is the value of the
+    /// Authorization, or Proxy-Authorization, header of the new request.
+    ///
+    /// Every `DigestAlgorithm` is supported, the algorithm of the
+    /// challenge being echoed in the credentials. When the challenge offers
+    /// a qop, `auth` is preferred over `auth-int`, which also hashes the
+    /// body. A random cnonce is generated and `nc` is used as the nonce
+    /// count. Without qop the RFC2069 compatible response is computed.
+    /// A Basic challenge is answered with Basic credentials, a Bearer
+    /// challenge can't be answered with a password.
+    /// ([RFC2617: Section 3.2.2, The Authorization Request Header](https://tools.ietf.org/html/rfc2617#section-3.2.2)
+    /// and [RFC7616: Section 3.4, The Authorization Header Field](https://tools.ietf.org/html/rfc7616#section-3.4))
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::{headers::parse::parse_auth_challenge, *};
+    /// use nom::error::VerboseError;
+    ///
+    /// let (_, challenge) = parse_auth_challenge::<VerboseError<&[u8]>>(
+    ///     b"Digest realm=\"atlanta.com\", nonce=\"84a4cc6f3082121f32b42a2187831a9e\", qop=\"auth\"",
+    /// )
+    /// .unwrap();
+    /// let uri = Uri::sip(domain!("atlanta.com"));
+    /// let ctx = AuthContext {
+    ///     user: "alice",
+    ///     pass: "secret",
+    ///     nc: 1,
+    ///     uri: &uri,
+    /// };
+    /// let credentials = ctx.respond(&challenge, &Method::Invite, b"").unwrap();
+    /// assert_eq!(Some(&"00000001".to_string()), credentials.1.get("nc"));
+    /// assert_eq!(Some(&"sip:atlanta.com".to_string()), credentials.1.get("uri"));
+    /// assert_eq!(32, credentials.1["response"].len());
+    /// ```
+    pub fn respond(
+        &self,
+        challenge: &AuthHeader,
+        method: &Method,
+        body: &[u8],
+    ) -> IoResult<AuthHeader> {
+        self.respond_with_cnonce(challenge, method, body, &generate_cnonce())
+    }
+
+    /// Compute the credentials like `respond`, using the given `cnonce`
+    /// instead of a random one.
+    pub fn respond_with_cnonce(
+        &self,
+        challenge: &AuthHeader,
+        method: &Method,
+        body: &[u8],
+        cnonce: &str,
+    ) -> IoResult<AuthHeader> {
+        match challenge.0 {
+            AuthSchema::Digest => {},
+            AuthSchema::Basic => return Ok(AuthHeader::basic(self.user, self.pass)),
+            AuthSchema::Bearer => {
+                return Err(IoError::new(
+                    IoErrorKind::InvalidInput,
+                    "bearer tokens can't be computed from a password",
+                ))
+            },
+        }
+        let params = &challenge.1;
+        let realm = required_param(params, "realm")?;
+        let nonce = required_param(params, "nonce")?;
+        let algorithm = challenge.algorithm().ok_or_else(|| {
+            IoError::new(
+                IoErrorKind::InvalidInput,
+                format!("unsupported digest algorithm: {}", params["algorithm"]),
+            )
+        })?;
+        let qop = match params.get("qop") {
+            Some(offered) => Some(select_qop(offered)?),
+            None => None,
+        };
+        let hash = |data: String| algorithm.hash(data.as_bytes());
+
+        let uri = format!("{}", self.uri);
+        let ha1 = digest_ha1(algorithm, self.user, realm, self.pass, nonce, cnonce);
+        let ha2 = if qop == Some("auth-int") {
+            hash(format!("{}:{}:{}", method, uri, algorithm.hash(body)))
+        } else {
+            hash(format!("{}:{}", method, uri))
+        };
+
+        let mut map = HashMap::new();
+        map.insert("username".into(), self.user.to_string());
+        map.insert("realm".into(), realm.clone());
+        map.insert("nonce".into(), nonce.clone());
+        map.insert("uri".into(), uri);
+        let response = match qop {
+            Some(qop) => {
+                let nc = format!("{:08x}", self.nc);
+                let response = hash(format!(
+                    "{}:{}:{}:{}:{}:{}",
+                    ha1, nonce, nc, cnonce, qop, ha2
+                ));
+                map.insert("qop".into(), qop.to_string());
+                map.insert("nc".into(), nc);
+                map.insert("cnonce".into(), cnonce.to_string());
+                response
+            },
+            None => {
+                if algorithm.is_sess() {
+                    map.insert("cnonce".into(), cnonce.to_string());
+                }
+                hash(format!("{}:{}:{}", ha1, nonce, ha2))
+            },
+        };
+        map.insert("response".into(), response);
+        if params.contains_key("algorithm") {
+            map.insert("algorithm".into(), algorithm.to_string());
+        }
+        if let Some(opaque) = params.get("opaque") {
+            map.insert("opaque".into(), opaque.clone());
+        }
+        Ok(AuthHeader(AuthSchema::Digest, map))
+    }
+}
+
+/// Compute the hash of the credentials, A1, hashed with the nonce and
+/// cnonce for the `-sess` algorithms.
+fn digest_ha1(
+    algorithm: DigestAlgorithm,
+    user: &str,
+    realm: &str,
+    pass: &str,
+    nonce: &str,
+    cnonce: &str,
+) -> String {
+    let ha1 = algorithm.hash(format!("{}:{}:{}", user, realm, pass).as_bytes());
+    if algorithm.is_sess() {
+        algorithm.hash(format!("{}:{}:{}", ha1, nonce, cnonce).as_bytes())
+    } else {
+        ha1
+    }
+}
+
+/// Generate a random client nonce.
+fn generate_cnonce() -> String {
+    format!("{:x}", md5::compute(rand::random::<[u8; 16]>()))
+}
+
+fn required_param<'a>(params: &'a HashMap<String, String>, name: &str) -> IoResult<&'a String> {
+    params.get(name).ok_or_else(|| {
+        IoError::new(
+            IoErrorKind::InvalidInput,
+            format!("challenge doesn't contain a {}", name),
+        )
+    })
+}
+
+/// Select the qop to use from the comma separated list offered by
+/// a challenge.
+fn select_qop(offered: &str) -> IoResult<&'static str> {
+    let offered: Vec<&str> = offered.split(',').map(str::trim).collect();
+    if offered.iter().any(|qop| qop.eq_ignore_ascii_case("auth")) {
+        Ok("auth")
+    } else if offered
+        .iter()
+        .any(|qop| qop.eq_ignore_ascii_case("auth-int"))
+    {
+        Ok("auth-int")
+    } else {
+        Err(IoError::new(
+            IoErrorKind::InvalidInput,
+            format!("unsupported qop: {}", offered.join(",")),
+        ))
+    }
+}
+
 impl AuthHeader {
-    /// Perform the authenticate action.
+    /// Perform the authenticate action, for a REGISTER request without
+    /// body. See `AuthContext::respond`.
     pub fn authenticate<'a>(&self, ctx: AuthContext<'a>) -> IoResult<AuthHeader> {
+        ctx.respond(self, &Method::Register, &[])
+    }
+
+    /// Create Basic credentials of `user` with the password `pass`.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::AuthHeader;
+    ///
+    /// let credentials = AuthHeader::basic("Aladdin", "open sesame");
+    /// assert_eq!("Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==", format!("{}", credentials));
+    /// ```
+    pub fn basic(user: &str, pass: &str) -> AuthHeader {
+        let token = base64::encode(format!("{}:{}", user, pass));
+        AuthHeader::with_token(AuthSchema::Basic, token)
+    }
+
+    /// Create Bearer credentials presenting the access `token`.
+    pub fn bearer<S: Into<String>>(token: S) -> AuthHeader {
+        AuthHeader::with_token(AuthSchema::Bearer, token.into())
+    }
+
+    fn with_token(schema: AuthSchema, token: String) -> AuthHeader {
+        let mut map = HashMap::new();
+        map.insert(TOKEN_PARAM.to_string(), token);
+        AuthHeader(schema, map)
+    }
+
+    /// The token68 of Basic or Bearer credentials.
+    pub fn token(&self) -> Option<&str> {
         match self.0 {
-            AuthSchema::Digest => self.handle_digest_auth(ctx),
-        }
-    }
-
-    /// Perform the Digest auth method.
-    fn handle_digest_auth<'a>(&self, ctx: AuthContext<'a>) -> IoResult<AuthHeader> {
-        let qop = self
-            .1
-            .get("qop")
-            .expect("Auth header does not contain a qop field");
-        match qop.as_ref() {
-            "auth" => self.handle_digest_qop_auth(ctx),
-            qop => panic!("unknown auth qop: {}", qop),
-        }
-    }
-
-    /// Decides witch authorization algorythm to use.
-    fn handle_digest_qop_auth<'a>(&self, ctx: AuthContext<'a>) -> IoResult<AuthHeader> {
-        let alg = self
-            .1
-            .get("algorithm")
-            .map(|item| item.to_string())
-            .unwrap_or_else(|| "md5".to_string());
-        match alg.as_ref() {
-            "md5" | "MD5" => self.handle_md5_digest_auth(ctx),
-            "sha-256" | "SHA-256" => self.handle_sha256_digest_auth(ctx),
-            "sha-512-256" | "SHA-512-256" => self.handle_sha512_digest_auth(ctx),
-            alg => panic!("Unknown Auth alogirithm: {}", alg),
-        }
-    }
-
-    /// Handle the MD5 digest auth method.
-    fn handle_md5_digest_auth<'a>(&self, ctx: AuthContext<'a>) -> IoResult<AuthHeader> {
-        let realm = self
-            .1
-            .get("realm")
-            .expect("Auth header does not contain a realm");
-        let nonce = self
-            .1
-            .get("nonce")
-            .expect("Auth header does not contain a nonce");
-        let mut map: HashMap<String, String> = HashMap::new();
-        let cnonce = self.generate_cnonce();
-        map.insert("username".into(), ctx.user.to_string());
-        map.insert("nonce".into(), nonce.to_string());
-        map.insert("realm".into(), realm.clone());
-        map.insert("uri".into(), format!("{}", ctx.uri));
-        map.insert("qop".into(), self.1.get("qop").unwrap().clone());
-        map.insert("algorithm".into(), "MD5".into());
-        map.insert("cnonce".into(), format!("{:x}", cnonce));
-        map.insert("nc".into(), format!("{:08}", ctx.nc));
-        let ha1 = md5::compute(&format!("{}:{}:{}", ctx.user, realm, ctx.pass));
-        let ha2 = md5::compute(format!("REGISTER:{}", ctx.uri));
-        let digest = format!(
-            "{:x}:{}:{:08}:{:x}:auth:{:x}",
-            ha1, nonce, ctx.nc, cnonce, ha2
-        );
-        let pass = md5::compute(digest);
-        map.insert("response".into(), format!("{:x}", pass));
-        Ok(AuthHeader(AuthSchema::Digest, map))
+            AuthSchema::Digest => None,
+            AuthSchema::Basic | AuthSchema::Bearer => self.1.get(TOKEN_PARAM).map(String::as_str),
+        }
     }
 
-    /// Handle sha256 Digest auth method.
-    fn handle_sha256_digest_auth<'a>(&self, ctx: AuthContext<'a>) -> IoResult<AuthHeader> {
-        let realm = self
-            .1
-            .get("realm")
-            .expect("Auth header does not contain a realm");
-        let nonce = self
-            .1
-            .get("nonce")
-            .expect("Auth header does not contain a nonce");
-        let opaque = self
-            .1
-            .get("opaque")
-            .expect("Auth header does not contain a opaque");
-        let mut map: HashMap<String, String> = HashMap::new();
-        let cnonce = self.generate_cnonce();
-        map.insert("username".into(), ctx.user.to_string());
-        map.insert("nonce".into(), nonce.to_string());
-        map.insert("realm".into(), realm.clone());
-        map.insert("uri".into(), format!("{}", ctx.uri));
-        map.insert("qop".into(), self.1.get("qop").unwrap().clone());
-        map.insert("algorithm".into(), "SHA-512-256".into());
-        map.insert("cnonce".into(), format!("{:x}", cnonce));
-        map.insert("nc".into(), format!("{:08}", ctx.nc));
-        map.insert("opaque".into(), opaque.into());
-        let mut ha1_hasher = Sha256::default();
-        let ha1 = ha1_hasher.digest(format!("{}:{}:{}", ctx.user, realm, ctx.pass).as_ref());
-        let mut ha2_hasher = Sha256::default();
-        let ha2 = ha2_hasher.digest(format!("REGISTER:{}", ctx.uri).as_ref());
-        let digest = format!(
-            "{}:{}:{:08}:{:x}:auth:{}",
-            ha1.to_hex(),
-            nonce,
-            ctx.nc,
-            cnonce,
-            ha2.to_hex()
-        );
-        let pass = md5::compute(digest);
-        map.insert("response".into(), format!("{:x}", pass));
-        Ok(AuthHeader(AuthSchema::Digest, map))
+    /// Decode the user id and password of Basic credentials.
+    pub fn basic_credentials(&self) -> Option<(String, String)> {
+        if self.0 != AuthSchema::Basic {
+            return None;
+        }
+        let decoded = base64::decode(self.token()?).ok()?;
+        let decoded = String::from_utf8(decoded).ok()?;
+        let mut parts = decoded.splitn(2, ':');
+        Some((parts.next()?.to_string(), parts.next()?.to_string()))
     }
 
-    /// Handle sha512 auth method.
-    fn handle_sha512_digest_auth<'a>(&self, ctx: AuthContext<'a>) -> IoResult<AuthHeader> {
-        let realm = self
-            .1
-            .get("realm")
-            .expect("Auth header does not contain a realm");
-        let nonce = self
-            .1
-            .get("nonce")
-            .expect("Auth header does not contain a nonce");
-        let opaque = self
-            .1
-            .get("opaque")
-            .expect("Auth header does not contain a opaque");
-        let mut map: HashMap<String, String> = HashMap::new();
-        let cnonce = self.generate_cnonce();
-        map.insert("username".into(), ctx.user.to_string());
-        map.insert("nonce".into(), nonce.to_string());
-        map.insert("realm".into(), realm.clone());
-        map.insert("uri".into(), format!("{}", ctx.uri));
-        map.insert("qop".into(), self.1.get("qop").unwrap().clone());
-        map.insert("algorithm".into(), "SHA-512".into());
-        map.insert("cnonce".into(), format!("{:x}", cnonce));
-        map.insert("nc".into(), format!("{:08}", ctx.nc));
-        map.insert("opaque".into(), opaque.into());
-        let mut ha1_hasher = Sha512::default();
-        let ha1 = ha1_hasher.digest(format!("{}:{}:{}", ctx.user, realm, ctx.pass).as_ref());
-        let mut ha2_hasher = Sha512::default();
-        let ha2 = ha2_hasher.digest(format!("REGISTER:{}", ctx.uri).as_ref());
-        let digest = format!(
-            "{}:{}:{:08}:{:x}:auth:{}",
-            ha1.to_hex(),
-            nonce,
-            ctx.nc,
-            cnonce,
-            ha2.to_hex()
-        );
-        let pass = md5::compute(digest);
-        map.insert("response".into(), format!("{:x}", pass));
-        Ok(AuthHeader(AuthSchema::Digest, map))
+    /// The protection space of this challenge or credentials.
+    pub fn realm(&self) -> Option<&str> {
+        self.1.get("realm").map(String::as_str)
+    }
+
+    /// The nonce chosen by the server.
+    pub fn nonce(&self) -> Option<&str> {
+        self.1.get("nonce").map(String::as_str)
+    }
+
+    /// The opaque value to return unchanged to the server.
+    pub fn opaque(&self) -> Option<&str> {
+        self.1.get("opaque").map(String::as_str)
+    }
+
+    /// The quality of protection values offered by a challenge, e.g.
+    /// `["auth", "auth-int"]`, empty when the `qop` parameter is absent.
+    pub fn qop(&self) -> Vec<String> {
+        self.list("qop", ',')
+    }
+
+    /// The URIs of the protection space of a challenge, empty when the
+    /// `domain` parameter is absent.
+    pub fn domain(&self) -> Vec<String> {
+        self.list("domain", ' ')
+    }
+
+    /// Determine if the challenge rejected a previous request only because
+    /// its nonce was stale.
+    pub fn stale(&self) -> bool {
+        self.1
+            .get("stale")
+            .is_some_and(|stale| stale.eq_ignore_ascii_case("true"))
+    }
+
+    fn list(&self, key: &str, separator: char) -> Vec<String> {
+        self.1
+            .get(key)
+            .map(|value| {
+                value
+                    .split(separator)
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The digest algorithm of this challenge or credentials, MD5 when
+    /// the `algorithm` parameter is absent. `None` for an unknown
+    /// algorithm.
+    pub fn algorithm(&self) -> Option<DigestAlgorithm> {
+        match self.1.get("algorithm") {
+            Some(token) => DigestAlgorithm::from_token(token),
+            None => Some(DigestAlgorithm::Md5),
+        }
+    }
+}
+
+/// The Authentication-Info header sent by a server in the response to
+/// a request with Digest credentials, giving the nonce to use for the
+/// next request and proving that the server knows the password.
+/// ([RFC7616: Section 3.5, The Authentication-Info Header](https://tools.ietf.org/html/rfc7616#section-3.5))
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AuthenticationInfo {
+    pub next_nonce: Option<String>,
+    pub qop: Option<String>,
+    /// The response digest of the server, see `DigestCredentials::verify`.
+    pub rspauth: Option<String>,
+    pub cnonce: Option<String>,
+    pub nc: Option<u32>,
+}
+
+impl fmt::Display for AuthenticationInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut params = vec![];
+        if let Some(next_nonce) = &self.next_nonce {
+            params.push(format!("nextnonce=\"{}\"", next_nonce));
+        }
+        if let Some(qop) = &self.qop {
+            params.push(format!("qop={}", qop));
+        }
+        if let Some(rspauth) = &self.rspauth {
+            params.push(format!("rspauth=\"{}\"", rspauth));
+        }
+        if let Some(cnonce) = &self.cnonce {
+            params.push(format!("cnonce=\"{}\"", cnonce));
+        }
+        if let Some(nc) = self.nc {
+            params.push(format!("nc={:08x}", nc));
+        }
+        write!(f, "{}", params.join(", "))
+    }
+}
+
+/// What a new challenge means for the credentials answering it, see
+/// `DigestCredentials::on_challenge`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ChallengeOutcome {
+    /// The request has to be sent again with credentials.
+    Retry,
+    /// The nonce of the credentials sent expired (`stale=true`), the
+    /// request has to be sent again with the new nonce.
+    Stale,
+    /// The credentials sent were rejected, retrying with them would
+    /// fail again.
+    Rejected,
+}
+
+/// The credentials of a user answering the Digest challenges of a realm.
+/// The nonce of the last challenge is reused for later requests, the
+/// nonce count being incremented every time, and a new cnonce is
+/// generated for every request.
+/// ([RFC7616: Section 3.4, The Authorization Header Field](https://tools.ietf.org/html/rfc7616#section-3.4))
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     headers::{parse::parse_auth_challenge, ChallengeOutcome, DigestCredentials},
+///     *,
+/// };
+/// use nom::error::VerboseError;
+///
+/// let (_, challenge) = parse_auth_challenge::<VerboseError<&[u8]>>(
+///     b"Digest realm=\"atlanta.com\", nonce=\"abc\", qop=\"auth\"",
+/// )
+/// .unwrap();
+/// let mut credentials = DigestCredentials::new("alice", "secret");
+/// assert_eq!(ChallengeOutcome::Retry, credentials.on_challenge(&challenge, None));
+///
+/// let uri = Uri::sip(domain!("atlanta.com"));
+/// let first = credentials.authorize(&Method::Register, &uri, b"").unwrap();
+/// let second = credentials.authorize(&Method::Register, &uri, b"").unwrap();
+/// assert_eq!("00000001", first.1["nc"]);
+/// assert_eq!("00000002", second.1["nc"]);
+/// assert_ne!(first.1["cnonce"], second.1["cnonce"]);
+///
+/// // A challenge of the request carrying credentials means the password is wrong.
+/// assert_eq!(
+///     ChallengeOutcome::Rejected,
+///     credentials.on_challenge(&challenge, Some(&second))
+/// );
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DigestCredentials {
+    user: String,
+    /// Never serialized, set it again with `set_password` once
+    /// deserialized.
+    #[cfg_attr(feature = "serde", serde(skip_serializing, default))]
+    pass: String,
+    challenge: Option<AuthHeader>,
+    nc: u32,
+    cnonce: Option<String>,
+}
+
+impl DigestCredentials {
+    /// Create the credentials of `user` with the password `pass`.
+    pub fn new<U: Into<String>, P: Into<String>>(user: U, pass: P) -> DigestCredentials {
+        DigestCredentials {
+            user: user.into(),
+            pass: pass.into(),
+            challenge: None,
+            nc: 0,
+            cnonce: None,
+        }
+    }
+
+    /// Set the password, e.g. of credentials restored from their serialized
+    /// form, which leaves it out.
+    pub fn set_password<P: Into<String>>(&mut self, pass: P) {
+        self.pass = pass.into();
+    }
+
+    /// Handle a challenge received in answer to a request, `sent` being
+    /// the credentials that request carried for the challenged realm, if
+    /// any. A challenge of a request that carried credentials means they
+    /// were wrong, unless it is marked as stale. Its nonce isn't compared,
+    /// as servers usually pick a new nonce for every challenge, including
+    /// the one rejecting the credentials. Otherwise the challenge replaces
+    /// the previous one and the nonce count restarts.
+    /// ([RFC3261: Section 22.2, User-to-User Authentication](https://tools.ietf.org/html/rfc3261#section-22.2))
+    pub fn on_challenge(
+        &mut self,
+        challenge: &AuthHeader,
+        sent: Option<&AuthHeader>,
+    ) -> ChallengeOutcome {
+        let answered = sent.is_some_and(|sent| sent.realm() == challenge.realm());
+        let outcome = if challenge.stale() {
+            ChallengeOutcome::Stale
+        } else if answered {
+            ChallengeOutcome::Rejected
+        } else {
+            ChallengeOutcome::Retry
+        };
+        if outcome != ChallengeOutcome::Rejected {
+            self.challenge = Some(challenge.clone());
+            self.nc = 0;
+        }
+        outcome
+    }
+
+    /// Replace the nonce of the current challenge, e.g. with the
+    /// `nextnonce` given by the server, restarting the nonce count.
+    pub fn set_nonce<S: Into<String>>(&mut self, nonce: S) {
+        if let Some(challenge) = &mut self.challenge {
+            challenge.1.insert("nonce".into(), nonce.into());
+            self.nc = 0;
+        }
+    }
+
+    /// Compute the credentials of the next request, with `method` and
+    /// `body`, sent to `uri`. Fails if no challenge was received yet.
+    pub fn authorize(&mut self, method: &Method, uri: &Uri, body: &[u8]) -> IoResult<AuthHeader> {
+        let challenge = self
+            .challenge
+            .as_ref()
+            .ok_or_else(|| IoError::new(IoErrorKind::InvalidInput, "no challenge was received"))?;
+        let cnonce = generate_cnonce();
+        let ctx = AuthContext {
+            user: &self.user,
+            pass: &self.pass,
+            nc: self.nc + 1,
+            uri,
+        };
+        let credentials = ctx.respond_with_cnonce(challenge, method, body, &cnonce)?;
+        self.nc += 1;
+        self.cnonce = Some(cnonce);
+        Ok(credentials)
+    }
+
+    /// Handle the Authentication-Info header of a response to a request
+    /// sent with these credentials, using its `nextnonce` if any for the
+    /// next requests. Call `verify` first as it needs the current nonce.
+    pub fn on_authentication_info(&mut self, info: &AuthenticationInfo) {
+        if let Some(next_nonce) = &info.next_nonce {
+            self.set_nonce(next_nonce.clone());
+        }
+    }
+
+    /// Check the `rspauth` of the Authentication-Info header of the
+    /// response to the last request sent to `uri`, proving that the
+    /// server knows the password. `body` is the body of the response,
+    /// used with `qop=auth-int`. Returns false if `rspauth` is absent.
+    /// ([RFC2617: Section 3.2.3, The Authentication-Info Header](https://tools.ietf.org/html/rfc2617#section-3.2.3))
+    pub fn verify(&self, info: &AuthenticationInfo, uri: &Uri, body: &[u8]) -> bool {
+        let (challenge, rspauth) = match (&self.challenge, &info.rspauth) {
+            (Some(challenge), Some(rspauth)) => (challenge, rspauth),
+            _ => return false,
+        };
+        let (realm, nonce, algorithm) =
+            match (challenge.realm(), challenge.nonce(), challenge.algorithm()) {
+                (Some(realm), Some(nonce), Some(algorithm)) => (realm, nonce, algorithm),
+                _ => return false,
+            };
+        let cnonce = self.cnonce.as_deref().unwrap_or_default();
+        let ha1 = digest_ha1(algorithm, &self.user, realm, &self.pass, nonce, cnonce);
+        let ha2 = match info.qop.as_deref() {
+            Some("auth-int") => {
+                algorithm.hash(format!(":{}:{}", uri, algorithm.hash(body)).as_bytes())
+            },
+            _ => algorithm.hash(format!(":{}", uri).as_bytes()),
+        };
+        let expected = match &info.qop {
+            Some(qop) => format!(
+                "{}:{}:{:08x}:{}:{}:{}",
+                ha1, nonce, self.nc, cnonce, qop, ha2
+            ),
+            None => format!("{}:{}:{}", ha1, nonce, ha2),
+        };
+        algorithm
+            .hash(expected.as_bytes())
+            .eq_ignore_ascii_case(rspauth)
+    }
+
+    /// The challenge answered by these credentials.
+    pub fn challenge(&self) -> Option<&AuthHeader> {
+        self.challenge.as_ref()
+    }
+
+    /// The nonce count of the last credentials computed.
+    pub fn nonce_count(&self) -> u32 {
+        self.nc
     }
 
-    /// Generate the nonce used during authorization.
-    fn generate_cnonce(&self) -> md5::Digest {
-        md5::compute(rand::random::<[u8; 16]>())
+    /// The cnonce of the last credentials computed.
+    pub fn cnonce(&self) -> Option<&str> {
+        self.cnonce.as_deref()
     }
 }
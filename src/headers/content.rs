@@ -1,75 +1,134 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use std::fmt;
 
-/// Sip protocol Content-Type value.
-#[derive(Debug, PartialEq, Clone, Copy)]
-pub enum ContentType {
-    Csv,
-    Sdp,
-    Xml,
-    Html,
-    VCard,
-    Calendar,
-    MarkDown,
-    MsWord,
-    Pdf,
-    Png,
-    PlainText,
-    Zip,
-    GZip,
-    Sql,
-    Json,
-    Javascript,
-    Css,
+use crate::headers::{write::write_generic_params, GenericParams};
+
+/// Sip protocol Content-Type value, a media type with its parameters.
+/// ([RFC3261: Section 20.15, Content-Type](https://tools.ietf.org/html/rfc3261#section-20.15))
+///
+/// The type and subtype are case-insensitive and are always stored in lowercase.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ContentType {
+    /// The top-level media type, e.g. `application` or `text`.
+    pub media_type: String,
+    pub subtype: String,
+    pub parameters: GenericParams,
+}
+
+impl ContentType {
+    pub fn new<T: Into<String>, S: Into<String>>(media_type: T, subtype: S) -> ContentType {
+        ContentType {
+            media_type: media_type.into().to_lowercase(),
+            subtype: subtype.into().to_lowercase(),
+            parameters: GenericParams::new(),
+        }
+    }
+
+    /// `application/sdp`
+    pub fn sdp() -> ContentType {
+        ContentType::new("application", "sdp")
+    }
+
+    /// `text/plain`
+    pub fn plain_text() -> ContentType {
+        ContentType::new("text", "plain")
+    }
+
+    /// `text/html`
+    pub fn html() -> ContentType {
+        ContentType::new("text", "html")
+    }
+
+    /// `application/xml`
+    pub fn xml() -> ContentType {
+        ContentType::new("application", "xml")
+    }
+
+    /// `application/json`
+    pub fn json() -> ContentType {
+        ContentType::new("application", "json")
+    }
+
+    /// `message/sipfrag`
+    pub fn sipfrag() -> ContentType {
+        ContentType::new("message", "sipfrag")
+    }
+
+    /// `application/dtmf-relay`
+    pub fn dtmf_relay() -> ContentType {
+        ContentType::new("application", "dtmf-relay")
+    }
+
+    /// `multipart/mixed`
+    pub fn multipart_mixed() -> ContentType {
+        ContentType::new("multipart", "mixed")
+    }
+
+    /// Adds a parameter with a given name and a given value to `parameters`.
+    pub fn param<N, V>(mut self, name: N, value: Option<V>) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.parameters.insert(name.into(), value.map(Into::into));
+        self
+    }
+
+    /// Determine if this is `media_type/subtype`, ignoring any parameters.
+    pub fn is(&self, media_type: &str, subtype: &str) -> bool {
+        self.media_type.eq_ignore_ascii_case(media_type)
+            && self.subtype.eq_ignore_ascii_case(subtype)
+    }
+
+    /// The `charset` parameter.
+    pub fn charset(&self) -> Option<&str> {
+        self.param_value("charset")
+    }
+
+    /// The `boundary` parameter of multipart bodies.
+    pub fn boundary(&self) -> Option<&str> {
+        self.param_value("boundary")
+    }
+
+    /// Retrieve the value of the parameter `name` if it is present and has a value.
+    pub fn param_value(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .and_then(|(_, value)| value.as_ref().map(String::as_str))
+    }
 }
 
 impl fmt::Display for ContentType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            ContentType::Sdp => write!(f, "application/sdp"),
-            ContentType::PlainText => write!(f, "text/plain"),
-            ContentType::Xml => write!(f, "application/xml"),
-            ContentType::Html => write!(f, "text/html"),
-            ContentType::VCard => write!(f, "text/vcard"),
-            ContentType::MarkDown => write!(f, "text/markdown"),
-            ContentType::Calendar => write!(f, "text/calendar"),
-            ContentType::MsWord => write!(f, "applciation/msword"),
-            ContentType::Pdf => write!(f, "application/pdf"),
-            ContentType::Png => write!(f, "image/png"),
-            ContentType::Csv => write!(f, "text/csv"),
-            ContentType::Zip => write!(f, "application/zip"),
-            ContentType::Sql => write!(f, "application/sql"),
-            ContentType::Json => write!(f, "application/json"),
-            ContentType::GZip => write!(f, "application/gzip"),
-            ContentType::Javascript => write!(f, "application/javascript"),
-            ContentType::Css => write!(f, "application/css"),
-        }
+        write!(f, "{}/{}", self.media_type, self.subtype)?;
+        write_generic_params(&self.parameters, f)
     }
 }
 
-use nom::{branch::alt, bytes::complete::tag_no_case, combinator::map, error::ParseError, IResult};
+use nom::{
+    bytes::complete::take_while1, character::complete::char, combinator::map_res,
+    error::ParseError, IResult,
+};
+
+use crate::{headers::named::parse_named_field_params, parse::*};
 
 pub fn parse_content_type<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], ContentType, E> {
-    Ok(alt((
-        map(tag_no_case("application/sdp"), |_| ContentType::Sdp),
-        map(tag_no_case("text/plain"), |_| ContentType::PlainText),
-        map(tag_no_case("text/html"), |_| ContentType::Html),
-        map(tag_no_case("applications/xml"), |_| ContentType::Xml),
-        map(tag_no_case("text/vcard"), |_| ContentType::VCard),
-        map(tag_no_case("text/calendar"), |_| ContentType::Calendar),
-        map(tag_no_case("text/markdown"), |_| ContentType::MarkDown),
-        map(tag_no_case("application/msword"), |_| ContentType::MsWord),
-        map(tag_no_case("application/pdf"), |_| ContentType::Pdf),
-        map(tag_no_case("image/png"), |_| ContentType::Png),
-        map(tag_no_case("text/csv"), |_| ContentType::Csv),
-        map(tag_no_case("application/zip"), |_| ContentType::Zip),
-        map(tag_no_case("application/sql"), |_| ContentType::Sql),
-        map(tag_no_case("application/json"), |_| ContentType::Json),
-        map(tag_no_case("application/gzip"), |_| ContentType::GZip),
-        map(tag_no_case("application/javascript"), |_| {
-            ContentType::Javascript
-        }),
-        map(tag_no_case("application/css"), |_| ContentType::Css),
-    ))(input)?)
+    let (input, media_type) = map_res(take_while1(is_token), slice_to_string::<E>)(input)?;
+    let (input, _) = char('/')(input)?;
+    let (input, subtype) = map_res(take_while1(is_token), slice_to_string::<E>)(input)?;
+    let (input, parameters) = parse_named_field_params(input)?;
+    Ok((
+        input,
+        ContentType {
+            media_type: media_type.to_lowercase(),
+            subtype: subtype.to_lowercase(),
+            parameters,
+        },
+    ))
 }
@@ -0,0 +1,290 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, tag_no_case, take_while_m_n},
+    character::{complete::char, is_digit},
+    combinator::{map, map_res},
+    error::ParseError,
+    IResult,
+};
+
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::parse::parse_u32;
+
+/// Day of the week used in `SipDate`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Weekday {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+impl fmt::Display for Weekday {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Weekday::Mon => write!(f, "Mon"),
+            Weekday::Tue => write!(f, "Tue"),
+            Weekday::Wed => write!(f, "Wed"),
+            Weekday::Thu => write!(f, "Thu"),
+            Weekday::Fri => write!(f, "Fri"),
+            Weekday::Sat => write!(f, "Sat"),
+            Weekday::Sun => write!(f, "Sun"),
+        }
+    }
+}
+
+/// Month of the year used in `SipDate`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Month {
+    Jan,
+    Feb,
+    Mar,
+    Apr,
+    May,
+    Jun,
+    Jul,
+    Aug,
+    Sep,
+    Oct,
+    Nov,
+    Dec,
+}
+
+impl Month {
+    const ALL: [Month; 12] = [
+        Month::Jan,
+        Month::Feb,
+        Month::Mar,
+        Month::Apr,
+        Month::May,
+        Month::Jun,
+        Month::Jul,
+        Month::Aug,
+        Month::Sep,
+        Month::Oct,
+        Month::Nov,
+        Month::Dec,
+    ];
+
+    /// The number of this month, starting with 1 for January.
+    pub fn number(self) -> u8 {
+        Month::ALL.iter().position(|m| *m == self).unwrap() as u8 + 1
+    }
+
+    /// Get the month from its number, starting with 1 for January.
+    pub fn from_number(num: u8) -> Option<Month> {
+        Month::ALL.get((num as usize).checked_sub(1)?).copied()
+    }
+}
+
+impl fmt::Display for Month {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A point in time as used by the Date header, an RFC 1123 date which is always in GMT.
+/// ([RFC3261: Section 20.17, Date](https://tools.ietf.org/html/rfc3261#section-20.17))
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SipDate {
+    pub weekday: Weekday,
+    pub day: u8,
+    pub month: Month,
+    pub year: u16,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl SipDate {
+    /// Get the current date.
+    pub fn now() -> SipDate {
+        SipDate::from(SystemTime::now())
+    }
+
+    /// Create a date from the number of seconds since the unix epoch.
+    pub fn from_unix_timestamp(timestamp: u64) -> SipDate {
+        let days = (timestamp / 86400) as i64;
+        let secs = timestamp % 86400;
+        // Civil from days algorithm, see http://howardhinnant.github.io/date_algorithms.html
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+        let weekday = [
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+        ][days.rem_euclid(7) as usize];
+        SipDate {
+            weekday,
+            day,
+            month: Month::from_number(month).unwrap(),
+            year: year as u16,
+            hour: (secs / 3600) as u8,
+            minute: (secs % 3600 / 60) as u8,
+            second: (secs % 60) as u8,
+        }
+    }
+
+    /// Get the number of seconds since the unix epoch.
+    pub fn unix_timestamp(&self) -> u64 {
+        // Days from civil algorithm, see http://howardhinnant.github.io/date_algorithms.html
+        let month = self.month.number() as i64;
+        let year = self.year as i64 - if month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let mp = (month + 9) % 12;
+        let doy = (153 * mp + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146_097 + doe - 719_468;
+        days.max(0) as u64 * 86400
+            + self.hour as u64 * 3600
+            + self.minute as u64 * 60
+            + self.second as u64
+    }
+}
+
+impl From<SystemTime> for SipDate {
+    fn from(time: SystemTime) -> SipDate {
+        let timestamp = time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_else(|_| Duration::from_secs(0))
+            .as_secs();
+        SipDate::from_unix_timestamp(timestamp)
+    }
+}
+
+impl From<SipDate> for SystemTime {
+    fn from(date: SipDate) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(date.unix_timestamp())
+    }
+}
+
+impl fmt::Display for SipDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+            self.weekday, self.day, self.month, self.year, self.hour, self.minute, self.second
+        )
+    }
+}
+
+/// Value of the Date header. Dates that are not valid
+/// RFC 1123 dates are kept as they were received.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DateHeader {
+    Date(SipDate),
+    Invalid(String),
+}
+
+impl fmt::Display for DateHeader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DateHeader::Date(date) => write!(f, "{}", date),
+            DateHeader::Invalid(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+fn parse_weekday<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Weekday, E> {
+    alt((
+        map(tag_no_case("Mon"), |_| Weekday::Mon),
+        map(tag_no_case("Tue"), |_| Weekday::Tue),
+        map(tag_no_case("Wed"), |_| Weekday::Wed),
+        map(tag_no_case("Thu"), |_| Weekday::Thu),
+        map(tag_no_case("Fri"), |_| Weekday::Fri),
+        map(tag_no_case("Sat"), |_| Weekday::Sat),
+        map(tag_no_case("Sun"), |_| Weekday::Sun),
+    ))(input)
+}
+
+fn parse_month<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Month, E> {
+    alt((
+        map(tag_no_case("Jan"), |_| Month::Jan),
+        map(tag_no_case("Feb"), |_| Month::Feb),
+        map(tag_no_case("Mar"), |_| Month::Mar),
+        map(tag_no_case("Apr"), |_| Month::Apr),
+        map(tag_no_case("May"), |_| Month::May),
+        map(tag_no_case("Jun"), |_| Month::Jun),
+        map(tag_no_case("Jul"), |_| Month::Jul),
+        map(tag_no_case("Aug"), |_| Month::Aug),
+        map(tag_no_case("Sep"), |_| Month::Sep),
+        map(tag_no_case("Oct"), |_| Month::Oct),
+        map(tag_no_case("Nov"), |_| Month::Nov),
+        map(tag_no_case("Dec"), |_| Month::Dec),
+    ))(input)
+}
+
+fn parse_digits<'a, E: ParseError<&'a [u8]>>(
+    count: usize,
+    max: u32,
+) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], u32, E> {
+    move |input: &'a [u8]| {
+        map_res(take_while_m_n(count, count, is_digit), |slice| {
+            parse_u32(slice).and_then(|value| {
+                if value <= max {
+                    Ok(value)
+                } else {
+                    Err(::std::io::ErrorKind::InvalidData.into())
+                }
+            })
+        })(input)
+    }
+}
+
+/// Parse an RFC 1123 date, e.g. `Sat, 13 Nov 2010 23:29:00 GMT`.
+pub fn parse_sip_date<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], SipDate, E> {
+    let (input, weekday) = parse_weekday(input)?;
+    let (input, _) = tag(", ")(input)?;
+    let (input, day) = parse_digits(2, 31)(input)?;
+    let (input, _) = char(' ')(input)?;
+    let (input, month) = parse_month(input)?;
+    let (input, _) = char(' ')(input)?;
+    let (input, year) = parse_digits(4, 9999)(input)?;
+    let (input, _) = char(' ')(input)?;
+    let (input, hour) = parse_digits(2, 23)(input)?;
+    let (input, _) = char(':')(input)?;
+    let (input, minute) = parse_digits(2, 59)(input)?;
+    let (input, _) = char(':')(input)?;
+    let (input, second) = parse_digits(2, 60)(input)?;
+    let (input, _) = tag(" GMT")(input)?;
+    Ok((
+        input,
+        SipDate {
+            weekday,
+            day: day as u8,
+            month,
+            year: year as u16,
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+        },
+    ))
+}
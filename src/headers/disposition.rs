@@ -0,0 +1,187 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    combinator::map_res,
+    error::ParseError,
+    IResult,
+};
+
+use std::fmt;
+
+use crate::{
+    core::extract_opt_param,
+    headers::{
+        named::parse_named_field_params,
+        parse::parse_header_name,
+        write::{write_generic_params, write_optional_param},
+        GenericParams, Header,
+    },
+    parse::{is_token, slice_to_string},
+};
+
+/// How the message body is to be interpreted by the receiver.
+/// ([RFC3261: Section 20.11, Content-Disposition](https://tools.ietf.org/html/rfc3261#section-20.11))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DispositionType {
+    /// The body describes a session, e.g. an SDP body.
+    Session,
+    /// The body is to be displayed or otherwise rendered to the user.
+    Render,
+    /// The body is an image suitable as an iconic representation of the caller.
+    Icon,
+    /// The body is a sound to be used to alert the user.
+    Alert,
+    Other(String),
+}
+
+impl DispositionType {
+    /// Get the disposition type for a disp-type token, ignoring case.
+    pub fn from_token(token: &str) -> DispositionType {
+        match token.to_ascii_lowercase().as_str() {
+            "session" => DispositionType::Session,
+            "render" => DispositionType::Render,
+            "icon" => DispositionType::Icon,
+            "alert" => DispositionType::Alert,
+            _ => DispositionType::Other(token.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for DispositionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DispositionType::Session => write!(f, "session"),
+            DispositionType::Render => write!(f, "render"),
+            DispositionType::Icon => write!(f, "icon"),
+            DispositionType::Alert => write!(f, "alert"),
+            DispositionType::Other(token) => write!(f, "{}", token),
+        }
+    }
+}
+
+/// Value of the `handling` parameter: whether the receiver may ignore a
+/// body whose type or disposition it doesn't understand.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Handling {
+    Optional,
+    Required,
+}
+
+impl fmt::Display for Handling {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Handling::Optional => write!(f, "optional"),
+            Handling::Required => write!(f, "required"),
+        }
+    }
+}
+
+impl std::str::FromStr for Handling {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("optional") {
+            Ok(Handling::Optional)
+        } else if s.eq_ignore_ascii_case("required") {
+            Ok(Handling::Required)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Value of the Content-Disposition header.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ContentDisposition {
+    pub disposition: DispositionType,
+    pub handling: Option<Handling>,
+    pub parameters: GenericParams,
+}
+
+impl ContentDisposition {
+    pub fn new(disposition: DispositionType) -> ContentDisposition {
+        ContentDisposition {
+            disposition,
+            handling: None,
+            parameters: GenericParams::new(),
+        }
+    }
+
+    /// Set the `handling` parameter.
+    pub fn handling(mut self, handling: Handling) -> Self {
+        self.handling = Some(handling);
+        self
+    }
+
+    /// Adds a parameter with a given name and a given value to `parameters`.
+    pub fn param<N, V>(mut self, name: N, value: Option<V>) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.parameters.insert(name.into(), value.map(Into::into));
+        self
+    }
+
+    /// Determine if the body may be ignored when it isn't understood.
+    /// Handling defaults to `required` when the parameter is missing.
+    pub fn is_optional(&self) -> bool {
+        self.handling == Some(Handling::Optional)
+    }
+}
+
+impl fmt::Display for ContentDisposition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.disposition)?;
+        write_optional_param("handling", &self.handling, f)?;
+        write_generic_params(&self.parameters, f)
+    }
+}
+
+/// Parses Content-Disposition header ([RFC3261: Page 226, "Content-Disposition"](https://tools.ietf.org/html/rfc3261#page-226))
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     headers::disposition::{
+///         parse_content_disposition_header, ContentDisposition, DispositionType, Handling,
+///     },
+///     Header,
+/// };
+/// use nom::error::VerboseError;
+///
+/// assert_eq!(
+///     parse_content_disposition_header::<VerboseError<_>>(
+///         b"Content-Disposition: session;handling=optional\r\n"
+///     ),
+///     Ok((
+///         "".as_bytes(),
+///         Header::ContentDisposition(
+///             ContentDisposition::new(DispositionType::Session).handling(Handling::Optional)
+///         )
+///     ))
+/// );
+/// ```
+pub fn parse_content_disposition_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Content-Disposition")(input)?;
+    let (input, disposition) = map_res(take_while1(is_token), slice_to_string::<E>)(input)?;
+    let (input, mut parameters) = parse_named_field_params(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    let mut handling = None;
+    extract_opt_param(&mut parameters, "handling", &mut handling);
+    Ok((
+        input,
+        Header::ContentDisposition(ContentDisposition {
+            disposition: DispositionType::from_token(&disposition),
+            handling,
+            parameters,
+        }),
+    ))
+}
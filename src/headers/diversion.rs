@@ -0,0 +1,89 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{bytes::complete::tag, error::ParseError, multi::separated_list1, IResult};
+
+use std::fmt;
+
+use crate::{
+    core::extract_opt_param,
+    headers::{
+        named::parse_named_header,
+        parse::{parse_comma_separator, parse_header_name},
+        write::write_optional_param,
+        Header, NamedHeader,
+    },
+};
+
+/// Value of the Diversion header, identifying the party that diverted
+/// the call and why.
+/// ([RFC5806: Section 4, Diversion Header Syntax](https://tools.ietf.org/html/rfc5806#section-4))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DiversionHeader {
+    /// The diverting party along with the remaining diversion params.
+    pub target: NamedHeader,
+    /// e.g. `unconditional`, `user-busy`, `no-answer` or `deflection`.
+    pub reason: Option<String>,
+    /// Number of diversions that occurred.
+    pub counter: Option<u32>,
+}
+
+impl DiversionHeader {
+    pub fn new(target: NamedHeader) -> DiversionHeader {
+        DiversionHeader {
+            target,
+            reason: None,
+            counter: None,
+        }
+    }
+
+    /// Set the `reason` parameter.
+    pub fn reason<S: Into<String>>(mut self, reason: S) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Set the `counter` parameter.
+    pub fn counter(mut self, counter: u32) -> Self {
+        self.counter = Some(counter);
+        self
+    }
+}
+
+impl fmt::Display for DiversionHeader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.target.fmt_name_addr(f)?;
+        write_optional_param("reason", &self.reason, f)?;
+        write_optional_param("counter", &self.counter, f)
+    }
+}
+
+/// Parse a single Diversion value.
+pub fn parse_diversion<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], DiversionHeader, E> {
+    let (input, mut target) = parse_named_header(input)?;
+    let mut reason = None;
+    let mut counter = None;
+    extract_opt_param(&mut target.parameters, "reason", &mut reason);
+    extract_opt_param(&mut target.parameters, "counter", &mut counter);
+    Ok((
+        input,
+        DiversionHeader {
+            target,
+            reason,
+            counter,
+        },
+    ))
+}
+
+/// Parses Diversion header ([RFC5806: Page 7, "Diversion"](https://tools.ietf.org/html/rfc5806#page-7))
+pub fn parse_diversion_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Diversion")(input)?;
+    let (input, values) = separated_list1(parse_comma_separator, parse_diversion)(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::Diversion(values)))
+}
@@ -0,0 +1,168 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    combinator::map_res,
+    error::ParseError,
+    multi::{separated_list0, separated_list1},
+    IResult,
+};
+
+use std::fmt;
+
+use crate::{
+    core::extract_opt_param,
+    headers::{
+        named::parse_named_field_params,
+        parse::{parse_comma_separator, parse_header_name},
+        write::{write_generic_params, write_optional_param},
+        GenericParams, Header,
+    },
+    parse::{is_token, slice_to_string},
+};
+
+/// Content-coding applied to a message body, e.g. `gzip`.
+/// ([RFC3261: Section 20.12, Content-Encoding](https://tools.ietf.org/html/rfc3261#section-20.12))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ContentCoding {
+    Gzip,
+    Compress,
+    Deflate,
+    Identity,
+    /// The `*` coding, only valid in the Accept-Encoding header.
+    Any,
+    Other(String),
+}
+
+impl ContentCoding {
+    /// Get the content-coding for a coding token, ignoring case.
+    pub fn from_token(token: &str) -> ContentCoding {
+        match token.to_ascii_lowercase().as_str() {
+            "gzip" => ContentCoding::Gzip,
+            "compress" => ContentCoding::Compress,
+            "deflate" => ContentCoding::Deflate,
+            "identity" => ContentCoding::Identity,
+            "*" => ContentCoding::Any,
+            _ => ContentCoding::Other(token.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ContentCoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ContentCoding::Gzip => write!(f, "gzip"),
+            ContentCoding::Compress => write!(f, "compress"),
+            ContentCoding::Deflate => write!(f, "deflate"),
+            ContentCoding::Identity => write!(f, "identity"),
+            ContentCoding::Any => write!(f, "*"),
+            ContentCoding::Other(token) => write!(f, "{}", token),
+        }
+    }
+}
+
+/// A single entry of the Accept-Encoding header, a content-coding
+/// with its q-value and any other parameters.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeightedCoding {
+    pub coding: ContentCoding,
+    pub q: Option<f32>,
+    pub parameters: GenericParams,
+}
+
+impl WeightedCoding {
+    pub fn new(coding: ContentCoding) -> WeightedCoding {
+        WeightedCoding {
+            coding,
+            q: None,
+            parameters: GenericParams::new(),
+        }
+    }
+
+    /// Set the q-value of this coding.
+    pub fn q(mut self, q: f32) -> Self {
+        self.q = Some(q);
+        self
+    }
+}
+
+impl fmt::Display for WeightedCoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.coding)?;
+        write_optional_param("q", &self.q, f)?;
+        write_generic_params(&self.parameters, f)
+    }
+}
+
+/// Parse a single content-coding token.
+pub fn parse_content_coding<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], ContentCoding, E> {
+    let (input, token) = map_res(
+        take_while1(|chr| is_token(chr) || chr == b'*'),
+        slice_to_string::<E>,
+    )(input)?;
+    Ok((input, ContentCoding::from_token(&token)))
+}
+
+/// Parse a content-coding along with its parameters, e.g. `gzip;q=0.5`.
+pub fn parse_weighted_coding<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], WeightedCoding, E> {
+    let (input, coding) = parse_content_coding(input)?;
+    let (input, mut parameters) = parse_named_field_params(input)?;
+    let mut q = None;
+    extract_opt_param(&mut parameters, "q", &mut q);
+    Ok((
+        input,
+        WeightedCoding {
+            coding,
+            q,
+            parameters,
+        },
+    ))
+}
+
+/// Parses Content-Encoding header ([RFC3261: Page 173, "Content-Encoding"](https://tools.ietf.org/html/rfc3261#page-173))
+pub fn parse_content_encoding_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Content-Encoding")(input)?;
+    let (input, codings) = separated_list1(parse_comma_separator, parse_content_coding)(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::ContentEncoding(codings)))
+}
+
+/// Parses Accept-Encoding header ([RFC3261: Page 171, "Accept-Encoding"](https://tools.ietf.org/html/rfc3261#page-171))
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     headers::{encoding::parse_accept_encoding_header, ContentCoding, WeightedCoding},
+///     Header,
+/// };
+/// use nom::error::VerboseError;
+///
+/// assert_eq!(
+///     parse_accept_encoding_header::<VerboseError<_>>(b"Accept-Encoding: gzip;q=1.0, identity; q=0.5, *;q=0\r\n"),
+///     Ok((
+///         "".as_bytes(),
+///         Header::AcceptEncoding(vec![
+///             WeightedCoding::new(ContentCoding::Gzip).q(1.0),
+///             WeightedCoding::new(ContentCoding::Identity).q(0.5),
+///             WeightedCoding::new(ContentCoding::Any).q(0.0),
+///         ])
+///     ))
+/// );
+/// ```
+pub fn parse_accept_encoding_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Accept-Encoding")(input)?;
+    let (input, codings) = separated_list0(parse_comma_separator, parse_weighted_coding)(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::AcceptEncoding(codings)))
+}
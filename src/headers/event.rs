@@ -0,0 +1,110 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    combinator::map_res,
+    error::ParseError,
+    multi::separated_list1,
+    IResult,
+};
+
+use std::fmt;
+
+use crate::{
+    headers::{
+        named::parse_named_field_params,
+        parse::{parse_comma_separator, parse_header_name},
+        Header,
+    },
+    parse::{is_token, slice_to_string},
+};
+
+/// Event package used in the Event and Allow-Events headers.
+/// ([RFC6665: Section 8.2.1, Event](https://tools.ietf.org/html/rfc6665#section-8.2.1))
+///
+/// Packages (and templates such as `presence.winfo`) that don't
+/// have a variant are kept as `Other`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EventType {
+    Presence,
+    MessageSummary,
+    Dialog,
+    Refer,
+    Reg,
+    Other(String),
+}
+
+impl EventType {
+    /// Get the event type for an event-type token, ignoring case.
+    pub fn from_token(token: &str) -> EventType {
+        match token.to_ascii_lowercase().as_str() {
+            "presence" => EventType::Presence,
+            "message-summary" => EventType::MessageSummary,
+            "dialog" => EventType::Dialog,
+            "refer" => EventType::Refer,
+            "reg" => EventType::Reg,
+            _ => EventType::Other(token.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EventType::Presence => write!(f, "presence"),
+            EventType::MessageSummary => write!(f, "message-summary"),
+            EventType::Dialog => write!(f, "dialog"),
+            EventType::Refer => write!(f, "refer"),
+            EventType::Reg => write!(f, "reg"),
+            EventType::Other(token) => write!(f, "{}", token),
+        }
+    }
+}
+
+/// Parse a single event-type token.
+pub fn parse_event_type<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], EventType, E> {
+    let (input, token) = map_res(take_while1(is_token), slice_to_string::<E>)(input)?;
+    Ok((input, EventType::from_token(&token)))
+}
+
+/// Parses Event header ([RFC6665: Page 42, "Event"](https://tools.ietf.org/html/rfc6665#page-42))
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     headers::event::{parse_event_header, EventType},
+///     Header,
+/// };
+/// use nom::error::VerboseError;
+/// use libsip::headers::GenericParams;
+///
+/// let mut params = GenericParams::new();
+/// params.insert("id", Some("1234"));
+/// assert_eq!(
+///     parse_event_header::<VerboseError<_>>(b"Event: refer;id=1234\r\n"),
+///     Ok(("".as_bytes(), Header::Event(EventType::Refer, params)))
+/// );
+/// ```
+pub fn parse_event_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Event")(input)?;
+    let (input, event) = parse_event_type(input)?;
+    let (input, parameters) = parse_named_field_params(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::Event(event, parameters)))
+}
+
+/// Parses Allow-Events header ([RFC6665: Page 42, "Allow-Events"](https://tools.ietf.org/html/rfc6665#page-42))
+pub fn parse_allow_events_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Allow-Events")(input)?;
+    let (input, events) = separated_list1(parse_comma_separator, parse_event_type)(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::AllowEvents(events)))
+}
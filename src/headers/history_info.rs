@@ -0,0 +1,110 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{bytes::complete::tag, error::ParseError, multi::separated_list1, IResult};
+
+use std::fmt;
+
+use crate::headers::{
+    named::parse_named_header,
+    parse::{parse_comma_separator, parse_header_name},
+    Header, NamedHeader, ReasonHeader,
+};
+
+/// Single hi-entry of the History-Info header.
+/// ([RFC7044: Section 9, Syntax](https://tools.ietf.org/html/rfc7044#section-9))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HistoryInfoEntry {
+    /// The targeted-to uri along with its hi-params, the `index` among
+    /// them, in the order they appear.
+    pub target: NamedHeader,
+}
+
+impl HistoryInfoEntry {
+    /// Create the entry of `target` at `index`, the `index` parameter is
+    /// added after the parameters of `target`.
+    pub fn new<S: Into<String>>(target: NamedHeader, index: S) -> HistoryInfoEntry {
+        HistoryInfoEntry {
+            target: target.param("index", Some(index)),
+        }
+    }
+
+    /// Position of this entry in the history tree, e.g. `1.1.2`.
+    pub fn index(&self) -> &str {
+        self.target.param_value("index").unwrap_or_default()
+    }
+
+    /// Set the `index` parameter, keeping its position if present.
+    pub fn set_index<S: Into<String>>(&mut self, index: S) {
+        self.target.set_param("index", Some(index));
+    }
+
+    /// The levels of the index, e.g. `[1, 1, 2]` for `1.1.2`.
+    /// Returns None if the index is malformed.
+    pub fn index_levels(&self) -> Option<Vec<u32>> {
+        self.index()
+            .split('.')
+            .map(|level| level.parse().ok())
+            .collect()
+    }
+
+    /// The Reason header embedded in the targeted-to uri, explaining why
+    /// the request was retargeted from this entry.
+    pub fn reason(&self) -> Option<ReasonHeader> {
+        self.target
+            .uri
+            .header_value("Reason")
+            .and_then(|value| value.parse().ok())
+    }
+}
+
+impl fmt::Display for HistoryInfoEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.target.fmt_name_addr(f)
+    }
+}
+
+/// Parse a single hi-entry, failing if it has no index.
+pub fn parse_history_info_entry<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], HistoryInfoEntry, E> {
+    let (input, target) = parse_named_header(input)?;
+    match target.param_value("index") {
+        Some(_) => Ok((input, HistoryInfoEntry { target })),
+        None => Err(nom::Err::Error(E::from_error_kind(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+/// Parses History-Info header ([RFC7044: Page 30, "History-Info"](https://tools.ietf.org/html/rfc7044#page-30))
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     headers::history_info::{parse_history_info_header, HistoryInfoEntry},
+///     *,
+/// };
+/// use nom::error::VerboseError;
+///
+/// let uri = Uri::sip(Domain::Domain("example.com".into(), None)).auth(UriAuth::new("bob"));
+/// assert_eq!(
+///     parse_history_info_header::<VerboseError<_>>(
+///         b"History-Info: <sip:bob@example.com>;index=1.1\r\n"
+///     ),
+///     Ok((
+///         "".as_bytes(),
+///         Header::HistoryInfo(vec![HistoryInfoEntry::new(NamedHeader::new(uri), "1.1")])
+///     ))
+/// );
+/// ```
+pub fn parse_history_info_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("History-Info")(input)?;
+    let (input, entries) = separated_list1(parse_comma_separator, parse_history_info_entry)(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::HistoryInfo(entries)))
+}
@@ -0,0 +1,81 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    bytes::complete::take_until, character::complete::char, combinator::map_res, error::ParseError,
+    multi::separated_list1, IResult,
+};
+
+use std::fmt;
+
+use crate::{
+    headers::{
+        named::parse_named_field_params, parse::parse_comma_separator, write::write_generic_params,
+        GenericParams,
+    },
+    parse::slice_to_string,
+};
+
+/// Header value of the Call-Info, Alert-Info and Error-Info headers: a uri
+/// enclosed in angle brackets followed by parameters, e.g.
+/// `<http://www.example.com/alice/photo.jpg>;purpose=icon`.
+///
+/// The uri is usually not a SIP uri (e.g. http), so it is kept as it was received.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InfoHeader {
+    pub uri: String,
+    pub parameters: GenericParams,
+}
+
+impl InfoHeader {
+    pub fn new<S: Into<String>>(uri: S) -> InfoHeader {
+        InfoHeader {
+            uri: uri.into(),
+            parameters: GenericParams::new(),
+        }
+    }
+
+    /// Adds a parameter with a given name and a given value to `parameters`,
+    /// replacing the previous value if the parameter was already present.
+    pub fn param<N, V>(mut self, name: N, value: Option<V>) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.parameters.insert(name, value);
+        self
+    }
+
+    /// The `purpose` parameter of a Call-Info value, e.g. `icon`, `info` or `card`.
+    pub fn purpose(&self) -> Option<&str> {
+        self.parameters
+            .get("purpose")
+            .and_then(|value| value.as_ref().map(String::as_str))
+    }
+}
+
+impl fmt::Display for InfoHeader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}>", self.uri)?;
+        write_generic_params(&self.parameters, f)
+    }
+}
+
+/// Parse a single uri enclosed in angle brackets along with its parameters.
+pub fn parse_info_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], InfoHeader, E> {
+    let (input, _) = char('<')(input)?;
+    let (input, uri) = map_res(take_until(">"), slice_to_string::<E>)(input)?;
+    let (input, _) = char('>')(input)?;
+    let (input, parameters) = parse_named_field_params(input)?;
+    Ok((input, InfoHeader { uri, parameters }))
+}
+
+/// Parse a comma separated list of InfoHeader values.
+pub fn parse_info_header_list<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Vec<InfoHeader>, E> {
+    separated_list1(parse_comma_separator, parse_info_header)(input)
+}
@@ -0,0 +1,226 @@
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use super::{compact_form, Header};
+
+macro_rules! header_kinds {
+    ($($variant:ident => $name:tt,)*) => {
+        /// The kind of a `Header`, one for every header name. Used to find,
+        /// remove or replace headers without matching on their values.
+        ///
+        /// Headers without a dedicated variant are identified by their name,
+        /// which is compared case insensitively.
+        #[derive(Debug, Clone)]
+        #[non_exhaustive]
+        pub enum HeaderKind {
+            $($variant,)*
+            Other(String),
+        }
+
+        impl HeaderKind {
+            /// The name of the header, as it is written.
+            pub fn name(&self) -> &str {
+                match self {
+                    $(HeaderKind::$variant => $name,)*
+                    HeaderKind::Other(name) => name,
+                }
+            }
+
+            /// Get the kind of the header called `name`, ignoring case.
+            /// Compact header names are accepted as well.
+            pub fn from_name(name: &str) -> HeaderKind {
+                $(
+                    if name.eq_ignore_ascii_case($name)
+                        || compact_form($name).is_some_and(|compact| name.eq_ignore_ascii_case(compact))
+                    {
+                        return HeaderKind::$variant;
+                    }
+                )*
+                HeaderKind::Other(name.to_string())
+            }
+        }
+    };
+}
+
+header_kinds! {
+    To => "To",
+    Contact => "Contact",
+    From => "From",
+    ReplyTo => "Reply-To",
+    ReferTo => "Refer-To",
+    ReferredBy => "Referred-By",
+    Replaces => "Replaces",
+    RSeq => "RSeq",
+    RAck => "RAck",
+    SessionExpires => "Session-Expires",
+    MinSE => "Min-SE",
+    CSeq => "CSeq",
+    MaxForwards => "Max-Forwards",
+    Event => "Event",
+    AllowEvents => "Allow-Events",
+    Expires => "Expires",
+    Accept => "Accept",
+    ContentLength => "Content-Length",
+    Allow => "Allow",
+    UserAgent => "User-Agent",
+    CallId => "Call-ID",
+    ContentType => "Content-Type",
+    ContentLanguage => "Content-Language",
+    ContentEncoding => "Content-Encoding",
+    AcceptLanguage => "Accept-Language",
+    AcceptEncoding => "Accept-Encoding",
+    AlertInfo => "Alert-Info",
+    ErrorInfo => "Error-Info",
+    AuthenticationInfo => "Authentication-Info",
+    Authorization => "Authorization",
+    CallInfo => "Call-Info",
+    InReplyTo => "In-Reply-To",
+    ContentDisposition => "Content-Disposition",
+    Date => "Date",
+    MinExpires => "Min-Expires",
+    FlowTimer => "Flow-Timer",
+    MimeVersion => "MIME-Version",
+    Organization => "Organization",
+    ProxyAuthenticate => "Proxy-Authenticate",
+    ProxyAuthorization => "Proxy-Authorization",
+    ProxyRequire => "Proxy-Require",
+    Require => "Require",
+    RetryAfter => "Retry-After",
+    Route => "Route",
+    Subject => "Subject",
+    SubscriptionState => "Subscription-State",
+    RecordRoute => "Record-Route",
+    Path => "Path",
+    ServiceRoute => "Service-Route",
+    PAssertedIdentity => "P-Asserted-Identity",
+    PPreferredIdentity => "P-Preferred-Identity",
+    Privacy => "Privacy",
+    Reason => "Reason",
+    HistoryInfo => "History-Info",
+    Diversion => "Diversion",
+    Server => "Server",
+    SipETag => "SIP-ETag",
+    SipIfMatch => "SIP-If-Match",
+    Supported => "Supported",
+    Timestamp => "Timestamp",
+    Unsupported => "Unsupported",
+    Warning => "Warning",
+    Via => "Via",
+    Priority => "Priority",
+    WwwAuthenticate => "WWW-Authenticate",
+    XFsSendingMessage => "X-FS-Sending-Message",
+}
+
+impl PartialEq for HeaderKind {
+    fn eq(&self, other: &HeaderKind) -> bool {
+        match (self, other) {
+            (HeaderKind::Other(a), HeaderKind::Other(b)) => a.eq_ignore_ascii_case(b),
+            (HeaderKind::Other(_), _) | (_, HeaderKind::Other(_)) => false,
+            _ => self.name() == other.name(),
+        }
+    }
+}
+
+impl Eq for HeaderKind {}
+
+impl Hash for HeaderKind {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name().to_ascii_lowercase().hash(state)
+    }
+}
+
+impl fmt::Display for HeaderKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Header {
+    /// Get the kind of this header. The wildcard Contact is a `Contact`
+    /// and `Other` headers are identified by their name.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::headers::{Header, HeaderKind};
+    /// assert_eq!(Header::Expires(60).kind(), HeaderKind::Expires);
+    /// assert_eq!(Header::ContactWildcard.kind(), HeaderKind::Contact);
+    /// assert_eq!(
+    ///     Header::Other("X-Custom".into(), "1".into()).kind(),
+    ///     HeaderKind::Other("x-custom".into())
+    /// );
+    /// ```
+    pub fn kind(&self) -> HeaderKind {
+        match self {
+            Header::To(..) => HeaderKind::To,
+            Header::Contact(..) => HeaderKind::Contact,
+            Header::ContactWildcard => HeaderKind::Contact,
+            Header::From(..) => HeaderKind::From,
+            Header::ReplyTo(..) => HeaderKind::ReplyTo,
+            Header::ReferTo(..) => HeaderKind::ReferTo,
+            Header::ReferredBy(..) => HeaderKind::ReferredBy,
+            Header::Replaces(..) => HeaderKind::Replaces,
+            Header::RSeq(..) => HeaderKind::RSeq,
+            Header::RAck(..) => HeaderKind::RAck,
+            Header::SessionExpires(..) => HeaderKind::SessionExpires,
+            Header::MinSE(..) => HeaderKind::MinSE,
+            Header::CSeq(..) => HeaderKind::CSeq,
+            Header::MaxForwards(..) => HeaderKind::MaxForwards,
+            Header::Event(..) => HeaderKind::Event,
+            Header::AllowEvents(..) => HeaderKind::AllowEvents,
+            Header::Expires(..) => HeaderKind::Expires,
+            Header::Accept(..) => HeaderKind::Accept,
+            Header::ContentLength(..) => HeaderKind::ContentLength,
+            Header::Allow(..) => HeaderKind::Allow,
+            Header::UserAgent(..) => HeaderKind::UserAgent,
+            Header::CallId(..) => HeaderKind::CallId,
+            Header::ContentType(..) => HeaderKind::ContentType,
+            Header::ContentLanguage(..) => HeaderKind::ContentLanguage,
+            Header::ContentEncoding(..) => HeaderKind::ContentEncoding,
+            Header::AcceptLanguage(..) => HeaderKind::AcceptLanguage,
+            Header::AcceptEncoding(..) => HeaderKind::AcceptEncoding,
+            Header::AlertInfo(..) => HeaderKind::AlertInfo,
+            Header::ErrorInfo(..) => HeaderKind::ErrorInfo,
+            Header::AuthenticationInfo(..) => HeaderKind::AuthenticationInfo,
+            Header::Authorization(..) => HeaderKind::Authorization,
+            Header::CallInfo(..) => HeaderKind::CallInfo,
+            Header::InReplyTo(..) => HeaderKind::InReplyTo,
+            Header::ContentDisposition(..) => HeaderKind::ContentDisposition,
+            Header::Date(..) => HeaderKind::Date,
+            Header::MinExpires(..) => HeaderKind::MinExpires,
+            Header::FlowTimer(..) => HeaderKind::FlowTimer,
+            Header::MimeVersion(..) => HeaderKind::MimeVersion,
+            Header::Organization(..) => HeaderKind::Organization,
+            Header::ProxyAuthenticate(..) => HeaderKind::ProxyAuthenticate,
+            Header::ProxyAuthorization(..) => HeaderKind::ProxyAuthorization,
+            Header::ProxyRequire(..) => HeaderKind::ProxyRequire,
+            Header::Require(..) => HeaderKind::Require,
+            Header::RetryAfter(..) => HeaderKind::RetryAfter,
+            Header::Route(..) => HeaderKind::Route,
+            Header::Subject(..) => HeaderKind::Subject,
+            Header::SubscriptionState(..) => HeaderKind::SubscriptionState,
+            Header::RecordRoute(..) => HeaderKind::RecordRoute,
+            Header::Path(..) => HeaderKind::Path,
+            Header::ServiceRoute(..) => HeaderKind::ServiceRoute,
+            Header::PAssertedIdentity(..) => HeaderKind::PAssertedIdentity,
+            Header::PPreferredIdentity(..) => HeaderKind::PPreferredIdentity,
+            Header::Privacy(..) => HeaderKind::Privacy,
+            Header::Reason(..) => HeaderKind::Reason,
+            Header::HistoryInfo(..) => HeaderKind::HistoryInfo,
+            Header::Diversion(..) => HeaderKind::Diversion,
+            Header::Server(..) => HeaderKind::Server,
+            Header::SipETag(..) => HeaderKind::SipETag,
+            Header::SipIfMatch(..) => HeaderKind::SipIfMatch,
+            Header::Supported(..) => HeaderKind::Supported,
+            Header::Timestamp(..) => HeaderKind::Timestamp,
+            Header::Unsupported(..) => HeaderKind::Unsupported,
+            Header::Warning(..) => HeaderKind::Warning,
+            Header::Via(..) => HeaderKind::Via,
+            Header::Priority(..) => HeaderKind::Priority,
+            Header::WwwAuthenticate(..) => HeaderKind::WwwAuthenticate,
+            Header::XFsSendingMessage(..) => HeaderKind::XFsSendingMessage,
+            Header::Other(name, _) => HeaderKind::from_name(name),
+        }
+    }
+}
@@ -1,7 +1,35 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while_m_n},
+    character::complete::char,
+    combinator::{map, map_res},
+    error::ParseError,
+    multi::{many0, separated_list0, separated_list1},
+    sequence::preceded,
+    IResult,
+};
+
 use std::fmt;
 
+use crate::{
+    core::extract_opt_param,
+    headers::{
+        named::parse_named_field_params,
+        parse::{parse_comma_separator, parse_header_name},
+        write::{write_generic_params, write_optional_param},
+        GenericParams, Header,
+    },
+    parse::slice_to_string,
+};
+
 /// Sip Protocol languages.
-#[derive(Debug, PartialEq, Clone, Copy)]
+/// Primary language subtags of the ISO 639-1 languages, any other primary
+/// subtag is kept as `Other`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Language {
     Abkhazian,
     Afar,
@@ -187,6 +215,7 @@ pub enum Language {
     Yoruba,
     Zhuang,
     Zulu,
+    Other(String),
 }
 
 impl fmt::Display for Language {
@@ -318,7 +347,7 @@ impl fmt::Display for Language {
             Language::Punjabi => write!(f, "pa"),
             Language::Pali => write!(f, "pi"),
             Language::Persian => write!(f, "fa"),
-            Language::Polish => write!(f, "pi"),
+            Language::Polish => write!(f, "pl"),
             Language::Pashto => write!(f, "ps"),
             Language::Portuguese => write!(f, "pt"),
             Language::Quechua => write!(f, "qu"),
@@ -376,191 +405,367 @@ impl fmt::Display for Language {
             Language::Yoruba => write!(f, "yo"),
             Language::Zhuang => write!(f, "za"),
             Language::Zulu => write!(f, "zu"),
+            Language::Other(code) => write!(f, "{}", code),
+        }
+    }
+}
+
+impl Language {
+    /// Get the language for a primary language subtag, e.g. `en`.
+    pub fn from_code(code: &str) -> Language {
+        match code.to_ascii_lowercase().as_str() {
+            "ab" => Language::Abkhazian,
+            "aa" => Language::Afar,
+            "af" => Language::Afrikaans,
+            "ak" => Language::Akan,
+            "sq" => Language::Albanian,
+            "am" => Language::Amharic,
+            "ar" => Language::Arabic,
+            "an" => Language::Aragonese,
+            "hy" => Language::Armenian,
+            "as" => Language::Assamese,
+            "av" => Language::Avaric,
+            "ae" => Language::Avestan,
+            "ay" => Language::Aymara,
+            "az" => Language::Azerbaijani,
+            "bm" => Language::Bambara,
+            "ba" => Language::Bashkir,
+            "eu" => Language::Basque,
+            "be" => Language::Belarusian,
+            "bn" => Language::Bengali,
+            "bh" => Language::Bihari,
+            "bi" => Language::Bislama,
+            "bs" => Language::Bosnian,
+            "br" => Language::Breton,
+            "bg" => Language::Bulgarian,
+            "my" => Language::Burmese,
+            "ca" => Language::Catalan,
+            "ch" => Language::Chamorro,
+            "ce" => Language::Chechen,
+            "ny" => Language::Chichewa,
+            "zh" => Language::Chinese,
+            "cv" => Language::Chuvash,
+            "kw" => Language::Cornish,
+            "co" => Language::Corsican,
+            "cr" => Language::Cree,
+            "hr" => Language::Croatian,
+            "cs" => Language::Czech,
+            "da" => Language::Danish,
+            "dv" => Language::Divehi,
+            "nl" => Language::Dutch,
+            "dz" => Language::Dzongkha,
+            "en" => Language::English,
+            "eo" => Language::Esperanto,
+            "et" => Language::Estonian,
+            "ee" => Language::Ewe,
+            "fo" => Language::Faroese,
+            "fj" => Language::Fijian,
+            "fi" => Language::Finnish,
+            "fr" => Language::French,
+            "ff" => Language::Fulah,
+            "gl" => Language::Galician,
+            "ka" => Language::Georgian,
+            "de" => Language::German,
+            "el" => Language::Greek,
+            "gn" => Language::Guarani,
+            "gu" => Language::Gujarati,
+            "ht" => Language::Haitian,
+            "ha" => Language::Hausa,
+            "he" => Language::Hebrew,
+            "hz" => Language::Herero,
+            "hi" => Language::Hindi,
+            "ho" => Language::HiriMotu,
+            "hu" => Language::Hungarian,
+            "ia" => Language::Interlingua,
+            "id" => Language::Indonesian,
+            "ie" => Language::Interlingue,
+            "ga" => Language::Irish,
+            "ig" => Language::Igbo,
+            "ik" => Language::Inupiaq,
+            "io" => Language::Ido,
+            "is" => Language::Icelandic,
+            "it" => Language::Italian,
+            "iu" => Language::Inuktitut,
+            "ja" => Language::Japanese,
+            "jv" => Language::Javanese,
+            "kl" => Language::Kalaallisut,
+            "kn" => Language::Kannada,
+            "kr" => Language::Kanuri,
+            "ks" => Language::Kashmiri,
+            "kk" => Language::Kazakh,
+            "km" => Language::Khmer,
+            "ki" => Language::Kikuyu,
+            "rw" => Language::Kinyarwanda,
+            "ky" => Language::Kirghiz,
+            "kv" => Language::Komi,
+            "kg" => Language::Kongo,
+            "ko" => Language::Korean,
+            "ku" => Language::Kurdish,
+            "kj" => Language::Kuanyama,
+            "la" => Language::Latin,
+            "lb" => Language::Luxembourgish,
+            "lg" => Language::Ganda,
+            "li" => Language::Limburgan,
+            "ln" => Language::Lingala,
+            "lo" => Language::Lao,
+            "lt" => Language::Lithuanian,
+            "lu" => Language::LubaKatanga,
+            "lv" => Language::Latvian,
+            "gv" => Language::Manx,
+            "mk" => Language::Macedonian,
+            "mg" => Language::Malagasy,
+            "ms" => Language::Malay,
+            "ml" => Language::Malayalam,
+            "mt" => Language::Maltese,
+            "mi" => Language::Maori,
+            "mr" => Language::Marathi,
+            "mh" => Language::Marshallese,
+            "mn" => Language::Mongolian,
+            "ne" => Language::Nepali,
+            "na" => Language::Nauru,
+            "nv" => Language::Navajo,
+            "nd" => Language::NorthNdebele,
+            "ng" => Language::Ndonga,
+            "nb" => Language::NorwegianBokmal,
+            "nn" => Language::NorwegianNynorsk,
+            "no" => Language::Norwegian,
+            "ii" => Language::SichuanYi,
+            "nr" => Language::SouthNdebele,
+            "oc" => Language::Occitan,
+            "oj" => Language::Ojibwa,
+            "cu" => Language::ChurchSlavic,
+            "om" => Language::Oromo,
+            "or" => Language::Oriya,
+            "os" => Language::Ossetian,
+            "pa" => Language::Punjabi,
+            "pi" => Language::Pali,
+            "fa" => Language::Persian,
+            "pl" => Language::Polish,
+            "ps" => Language::Pashto,
+            "pt" => Language::Portuguese,
+            "qu" => Language::Quechua,
+            "rm" => Language::Romansh,
+            "rn" => Language::Rundi,
+            "ro" => Language::Romanian,
+            "ru" => Language::Russian,
+            "sa" => Language::Sanskrit,
+            "sc" => Language::Sardinian,
+            "sd" => Language::Sindhi,
+            "se" => Language::NorthernSami,
+            "sm" => Language::Samoan,
+            "sg" => Language::Sango,
+            "sr" => Language::Serbian,
+            "gd" => Language::Gaelic,
+            "sn" => Language::Shona,
+            "si" => Language::Sinhala,
+            "sk" => Language::Slovak,
+            "sl" => Language::Slovenian,
+            "so" => Language::Somali,
+            "st" => Language::SouthernSotho,
+            "es" => Language::Spanish,
+            "su" => Language::Sundanese,
+            "sw" => Language::Swahili,
+            "ss" => Language::Swati,
+            "sv" => Language::Swedish,
+            "ta" => Language::Tamil,
+            "te" => Language::Telugu,
+            "tg" => Language::Tajik,
+            "th" => Language::Thai,
+            "ti" => Language::Tigrinya,
+            "bo" => Language::Tibetan,
+            "tk" => Language::Turkmen,
+            "tl" => Language::Tagalog,
+            "tn" => Language::Tswana,
+            "to" => Language::Tonga,
+            "tr" => Language::Turkish,
+            "ts" => Language::Tsonga,
+            "tt" => Language::Tatar,
+            "tw" => Language::Twi,
+            "ty" => Language::Tahitian,
+            "ug" => Language::Uighur,
+            "uk" => Language::Ukrainian,
+            "ur" => Language::Urdu,
+            "uz" => Language::Uzbek,
+            "ve" => Language::Venda,
+            "vi" => Language::Vietnamese,
+            "vo" => Language::Volapuk,
+            "wa" => Language::Walloon,
+            "cy" => Language::Welsh,
+            "wo" => Language::Wolof,
+            "fy" => Language::WesternFrisian,
+            "xh" => Language::Xhosa,
+            "yi" => Language::Yiddish,
+            "yo" => Language::Yoruba,
+            "za" => Language::Zhuang,
+            "zu" => Language::Zulu,
+            _ => Language::Other(code.to_string()),
+        }
+    }
+}
+
+/// Language tag as used in the Content-Language header, a primary language
+/// followed by any number of subtags, e.g. `en-US`.
+/// ([RFC5646: Section 2.1, Syntax](https://tools.ietf.org/html/rfc5646#section-2.1))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LanguageTag {
+    pub language: Language,
+    pub subtags: Vec<String>,
+}
+
+impl LanguageTag {
+    pub fn new(language: Language) -> LanguageTag {
+        LanguageTag {
+            language,
+            subtags: vec![],
+        }
+    }
+
+    /// Add a subtag (e.g. a region or script) to this tag.
+    pub fn subtag<S: Into<String>>(mut self, subtag: S) -> Self {
+        self.subtags.push(subtag.into());
+        self
+    }
+}
+
+impl From<Language> for LanguageTag {
+    fn from(language: Language) -> LanguageTag {
+        LanguageTag::new(language)
+    }
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.language)?;
+        for subtag in &self.subtags {
+            write!(f, "-{}", subtag)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single entry of the Accept-Language header, a language range
+/// with its q-value and any other parameters.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LanguageRange {
+    /// The accepted language, `None` represents the `*` range.
+    pub tag: Option<LanguageTag>,
+    pub q: Option<f32>,
+    pub parameters: GenericParams,
+}
+
+impl LanguageRange {
+    pub fn new<T: Into<LanguageTag>>(tag: T) -> LanguageRange {
+        LanguageRange {
+            tag: Some(tag.into()),
+            q: None,
+            parameters: GenericParams::new(),
+        }
+    }
+
+    /// The `*` range matching any language.
+    pub fn any() -> LanguageRange {
+        LanguageRange {
+            tag: None,
+            q: None,
+            parameters: GenericParams::new(),
+        }
+    }
+
+    /// Set the q-value of this range.
+    pub fn q(mut self, q: f32) -> Self {
+        self.q = Some(q);
+        self
+    }
+}
+
+impl fmt::Display for LanguageRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.tag {
+            Some(tag) => write!(f, "{}", tag)?,
+            None => write!(f, "*")?,
         }
+        write_optional_param("q", &self.q, f)?;
+        write_generic_params(&self.parameters, f)
     }
 }
 
-named!(pub parse_language<&[u8], Language>, alt!(
-    map!(tag_no_case!("ab"), |_| Language::Abkhazian) |
-    map!(tag_no_case!("aa"), |_| Language::Afar) |
-    map!(tag_no_case!("af"), |_| Language::Afrikaans) |
-    map!(tag_no_case!("ak"), |_| Language::Akan) |
-    map!(tag_no_case!("sq"), |_| Language::Albanian) |
-    map!(tag_no_case!("ar"), |_| Language::Arabic) |
-    map!(tag_no_case!("an"), |_| Language::Aragonese) |
-    map!(tag_no_case!("hy"), |_| Language::Armenian) |
-    map!(tag_no_case!("as"), |_| Language::Assamese) |
-    map!(tag_no_case!("av"), |_| Language::Avaric) |
-    map!(tag_no_case!("ae"), |_| Language::Avestan) |
-    map!(tag_no_case!("az"), |_| Language::Azerbaijani) |
-    map!(tag_no_case!("bm"), |_| Language::Bambara) |
-    map!(tag_no_case!("ba"), |_| Language::Bashkir) |
-    map!(tag_no_case!("eu"), |_| Language::Basque) |
-    map!(tag_no_case!("be"), |_| Language::Belarusian) |
-    map!(tag_no_case!("bn"), |_| Language::Bengali) |
-    map!(tag_no_case!("bh"), |_| Language::Bihari) |
-    map!(tag_no_case!("bi"), |_| Language::Bislama) |
-    map!(tag_no_case!("bs"), |_| Language::Bosnian) |
-    map!(tag_no_case!("br"), |_| Language::Breton) |
-    map!(tag_no_case!("bg"), |_| Language::Bulgarian) |
-    map!(tag_no_case!("my"), |_| Language::Burmese) |
-    map!(tag_no_case!("ca"), |_| Language::Catalan) |
-    map!(tag_no_case!("ch"), |_| Language::Chamorro) |
-    map!(tag_no_case!("ce"), |_| Language::Chechen) |
-    map!(tag_no_case!("ny"), |_| Language::Chichewa) |
-    map!(tag_no_case!("zh"), |_| Language::Chinese) |
-    map!(tag_no_case!("cv"), |_| Language::Chuvash) |
-    map!(tag_no_case!("kw"), |_| Language::Cornish) |
-    map!(tag_no_case!("co"), |_| Language::Corsican) |
-    map!(tag_no_case!("cr"), |_| Language::Cree) |
-    map!(tag_no_case!("hr"), |_| Language::Croatian) |
-    map!(tag_no_case!("cs"), |_| Language::Czech) |
-    map!(tag_no_case!("da"), |_| Language::Danish) |
-    map!(tag_no_case!("dv"), |_| Language::Divehi) |
-    map!(tag_no_case!("nl"), |_| Language::Dutch) |
-    map!(tag_no_case!("dz"), |_| Language::Dzongkha) |
-    map!(tag_no_case!("en"), |_| Language::English) |
-    map!(tag_no_case!("eo"), |_| Language::Esperanto) |
-    map!(tag_no_case!("et"), |_| Language::Estonian) |
-    map!(tag_no_case!("ee"), |_| Language::Ewe) |
-    map!(tag_no_case!("fo"), |_| Language::Faroese) |
-    map!(tag_no_case!("fj"), |_| Language::Fijian) |
-    map!(tag_no_case!("fi"), |_| Language::Finnish) |
-    map!(tag_no_case!("fr"), |_| Language::French) |
-    map!(tag_no_case!("ff"), |_| Language::Fulah) |
-    map!(tag_no_case!("gl"), |_| Language::Galician) |
-    map!(tag_no_case!("ka"), |_| Language::Georgian) |
-    map!(tag_no_case!("de"), |_| Language::German) |
-    map!(tag_no_case!("el"), |_| Language::Greek) |
-    map!(tag_no_case!("gn"), |_| Language::Guarani) |
-    map!(tag_no_case!("gu"), |_| Language::Gujarati) |
-    map!(tag_no_case!("ht"), |_| Language::Haitian) |
-    map!(tag_no_case!("ha"), |_| Language::Hausa) |
-    map!(tag_no_case!("he"), |_| Language::Hebrew) |
-    map!(tag_no_case!("hz"), |_| Language::Herero) |
-    map!(tag_no_case!("hi"), |_| Language::Hindi) |
-    map!(tag_no_case!("ho"), |_| Language::HiriMotu) |
-    map!(tag_no_case!("hu"), |_| Language::Hungarian) |
-    map!(tag_no_case!("ia"), |_| Language::Interlingua) |
-    map!(tag_no_case!("id"), |_| Language::	Indonesian) |
-    map!(tag_no_case!("ie"), |_| Language::Interlingue) |
-    map!(tag_no_case!("ga"), |_| Language::Irish) |
-    map!(tag_no_case!("ig"), |_| Language::Igbo) |
-    map!(tag_no_case!("ik"), |_| Language::Inupiaq) |
-    map!(tag_no_case!("io"), |_| Language::Ido) |
-    map!(tag_no_case!("is"), |_| Language::Icelandic) |
-    map!(tag_no_case!("it"), |_| Language::Italian) |
-    map!(tag_no_case!("iu"), |_| Language::Inuktitut) |
-    map!(tag_no_case!("ja"), |_| Language::Japanese) |
-    map!(tag_no_case!("jv"), |_| Language::Javanese) |
-    map!(tag_no_case!("kl"), |_| Language::Kalaallisut) |
-    map!(tag_no_case!("kn"), |_| Language::Kannada) |
-    map!(tag_no_case!("kr"), |_| Language::Kanuri) |
-    map!(tag_no_case!("ks"), |_| Language::Kashmiri) |
-    map!(tag_no_case!("kk"), |_| Language::Kazakh) |
-    map!(tag_no_case!("km"), |_| Language::Khmer) |
-    map!(tag_no_case!("ki"), |_| Language::Kikuyu) |
-    map!(tag_no_case!("rw"), |_| Language::Kinyarwanda) |
-    map!(tag_no_case!("ky"), |_| Language::Kirghiz) |
-    map!(tag_no_case!("kv"), |_| Language::Komi) |
-    map!(tag_no_case!("kg"), |_| Language::Kongo) |
-    map!(tag_no_case!("ko"), |_| Language::Korean) |
-    map!(tag_no_case!("ku"), |_| Language::Kurdish) |
-    map!(tag_no_case!("kj"), |_| Language::Kuanyama) |
-    map!(tag_no_case!("la"), |_| Language::Latin) |
-    map!(tag_no_case!("lb"), |_| Language::Luxembourgish) |
-    map!(tag_no_case!("lg"), |_| Language::Ganda) |
-    map!(tag_no_case!("li"), |_| Language::Limburgan) |
-    map!(tag_no_case!("ln"), |_| Language::Lingala) |
-    map!(tag_no_case!("lo"), |_| Language::Lao) |
-    map!(tag_no_case!("lt"), |_| Language::Lithuanian) |
-    map!(tag_no_case!("lu"), |_| Language::LubaKatanga) |
-    map!(tag_no_case!("lv"), |_| Language::Latvian) |
-    map!(tag_no_case!("gv"), |_| Language::Manx) |
-    map!(tag_no_case!("mk"), |_| Language::Macedonian) |
-    map!(tag_no_case!("mg"), |_| Language::Malagasy) |
-    map!(tag_no_case!("ms"), |_| Language::Malay) |
-    map!(tag_no_case!("ml"), |_| Language::Malayalam) |
-    map!(tag_no_case!("mt"), |_| Language::Maltese) |
-    map!(tag_no_case!("mi"), |_| Language::Maori) |
-    map!(tag_no_case!("mr"), |_| Language::Marathi) |
-    map!(tag_no_case!("mh"), |_| Language::Marshallese) |
-    map!(tag_no_case!("mn"), |_| Language::Mongolian) |
-    map!(tag_no_case!("na"), |_| Language::Nauru) |
-    map!(tag_no_case!("nv"), |_| Language::Navajo) |
-    map!(tag_no_case!("nd"), |_| Language::NorthNdebele) |
-    map!(tag_no_case!("ne"), |_| Language::Nepali) |
-    map!(tag_no_case!("ng"), |_| Language::Ndonga) |
-    map!(tag_no_case!("nb"), |_| Language::NorwegianBokmal) |
-    map!(tag_no_case!("nn"), |_| Language::NorwegianNynorsk) |
-    map!(tag_no_case!("no"), |_| Language::Norwegian) |
-    map!(tag_no_case!("ii"), |_| Language::SichuanYi) |
-    map!(tag_no_case!("nr"), |_| Language::SouthNdebele) |
-    map!(tag_no_case!("oc"), |_| Language::Occitan) |
-    map!(tag_no_case!("oj"), |_| Language::Ojibwa) |
-    map!(tag_no_case!("cu"), |_| Language::ChurchSlavic) |
-    map!(tag_no_case!("om"), |_| Language::Oromo) |
-    map!(tag_no_case!("or"), |_| Language::Oriya) |
-    map!(tag_no_case!("os"), |_| Language::Ossetian) |
-    map!(tag_no_case!("pa"), |_| Language::Punjabi) |
-    map!(tag_no_case!("pi"), |_| Language::Pali) |
-    map!(tag_no_case!("fa"), |_| Language::Persian) |
-    map!(tag_no_case!("pi"), |_| Language::Polish) |
-    map!(tag_no_case!("ps"), |_| Language::Pashto) |
-    map!(tag_no_case!("pt"), |_| Language::Portuguese) |
-    map!(tag_no_case!("qu"), |_| Language::Quechua) |
-    map!(tag_no_case!("rm"), |_| Language::Romansh) |
-    map!(tag_no_case!("rn"), |_| Language::Rundi) |
-    map!(tag_no_case!("ro"), |_| Language::Romanian) |
-    map!(tag_no_case!("ru"), |_| Language::Russian) |
-    map!(tag_no_case!("sa"), |_| Language::Sanskrit) |
-    map!(tag_no_case!("sc"), |_| Language::Sardinian) |
-    map!(tag_no_case!("sd"), |_| Language::Sindhi) |
-    map!(tag_no_case!("se"), |_| Language::NorthernSami) |
-    map!(tag_no_case!("sm"), |_| Language::Samoan) |
-    map!(tag_no_case!("sg"), |_| Language::Sango) |
-    map!(tag_no_case!("sr"), |_| Language::Serbian) |
-    map!(tag_no_case!("gd"), |_| Language::Gaelic) |
-    map!(tag_no_case!("sn"), |_| Language::Shona) |
-    map!(tag_no_case!("si"), |_| Language::Sinhala) |
-    map!(tag_no_case!("sk"), |_| Language::Slovak) |
-    map!(tag_no_case!("sl"), |_| Language::Slovenian) |
-    map!(tag_no_case!("so"), |_| Language::Somali) |
-    map!(tag_no_case!("st"), |_| Language::SouthernSotho) |
-    map!(tag_no_case!("es"), |_| Language::Spanish) |
-    map!(tag_no_case!("su"), |_| Language::Sundanese) |
-    map!(tag_no_case!("sw"), |_| Language::Swahili) |
-    map!(tag_no_case!("ss"), |_| Language::Swati) |
-    map!(tag_no_case!("sv"), |_| Language::Swedish) |
-    map!(tag_no_case!("ta"), |_| Language::Tamil) |
-    map!(tag_no_case!("te"), |_| Language::Telugu) |
-    map!(tag_no_case!("tg"), |_| Language::Tajik) |
-    map!(tag_no_case!("th"), |_| Language::Thai) |
-    map!(tag_no_case!("ti"), |_| Language::Tigrinya) |
-    map!(tag_no_case!("bo"), |_| Language::Tibetan) |
-    map!(tag_no_case!("tk"), |_| Language::Turkmen) |
-    map!(tag_no_case!("tl"), |_| Language::Tagalog) |
-    map!(tag_no_case!("tn"), |_| Language::Tswana) |
-    map!(tag_no_case!("to"), |_| Language::Tonga) |
-    map!(tag_no_case!("tr"), |_| Language::Turkish) |
-    map!(tag_no_case!("ts"), |_| Language::Tsonga) |
-    map!(tag_no_case!("tt"), |_| Language::Tatar) |
-    map!(tag_no_case!("tw"), |_| Language::Twi) |
-    map!(tag_no_case!("ty"), |_| Language::Tahitian) |
-    map!(tag_no_case!("ug"), |_| Language::Uighur) |
-    map!(tag_no_case!("uk"), |_| Language::Ukrainian) |
-    map!(tag_no_case!("ur"), |_| Language::Urdu) |
-    map!(tag_no_case!("uz"), |_| Language::Uzbek) |
-    map!(tag_no_case!("ve"), |_| Language::Venda) |
-    map!(tag_no_case!("vi"), |_| Language::Vietnamese) |
-    map!(tag_no_case!("vo"), |_| Language::Volapuk) |
-    map!(tag_no_case!("wa"), |_| Language::Walloon) |
-    map!(tag_no_case!("cy"), |_| Language::Welsh) |
-    map!(tag_no_case!("wo"), |_| Language::Wolof) |
-    map!(tag_no_case!("fy"), |_| Language::WesternFrisian) |
-    map!(tag_no_case!("xh"), |_| Language::Xhosa) |
-    map!(tag_no_case!("yi"), |_| Language::Yiddish) |
-    map!(tag_no_case!("yo"), |_| Language::Yoruba) |
-    map!(tag_no_case!("za"), |_| Language::Zhuang) |
-    map!(tag_no_case!("zu"), |_| Language::Zulu)
-));
+fn is_subtag_char(chr: u8) -> bool {
+    chr.is_ascii_alphanumeric()
+}
+
+/// Parse a language tag, e.g. `en` or `zh-Hant-TW`.
+pub fn parse_language_tag<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], LanguageTag, E> {
+    let (input, language) = map_res(
+        take_while_m_n(1, 8, |chr: u8| chr.is_ascii_alphabetic()),
+        slice_to_string::<E>,
+    )(input)?;
+    let (input, subtags) = many0(preceded(
+        char('-'),
+        map_res(take_while_m_n(1, 8, is_subtag_char), slice_to_string::<E>),
+    ))(input)?;
+    Ok((
+        input,
+        LanguageTag {
+            language: Language::from_code(&language),
+            subtags,
+        },
+    ))
+}
+
+/// Parse a language range along with its parameters, e.g. `da;q=0.8` or `*`.
+pub fn parse_language_range<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], LanguageRange, E> {
+    let (input, tag) = alt((map(char('*'), |_| None), map(parse_language_tag, Some)))(input)?;
+    let (input, mut parameters) = parse_named_field_params(input)?;
+    let mut q = None;
+    extract_opt_param(&mut parameters, "q", &mut q);
+    Ok((input, LanguageRange { tag, q, parameters }))
+}
+
+/// Parses Content-Language header ([RFC3261: Page 173, "Content-Language"](https://tools.ietf.org/html/rfc3261#page-173))
+pub fn parse_content_language_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Content-Language")(input)?;
+    let (input, tags) = separated_list1(parse_comma_separator, parse_language_tag)(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::ContentLanguage(tags)))
+}
+
+/// Parses Accept-Language header ([RFC3261: Page 171, "Accept-Language"](https://tools.ietf.org/html/rfc3261#page-171))
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     headers::{language::parse_accept_language_header, Language, LanguageRange, LanguageTag},
+///     Header,
+/// };
+/// use nom::error::VerboseError;
+///
+/// assert_eq!(
+///     parse_accept_language_header::<VerboseError<_>>(
+///         b"Accept-Language: da, en-GB;q=0.8, en;q=0.7\r\n"
+///     ),
+///     Ok((
+///         "".as_bytes(),
+///         Header::AcceptLanguage(vec![
+///             LanguageRange::new(Language::Danish),
+///             LanguageRange::new(LanguageTag::new(Language::English).subtag("GB")).q(0.8),
+///             LanguageRange::new(Language::English).q(0.7),
+///         ])
+///     ))
+/// );
+/// ```
+pub fn parse_accept_language_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Accept-Language")(input)?;
+    let (input, ranges) = separated_list0(parse_comma_separator, parse_language_range)(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::AcceptLanguage(ranges)))
+}
@@ -1,25 +1,63 @@
 pub mod auth;
 mod content;
-mod language;
+pub mod date;
+pub mod disposition;
+pub mod diversion;
+pub mod encoding;
+pub mod event;
+pub mod history_info;
+pub mod info;
+pub mod kind;
+pub mod language;
 mod named;
+pub mod params;
 pub mod parse;
+pub mod privacy;
+pub mod reason;
+pub mod replaces;
+pub mod retry_after;
+pub mod session_expires;
 pub mod subscription_state;
 pub mod via;
+pub mod warning;
 mod write;
 pub use self::{
-    auth::{AuthContext, AuthHeader, AuthSchema},
+    auth::{
+        AuthContext, AuthHeader, AuthSchema, AuthenticationInfo, ChallengeOutcome, DigestAlgorithm,
+        DigestCredentials,
+    },
     content::ContentType,
-    language::Language,
-    named::NamedHeader,
+    date::{DateHeader, SipDate},
+    disposition::{ContentDisposition, DispositionType, Handling},
+    diversion::DiversionHeader,
+    encoding::{ContentCoding, WeightedCoding},
+    event::EventType,
+    history_info::HistoryInfoEntry,
+    info::InfoHeader,
+    kind::HeaderKind,
+    language::{Language, LanguageRange, LanguageTag},
+    named::{generate_instance_id, generate_tag, instance_id_from_seed, NamedHeader},
+    params::GenericParams,
     parse::parse_header,
+    privacy::PrivacyToken,
+    reason::{ReasonHeader, ReasonProtocol},
+    replaces::ReplacesHeader,
+    retry_after::RetryAfter,
+    session_expires::{Refresher, SessionExpires},
     subscription_state::SubscriptionState,
+    via::{generate_branch, response_destination},
+    warning::WarningValue,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::core::Method;
 
 /// Wrapper around a Vec<Header> to simplify creating
 /// and a list of headers
 #[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Headers(pub Vec<Header>);
 
 impl Headers {
@@ -37,11 +75,53 @@ impl Headers {
         self.0.iter()
     }
 
+    /// Write every header followed by CRLF to `w`, using the compact
+    /// form of the header names that have one when `compact` is set.
+    pub fn write_to(&self, w: &mut impl std::io::Write, compact: bool) -> std::io::Result<()> {
+        for header in self.iter() {
+            if compact {
+                write!(w, "{:#}\r\n", header)?;
+            } else {
+                write!(w, "{}\r\n", header)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Add the Headers onto the interior Vec<Header>.
     pub fn extend(&mut self, i: Vec<Header>) {
         self.0.extend(i)
     }
 
+    /// Return the first header of the given kind.
+    pub fn get(&self, kind: HeaderKind) -> Option<&Header> {
+        self.0.iter().find(|h| h.kind() == kind)
+    }
+
+    /// Return every header of the given kind, in order.
+    pub fn get_all(&self, kind: HeaderKind) -> Vec<&Header> {
+        self.0.iter().filter(|h| h.kind() == kind).collect()
+    }
+
+    /// Remove every header of the given kind, returning the removed headers.
+    pub fn remove(&mut self, kind: HeaderKind) -> Vec<Header> {
+        let (removed, kept) = self.0.drain(..).partition(|h| h.kind() == kind);
+        self.0 = kept;
+        removed
+    }
+
+    /// Replace the headers of the given kind with `header`. The new header
+    /// takes the place of the first one removed, or is added at the end if
+    /// there was none.
+    pub fn replace(&mut self, kind: HeaderKind, header: Header) {
+        let index = self.0.iter().position(|h| h.kind() == kind);
+        self.remove(kind);
+        match index {
+            Some(index) => self.0.insert(index, header),
+            None => self.0.push(header),
+        }
+    }
+
     /// Return An Expires header if one is present.
     pub fn expires(&self) -> Option<Header> {
         for h in &self.0 {
@@ -55,8 +135,58 @@ impl Headers {
     /// Return the Event header if one is present.
     pub fn event(&self) -> Option<Header> {
         for h in &self.0 {
-            if let Header::Event(a) = h {
-                return Some(Header::Event(a.clone()));
+            if let Header::Event(a, b) = h {
+                return Some(Header::Event(a.clone(), b.clone()));
+            }
+        }
+        None
+    }
+
+    /// Return the Session-Expires header if one is present.
+    pub fn session_expires(&self) -> Option<Header> {
+        for h in &self.0 {
+            if let Header::SessionExpires(a) = h {
+                return Some(Header::SessionExpires(a.clone()));
+            }
+        }
+        None
+    }
+
+    /// Return the Min-Expires header if one is present.
+    pub fn min_expires(&self) -> Option<Header> {
+        for h in &self.0 {
+            if let Header::MinExpires(a) = h {
+                return Some(Header::MinExpires(*a));
+            }
+        }
+        None
+    }
+
+    /// Return the Flow-Timer header if one is present.
+    pub fn flow_timer(&self) -> Option<Header> {
+        for h in &self.0 {
+            if let Header::FlowTimer(a) = h {
+                return Some(Header::FlowTimer(*a));
+            }
+        }
+        None
+    }
+
+    /// Return the Min-SE header if one is present.
+    pub fn min_se(&self) -> Option<Header> {
+        for h in &self.0 {
+            if let Header::MinSE(a) = h {
+                return Some(Header::MinSE(*a));
+            }
+        }
+        None
+    }
+
+    /// Return the Content-Length header if one is present.
+    pub fn content_length(&self) -> Option<Header> {
+        for h in &self.0 {
+            if let Header::ContentLength(a) = h {
+                return Some(Header::ContentLength(*a));
             }
         }
         None
@@ -66,7 +196,7 @@ impl Headers {
     pub fn cseq(&self) -> Option<Header> {
         for h in &self.0 {
             if let Header::CSeq(a, b) = h {
-                return Some(Header::CSeq(*a, *b));
+                return Some(Header::CSeq(*a, b.clone()));
             }
         }
         None
@@ -92,6 +222,11 @@ impl Headers {
         None
     }
 
+    /// Determine if the wildcard Contact header is present.
+    pub fn contact_wildcard(&self) -> bool {
+        self.0.iter().any(|h| h == &Header::ContactWildcard)
+    }
+
     /// Return the Contact header if one is present.
     pub fn contact(&self) -> Option<Header> {
         for h in &self.0 {
@@ -102,6 +237,38 @@ impl Headers {
         None
     }
 
+    /// Return the values of all of the Contact headers, in the order
+    /// they appear.
+    pub fn contacts(&self) -> Vec<NamedHeader> {
+        let mut contacts = vec![];
+        for h in &self.0 {
+            if let Header::Contact(a) = h {
+                contacts.extend(a.iter().cloned());
+            }
+        }
+        contacts
+    }
+
+    /// Return the Refer-To header if one is present.
+    pub fn refer_to(&self) -> Option<Header> {
+        for h in &self.0 {
+            if let Header::ReferTo(a) = h {
+                return Some(Header::ReferTo(a.clone()));
+            }
+        }
+        None
+    }
+
+    /// Return the Referred-By header if one is present.
+    pub fn referred_by(&self) -> Option<Header> {
+        for h in &self.0 {
+            if let Header::ReferredBy(a) = h {
+                return Some(Header::ReferredBy(a.clone()));
+            }
+        }
+        None
+    }
+
     /// Return the CoallId header if one is present.
     pub fn call_id(&self) -> Option<Header> {
         for h in &self.0 {
@@ -132,6 +299,154 @@ impl Headers {
         None
     }
 
+    /// Determine if `option_tag` (e.g. `100rel`) is listed in a
+    /// Supported or Require header.
+    pub fn supports(&self, option_tag: &str) -> bool {
+        self.0.iter().any(|h| match h {
+            Header::Supported(tags) | Header::Require(tags) => has_option_tag(tags, option_tag),
+            _ => false,
+        })
+    }
+
+    /// Determine if `option_tag` is listed in a Require header.
+    pub fn requires(&self, option_tag: &str) -> bool {
+        self.0.iter().any(|h| match h {
+            Header::Require(tags) => has_option_tag(tags, option_tag),
+            _ => false,
+        })
+    }
+
+    /// Determine if `option_tag` is listed in a Proxy-Require header.
+    pub fn proxy_requires(&self, option_tag: &str) -> bool {
+        self.0.iter().any(|h| match h {
+            Header::ProxyRequire(tags) => has_option_tag(tags, option_tag),
+            _ => false,
+        })
+    }
+
+    /// Determine if `option_tag` is listed in an Unsupported header.
+    pub fn unsupported(&self, option_tag: &str) -> bool {
+        self.0.iter().any(|h| match h {
+            Header::Unsupported(tags) => has_option_tag(tags, option_tag),
+            _ => false,
+        })
+    }
+
+    /// Return the route set formed by all of the Route headers, in the
+    /// order they appear.
+    pub fn route_set(&self) -> Vec<NamedHeader> {
+        let mut routes = vec![];
+        for h in &self.0 {
+            if let Header::Route(a) = h {
+                routes.extend(a.iter().cloned());
+            }
+        }
+        routes
+    }
+
+    /// Return the values of all of the Error-Info headers, in the
+    /// order they appear.
+    pub fn error_info(&self) -> Vec<InfoHeader> {
+        let mut values = vec![];
+        for h in &self.0 {
+            if let Header::ErrorInfo(a) = h {
+                values.extend(a.iter().cloned());
+            }
+        }
+        values
+    }
+
+    /// Return the values of all of the Record-Route headers, in the
+    /// order they appear.
+    pub fn record_route_set(&self) -> Vec<NamedHeader> {
+        let mut routes = vec![];
+        for h in &self.0 {
+            if let Header::RecordRoute(a) = h {
+                routes.extend(a.iter().cloned());
+            }
+        }
+        routes
+    }
+
+    /// Return the challenges of all of the WWW-Authenticate and
+    /// Proxy-Authenticate headers, in the order they appear, so that
+    /// 401 and 407 responses can be handled the same way.
+    pub fn challenges(&self) -> Vec<&auth::AuthHeader> {
+        let mut challenges = vec![];
+        for h in &self.0 {
+            match h {
+                Header::WwwAuthenticate(a) | Header::ProxyAuthenticate(a) => challenges.push(a),
+                _ => {},
+            }
+        }
+        challenges
+    }
+
+    /// Return the values of all of the Path headers, in the order they appear.
+    pub fn path_set(&self) -> Vec<NamedHeader> {
+        let mut routes = vec![];
+        for h in &self.0 {
+            if let Header::Path(a) = h {
+                routes.extend(a.iter().cloned());
+            }
+        }
+        routes
+    }
+
+    /// Return the values of all of the Service-Route headers, in the
+    /// order they appear.
+    pub fn service_route_set(&self) -> Vec<NamedHeader> {
+        let mut routes = vec![];
+        for h in &self.0 {
+            if let Header::ServiceRoute(a) = h {
+                routes.extend(a.iter().cloned());
+            }
+        }
+        routes
+    }
+
+    /// Return all of the History-Info entries, in the order they appear.
+    pub fn history_info(&self) -> Vec<HistoryInfoEntry> {
+        let mut entries = vec![];
+        for h in &self.0 {
+            if let Header::HistoryInfo(a) = h {
+                entries.extend(a.iter().cloned());
+            }
+        }
+        entries
+    }
+
+    /// Return the values of all of the Diversion headers, in the order they appear.
+    pub fn diversion(&self) -> Vec<DiversionHeader> {
+        let mut values = vec![];
+        for h in &self.0 {
+            if let Header::Diversion(a) = h {
+                values.extend(a.iter().cloned());
+            }
+        }
+        values
+    }
+
+    /// Return the SIP-ETag header if one is present.
+    pub fn sip_etag(&self) -> Option<Header> {
+        for h in &self.0 {
+            if let Header::SipETag(a) = h {
+                return Some(Header::SipETag(a.clone()));
+            }
+        }
+        None
+    }
+
+    /// Return the SIP-If-Match header if one is present.
+    pub fn sip_if_match(&self) -> Option<Header> {
+        for h in &self.0 {
+            if let Header::SipIfMatch(a) = h {
+                return Some(Header::SipIfMatch(a.clone()));
+            }
+        }
+        None
+    }
+
     /// Return XFS Sending Header if one is present.
     pub fn xfs_sending_message(&self) -> Option<Header> {
         for h in &self.0 {
@@ -152,53 +467,127 @@ impl IntoIterator for Headers {
     }
 }
 
+/// Option tags are compared case insensitively.
+fn has_option_tag(tags: &[String], option_tag: &str) -> bool {
+    tags.iter().any(|tag| tag.eq_ignore_ascii_case(option_tag))
+}
+
+/// Get the compact form of the header `name` if it has one.
+/// ([RFC3261: Section 7.3.3, Compact Form](https://tools.ietf.org/html/rfc3261#section-7.3.3))
+///
+/// Compact forms are accepted by all of the header parsers and are written when
+/// a `Header` or a `SipMessage` is formatted using the alternate flag (`{:#}`).
+/// # Examples
+///
+/// ```
+/// use libsip::headers::compact_form;
+/// assert_eq!(compact_form("Via"), Some("v"));
+/// assert_eq!(compact_form("Call-ID"), Some("i"));
+/// assert_eq!(compact_form("CSeq"), None);
+/// ```
+pub fn compact_form(name: &str) -> Option<&'static str> {
+    match name {
+        "Allow-Events" => Some("u"),
+        "Call-ID" => Some("i"),
+        "Contact" => Some("m"),
+        "Content-Encoding" => Some("e"),
+        "Content-Length" => Some("l"),
+        "Content-Type" => Some("c"),
+        "Event" => Some("o"),
+        "From" => Some("f"),
+        "Refer-To" => Some("r"),
+        "Referred-By" => Some("b"),
+        "Session-Expires" => Some("x"),
+        "Subject" => Some("s"),
+        "Supported" => Some("k"),
+        "To" => Some("t"),
+        "Via" => Some("v"),
+        _ => None,
+    }
+}
+
 /// Single SIP Header Representation.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum Header {
     To(NamedHeader),
-    Contact(NamedHeader),
+    /// The Contact values of a header. A single Contact header may list
+    /// several comma separated values, e.g. the bindings in a response
+    /// to a REGISTER.
+    Contact(Vec<NamedHeader>),
+    /// The wildcard Contact (`Contact: *`) used to remove all registrations.
+    ContactWildcard,
     From(NamedHeader),
     ReplyTo(NamedHeader),
+    ReferTo(NamedHeader),
+    ReferredBy(NamedHeader),
+    Replaces(ReplacesHeader),
+    RSeq(u32),
+    /// Response number, CSeq number and method of the acknowledged response.
+    RAck(u32, u32, Method),
+    SessionExpires(SessionExpires),
+    MinSE(u32),
     CSeq(u32, Method),
     MaxForwards(u32),
-    Event(String),
+    Event(EventType, GenericParams),
+    AllowEvents(Vec<EventType>),
     Expires(u32),
-    Accept(Vec<Method>),
+    /// Media ranges accepted in the body of responses.
+    Accept(Vec<ContentType>),
     ContentLength(u32),
     Allow(Vec<Method>),
     UserAgent(String),
     CallId(String),
     ContentType(ContentType),
-    ContentLanguage(Language),
-    ContentEncoding(ContentType),
-    AcceptLanguage(Language),
-    AcceptEncoding(ContentType),
-    AlertInfo(String),
-    ErrorInfo(String),
-    AuthenticationInfo(String),
+    ContentLanguage(Vec<LanguageTag>),
+    ContentEncoding(Vec<ContentCoding>),
+    AcceptLanguage(Vec<LanguageRange>),
+    AcceptEncoding(Vec<WeightedCoding>),
+    AlertInfo(Vec<InfoHeader>),
+    ErrorInfo(Vec<InfoHeader>),
+    AuthenticationInfo(auth::AuthenticationInfo),
     Authorization(auth::AuthHeader),
-    CallInfo(String),
-    InReplyTo(String),
-    ContentDisposition(String),
-    Date(String),
+    CallInfo(Vec<InfoHeader>),
+    InReplyTo(Vec<String>),
+    ContentDisposition(ContentDisposition),
+    Date(DateHeader),
     MinExpires(u32),
-    MimeVersion(f32),
+    /// The seconds between keep-alives the registrar expects on an
+    /// outbound flow.
+    FlowTimer(u32),
+    /// Major and minor version numbers, e.g. `(1, 0)` for `1.0`.
+    MimeVersion(u8, u8),
     Organization(String),
-    ProxyAuthenticate(String),
-    ProxyAuthorization(String),
-    ProxyRequire(String),
-    Require(String),
-    RetryAfter(String),
-    Route(String),
+    ProxyAuthenticate(auth::AuthHeader),
+    ProxyAuthorization(auth::AuthHeader),
+    ProxyRequire(Vec<String>),
+    Require(Vec<String>),
+    RetryAfter(retry_after::RetryAfter),
+    Route(Vec<NamedHeader>),
     Subject(String),
     SubscriptionState(SubscriptionState),
-    RecordRoute(String),
+    RecordRoute(Vec<NamedHeader>),
+    Path(Vec<NamedHeader>),
+    ServiceRoute(Vec<NamedHeader>),
+    PAssertedIdentity(Vec<NamedHeader>),
+    PPreferredIdentity(Vec<NamedHeader>),
+    Privacy(Vec<PrivacyToken>),
+    Reason(Vec<ReasonHeader>),
+    HistoryInfo(Vec<HistoryInfoEntry>),
+    Diversion(Vec<DiversionHeader>),
     Server(String),
+    /// Entity-tag of a publication, sent in 2xx responses to PUBLISH.
+    SipETag(String),
+    /// Entity-tag of the publication a PUBLISH refreshes, modifies or removes.
+    SipIfMatch(String),
     Supported(Vec<String>),
     Timestamp(u32),
-    Unsupported(String),
-    Warning(String),
-    Via(via::ViaHeader),
+    Unsupported(Vec<String>),
+    Warning(Vec<warning::WarningValue>),
+    /// The Via values of a header, topmost first. A single Via header
+    /// may list several comma separated values.
+    Via(Vec<via::ViaHeader>),
     Priority(String),
     WwwAuthenticate(auth::AuthHeader),
     XFsSendingMessage(String),
@@ -1,33 +1,47 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use nom::{
     branch::alt,
-    bytes::complete::take_while,
+    bytes::complete::{take_while, take_while1},
     character::{complete::char, *},
-    combinator::{map_res, opt},
+    combinator::{map_res, opt, peek},
     error::ParseError,
+    multi::{many1, separated_list1},
+    sequence::{terminated, tuple},
     IResult,
 };
 
-use crate::{headers::parse::parse_generic_param, parse::*, uri::parse_uri, Uri};
-
-use std::{
-    collections::{hash_map::Entry, HashMap},
-    fmt,
+use crate::{
+    headers::{
+        parse::parse_generic_param,
+        write::{write_param_value, write_quoted_string},
+        GenericParams,
+    },
+    parse::*,
+    uri::{
+        parse_absolute_uri, parse_absolute_uri_without_params, parse_uri, parse_uri_without_params,
+    },
+    Uri,
 };
 
+use std::fmt;
+
 /// Header Value for Named Headers,
 /// e.g. From, To, Contact
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NamedHeader {
     pub display_name: Option<String>,
     pub uri: Uri,
-    pub parameters: HashMap<String, Option<String>>,
+    pub parameters: GenericParams,
 }
 
 impl NamedHeader {
     pub fn new(uri: Uri) -> NamedHeader {
         NamedHeader {
             display_name: None,
-            parameters: HashMap::new(),
+            parameters: GenericParams::new(),
             uri,
         }
     }
@@ -58,79 +72,249 @@ impl NamedHeader {
         N: Into<String>,
         V: Into<String>,
     {
-        let name = name.into();
-        let value = value.map(Into::into);
-        match self.parameters.entry(name) {
-            Entry::Occupied(mut entry) => {
-                entry.insert(value);
-            },
-            Entry::Vacant(entry) => {
-                entry.insert(value);
-            },
-        }
+        self.parameters.insert(name, value);
     }
 }
 
-impl fmt::Display for NamedHeader {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if let Some(name) = &self.display_name {
-            if name.contains(' ') {
-                write!(f, "\"{}\" <{}>", name, self.uri)?;
-            } else if name.is_empty() {
-                write!(f, "\"\" <{}>", self.uri)?;
-            } else {
-                write!(f, "{} <{}>", name, self.uri)?;
-            }
-        } else {
-            write!(f, "{}", self.uri)?;
+impl NamedHeader {
+    /// Retrieve the value of the parameter `name` if it is present and has a value.
+    pub fn param_value(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .get(name)
+            .and_then(|value| value.as_ref().map(String::as_str))
+    }
+
+    /// The `tag` parameter of a To or From header, identifying a dialog.
+    pub fn tag(&self) -> Option<&str> {
+        self.param_value("tag")
+    }
+
+    /// Set the `tag` parameter, replacing the previous one if present.
+    pub fn set_tag<S: Into<String>>(&mut self, tag: S) {
+        self.set_param("tag", Some(tag.into()));
+    }
+
+    /// The `q` parameter of a Contact, the relative preference of this
+    /// contact between 0.0 and 1.0.
+    pub fn q(&self) -> Option<f32> {
+        self.param_value("q").and_then(|value| value.parse().ok())
+    }
+
+    /// The `expires` parameter of a Contact, the lifetime of the
+    /// registration binding in seconds.
+    pub fn expires(&self) -> Option<u32> {
+        self.param_value("expires")
+            .and_then(|value| value.parse().ok())
+    }
+
+    /// The `+sip.instance` parameter of a Contact without the enclosing
+    /// angle brackets, e.g. `urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6`.
+    /// ([RFC5626: Section 4.1, Instance-ID](https://tools.ietf.org/html/rfc5626#section-4.1))
+    pub fn instance(&self) -> Option<&str> {
+        self.param_value("+sip.instance").map(|value| {
+            value
+                .strip_prefix('<')
+                .and_then(|value| value.strip_suffix('>'))
+                .unwrap_or(value)
+        })
+    }
+
+    /// Set the `+sip.instance` parameter to the instance id `urn`.
+    pub fn set_instance<S: Into<String>>(&mut self, urn: S) {
+        self.set_param("+sip.instance", Some(format!("<{}>", urn.into())));
+    }
+
+    /// The `reg-id` parameter of a Contact, identifying the flow of an
+    /// outbound registration.
+    /// ([RFC5626: Section 4.2, Registrations](https://tools.ietf.org/html/rfc5626#section-4.2))
+    pub fn reg_id(&self) -> Option<u32> {
+        self.param_value("reg-id")
+            .and_then(|value| value.parse().ok())
+    }
+
+    /// Set the `reg-id` parameter.
+    pub fn set_reg_id(&mut self, reg_id: u32) {
+        self.set_param("reg-id", Some(reg_id.to_string()));
+    }
+
+    /// The public GRUU assigned by the registrar in the `pub-gruu` parameter.
+    /// ([RFC5627: Section 5.1, Structure of GRUUs](https://tools.ietf.org/html/rfc5627#section-5.1))
+    pub fn pub_gruu(&self) -> Option<Uri> {
+        self.param_value("pub-gruu")
+            .and_then(|value| value.parse().ok())
+    }
+
+    /// The temporary GRUU assigned by the registrar in the `temp-gruu` parameter.
+    pub fn temp_gruu(&self) -> Option<Uri> {
+        self.param_value("temp-gruu")
+            .and_then(|value| value.parse().ok())
+    }
+}
+
+impl NamedHeader {
+    /// Write this header value in the name-addr form, the uri is always
+    /// enclosed in angle brackets.
+    pub fn fmt_name_addr(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.display_name {
+            Some(name) if !name.is_empty() && name.bytes().all(is_token) => write!(f, "{} ", name)?,
+            Some(name) => {
+                write_quoted_string(name, f)?;
+                write!(f, " ")?
+            },
+            None => {},
         }
+        write!(f, "<{}>", self.uri)?;
+        self.fmt_params(f)
+    }
+
+    fn fmt_params(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for (key, value) in self.parameters.iter() {
             write!(f, ";{}", key)?;
             if let Some(value) = value {
-                write!(f, "={}", value)?;
+                write!(f, "=")?;
+                write_param_value(value, f)?;
             }
         }
         Ok(())
     }
 }
 
+/// The uri is only written without angle brackets when there is no display
+/// name and the uri has no parameters or headers that could be confused with
+/// the header's own parameters. Absolute uris are always enclosed, as any
+/// `;`, `,` or `?` they contain would be.
+/// ([RFC3261: Section 20](https://tools.ietf.org/html/rfc3261#section-20))
+impl fmt::Display for NamedHeader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.display_name.is_some()
+            || self.uri.is_absolute()
+            || !self.uri.parameters.is_empty()
+            || !self.uri.headers.is_empty()
+        {
+            self.fmt_name_addr(f)
+        } else {
+            write!(f, "{}", self.uri)?;
+            self.fmt_params(f)
+        }
+    }
+}
+
+/// Generate a random tag for a To or From header. Tags carry 64 bits of
+/// randomness to be globally unique.
+/// ([RFC3261: Section 19.3, Tags](https://tools.ietf.org/html/rfc3261#section-19.3))
+pub fn generate_tag() -> String {
+    format!("{:016x}", rand::random::<u64>())
+}
+
+/// Generate a random instance id for the `+sip.instance` Contact parameter,
+/// e.g. `urn:uuid:f81d4fae-7dec-41d0-a765-00a0c91e6bf6`. The instance id has
+/// to be stored by the application, as it must stay the same across restarts.
+pub fn generate_instance_id() -> String {
+    format_uuid(rand::random::<[u8; 16]>(), 4)
+}
+
+/// Generate an instance id derived from `seed`, e.g. the MAC address of
+/// the device, so the same instance id is produced after every restart
+/// without having to store it.
+pub fn instance_id_from_seed(seed: &str) -> String {
+    format_uuid(md5::compute(seed).0, 3)
+}
+
+fn format_uuid(mut bytes: [u8; 16], version: u8) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | (version << 4);
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!(
+        "urn:uuid:{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
 /// Parse the name part of the NamedHeader.
 pub fn parse_name<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], String, E> {
-    Ok(alt::<_, _, E, _>((
-        parse_quoted_string::<E>,
-        parse_unquoted_string::<E>,
-    ))(input)?)
+    alt::<_, _, E, _>((parse_quoted_string::<E>, parse_unquoted_string::<E>))(input)
 }
 
-/// Parse a stream of text that is not quoted. This will stop
-/// at the first ' ' char the input contains.
+/// Parse a display name that is not quoted: one or more tokens separated by
+/// whitespace, which must be followed by the `<` starting the uri. The tokens
+/// are joined with a single space.
 pub fn parse_unquoted_string<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], String, E> {
-    let (input, string_data) = map_res(take_while(is_alphabetic), slice_to_string::<E>)(input)?;
-    let (input, _) = char(' ')(input)?;
-    Ok((input, string_data))
+    let (input, words) = many1(terminated(
+        map_res(take_while1(is_token), slice_to_string::<E>),
+        take_while(is_space),
+    ))(input)?;
+    let (input, _) = peek(char('<'))(input)?;
+    Ok((input, words.join(" ")))
 }
 
-/// Parse a single NamedHeader value.
+/// Parse a single NamedHeader value, either in the name-addr form
+/// (`Bob <sip:bob@example.com;transport=tcp>`) or as a bare addr-spec
+/// (`sip:bob@example.com`). Parameters following a bare addr-spec belong to
+/// the header and are left for `parse_named_field_params`.
+/// Uris that are not sip or tel uris, e.g. `urn:service:sos`, are parsed
+/// with `parse_absolute_uri`.
 pub fn parse_named_field_value<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], (Option<String>, Uri), E> {
     let (input, name) = opt(parse_name)(input)?;
     let (input, _) = opt(take_while(is_space))(input)?;
-    let (input, _) = opt(char('<'))(input)?;
-    let (input, value) = parse_uri(input)?;
-    let (input, _) = opt(char('>'))(input)?;
-    Ok((input, (name, value)))
+    let (input, bracket) = opt(char('<'))(input)?;
+    if bracket.is_some() {
+        let (input, value) = alt((
+            terminated(parse_uri, char('>')),
+            terminated(parse_absolute_uri, char('>')),
+        ))(input)?;
+        Ok((input, (name, value)))
+    } else {
+        let (input, value) =
+            alt((parse_absolute_uri_without_params, parse_uri_without_params))(input)?;
+        Ok((input, (name, value)))
+    }
+}
+
+/// Parse a single NamedHeader value along with its parameters.
+pub fn parse_named_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], NamedHeader, E> {
+    let (input, (display_name, uri)) = parse_named_field_value(input)?;
+    let (input, parameters) = parse_named_field_params(input)?;
+    Ok((
+        input,
+        NamedHeader {
+            display_name,
+            uri,
+            parameters,
+        },
+    ))
+}
+
+/// Parse a comma separated list of NamedHeader values.
+pub fn parse_named_header_list<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Vec<NamedHeader>, E> {
+    separated_list1(
+        tuple((
+            opt(take_while(is_space)),
+            char(','),
+            opt(take_while(is_space)),
+        )),
+        parse_named_header,
+    )(input)
 }
 
 /// Parse as many valid named field params as the input contains.
 pub fn parse_named_field_params<'a, E: ParseError<&'a [u8]>>(
     mut input: &'a [u8],
-) -> IResult<&'a [u8], HashMap<String, Option<String>>, E> {
-    let mut map = HashMap::new();
+) -> IResult<&'a [u8], GenericParams, E> {
+    let mut map = GenericParams::new();
     while let Ok((data, (key, value))) = parse_generic_param::<E>(input) {
-        map.insert(key, value);
+        map.push(key, value);
         input = data;
     }
     Ok((input, map))
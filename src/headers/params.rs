@@ -0,0 +1,109 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::iter::FromIterator;
+
+/// Ordered list of header parameters (`;name=value`) with map-style lookup.
+///
+/// Parameters are kept in the order they were added or received, including
+/// duplicates, so that headers are written back exactly as they were parsed.
+/// Names are compared case-insensitively but keep their original casing.
+/// ([RFC3261: Section 7.3.1, Header Field Format](https://tools.ietf.org/html/rfc3261#section-7.3.1))
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GenericParams(Vec<(String, Option<String>)>);
+
+impl GenericParams {
+    pub fn new() -> GenericParams {
+        GenericParams(vec![])
+    }
+
+    /// Retrieve the value of the first parameter called `name`.
+    pub fn get(&self, name: &str) -> Option<&Option<String>> {
+        self.0
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// Retrieve the values of every parameter called `name`, in order.
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Option<String>> {
+        self.0
+            .iter()
+            .filter(move |(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// Determine if a parameter called `name` is present.
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Set the value of the parameter `name`. If the parameter is already
+    /// present its value is replaced in place, keeping the casing of its
+    /// name, and the previous value is returned, otherwise it is added at
+    /// the end.
+    pub fn insert<N, V>(&mut self, name: N, value: Option<V>) -> Option<Option<String>>
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        let name = name.into();
+        let value = value.map(Into::into);
+        match self
+            .0
+            .iter_mut()
+            .find(|(key, _)| key.eq_ignore_ascii_case(&name))
+        {
+            Some((_, old)) => Some(std::mem::replace(old, value)),
+            None => {
+                self.0.push((name, value));
+                None
+            },
+        }
+    }
+
+    /// Add a parameter at the end, even if one with the same name is
+    /// already present.
+    pub fn push<N, V>(&mut self, name: N, value: Option<V>)
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.0.push((name.into(), value.map(Into::into)));
+    }
+
+    /// Remove every parameter called `name`, returning the value of the first one.
+    pub fn remove(&mut self, name: &str) -> Option<Option<String>> {
+        let value = self.get(name).cloned();
+        self.0.retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+        value
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Option<String>)> {
+        self.0.iter().map(|(key, value)| (key, value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<(String, Option<String>)> for GenericParams {
+    fn from_iter<I: IntoIterator<Item = (String, Option<String>)>>(iter: I) -> Self {
+        GenericParams(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for GenericParams {
+    type IntoIter = std::vec::IntoIter<(String, Option<String>)>;
+    type Item = (String, Option<String>);
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
@@ -1,40 +1,125 @@
 use super::{
-    content::*, language::*, named::*, subscription_state::parse_subscription_state_header, *,
+    content::*, date::*, disposition::parse_content_disposition_header,
+    diversion::parse_diversion_header, encoding::*, event::*,
+    history_info::parse_history_info_header, info::*, language::*, named::*,
+    privacy::parse_privacy_header, reason::parse_reason_header, replaces::parse_replaces_header,
+    retry_after::parse_retry_after_header, session_expires::parse_session_expires_header,
+    subscription_state::parse_subscription_state_header, warning::parse_warning_header, *,
 };
 use crate::{
     core::{parse_method, parse_transport, parse_version},
     parse::*,
-    uri::parse_uri,
+    uri::parse_domain,
 };
 use nom::{
     branch::alt,
-    bytes::complete::{tag, tag_no_case, take_until, take_while},
+    bytes::complete::{tag, tag_no_case, take_until, take_while, take_while1},
     character::{complete::char, *},
-    combinator::{map, map_res, opt},
+    combinator::{map, map_res, opt, recognize},
     error::ParseError,
-    multi::separated_list0,
+    multi::{separated_list0, separated_list1},
     sequence::pair,
     IResult,
 };
 use std::collections::HashMap;
 
+/// Parse the name of the header `name` followed by the `:` separator and any
+/// surrounding whitespace. The compact form of the header name is accepted as
+/// well if the header has one (see `compact_form`).
+pub fn parse_header_name<'a, E: ParseError<&'a [u8]>>(
+    name: &'static str,
+) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], &'a [u8], E> {
+    move |input: &'a [u8]| {
+        let (input, matched) = match compact_form(name) {
+            Some(compact) => alt((tag_no_case(name), tag_no_case(compact)))(input)?,
+            None => tag_no_case(name)(input)?,
+        };
+        let (input, _) = opt(take_while(is_space))(input)?;
+        let (input, _) = char(':')(input)?;
+        let (input, _) = opt(take_while(is_space))(input)?;
+        Ok((input, matched))
+    }
+}
+
+/// Parse the `,` separating the values of a header, along with any surrounding whitespace.
+pub fn parse_comma_separator<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], (), E> {
+    let (input, _) = opt(take_while(is_space))(input)?;
+    let (input, _) = char(',')(input)?;
+    let (input, _) = opt(take_while(is_space))(input)?;
+    Ok((input, ()))
+}
+
+/// Parse a single header. Header values folded over multiple lines
+/// (a line break followed by whitespace) are unfolded before being parsed.
 pub fn parse_header<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Header> {
     let (input, _) = opt(tag("\r\n"))(input)?;
     let (input, _) = opt(take_while(is_space))(input)?;
+    if let Some(end) = folded_header_end(input) {
+        let unfolded = unfold_header(&input[..end]);
+        return match _parse_header(&unfolded) {
+            Ok((&[], header)) => Ok((&input[end..], header)),
+            _ => Err(nom::Err::Error((input, nom::error::ErrorKind::Alt))),
+        };
+    }
     let (input, header) = _parse_header(input)?;
     Ok((input, header))
 }
 
+/// Find the end (including the final CRLF) of a header spanning
+/// multiple lines. Returns None if the header isn't folded.
+fn folded_header_end(input: &[u8]) -> Option<usize> {
+    let mut folded = false;
+    let mut index = 0;
+    while index + 1 < input.len() {
+        if &input[index..index + 2] == b"\r\n" {
+            match input.get(index + 2) {
+                Some(b' ') | Some(b'\t') => folded = true,
+                _ => return if folded { Some(index + 2) } else { None },
+            }
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Replace every line fold (optional whitespace, CRLF and whitespace) of
+/// a header with a single space, keeping the final CRLF.
+/// ([RFC3261: Section 7.3.1, Header Field Format](https://tools.ietf.org/html/rfc3261#section-7.3.1))
+fn unfold_header(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut index = 0;
+    while index < input.len() {
+        if input[index..].starts_with(b"\r\n") && index + 2 < input.len() {
+            while out.last().is_some_and(|chr| is_space(*chr)) {
+                out.pop();
+            }
+            out.push(b' ');
+            index += 2;
+            while index < input.len() && is_space(input[index]) {
+                index += 1;
+            }
+        } else {
+            out.push(input[index]);
+            index += 1;
+        }
+    }
+    out
+}
+
 named!(pub _parse_header<Header>, alt!(
     parse_accept_encoding_header |
     parse_accept_header |
     parse_accept_language_header |
     parse_alert_info_header |
+    parse_allow_events_header |
     parse_allow_header |
     parse_authentication_info_header |
     parse_authorization_header |
     parse_call_info_header |
     parse_callid_header |
+    parse_contact_wildcard_header |
     parse_contact_header |
     parse_content_disposition_header |
     parse_content_encoding_header |
@@ -43,25 +128,43 @@ named!(pub _parse_header<Header>, alt!(
     parse_content_type_header |
     parse_cseq_header |
     parse_date_header |
+    parse_diversion_header |
     parse_error_info_header |
     parse_expires_header |
+    parse_flow_timer_header |
     parse_event_header |
     parse_from_header |
+    parse_history_info_header |
     parse_in_reply_to_header |
     parse_max_forwards_header |
     parse_mime_version_header |
     parse_min_expires_header |
+    parse_min_se_header |
     parse_organization_header |
+    parse_p_asserted_identity_header |
+    parse_p_preferred_identity_header |
+    parse_path_header |
     parse_priority_header |
+    parse_privacy_header |
     parse_proxy_authenticate_header |
     parse_proxy_authorization_header |
     parse_proxy_require_header |
+    parse_reason_header |
     parse_record_route_header |
+    parse_refer_to_header |
+    parse_referred_by_header |
+    parse_replaces_header |
     parse_reply_to_header |
+    parse_rack_header |
+    parse_rseq_header |
     parse_require_header |
     parse_retry_after_header |
     parse_route_header |
     parse_server_header |
+    parse_service_route_header |
+    parse_session_expires_header |
+    parse_sip_etag_header |
+    parse_sip_if_match_header |
     parse_subject_header |
     parse_subscription_state_header |
     parse_supported_header |
@@ -79,38 +182,30 @@ named!(pub _parse_header<Header>, alt!(
 macro_rules! impl_u32_parser {
     ($name:tt, $tag:tt, $variant:ident) => {
         pub fn $name<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Header, E> {
-            let (input, _) = tag($tag)(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
-            let (input, _) = char(':')(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
+            let (input, _) = parse_header_name($tag)(input)?;
             let (input, value) = map_res(take_while(is_digit), parse_u32)(input)?;
             let (input, _) = tag("\r\n")(input)?;
             Ok((input, Header::$variant(value)))
         }
     };
 }
-macro_rules! impl_f32_parser {
+macro_rules! impl_string_parser {
     ($name:tt, $tag:tt, $variant:ident) => {
         pub fn $name<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Header, E> {
-            let (input, _) = tag_no_case($tag)(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
-            let (input, _) = char(':')(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
-            let (input, value) =
-                map_res(take_while(|item| is_digit(item) || item == b'.'), parse_f32)(input)?;
+            let (input, _) = parse_header_name($tag)(input)?;
+            let (input, value) = map_res(take_until("\r"), slice_to_string::<E>)(input)?;
+            let (input, _) = tag("\r\n")(input)?;
             Ok((input, Header::$variant(value)))
         }
     };
 }
 
-macro_rules! impl_string_parser {
+macro_rules! impl_token_parser {
     ($name:tt, $tag:tt, $variant:ident) => {
         pub fn $name<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Header, E> {
-            let (input, _) = tag_no_case::<_, _, E>($tag)(input)?;
+            let (input, _) = parse_header_name($tag)(input)?;
+            let (input, value) = map_res(take_while1(is_token), slice_to_string::<E>)(input)?;
             let (input, _) = opt(take_while(is_space))(input)?;
-            let (input, _) = char(':')(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
-            let (input, value) = map_res(take_until("\r"), slice_to_string::<E>)(input)?;
             let (input, _) = tag("\r\n")(input)?;
             Ok((input, Header::$variant(value)))
         }
@@ -120,24 +215,29 @@ macro_rules! impl_string_parser {
 macro_rules! impl_array_parser {
     ($name:tt, $tag:tt, $variant:ident, $func:ident) => {
         pub fn $name<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Header, E> {
-            let (input, _) = tag_no_case($tag)(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
-            let (input, _) = char(':')(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
-            let (input, data) = separated_list0(pair(char(','), opt(char(' '))), $func)(input)?;
+            let (input, _) = parse_header_name($tag)(input)?;
+            let (input, data) = separated_list0(parse_comma_separator, $func)(input)?;
             let (input, _) = tag("\r\n")(input)?;
             Ok((input, Header::$variant(data)))
         }
     };
 }
 
+macro_rules! impl_option_tag_parser {
+    ($name:tt, $tag:tt, $variant:ident) => {
+        pub fn $name<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Header, E> {
+            let (input, _) = parse_header_name($tag)(input)?;
+            let (input, tags) = separated_list0(parse_comma_separator, parse_option_tag)(input)?;
+            let (input, _) = tag("\r\n")(input)?;
+            Ok((input, Header::$variant(tags)))
+        }
+    };
+}
+
 macro_rules! impl_named_parser {
     ($name:tt, $tag:tt, $variant:ident) => {
         pub fn $name<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Header, E> {
-            let (input, _) = tag_no_case($tag)(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
-            let (input, _) = char(':')(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
+            let (input, _) = parse_header_name($tag)(input)?;
             let (input, out) = parse_named_field_value(input)?;
             let (input, params) = parse_named_field_params(input)?;
             let (input, _) = tag("\r\n")(input)?;
@@ -153,77 +253,74 @@ macro_rules! impl_named_parser {
     };
 }
 
-macro_rules! impl_type_parser {
+macro_rules! impl_named_array_parser {
     ($name:tt, $tag:tt, $variant:ident) => {
         pub fn $name<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Header, E> {
-            let (input, _) = tag_no_case($tag)(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
-            let (input, _) = char(':')(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
-            let (input, ty) = parse_content_type::<E>(input)?;
-            Ok((input, Header::$variant(ty)))
+            let (input, _) = parse_header_name($tag)(input)?;
+            let (input, values) = parse_named_header_list(input)?;
+            let (input, _) = tag("\r\n")(input)?;
+            Ok((input, Header::$variant(values)))
         }
     };
 }
 
-macro_rules! impl_lang_parser {
+macro_rules! impl_info_parser {
     ($name:tt, $tag:tt, $variant:ident) => {
         pub fn $name<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Header, E> {
-            let (input, _) = tag_no_case($tag)(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
-            let (input, _) = char(':')(input)?;
-            let (input, _) = opt(take_while(is_space))(input)?;
-            let (input, ty) = parse_language(input).map_err(|_| {
-                nom::Err::Failure(E::from_error_kind(input, nom::error::ErrorKind::IsNot))
-            })?;
+            let (input, _) = parse_header_name($tag)(input)?;
+            let (input, values) = parse_info_header_list(input)?;
+            let (input, _) = tag("\r\n")(input)?;
+            Ok((input, Header::$variant(values)))
+        }
+    };
+}
+
+macro_rules! impl_type_parser {
+    ($name:tt, $tag:tt, $variant:ident) => {
+        pub fn $name<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Header, E> {
+            let (input, _) = parse_header_name($tag)(input)?;
+            let (input, ty) = parse_content_type::<E>(input)?;
+            let (input, _) = opt(tag("\r\n"))(input)?;
             Ok((input, Header::$variant(ty)))
         }
     };
 }
 
 impl_u32_parser!(parse_expires_header, "Expires", Expires);
-impl_string_parser!(parse_event_header, "Event", Event);
 impl_u32_parser!(parse_min_expires_header, "Min-Expires", MinExpires);
+impl_u32_parser!(parse_flow_timer_header, "Flow-Timer", FlowTimer);
 impl_u32_parser!(parse_content_length_header, "Content-Length", ContentLength);
 impl_u32_parser!(parse_max_forwards_header, "Max-Forwards", MaxForwards);
-impl_f32_parser!(parse_mime_version_header, "MIME-Version", MimeVersion);
+impl_u32_parser!(parse_rseq_header, "RSeq", RSeq);
+impl_u32_parser!(parse_min_se_header, "Min-SE", MinSE);
 impl_string_parser!(parse_useragent_header, "User-Agent", UserAgent);
 impl_string_parser!(parse_callid_header, "Call-ID", CallId);
-impl_string_parser!(parse_alert_info_header, "Alert-Info", AlertInfo);
-impl_string_parser!(parse_error_info_header, "Error-Info", ErrorInfo);
-impl_string_parser!(
-    parse_authentication_info_header,
-    "Authentication-Info",
-    AuthenticationInfo
-);
-impl_string_parser!(parse_call_info_header, "Call-Info", CallInfo);
-impl_string_parser!(parse_in_reply_to_header, "In-Reply-To", InReplyTo);
-impl_string_parser!(
-    parse_content_disposition_header,
-    "Content-Disposition",
-    ContentDisposition
-);
-impl_string_parser!(parse_date_header, "Date", Date);
+impl_info_parser!(parse_alert_info_header, "Alert-Info", AlertInfo);
+impl_info_parser!(parse_error_info_header, "Error-Info", ErrorInfo);
+impl_info_parser!(parse_call_info_header, "Call-Info", CallInfo);
 impl_string_parser!(parse_organization_header, "Organization", Organization);
-impl_string_parser!(
-    parse_proxy_authenticate_header,
-    "Proxy-Authenticate",
-    ProxyAuthenticate
+impl_option_tag_parser!(parse_proxy_require_header, "Proxy-Require", ProxyRequire);
+impl_option_tag_parser!(parse_require_header, "Require", Require);
+impl_option_tag_parser!(parse_supported_header, "Supported", Supported);
+impl_named_array_parser!(parse_route_header, "Route", Route);
+impl_named_array_parser!(parse_path_header, "Path", Path);
+impl_named_array_parser!(parse_service_route_header, "Service-Route", ServiceRoute);
+impl_named_array_parser!(
+    parse_p_asserted_identity_header,
+    "P-Asserted-Identity",
+    PAssertedIdentity
 );
-impl_string_parser!(
-    parse_proxy_authorization_header,
-    "Proxy-Authorization",
-    ProxyAuthorization
+impl_named_array_parser!(
+    parse_p_preferred_identity_header,
+    "P-Preferred-Identity",
+    PPreferredIdentity
 );
-impl_string_parser!(parse_proxy_require_header, "Proxy-Require", ProxyRequire);
-impl_string_parser!(parse_require_header, "Require", Require);
-impl_string_parser!(parse_retry_after_header, "Retry-After", RetryAfter);
-impl_string_parser!(parse_route_header, "Route", Route);
 impl_string_parser!(parse_subject_header, "Subject", Subject);
-impl_string_parser!(parse_record_route_header, "Record-Route", RecordRoute);
+impl_named_array_parser!(parse_record_route_header, "Record-Route", RecordRoute);
 impl_string_parser!(parse_server_header, "Server", Server);
-impl_string_parser!(parse_unsupported_header, "Unsupported", Unsupported);
-impl_string_parser!(parse_warning_header, "Warning", Warning);
+impl_token_parser!(parse_sip_etag_header, "SIP-ETag", SipETag);
+impl_token_parser!(parse_sip_if_match_header, "SIP-If-Match", SipIfMatch);
+impl_option_tag_parser!(parse_unsupported_header, "Unsupported", Unsupported);
 impl_string_parser!(
     parse_xfs_sending_message_header,
     "X-FS-Sending-Message",
@@ -231,34 +328,15 @@ impl_string_parser!(
 );
 impl_string_parser!(parse_priority_header, "Priority", Priority);
 impl_u32_parser!(parse_timestamp_header, "Timestamp", Timestamp);
-impl_array_parser!(parse_accept_header, "Accept", Accept, parse_method);
+impl_array_parser!(parse_accept_header, "Accept", Accept, parse_content_type);
 impl_array_parser!(parse_allow_header, "Allow", Allow, parse_method);
-impl_array_parser!(parse_supported_header, "Supported", Supported, parse_string);
 impl_named_parser!(parse_to_header, "To", To);
 impl_named_parser!(parse_from_header, "From", From);
-impl_named_parser!(parse_contact_header, "Contact", Contact);
+impl_named_array_parser!(parse_contact_header, "Contact", Contact);
 impl_named_parser!(parse_reply_to_header, "Reply-To", ReplyTo);
+impl_named_parser!(parse_refer_to_header, "Refer-To", ReferTo);
+impl_named_parser!(parse_referred_by_header, "Referred-By", ReferredBy);
 impl_type_parser!(parse_content_type_header, "Content-Type", ContentType);
-impl_type_parser!(
-    parse_content_encoding_header,
-    "Content-Encoding",
-    ContentEncoding
-);
-impl_type_parser!(
-    parse_accept_encoding_header,
-    "Accept-Encoding",
-    AcceptEncoding
-);
-impl_lang_parser!(
-    parse_content_language_header,
-    "Content-Language",
-    ContentLanguage
-);
-impl_lang_parser!(
-    parse_accept_language_header,
-    "Accept-Language",
-    AcceptLanguage
-);
 
 fn parse_auth_header_vars<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
@@ -272,28 +350,40 @@ fn parse_auth_header_vars<'a, E: ParseError<&'a [u8]>>(
     Ok((data, map))
 }
 
+/// Parse any `Name: value` header line into `Header::Other`. This is used as
+/// the fallback for extension headers and headers whose value could not be
+/// parsed by their typed parser. The casing of the name is kept as received.
+/// # Examples
+///
+/// ```
+/// use libsip::{headers::parse::parse_other_header, Header};
+/// use nom::error::VerboseError;
+/// assert_eq!(
+///     parse_other_header::<VerboseError<_>>(b"X-Custom-Header : some value \r\n"),
+///     Ok((
+///         "".as_bytes(),
+///         Header::Other(String::from("X-Custom-Header"), String::from("some value"))
+///     ))
+/// );
+/// ```
 pub fn parse_other_header<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], Header, E> {
     let (input, _) = opt(tag("\r\n"))(input)?;
-    let (input, key) = map_res(
-        take_while(|item| is_alphanumeric(item) || item == b'-'),
-        slice_to_string::<E>,
-    )(input)?;
+    let (input, key) = map_res(take_while1(is_token), slice_to_string::<E>)(input)?;
+    let (input, _) = opt(take_while(is_space))(input)?;
     let (input, _) = char(':')(input)?;
-    let (input, value) = map_res(take_until("\r"), slice_to_string::<E>)(input)?;
+    let (input, _) = opt(take_while(is_space))(input)?;
+    let (input, value) = map_res(take_until("\r\n"), slice_to_string::<E>)(input)?;
     let (input, _) = tag("\r\n")(input)?;
-    Ok((input, Header::Other(key, value)))
+    Ok((input, Header::Other(key, value.trim_end().to_string())))
 }
 
 pub fn parse_cseq_header<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], Header, E> {
     let (input, _) = opt(tag("\r\n"))(input)?;
-    let (input, _) = tag_no_case("CSeq")(input)?;
-    let (input, _) = opt(take_while(is_space))(input)?;
-    let (input, _) = char(':')(input)?;
-    let (input, _) = opt(take_while(is_space))(input)?;
+    let (input, _) = parse_header_name("CSeq")(input)?;
     let (input, value) = map_res(take_while(is_digit), parse_u32)(input)?;
     let (input, _) = opt(take_while(is_space))(input)?;
     let (input, method) = parse_method(input)?;
@@ -301,25 +391,90 @@ pub fn parse_cseq_header<'a, E: ParseError<&'a [u8]>>(
     Ok((input, Header::CSeq(value, method)))
 }
 
-pub fn parse_via_header<'a, E: ParseError<&'a [u8]>>(
+pub fn parse_rack_header<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], Header, E> {
-    let (input, _) = tag_no_case("Via")(input)?;
-    let (input, _) = opt(take_while(is_space))(input)?;
-    let (input, _) = char(':')(input)?;
+    let (input, _) = parse_header_name("RAck")(input)?;
+    let (input, rseq) = map_res(take_while(is_digit), parse_u32)(input)?;
+    let (input, _) = take_while1(is_space)(input)?;
+    let (input, cseq) = map_res(take_while(is_digit), parse_u32)(input)?;
+    let (input, _) = take_while1(is_space)(input)?;
+    let (input, method) = parse_method(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::RAck(rseq, cseq, method)))
+}
+
+pub fn parse_in_reply_to_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("In-Reply-To")(input)?;
+    let (input, call_ids) = separated_list1(parse_comma_separator, parse_callid)(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::InReplyTo(call_ids)))
+}
+
+pub fn parse_mime_version_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("MIME-Version")(input)?;
+    let (input, major) = map_res(take_while1(is_digit), parse_u8)(input)?;
+    let (input, _) = char('.')(input)?;
+    let (input, minor) = map_res(take_while1(is_digit), parse_u8)(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::MimeVersion(major, minor)))
+}
+
+pub fn parse_contact_wildcard_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Contact")(input)?;
+    let (input, _) = char('*')(input)?;
     let (input, _) = opt(take_while(is_space))(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::ContactWildcard))
+}
+
+pub fn parse_date_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Date")(input)?;
+    let (input, value) = take_until("\r")(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    let date = match parse_sip_date::<E>(value) {
+        Ok((&[], date)) => DateHeader::Date(date),
+        _ => DateHeader::Invalid(String::from_utf8_lossy(value).to_string()),
+    };
+    Ok((input, Header::Date(date)))
+}
+
+pub fn parse_via_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Via")(input)?;
+    let (input, values) = separated_list1(parse_comma_separator, parse_via_value)(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::Via(values)))
+}
+
+/// Parse a single value of a Via header, e.g. `SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds`.
+/// ([RFC3261: Page 232, "via-parm"](https://tools.ietf.org/html/rfc3261#page-232))
+pub fn parse_via_value<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], via::ViaHeader, E> {
     let (input, version) = parse_version(input)?;
     let (input, _) = char('/')(input)?;
     let (input, transport) = parse_transport(input)?;
     let (input, _) = opt(take_while(is_space))(input)?;
-    let (input, uri) = parse_uri(input)?;
+    let (input, host) = parse_domain(input)?;
+    let (input, parameters) = parse_named_field_params(input)?;
     Ok((
         input,
-        Header::Via(via::ViaHeader {
+        via::ViaHeader {
             version,
             transport,
-            uri,
-        }),
+            host,
+            parameters,
+        },
     ))
 }
 
@@ -327,37 +482,86 @@ pub fn parse_www_authenticate_header<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], Header, E> {
     let (input, _) = opt(tag("\r\n"))(input)?;
-    let (input, _) = tag_no_case("WWW-Authenticate")(input)?;
-    let (input, _) = opt(take_while(is_space))(input)?;
-    let (input, _) = char(':')(input)?;
-    let (input, _) = opt(take_while(is_space))(input)?;
-    let (input, schema) = parse_auth_schema::<E>(input)?;
-    let (input, _) = char(' ')(input)?;
-    let (input, res) = parse_auth_header_vars(input)?;
+    let (input, _) = parse_header_name("WWW-Authenticate")(input)?;
+    let (input, challenge) = parse_auth_challenge::<E>(input)?;
     let (input, _) = opt(char(' '))(input)?;
     let (input, _) = tag("\r\n")(input)?;
-    Ok((
-        input,
-        Header::WwwAuthenticate(auth::AuthHeader(schema, res)),
-    ))
+    Ok((input, Header::WwwAuthenticate(challenge)))
+}
+
+pub fn parse_proxy_authenticate_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = opt(tag("\r\n"))(input)?;
+    let (input, _) = parse_header_name("Proxy-Authenticate")(input)?;
+    let (input, challenge) = parse_auth_challenge::<E>(input)?;
+    let (input, _) = opt(char(' '))(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::ProxyAuthenticate(challenge)))
+}
+
+/// Parse the value of a challenge header, WWW-Authenticate or
+/// Proxy-Authenticate: the authentication schema followed by its parameters.
+pub fn parse_auth_challenge<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], auth::AuthHeader, E> {
+    let (input, schema) = parse_auth_schema(input)?;
+    let (input, _) = char(' ')(input)?;
+    let (input, res) = parse_auth_params(input)?;
+    Ok((input, auth::AuthHeader(schema, res)))
 }
 
 pub fn parse_authorization_header<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], Header, E> {
     let (input, _) = opt(tag("\r\n"))(input)?;
-    let (input, _) = tag_no_case("Authorization")(input)?;
-    let (input, _) = opt(take_while(is_space))(input)?;
-    let (input, _) = char(':')(input)?;
-    let (input, _) = opt(take_while(is_space))(input)?;
+    let (input, _) = parse_header_name("Authorization")(input)?;
     let (input, schema) = parse_auth_schema(input)?;
     let (input, _) = char(' ')(input)?;
-    let (input, res) = parse_auth_header_vars(input)?;
+    let (input, res) = parse_auth_params(input)?;
     let (input, _) = opt(char(' '))(input)?;
     let (input, _) = tag("\r\n")(input)?;
     Ok((input, Header::Authorization(auth::AuthHeader(schema, res))))
 }
 
+pub fn parse_proxy_authorization_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = opt(tag("\r\n"))(input)?;
+    let (input, _) = parse_header_name("Proxy-Authorization")(input)?;
+    let (input, schema) = parse_auth_schema(input)?;
+    let (input, _) = char(' ')(input)?;
+    let (input, res) = parse_auth_params(input)?;
+    let (input, _) = opt(char(' '))(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((
+        input,
+        Header::ProxyAuthorization(auth::AuthHeader(schema, res)),
+    ))
+}
+
+pub fn parse_authentication_info_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = opt(tag("\r\n"))(input)?;
+    let (input, _) = parse_header_name("Authentication-Info")(input)?;
+    let (input, mut res) = parse_auth_header_vars(input)?;
+    let (input, _) = opt(char(' '))(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((
+        input,
+        Header::AuthenticationInfo(auth::AuthenticationInfo {
+            next_nonce: res.remove("nextnonce"),
+            qop: res.remove("qop"),
+            rspauth: res.remove("rspauth"),
+            cnonce: res.remove("cnonce"),
+            nc: res
+                .get("nc")
+                .and_then(|nc| u32::from_str_radix(nc, 16).ok()),
+        }),
+    ))
+}
+
 pub fn parse_key_value_pair<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], (String, String), E> {
@@ -365,16 +569,91 @@ pub fn parse_key_value_pair<'a, E: ParseError<&'a [u8]>>(
     let (input, _) = opt(char(' '))(input)?;
     let (input, key) = map_res(take_while(is_alphanumeric), slice_to_string::<E>)(input)?;
     let (input, _) = opt(char('='))(input)?;
-    let (input, value) = parse_possibly_quoted_string(input)?;
+    let (input, value) = alt((
+        parse_quoted_string,
+        map_res(take_while1(is_token), slice_to_string::<E>),
+    ))(input)?;
     Ok((input, (key, value)))
 }
 
 pub fn parse_auth_schema<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], auth::AuthSchema, E> {
-    Ok(map(tag_no_case("Digest"), |_| auth::AuthSchema::Digest)(
-        input,
-    )?)
+    alt((
+        map(tag_no_case("Digest"), |_| auth::AuthSchema::Digest),
+        map(tag_no_case("Basic"), |_| auth::AuthSchema::Basic),
+        map(tag_no_case("Bearer"), |_| auth::AuthSchema::Bearer),
+    ))(input)
+}
+
+/// Parse the parameters following the authentication schema, either
+/// a single token68, e.g. the Basic or Bearer credentials, stored as
+/// the `token` parameter, or a list of `key=value` pairs.
+/// ([RFC7235: Section 2.1, Challenge and Response](https://tools.ietf.org/html/rfc7235#section-2.1))
+fn parse_auth_params<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], HashMap<String, String>, E> {
+    let (rest, token) = recognize::<_, _, E, _>(pair(
+        take_while1(is_token68),
+        take_while(|chr| chr == b'='),
+    ))(input)
+    .unwrap_or((input, &[]));
+    let ends = rest.is_empty() || rest.starts_with(b"\r\n") || rest.starts_with(b" \r\n");
+    if token.is_empty() || !ends {
+        return parse_auth_header_vars(input);
+    }
+    let mut map = HashMap::new();
+    map.insert(
+        auth::TOKEN_PARAM.to_string(),
+        String::from_utf8_lossy(token).to_string(),
+    );
+    Ok((rest, map))
+}
+
+fn is_token68(chr: u8) -> bool {
+    is_alphanumeric(chr) || b"-._~+/".contains(&chr)
+}
+
+/// Parses "callid" ([RFC3261: Page 222, "callid"](https://tools.ietf.org/html/rfc3261#page-222))
+/// # Examples
+///
+/// ```
+/// use libsip::headers::parse::parse_callid;
+/// use nom::error::VerboseError;
+/// assert_eq!(
+///     parse_callid::<VerboseError<_>>(b"70710@saturn.bell-tel.com, 17320@saturn"),
+///     Ok((", 17320@saturn".as_bytes(), String::from("70710@saturn.bell-tel.com")))
+/// );
+/// ```
+pub fn parse_callid<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], String, E> {
+    map_res(
+        recognize(pair(
+            take_while1(is_word),
+            opt(pair(char('@'), take_while1(is_word))),
+        )),
+        slice_to_string::<E>,
+    )(input)
+}
+
+/// Parses "option-tag" ([RFC3261: Page 223, "option-tag"](https://tools.ietf.org/html/rfc3261#page-223))
+/// # Examples
+///
+/// ```
+/// use libsip::headers::parse::parse_option_tag;
+/// use nom::error::VerboseError;
+/// assert_eq!(
+///     parse_option_tag::<VerboseError<_>>(b"100rel"),
+///     Ok(("".as_bytes(), String::from("100rel")))
+/// );
+/// assert_eq!(
+///     parse_option_tag::<VerboseError<_>>(b"sec-agree, timer"),
+///     Ok((", timer".as_bytes(), String::from("sec-agree")))
+/// );
+/// ```
+pub fn parse_option_tag<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], String, E> {
+    map_res(take_while1(is_token), slice_to_string::<E>)(input)
 }
 
 /// Parses "generic-param" ([RFC3261: Page 227, "generic-param"](https://tools.ietf.org/html/rfc3261#page-227))
@@ -410,7 +689,9 @@ pub fn parse_auth_schema<'a, E: ParseError<&'a [u8]>>(
 pub fn parse_generic_param<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], (String, Option<String>), E> {
+    let (input, _) = opt(take_while(is_space))(input)?;
     let (input, _) = char(';')(input)?;
+    let (input, _) = opt(take_while(is_space))(input)?;
     let (input, name) = map_res(take_while(is_token), slice_to_string_nullable)(input)?;
     let (input, chr) = opt(char('='))(input)?;
     if chr.is_some() {
@@ -421,7 +702,8 @@ pub fn parse_generic_param<'a, E: ParseError<&'a [u8]>>(
     }
 }
 
-/// Parses "gen-value" ([RFC3261: Page 227, "gen-value"](https://tools.ietf.org/html/rfc3261#page-227)), however, parsing host isn't implemented
+/// Parses "gen-value" ([RFC3261: Page 227, "gen-value"](https://tools.ietf.org/html/rfc3261#page-227)).
+/// Hosts are accepted as they are, including IPv6 references.
 /// # Examples
 ///
 /// ```
@@ -432,6 +714,10 @@ pub fn parse_generic_param<'a, E: ParseError<&'a [u8]>>(
 ///     Ok(("".as_bytes(), String::from("30")))
 /// );
 /// assert_eq!(
+///     parse_gen_value::<VerboseError<_>>(b"2001:db8::1"),
+///     Ok(("".as_bytes(), String::from("2001:db8::1")))
+/// );
+/// assert_eq!(
 ///     parse_gen_value::<VerboseError<_>>(b"\"comma,separated,values\""),
 ///     Ok(("".as_bytes(), String::from("comma,separated,values")))
 /// );
@@ -440,10 +726,13 @@ pub fn parse_gen_value<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], String, E> {
     // gen-value = token / host / quoted-string
-    // host isn't parsed yet
+    let is_host_char = |chr| is_token(chr) || b"[]:".contains(&chr);
     let (input, value) = alt::<_, _, E, _>((
         parse_quoted_string::<E>,
-        map_res(take_while::<_, _, E>(is_token), slice_to_string_nullable),
+        map_res(
+            take_while::<_, _, E>(is_host_char),
+            slice_to_string_nullable,
+        ),
     ))(input)?;
     Ok((input, value))
 }
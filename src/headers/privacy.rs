@@ -0,0 +1,106 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    bytes::complete::{tag, take_while, take_while1},
+    character::{complete::char, is_space},
+    combinator::{map_res, opt},
+    error::ParseError,
+    multi::separated_list1,
+    sequence::tuple,
+    IResult,
+};
+
+use std::fmt;
+
+use crate::{
+    headers::{parse::parse_header_name, Header},
+    parse::{is_token, slice_to_string},
+};
+
+/// Privacy value requested by the user agent.
+/// ([RFC3323: Section 4.2, Privacy Header](https://tools.ietf.org/html/rfc3323#section-4.2))
+///
+/// Extension values that don't have a variant are kept as `Other`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PrivacyToken {
+    Id,
+    Header,
+    Session,
+    User,
+    None,
+    Critical,
+    Other(String),
+}
+
+impl PrivacyToken {
+    /// Get the privacy value for a priv-value token, ignoring case.
+    pub fn from_token(token: &str) -> PrivacyToken {
+        match token.to_ascii_lowercase().as_str() {
+            "id" => PrivacyToken::Id,
+            "header" => PrivacyToken::Header,
+            "session" => PrivacyToken::Session,
+            "user" => PrivacyToken::User,
+            "none" => PrivacyToken::None,
+            "critical" => PrivacyToken::Critical,
+            _ => PrivacyToken::Other(token.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for PrivacyToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PrivacyToken::Id => write!(f, "id"),
+            PrivacyToken::Header => write!(f, "header"),
+            PrivacyToken::Session => write!(f, "session"),
+            PrivacyToken::User => write!(f, "user"),
+            PrivacyToken::None => write!(f, "none"),
+            PrivacyToken::Critical => write!(f, "critical"),
+            PrivacyToken::Other(token) => write!(f, "{}", token),
+        }
+    }
+}
+
+/// Parse a single priv-value token.
+pub fn parse_privacy_token<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], PrivacyToken, E> {
+    let (input, token) = map_res(take_while1(is_token), slice_to_string::<E>)(input)?;
+    Ok((input, PrivacyToken::from_token(&token)))
+}
+
+/// Parses Privacy header ([RFC3323: Page 10, "Privacy-hdr"](https://tools.ietf.org/html/rfc3323#page-10))
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     headers::privacy::{parse_privacy_header, PrivacyToken},
+///     Header,
+/// };
+/// use nom::error::VerboseError;
+///
+/// assert_eq!(
+///     parse_privacy_header::<VerboseError<_>>(b"Privacy: id;Critical\r\n"),
+///     Ok((
+///         "".as_bytes(),
+///         Header::Privacy(vec![PrivacyToken::Id, PrivacyToken::Critical])
+///     ))
+/// );
+/// ```
+pub fn parse_privacy_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Privacy")(input)?;
+    let (input, tokens) = separated_list1(
+        tuple((
+            opt(take_while(is_space)),
+            char(';'),
+            opt(take_while(is_space)),
+        )),
+        parse_privacy_token,
+    )(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::Privacy(tokens)))
+}
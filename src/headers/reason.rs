@@ -0,0 +1,179 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    combinator::map_res,
+    error::{ParseError, VerboseError},
+    multi::separated_list1,
+    IResult,
+};
+
+use std::{
+    fmt,
+    io::{Error as IoError, ErrorKind as IoErrorKind},
+    str::FromStr,
+};
+
+use crate::{
+    core::extract_opt_param,
+    headers::{
+        named::parse_named_field_params,
+        parse::{parse_comma_separator, parse_header_name},
+        write::{write_generic_params, write_optional_param, write_quoted_string},
+        GenericParams, Header,
+    },
+    parse::{is_token, slice_to_string},
+};
+
+/// Protocol the cause code of a Reason header value belongs to.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReasonProtocol {
+    /// Cause is a SIP status code.
+    Sip,
+    /// Cause is an ITU-T Q.850 cause value.
+    Q850,
+    Other(String),
+}
+
+impl ReasonProtocol {
+    /// Get the protocol for a protocol token, ignoring case.
+    pub fn from_token(token: &str) -> ReasonProtocol {
+        match token.to_ascii_uppercase().as_str() {
+            "SIP" => ReasonProtocol::Sip,
+            "Q.850" => ReasonProtocol::Q850,
+            _ => ReasonProtocol::Other(token.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ReasonProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReasonProtocol::Sip => write!(f, "SIP"),
+            ReasonProtocol::Q850 => write!(f, "Q.850"),
+            ReasonProtocol::Other(token) => write!(f, "{}", token),
+        }
+    }
+}
+
+/// Value of the Reason header.
+/// ([RFC3326: Section 2, The Reason Header Field](https://tools.ietf.org/html/rfc3326#section-2))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReasonHeader {
+    pub protocol: ReasonProtocol,
+    pub cause: Option<u16>,
+    pub text: Option<String>,
+    pub parameters: GenericParams,
+}
+
+impl ReasonHeader {
+    pub fn new(protocol: ReasonProtocol) -> ReasonHeader {
+        ReasonHeader {
+            protocol,
+            cause: None,
+            text: None,
+            parameters: GenericParams::new(),
+        }
+    }
+
+    /// Create a SIP reason with the given status code.
+    pub fn sip(cause: u16) -> ReasonHeader {
+        ReasonHeader::new(ReasonProtocol::Sip).cause(cause)
+    }
+
+    /// Create a Q.850 reason with the given cause value.
+    pub fn q850(cause: u16) -> ReasonHeader {
+        ReasonHeader::new(ReasonProtocol::Q850).cause(cause)
+    }
+
+    /// Set the `cause` parameter.
+    pub fn cause(mut self, cause: u16) -> Self {
+        self.cause = Some(cause);
+        self
+    }
+
+    /// Set the `text` parameter.
+    pub fn text<S: Into<String>>(mut self, text: S) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+}
+
+impl fmt::Display for ReasonHeader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.protocol)?;
+        write_optional_param("cause", &self.cause, f)?;
+        if let Some(text) = &self.text {
+            write!(f, ";text=")?;
+            write_quoted_string(text, f)?;
+        }
+        write_generic_params(&self.parameters, f)
+    }
+}
+
+/// Parse a Reason value, e.g. the unescaped value of the Reason header
+/// embedded in a History-Info uri.
+impl FromStr for ReasonHeader {
+    type Err = IoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_reason::<VerboseError<&[u8]>>(s.as_bytes()) {
+            Ok((&[], value)) => Ok(value),
+            _ => Err(IoError::new(
+                IoErrorKind::InvalidInput,
+                "Failed to parse Reason value",
+            )),
+        }
+    }
+}
+
+/// Parse a single reason-value: `protocol *(SEMI reason-params)`.
+pub fn parse_reason<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], ReasonHeader, E> {
+    let (input, protocol) = map_res(take_while1(is_token), slice_to_string::<E>)(input)?;
+    let (input, mut parameters) = parse_named_field_params(input)?;
+    let mut cause = None;
+    let mut text = None;
+    extract_opt_param(&mut parameters, "cause", &mut cause);
+    extract_opt_param(&mut parameters, "text", &mut text);
+    Ok((
+        input,
+        ReasonHeader {
+            protocol: ReasonProtocol::from_token(&protocol),
+            cause,
+            text,
+            parameters,
+        },
+    ))
+}
+
+/// Parses Reason header ([RFC3326: Page 3, "Reason"](https://tools.ietf.org/html/rfc3326#page-3))
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     headers::reason::{parse_reason_header, ReasonHeader},
+///     Header,
+/// };
+/// use nom::error::VerboseError;
+///
+/// assert_eq!(
+///     parse_reason_header::<VerboseError<_>>(b"Reason: Q.850;cause=16;text=\"Terminated\"\r\n"),
+///     Ok((
+///         "".as_bytes(),
+///         Header::Reason(vec![ReasonHeader::q850(16).text("Terminated")])
+///     ))
+/// );
+/// ```
+pub fn parse_reason_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Reason")(input)?;
+    let (input, reasons) = separated_list1(parse_comma_separator, parse_reason)(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::Reason(reasons)))
+}
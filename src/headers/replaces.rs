@@ -0,0 +1,153 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    combinator::map_res,
+    error::{ParseError, VerboseError},
+    IResult,
+};
+
+use std::{
+    fmt,
+    io::{Error as IoError, ErrorKind as IoErrorKind},
+    str::FromStr,
+};
+
+use crate::{
+    core::extract_opt_param,
+    headers::{
+        named::parse_named_field_params, parse::parse_header_name, write::write_generic_params,
+        GenericParams, Header,
+    },
+    parse::slice_to_string,
+};
+
+/// Value of the Replaces header, identifying the dialog to be replaced.
+/// ([RFC3891: Section 6.1, Replaces](https://tools.ietf.org/html/rfc3891#section-6.1))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReplacesHeader {
+    pub call_id: String,
+    pub to_tag: String,
+    pub from_tag: String,
+    /// Only replace the dialog if it is still in the early state.
+    pub early_only: bool,
+    pub parameters: GenericParams,
+}
+
+impl ReplacesHeader {
+    pub fn new<C, T, F>(call_id: C, to_tag: T, from_tag: F) -> ReplacesHeader
+    where
+        C: Into<String>,
+        T: Into<String>,
+        F: Into<String>,
+    {
+        ReplacesHeader {
+            call_id: call_id.into(),
+            to_tag: to_tag.into(),
+            from_tag: from_tag.into(),
+            early_only: false,
+            parameters: GenericParams::new(),
+        }
+    }
+
+    /// Set the `early-only` flag.
+    pub fn early_only(mut self) -> Self {
+        self.early_only = true;
+        self
+    }
+}
+
+impl fmt::Display for ReplacesHeader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{};to-tag={};from-tag={}",
+            self.call_id, self.to_tag, self.from_tag
+        )?;
+        if self.early_only {
+            write!(f, ";early-only")?;
+        }
+        write_generic_params(&self.parameters, f)
+    }
+}
+
+/// Parse a Replaces value, e.g. the unescaped value of the Replaces
+/// header embedded in a Refer-To uri.
+impl FromStr for ReplacesHeader {
+    type Err = IoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_replaces::<VerboseError<&[u8]>>(s.as_bytes()) {
+            Ok((&[], value)) => Ok(value),
+            _ => Err(IoError::new(
+                IoErrorKind::InvalidInput,
+                "Failed to parse Replaces value",
+            )),
+        }
+    }
+}
+
+/// Parse a Replaces value: `callid *(SEMI replaces-param)`.
+pub fn parse_replaces<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], ReplacesHeader, E> {
+    let (input, call_id) = map_res(
+        take_while1(|chr: u8| chr.is_ascii_graphic() && chr != b';'),
+        slice_to_string::<E>,
+    )(input)?;
+    let (input, mut parameters) = parse_named_field_params(input)?;
+    let mut to_tag = None;
+    let mut from_tag = None;
+    extract_opt_param(&mut parameters, "to-tag", &mut to_tag);
+    extract_opt_param(&mut parameters, "from-tag", &mut from_tag);
+    let early_only = parameters.remove("early-only").is_some();
+    match (to_tag, from_tag) {
+        (Some(to_tag), Some(from_tag)) => Ok((
+            input,
+            ReplacesHeader {
+                call_id,
+                to_tag,
+                from_tag,
+                early_only,
+                parameters,
+            },
+        )),
+        _ => Err(nom::Err::Error(E::from_error_kind(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+/// Parses Replaces header ([RFC3891: Page 13, "Replaces"](https://tools.ietf.org/html/rfc3891#page-13))
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     headers::replaces::{parse_replaces_header, ReplacesHeader},
+///     Header,
+/// };
+/// use nom::error::VerboseError;
+///
+/// assert_eq!(
+///     parse_replaces_header::<VerboseError<_>>(
+///         b"Replaces: 98732@sip.example.com;from-tag=r33th4x0r;to-tag=ff87ff;early-only\r\n"
+///     ),
+///     Ok((
+///         "".as_bytes(),
+///         Header::Replaces(
+///             ReplacesHeader::new("98732@sip.example.com", "ff87ff", "r33th4x0r").early_only()
+///         )
+///     ))
+/// );
+/// ```
+pub fn parse_replaces_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Replaces")(input)?;
+    let (input, value) = parse_replaces(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::Replaces(value)))
+}
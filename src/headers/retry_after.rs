@@ -0,0 +1,131 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    bytes::complete::{tag, take_until, take_while, take_while1},
+    character::{complete::char, is_digit, is_space},
+    combinator::{map_res, opt},
+    error::ParseError,
+    IResult,
+};
+
+use std::{fmt, time::Duration};
+
+use crate::{
+    headers::{
+        named::parse_named_field_params, parse::parse_header_name, write::write_generic_params,
+        GenericParams, Header,
+    },
+    parse::{parse_u32, slice_to_string_nullable},
+};
+
+/// Value of the Retry-After header.
+/// ([RFC3261: Section 20.33, Retry-After](https://tools.ietf.org/html/rfc3261#section-20.33))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RetryAfter {
+    /// Number of seconds to wait before retrying the request.
+    pub seconds: u32,
+    /// Free form comment, without the enclosing parentheses.
+    pub comment: Option<String>,
+    pub parameters: GenericParams,
+}
+
+impl RetryAfter {
+    pub fn new(seconds: u32) -> RetryAfter {
+        RetryAfter {
+            seconds,
+            comment: None,
+            parameters: GenericParams::new(),
+        }
+    }
+
+    /// Set the comment of this header.
+    pub fn comment<S: Into<String>>(mut self, comment: S) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Adds a parameter with a given name and a given value to `parameters`.
+    pub fn param<N, V>(mut self, name: N, value: Option<V>) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.parameters.insert(name.into(), value.map(Into::into));
+        self
+    }
+
+    /// The time to wait before retrying the request.
+    pub fn retry_in(&self) -> Duration {
+        Duration::from_secs(self.seconds as u64)
+    }
+
+    /// The `duration` parameter, i.e. for how many seconds
+    /// the callee will be available after `seconds` have passed.
+    pub fn duration(&self) -> Option<u32> {
+        self.parameters
+            .get("duration")
+            .and_then(|value| value.as_ref())
+            .and_then(|value| value.parse().ok())
+    }
+}
+
+impl fmt::Display for RetryAfter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.seconds)?;
+        if let Some(comment) = &self.comment {
+            write!(f, " ({})", comment)?;
+        }
+        write_generic_params(&self.parameters, f)
+    }
+}
+
+/// Parses Retry-After header ([RFC3261: Page 181, "Retry-After"](https://tools.ietf.org/html/rfc3261#page-181))
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     headers::retry_after::{parse_retry_after_header, RetryAfter},
+///     Header,
+/// };
+/// use nom::error::VerboseError;
+///
+/// assert_eq!(
+///     parse_retry_after_header::<VerboseError<_>>(b"Retry-After: 120 (busy);duration=3600\r\n"),
+///     Ok((
+///         "".as_bytes(),
+///         Header::RetryAfter(
+///             RetryAfter::new(120)
+///                 .comment("busy")
+///                 .param("duration", Some("3600"))
+///         )
+///     ))
+/// );
+/// ```
+pub fn parse_retry_after_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Retry-After")(input)?;
+    let (input, seconds) = map_res(take_while1(is_digit), parse_u32)(input)?;
+    let (input, _) = opt(take_while(is_space))(input)?;
+    let (input, comment) = opt(parse_comment)(input)?;
+    let (input, _) = opt(take_while(is_space))(input)?;
+    let (input, parameters) = parse_named_field_params(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((
+        input,
+        Header::RetryAfter(RetryAfter {
+            seconds,
+            comment,
+            parameters,
+        }),
+    ))
+}
+
+fn parse_comment<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], String, E> {
+    let (input, _) = char('(')(input)?;
+    let (input, comment) = map_res(take_until(")"), slice_to_string_nullable)(input)?;
+    let (input, _) = char(')')(input)?;
+    Ok((input, comment))
+}
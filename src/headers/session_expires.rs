@@ -0,0 +1,126 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    bytes::complete::{tag, take_while1},
+    character::is_digit,
+    combinator::map_res,
+    error::ParseError,
+    IResult,
+};
+
+use std::fmt;
+
+use crate::{
+    core::extract_opt_param,
+    headers::{
+        named::parse_named_field_params,
+        parse::parse_header_name,
+        write::{write_generic_params, write_optional_param},
+        GenericParams, Header,
+    },
+    parse::parse_u32,
+};
+
+/// The side of the session responsible for sending refresh requests.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Refresher {
+    Uac,
+    Uas,
+}
+
+impl fmt::Display for Refresher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Refresher::Uac => write!(f, "uac"),
+            Refresher::Uas => write!(f, "uas"),
+        }
+    }
+}
+
+impl std::str::FromStr for Refresher {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("uac") {
+            Ok(Refresher::Uac)
+        } else if s.eq_ignore_ascii_case("uas") {
+            Ok(Refresher::Uas)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Value of the Session-Expires header.
+/// ([RFC4028: Section 4, Session-Expires Header Field Definition](https://tools.ietf.org/html/rfc4028#section-4))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SessionExpires {
+    /// The session interval in seconds.
+    pub interval: u32,
+    pub refresher: Option<Refresher>,
+    pub parameters: GenericParams,
+}
+
+impl SessionExpires {
+    pub fn new(interval: u32) -> SessionExpires {
+        SessionExpires {
+            interval,
+            refresher: None,
+            parameters: GenericParams::new(),
+        }
+    }
+
+    /// Set the `refresher` parameter.
+    pub fn refresher(mut self, refresher: Refresher) -> Self {
+        self.refresher = Some(refresher);
+        self
+    }
+}
+
+impl fmt::Display for SessionExpires {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.interval)?;
+        write_optional_param("refresher", &self.refresher, f)?;
+        write_generic_params(&self.parameters, f)
+    }
+}
+
+/// Parses Session-Expires header ([RFC4028: Page 8, "Session-Expires"](https://tools.ietf.org/html/rfc4028#page-8))
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     headers::session_expires::{parse_session_expires_header, Refresher, SessionExpires},
+///     Header,
+/// };
+/// use nom::error::VerboseError;
+///
+/// assert_eq!(
+///     parse_session_expires_header::<VerboseError<_>>(b"Session-Expires: 4000;refresher=uac\r\n"),
+///     Ok((
+///         "".as_bytes(),
+///         Header::SessionExpires(SessionExpires::new(4000).refresher(Refresher::Uac))
+///     ))
+/// );
+/// ```
+pub fn parse_session_expires_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Session-Expires")(input)?;
+    let (input, interval) = map_res(take_while1(is_digit), parse_u32)(input)?;
+    let (input, mut parameters) = parse_named_field_params(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    let mut refresher = None;
+    extract_opt_param(&mut parameters, "refresher", &mut refresher);
+    Ok((
+        input,
+        Header::SessionExpires(SessionExpires {
+            interval,
+            refresher,
+            parameters,
+        }),
+    ))
+}
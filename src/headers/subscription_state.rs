@@ -1,45 +1,49 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::{
     core::extract_opt_param,
     headers::{
         named::parse_named_field_params,
+        parse::parse_header_name,
         write::{write_generic_params, write_optional_param},
-        Header,
+        GenericParams, Header,
     },
     parse::is_token,
 };
 use nom::{
     branch::alt,
-    bytes::complete::{tag, tag_no_case, take_while},
-    character::{complete::char, is_space},
+    bytes::complete::{tag, take_while},
     combinator::{map, opt},
     error::ParseError,
     IResult,
 };
-use std::{collections::HashMap, fmt};
+use std::fmt;
 
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SubscriptionState {
     Active {
         expires: Option<u32>,
-        parameters: HashMap<String, Option<String>>,
+        parameters: GenericParams,
     },
     Pending {
         expires: Option<u32>,
-        parameters: HashMap<String, Option<String>>,
+        parameters: GenericParams,
     },
     Terminated {
         retry_after: Option<u32>,
         reason: Option<String>,
-        parameters: HashMap<String, Option<String>>,
+        parameters: GenericParams,
     },
     Other {
         state: String,
-        parameters: HashMap<String, Option<String>>,
+        parameters: GenericParams,
     },
 }
 
 impl SubscriptionState {
-    pub fn set_params(&mut self, params: HashMap<String, Option<String>>) {
+    pub fn set_params(&mut self, params: GenericParams) {
         match self {
             Self::Active { parameters, .. }
             | Self::Pending { parameters, .. }
@@ -97,10 +101,10 @@ impl fmt::Display for SubscriptionState {
 ///     Header,
 /// };
 /// use nom::error::VerboseError;
-/// use std::collections::HashMap;
+/// use libsip::headers::GenericParams;
 ///
-/// let mut params = HashMap::new();
-/// params.insert(String::from("wow"), None);
+/// let mut params = GenericParams::new();
+/// params.insert("wow", None::<String>);
 /// assert_eq!(
 ///     parse_subscription_state_header::<VerboseError<_>>(
 ///         b"Subscription-State: active;expires=600;wow\r\n"
@@ -145,10 +149,7 @@ pub fn parse_subscription_state_header<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], Header, E> {
     let (input, _) = opt(tag("\r\n"))(input)?;
-    let (input, _) = tag_no_case("Subscription-State")(input)?;
-    let (input, _) = opt(take_while(is_space))(input)?;
-    let (input, _) = char(':')(input)?;
-    let (input, _) = opt(take_while(is_space))(input)?;
+    let (input, _) = parse_header_name("Subscription-State")(input)?;
     let (input, mut state) = parse_subscription_state_without_params(input)?;
     let (input, _) = parse_subscription_state_params(input, &mut state)?;
     let (input, _) = tag("\r\n")(input)?;
@@ -161,21 +162,21 @@ pub fn parse_subscription_state_without_params<'a, E: ParseError<&'a [u8]>>(
     alt::<_, _, E, _>((
         map(tag("active"), |_| SubscriptionState::Active {
             expires: None,
-            parameters: HashMap::new(),
+            parameters: GenericParams::new(),
         }),
         map(tag("pending"), |_| SubscriptionState::Pending {
             expires: None,
-            parameters: HashMap::new(),
+            parameters: GenericParams::new(),
         }),
         map(tag("terminated"), |_| SubscriptionState::Terminated {
             retry_after: None,
             reason: None,
-            parameters: HashMap::new(),
+            parameters: GenericParams::new(),
         }),
         map(take_while(is_token), |state: &[u8]| {
             SubscriptionState::Other {
                 state: String::from_utf8_lossy(state).to_string(),
-                parameters: HashMap::new(),
+                parameters: GenericParams::new(),
             }
         }),
     ))(input)
@@ -1,27 +1,188 @@
-use crate::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-use std::fmt;
+use crate::{
+    headers::{write::write_generic_params, GenericParams},
+    *,
+};
+
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr},
+};
 
 /// Value used in the Via Header.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ViaHeader {
     pub version: Version,
     pub transport: Transport,
-    pub uri: Uri,
+    /// The sent-by host and port of this hop.
+    pub host: Domain,
+    pub parameters: GenericParams,
 }
 
 impl ViaHeader {
-    pub fn new(uri: Uri, transport: Transport) -> ViaHeader {
+    pub fn new(host: Domain, transport: Transport) -> ViaHeader {
         ViaHeader {
             transport,
-            uri,
+            host,
             version: Version::default(),
+            parameters: GenericParams::new(),
         }
     }
+
+    /// Adds a parameter with a given name and a given value to `parameters`.
+    pub fn param<N, V>(mut self, name: N, value: Option<V>) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.set_param(name, value);
+        self
+    }
+
+    /// Adds a parameter with a given name and a given value to `parameters`,
+    /// replacing the previous value if the parameter was already present.
+    pub fn set_param<N, V>(&mut self, name: N, value: Option<V>)
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.parameters.insert(name, value);
+    }
+
+    /// Set the branch parameter of this Via.
+    pub fn branch_param<S: Into<String>>(self, branch: S) -> Self {
+        self.param("branch", Some(branch))
+    }
+
+    /// Retrieve the value of the parameter `name` if it is present and has a value.
+    pub fn param_value(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .get(name)
+            .and_then(|value| value.as_ref().map(String::as_str))
+    }
+
+    /// The transaction branch identifier.
+    pub fn branch(&self) -> Option<&str> {
+        self.param_value("branch")
+    }
+
+    /// The source address recorded by the receiving hop.
+    pub fn received(&self) -> Option<IpAddr> {
+        self.param_value("received")
+            .and_then(|value| value.parse().ok())
+    }
+
+    /// The port recorded by the receiving hop when `rport` was requested.
+    pub fn rport(&self) -> Option<u16> {
+        self.param_value("rport")
+            .and_then(|value| value.parse().ok())
+    }
+
+    /// Determine if this Via contains the `rport` parameter, with or without a value.
+    pub fn has_rport(&self) -> bool {
+        self.parameters.contains_key("rport")
+    }
+
+    /// The multicast time-to-live parameter.
+    pub fn ttl(&self) -> Option<u8> {
+        self.param_value("ttl").and_then(|value| value.parse().ok())
+    }
+
+    /// The sent-by port, or the default port of the transport when none
+    /// is set, see `Transport::default_port`.
+    pub fn port_or_default(&self) -> u16 {
+        self.host
+            .port()
+            .unwrap_or_else(|| self.transport.default_port())
+    }
+
+    /// The multicast address parameter.
+    pub fn maddr(&self) -> Option<&str> {
+        self.param_value("maddr")
+    }
+
+    /// Record the address a request with this Via was received from. The
+    /// `received` parameter is added when it differs from the sent-by host
+    /// or when the sender asked for `rport`, whose value is then filled.
+    /// ([RFC3581: Section 4, Server Behavior](https://tools.ietf.org/html/rfc3581#section-4))
+    pub fn set_received(&mut self, source: SocketAddr) {
+        if self.has_rport() {
+            self.set_param("rport", Some(source.port().to_string()));
+        }
+        if self.has_rport() || host_ip(&self.host) != Some(source.ip()) {
+            self.set_param("received", Some(source.ip().to_string()));
+        }
+    }
+
+    /// The address the responses to a request with this Via are sent to
+    /// over an unreliable transport: the `maddr` on the sent-by port, or
+    /// the `received` address, or else the sent-by host, on the `rport`
+    /// port or else the sent-by port. Returns `None` when the address is
+    /// a domain name that has to be resolved.
+    /// ([RFC3261: Section 18.2.2, Sending Responses](https://tools.ietf.org/html/rfc3261#section-18.2.2))
+    pub fn response_destination(&self) -> Option<SocketAddr> {
+        if let Some(maddr) = self.maddr() {
+            let ip = maddr.trim_start_matches('[').trim_end_matches(']');
+            return Some(SocketAddr::new(ip.parse().ok()?, self.port_or_default()));
+        }
+        let ip = self.received().or_else(|| host_ip(&self.host))?;
+        let port = self.rport().unwrap_or_else(|| self.port_or_default());
+        Some(SocketAddr::new(ip, port))
+    }
+}
+
+impl ViaHeader {
+    /// Write this value without the header name, as it appears in a
+    /// comma separated list of Via values.
+    pub fn fmt_value(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{} {}", self.version, self.transport, self.host)?;
+        write_generic_params(&self.parameters, f)
+    }
 }
 
 impl fmt::Display for ViaHeader {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Via: {}/{} {}", self.version, self.transport, self.uri)
+        let name = if f.alternate() { "v" } else { "Via" };
+        write!(f, "{}: ", name)?;
+        self.fmt_value(f)
+    }
+}
+
+fn host_ip(domain: &Domain) -> Option<IpAddr> {
+    match domain.host() {
+        Host::Ipv4(ip) => Some(ip.into()),
+        Host::Ipv6(ip) => Some(ip.into()),
+        Host::Domain(_) => None,
     }
 }
+
+/// The address the responses to the request `msg` are sent to over an
+/// unreliable transport, see `ViaHeader::response_destination`.
+/// # Examples
+///
+/// ```
+/// use libsip::{response_destination, SipMessage};
+///
+/// let mut invite = SipMessage::parse(
+///     b"INVITE sip:bob@example.com SIP/2.0\r\n\
+///     Via: SIP/2.0/UDP 10.0.0.1:5060;rport;branch=z9hG4bK776asdhds\r\n\r\n",
+/// )
+/// .unwrap();
+/// invite.set_received("192.0.2.1:4000".parse().unwrap());
+/// assert_eq!(Some("192.0.2.1:4000".parse().unwrap()), response_destination(&invite));
+/// ```
+pub fn response_destination(msg: &SipMessage) -> Option<SocketAddr> {
+    msg.top_via()?.response_destination()
+}
+
+/// Generate a random branch for a Via header, starting with the
+/// `z9hG4bK` magic cookie that identifies RFC3261 branches. The rest
+/// of the branch comes from the thread local cryptographically secure
+/// random number generator, so branches are unique across space and time.
+/// ([RFC3261: Section 8.1.1.7, Via](https://tools.ietf.org/html/rfc3261#section-8.1.1.7))
+pub fn generate_branch() -> String {
+    format!("z9hG4bK{:016x}", rand::random::<u64>())
+}
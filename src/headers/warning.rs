@@ -0,0 +1,114 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    bytes::complete::{tag, take_while, take_while1, take_while_m_n},
+    character::{complete::char, is_digit, is_space},
+    combinator::{map_res, opt},
+    error::ParseError,
+    multi::separated_list1,
+    sequence::tuple,
+    IResult,
+};
+
+use std::fmt;
+
+use crate::{
+    headers::{parse::parse_header_name, write::write_quoted_string, Header},
+    parse::{parse_quoted_string, parse_u32, slice_to_string},
+};
+
+/// A single warning carried by the Warning header.
+/// ([RFC3261: Section 20.43, Warning](https://tools.ietf.org/html/rfc3261#section-20.43))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WarningValue {
+    /// The three digit warn-code, e.g. `301` or `399`.
+    pub code: u16,
+    /// The host (or pseudonym) of the element adding the warning.
+    pub agent: String,
+    pub text: String,
+}
+
+impl WarningValue {
+    pub fn new<A, T>(code: u16, agent: A, text: T) -> WarningValue
+    where
+        A: Into<String>,
+        T: Into<String>,
+    {
+        WarningValue {
+            code,
+            agent: agent.into(),
+            text: text.into(),
+        }
+    }
+}
+
+impl fmt::Display for WarningValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:03} {} ", self.code, self.agent)?;
+        write_quoted_string(&self.text, f)
+    }
+}
+
+/// Parse a single warning-value: `warn-code SP warn-agent SP warn-text`.
+pub fn parse_warning_value<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], WarningValue, E> {
+    let (input, code) = map_res(take_while_m_n(3, 3, is_digit), parse_u32)(input)?;
+    let (input, _) = char(' ')(input)?;
+    let (input, agent) = map_res(
+        take_while1(|c| !is_space(c) && c != b'\r' && c != b','),
+        slice_to_string::<E>,
+    )(input)?;
+    let (input, _) = char(' ')(input)?;
+    let (input, text) = parse_quoted_string(input)?;
+    Ok((
+        input,
+        WarningValue {
+            code: code as u16,
+            agent,
+            text,
+        },
+    ))
+}
+
+/// Parses Warning header ([RFC3261: Page 185, "Warning"](https://tools.ietf.org/html/rfc3261#page-185))
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     headers::warning::{parse_warning_header, WarningValue},
+///     Header,
+/// };
+/// use nom::error::VerboseError;
+///
+/// assert_eq!(
+///     parse_warning_header::<VerboseError<_>>(
+///         b"Warning: 370 devnull.example.com \"Choose a bigger pipe\"\r\n"
+///     ),
+///     Ok((
+///         "".as_bytes(),
+///         Header::Warning(vec![WarningValue::new(
+///             370,
+///             "devnull.example.com",
+///             "Choose a bigger pipe"
+///         )])
+///     ))
+/// );
+/// ```
+pub fn parse_warning_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Header, E> {
+    let (input, _) = parse_header_name("Warning")(input)?;
+    let (input, warnings) = separated_list1(
+        tuple((
+            opt(take_while(is_space)),
+            char(','),
+            opt(take_while(is_space)),
+        )),
+        parse_warning_value,
+    )(input)?;
+    let (input, _) = tag("\r\n")(input)?;
+    Ok((input, Header::Warning(warnings)))
+}
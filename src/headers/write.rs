@@ -1,54 +1,89 @@
-use std::{collections::HashMap, fmt};
+use std::fmt;
 
 use super::*;
 
+/// Headers are written using their compact form (if they have one)
+/// when formatted with the alternate flag: `format!("{:#}", header)`.
 impl fmt::Display for Header {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Header::To(value) => write_simple_field("To", value, f),
             Header::From(value) => write_simple_field("From", value, f),
-            Header::Contact(value) => write_simple_field("Contact", value, f),
+            Header::Contact(value) => write_named_list_header("Contact", f, value),
+            Header::ContactWildcard => write_simple_field("Contact", "*", f),
             Header::ReplyTo(value) => write_simple_field("Reply-To", value, f),
+            Header::ReferTo(value) => write_simple_field("Refer-To", value, f),
+            Header::ReferredBy(value) => write_simple_field("Referred-By", value, f),
+            Header::Replaces(value) => write_simple_field("Replaces", value, f),
             Header::CSeq(num, method) => write!(f, "CSeq: {} {}", num, method),
+            Header::RSeq(num) => write!(f, "RSeq: {}", num),
+            Header::SessionExpires(value) => write_simple_field("Session-Expires", value, f),
+            Header::MinSE(num) => write_simple_field("Min-SE", num, f),
+            Header::RAck(rseq, cseq, method) => write!(f, "RAck: {} {} {}", rseq, cseq, method),
             Header::MaxForwards(num) => write!(f, "Max-Forwards: {}", num),
             Header::Expires(num) => write!(f, "Expires: {}", num),
-            Header::Event(value) => write!(f, "Event: {}", value),
-            Header::Accept(methods) => write_method_array_header("Accept", f, methods),
+            Header::Event(event, params) => {
+                write_simple_field("Event", event, f)?;
+                write_generic_params(params, f)
+            },
+            Header::AllowEvents(events) => write_event_array_header("Allow-Events", f, events),
+            Header::Accept(types) => write_content_type_array_header("Accept", f, types),
             Header::Allow(methods) => write_method_array_header("Allow", f, methods),
-            Header::ContentEncoding(ty) => write_simple_field("Content-Encoding", ty, f),
+            Header::ContentEncoding(ty) => write_coding_array_header("Content-Encoding", f, ty),
             Header::ContentLength(len) => write_simple_field("Content-Length", len, f),
             Header::ContentType(ty) => write_simple_field("Content-Type", ty, f),
             Header::UserAgent(agent) => write_simple_field("User-Agent", agent, f),
             Header::CallId(call_id) => write_simple_field("Call-ID", call_id, f),
-            Header::ContentLanguage(lang) => write_simple_field("Content-Language", lang, f),
-            Header::AcceptLanguage(lang) => write_simple_field("Accept-Language", lang, f),
-            Header::AcceptEncoding(ty) => write_simple_field("Accept-Encoding", ty, f),
-            Header::AlertInfo(data) => write_simple_field("Alert-Info", data, f),
-            Header::ErrorInfo(data) => write_simple_field("Error-Info", data, f),
-            Header::AuthenticationInfo(data) => write_simple_field("Authentication-Info", data, f),
+            Header::ContentLanguage(lang) => {
+                write_language_array_header("Content-Language", f, lang)
+            },
+            Header::AcceptLanguage(lang) => write_range_array_header("Accept-Language", f, lang),
+            Header::AcceptEncoding(ty) => {
+                write_weighted_coding_array_header("Accept-Encoding", f, ty)
+            },
+            Header::AlertInfo(data) => write_info_array_header("Alert-Info", f, data),
+            Header::ErrorInfo(data) => write_info_array_header("Error-Info", f, data),
+            Header::AuthenticationInfo(data) => write_auth_header("Authentication-Info", data, f),
             Header::Authorization(data) => write_auth_header("Authorization", data, f),
-            Header::CallInfo(data) => write_simple_field("Call-Info", data, f),
-            Header::InReplyTo(data) => write_simple_field("In-Reply-To", data, f),
+            Header::CallInfo(data) => write_info_array_header("Call-Info", f, data),
+            Header::InReplyTo(data) => write_string_array_header("In-Reply-To", f, data),
             Header::ContentDisposition(data) => write_simple_field("Content-Disposition", data, f),
-            Header::Date(string) => write_simple_field("Date", string, f),
+            Header::Date(date) => write_simple_field("Date", date, f),
             Header::MinExpires(exp) => write_simple_field("Min-Expires", exp, f),
-            Header::MimeVersion(exp) => write_simple_field("MIME-Version", exp, f),
+            Header::FlowTimer(secs) => write_simple_field("Flow-Timer", secs, f),
+            Header::MimeVersion(major, minor) => {
+                write!(f, "{}: {}.{}", header_name("MIME-Version", f), major, minor)
+            },
             Header::Organization(org) => write_simple_field("Organization", org, f),
-            Header::ProxyAuthenticate(data) => write_simple_field("Proxy-Authenticate", data, f),
-            Header::ProxyAuthorization(data) => write_simple_field("Proxy-Authorization", data, f),
-            Header::ProxyRequire(data) => write_simple_field("Proxy-Require", data, f),
-            Header::Require(data) => write_simple_field("Require", data, f),
+            Header::ProxyAuthenticate(data) => write_auth_header("Proxy-Authenticate", data, f),
+            Header::ProxyAuthorization(data) => write_auth_header("Proxy-Authorization", data, f),
+            Header::ProxyRequire(data) => write_string_array_header("Proxy-Require", f, data),
+            Header::Require(data) => write_string_array_header("Require", f, data),
             Header::RetryAfter(data) => write_simple_field("Retry-After", data, f),
-            Header::Route(data) => write_simple_field("Route", data, f),
+            Header::Route(data) => write_name_addr_array_header("Route", f, data),
             Header::Subject(data) => write_simple_field("Subject", data, f),
             Header::SubscriptionState(data) => write_simple_field("Subscription-State", data, f),
-            Header::RecordRoute(data) => write_simple_field("Record-Route", data, f),
+            Header::RecordRoute(data) => write_name_addr_array_header("Record-Route", f, data),
+            Header::Path(data) => write_name_addr_array_header("Path", f, data),
+            Header::ServiceRoute(data) => write_name_addr_array_header("Service-Route", f, data),
+            Header::PAssertedIdentity(data) => {
+                write_name_addr_array_header("P-Asserted-Identity", f, data)
+            },
+            Header::PPreferredIdentity(data) => {
+                write_name_addr_array_header("P-Preferred-Identity", f, data)
+            },
+            Header::Privacy(data) => write_privacy_header(f, data),
+            Header::Reason(data) => write_reason_array_header("Reason", f, data),
+            Header::HistoryInfo(data) => write_history_array_header("History-Info", f, data),
+            Header::Diversion(data) => write_diversion_array_header("Diversion", f, data),
             Header::Server(data) => write_simple_field("Server", data, f),
+            Header::SipETag(data) => write_simple_field("SIP-ETag", data, f),
+            Header::SipIfMatch(data) => write_simple_field("SIP-If-Match", data, f),
             Header::Supported(data) => write_string_array_header("Supported", f, data),
             Header::Timestamp(data) => write_simple_field("Timestamp", data, f),
-            Header::Unsupported(data) => write_simple_field("Unsupported", data, f),
-            Header::Warning(data) => write_simple_field("Warning", data, f),
-            Header::Via(data) => write!(f, "{}", data),
+            Header::Unsupported(data) => write_string_array_header("Unsupported", f, data),
+            Header::Warning(data) => write_warning_array_header("Warning", f, data),
+            Header::Via(data) => write_via_array_header("Via", f, data),
             Header::Priority(data) => write_simple_field("Priority", data, f),
             Header::WwwAuthenticate(data) => write_auth_header("WWW-Authenticate", data, f),
             Header::XFsSendingMessage(data) => write_simple_field("X-FS-Sending-Message", data, f),
@@ -60,7 +95,7 @@ impl fmt::Display for Header {
 macro_rules! write_array_header {
     ($name:ident, $item:ident) => {
         fn $name(name: &str, f: &mut fmt::Formatter, v: &[$item]) -> fmt::Result {
-            write!(f, "{}: ", name)?;
+            write!(f, "{}: ", header_name(name, f))?;
             for (index, item) in v.iter().enumerate() {
                 if index == 0 {
                     write!(f, "{}", item)?;
@@ -75,12 +110,85 @@ macro_rules! write_array_header {
 
 write_array_header!(write_method_array_header, Method);
 write_array_header!(write_string_array_header, String);
+write_array_header!(write_content_type_array_header, ContentType);
+write_array_header!(write_warning_array_header, WarningValue);
+write_array_header!(write_language_array_header, LanguageTag);
+write_array_header!(write_range_array_header, LanguageRange);
+write_array_header!(write_coding_array_header, ContentCoding);
+write_array_header!(write_weighted_coding_array_header, WeightedCoding);
+write_array_header!(write_info_array_header, InfoHeader);
+write_array_header!(write_event_array_header, EventType);
+write_array_header!(write_reason_array_header, ReasonHeader);
+write_array_header!(write_history_array_header, HistoryInfoEntry);
+write_array_header!(write_diversion_array_header, DiversionHeader);
+/// Privacy values are separated by `;` rather than `,`.
+fn write_privacy_header(f: &mut fmt::Formatter, v: &[PrivacyToken]) -> fmt::Result {
+    write!(f, "{}: ", header_name("Privacy", f))?;
+    for (index, item) in v.iter().enumerate() {
+        if index != 0 {
+            write!(f, ";")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+/// Write a comma separated list of NamedHeader values in the name-addr form.
+fn write_name_addr_array_header(
+    name: &str,
+    f: &mut fmt::Formatter,
+    v: &[NamedHeader],
+) -> fmt::Result {
+    write!(f, "{}: ", header_name(name, f))?;
+    for (index, item) in v.iter().enumerate() {
+        if index != 0 {
+            write!(f, ",")?;
+        }
+        item.fmt_name_addr(f)?;
+    }
+    Ok(())
+}
+
+/// Write a comma separated list of NamedHeader values, each in the same
+/// form as a header of its own.
+fn write_named_list_header(name: &str, f: &mut fmt::Formatter, v: &[NamedHeader]) -> fmt::Result {
+    write!(f, "{}: ", header_name(name, f))?;
+    for (index, item) in v.iter().enumerate() {
+        if index != 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+/// Write a comma separated list of Via values.
+fn write_via_array_header(name: &str, f: &mut fmt::Formatter, v: &[via::ViaHeader]) -> fmt::Result {
+    write!(f, "{}: ", header_name(name, f))?;
+    for (index, item) in v.iter().enumerate() {
+        if index != 0 {
+            write!(f, ", ")?;
+        }
+        item.fmt_value(f)?;
+    }
+    Ok(())
+}
+
 fn write_simple_field<D: fmt::Display>(
     header: &str,
     data: D,
     f: &mut fmt::Formatter,
 ) -> fmt::Result {
-    write!(f, "{}: {}", header, data)
+    write!(f, "{}: {}", header_name(header, f), data)
+}
+
+/// Select the compact form of `header` when the alternate flag is set.
+fn header_name<'a>(header: &'a str, f: &fmt::Formatter) -> &'a str {
+    if f.alternate() {
+        compact_form(header).unwrap_or(header)
+    } else {
+        header
+    }
 }
 
 fn write_auth_header<D: fmt::Display>(
@@ -105,15 +213,37 @@ pub fn write_optional_param<V: fmt::Display>(
 }
 
 /// Writes generic parameters, adding ';' before each (including the first one)
-pub fn write_generic_params(
-    params: &HashMap<String, Option<String>>,
-    f: &mut fmt::Formatter,
-) -> fmt::Result {
+pub fn write_generic_params(params: &GenericParams, f: &mut fmt::Formatter) -> fmt::Result {
     for (name, value) in params.iter() {
         write!(f, ";{}", name)?;
         if let Some(value) = value {
-            write!(f, "={}", value)?;
+            write!(f, "=")?;
+            write_param_value(value, f)?;
         }
     }
     Ok(())
 }
+
+/// Writes the value of a parameter as is if it is a token or a host,
+/// otherwise as a quoted string. An empty value isn't a valid token,
+/// it is written as `""`.
+pub fn write_param_value(value: &str, f: &mut fmt::Formatter) -> fmt::Result {
+    let is_plain = |chr: u8| crate::parse::is_token(chr) || b"[]:".contains(&chr);
+    if !value.is_empty() && value.bytes().all(is_plain) {
+        write!(f, "{}", value)
+    } else {
+        write_quoted_string(value, f)
+    }
+}
+
+/// Writes `value` enclosed in double quotes, escaping `"` and `\`.
+pub fn write_quoted_string(value: &str, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "\"")?;
+    for chr in value.chars() {
+        if chr == '"' || chr == '\\' {
+            write!(f, "\\")?;
+        }
+        write!(f, "{}", chr)?;
+    }
+    write!(f, "\"")
+}
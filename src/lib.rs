@@ -20,7 +20,9 @@
 //! ### Creating Messages
 //! This crate provides 2 abstraction's to aid in building sip messages.
 //! The `ResponseGenerator` is used to create sip response's and the
-//! `RequestGenerator` is used to generate sip requests.
+//! `RequestGenerator` is used to generate sip requests. Their `build`
+//! method returns a `BuildError` listing the mandatory headers that are
+//! missing, `build_unchecked` skips that check.
 //!  ```rust
 //!     extern crate libsip;
 //!     extern crate nom;
@@ -33,14 +35,14 @@
 //!
 //!     let _res = ResponseGenerator::new()
 //!                         .code(200)
-//!                         .build()
+//!                         .build_unchecked()
 //!                         .unwrap();
 //!
 //!     let uri = parse_uri::<VerboseError<&[u8]>>("sip:1@0.0.0.0:5060;transport=UDP".as_ref()).unwrap().1;
 //!     let _req = RequestGenerator::new()
 //!                         .method(Method::Invite)
 //!                         .uri(uri)
-//!                         .build()
+//!                         .build_unchecked()
 //!                         .unwrap();
 //!  ```
 //!
@@ -50,36 +52,70 @@
 //! it to the `set_challenge` method of the RegistrationManager.
 //! reqpeatedly calling the `get_request` method will cause the c_nonce
 //! counter to be incremented and a new hash computed.
+//! Alternatively every response can be given to `handle_response`, which
+//! answers challenges, tracks the registration state and tells when the
+//! registration has to be refreshed.
+//!
+//! ### Serde
+//! With the `serde` feature, messages and every header, uri and session
+//! description they contain implement `Serialize` and `Deserialize`,
+//! e.g. to log them as JSON or store them.
+//!
+//! ### Property Testing
+//! With the `arbitrary` feature, `Uri`, `Header`, `Headers` and
+//! `SipMessage` implement `quickcheck::Arbitrary`, generating values
+//! that are parsed back unchanged once written.
 
 #[macro_use]
 extern crate nom;
+#[cfg(feature = "serde")]
 extern crate serde;
 
 #[macro_use]
 mod macros;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+mod build;
 mod client;
 pub mod core;
 pub mod headers;
+#[cfg(any(
+    feature = "transport-udp",
+    feature = "transport-tcp",
+    feature = "transport-ws"
+))]
+pub mod net;
 pub mod parse;
+pub mod proxy;
 mod request;
+pub mod resolve;
 mod response;
+pub mod sdp;
+pub mod transaction;
 pub mod uri;
 
 pub use crate::{
+    build::BuildError,
     client::{
-        HeaderWriteConfig, InviteHelper, MessageHelper, MessageWriter, RegistrationManager,
-        SoftPhone,
+        options_response, parse_sipfrag, refer_to, route_target, uac_route_set, uas_route_set,
+        Authenticator, Dialog, DialogState, DtmfRelay, FlowKeepAlive, HeaderWriteConfig,
+        InviteHelper, MessageHelper, MessageWriter, PublicationManager, PublicationStatus,
+        RegistrationManager, RegistrationState, RouteTarget, SessionTimer, SoftPhone,
+        SubscriptionManager, SubscriptionStatus, TransferProgress, SUPPORTED_EXTENSIONS,
     },
     core::{
-        parse_message, parse_request, parse_response, parse_version, Method, SipMessage, Transport,
-        Version,
+        parse_message, parse_message_borrowed, parse_request, parse_response, parse_version,
+        Method, ParseConfig, ParseError, PreservedMessage, SipMessage, SipMessageRef, SipParser,
+        StatusCode, Transport, Version,
     },
     headers::{
-        parse_header, via::ViaHeader, AuthContext, AuthHeader, AuthSchema, ContentType, Header,
-        Headers, Language, NamedHeader,
+        generate_branch, generate_instance_id, generate_tag, instance_id_from_seed, parse_header,
+        response_destination, via::ViaHeader, AuthContext, AuthHeader, AuthSchema, ContentType,
+        DateHeader, GenericParams, Header, Headers, Language, NamedHeader,
     },
     request::RequestGenerator,
     response::ResponseGenerator,
-    uri::{parse_uri, Domain, Uri, UriAuth, UriParam, UriSchema},
+    uri::{parse_uri, Domain, Host, Uri, UriAuth, UriBuilder, UriParam, UriSchema},
 };
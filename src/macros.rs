@@ -71,14 +71,14 @@ macro_rules! named_header {
         libsip::NamedHeader {
             display_name: None,
             uri: $u,
-            parameters: ::std::collections::HashMap::new(),
+            parameters: libsip::headers::GenericParams::new(),
         }
     };
     ($u:tt, $name:tt) => {
         libsip::NamedHeader {
             display_name: Some($name.into()),
             uri: $u,
-            parameters: ::std::collections::HashMap::new(),
+            parameters: libsip::headers::GenericParams::new(),
         }
     };
 }
@@ -89,7 +89,7 @@ macro_rules! __named_header {
         crate::NamedHeader {
             display_name: None,
             uri: $u,
-            parameters: ::std::collections::HashMap::new(),
+            parameters: crate::headers::GenericParams::new(),
         }
     };
 }
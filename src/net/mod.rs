@@ -0,0 +1,21 @@
+//! Transports sending and receiving `SipMessage`s over the network.
+//! Every transport is behind its own feature: `transport-udp` for
+//! `UdpTransport` and `transport-tcp` for `StreamTransport`, both built
+//! on tokio. `transport-tls` adds TLS connections to `StreamTransport`,
+//! using rustls. `transport-ws` provides `WsFramer`, framing messages
+//! over a WebSocket connection.
+
+#[cfg(feature = "transport-tcp")]
+pub mod stream;
+#[cfg(feature = "transport-tcp")]
+pub use self::stream::{ConnectionKey, StreamEvent, StreamTransport};
+
+#[cfg(feature = "transport-udp")]
+pub mod udp;
+#[cfg(feature = "transport-udp")]
+pub use self::udp::{UdpTransport, UDP_MTU_LIMIT};
+
+#[cfg(feature = "transport-ws")]
+pub mod ws;
+#[cfg(feature = "transport-ws")]
+pub use self::ws::{WsFrame, WsFramer, WS_PROTOCOL};
@@ -0,0 +1,349 @@
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+};
+
+#[cfg(feature = "transport-tls")]
+use tokio_rustls::{rustls::ClientConfig, webpki::DNSNameRef, TlsAcceptor, TlsConnector};
+
+#[cfg(feature = "transport-tls")]
+use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+};
+
+use crate::core::{SipMessage, SipParser, Transport};
+
+/// Identifies a connection of a `StreamTransport` by the remote host,
+/// port and transport, so that messages to the same destination reuse
+/// the same connection.
+/// ([RFC3261: Section 18.1.1, Sending Requests](https://tools.ietf.org/html/rfc3261#section-18.1.1))
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ConnectionKey {
+    pub host: String,
+    pub port: u16,
+    pub transport: Transport,
+}
+
+impl ConnectionKey {
+    pub fn new<S: Into<String>>(host: S, port: u16, transport: Transport) -> ConnectionKey {
+        ConnectionKey {
+            host: host.into(),
+            port,
+            transport,
+        }
+    }
+}
+
+/// Something that happened on a connection of a `StreamTransport`.
+#[derive(Debug)]
+pub enum StreamEvent {
+    /// A complete message was received.
+    Message(Box<SipMessage>, ConnectionKey),
+    /// The data received could not be parsed as a message. The invalid
+    /// message was skipped and the connection is still usable.
+    Invalid(ConnectionKey, IoError),
+    /// Empty lines were received between messages, e.g. the pong
+    /// answering a keep-alive ping, see `FlowKeepAlive`.
+    KeepAlive(ConnectionKey),
+    /// The peer closed its side of the connection. Messages can still be
+    /// sent on it, e.g. the responses to the requests received, until
+    /// it is closed with `close`.
+    HalfClosed(ConnectionKey),
+    /// The connection is closed, with the error that caused it if any,
+    /// and is no longer used to send messages. Connections are closed
+    /// when the data received can't be framed into messages anymore,
+    /// see `SipParser::next_message`.
+    Closed(ConnectionKey, Option<IoError>),
+}
+
+/// Sends and receives SIP messages over stream connections, framing
+/// them with a `SipParser`.
+///
+/// Connections are reused for every message sent to the same
+/// `ConnectionKey`. A TCP connection is opened by `send` when none
+/// exists, as is a TLS connection with the `transport-tls` feature once
+/// the client configuration is set with `set_tls_config`. Connections
+/// of other transports, as well as connections accepted on other
+/// listeners than the ones given to `accept` or `accept_tls`, have to
+/// be given to `add_stream`.
+///
+/// Every connection has a queue of messages to write, so that sending
+/// never waits for the peer to read. Received messages and the closing
+/// of connections are returned by `recv`.
+/// # Examples
+///
+/// ```no_run
+/// use libsip::{net::{ConnectionKey, StreamEvent, StreamTransport}, SipMessage, Transport};
+///
+/// # async fn run(request: SipMessage) -> std::io::Result<()> {
+/// let mut transport = StreamTransport::new();
+/// let key = ConnectionKey::new("192.0.2.4", 5060, Transport::Tcp);
+/// transport.send(&request, &key).await?;
+/// if let StreamEvent::Message(response, _) = transport.recv().await {
+///     println!("{}", response);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct StreamTransport {
+    connections: HashMap<ConnectionKey, (u64, UnboundedSender<Outgoing>)>,
+    next_id: u64,
+    events_tx: UnboundedSender<(u64, StreamEvent)>,
+    events: UnboundedReceiver<(u64, StreamEvent)>,
+    #[cfg(feature = "transport-tls")]
+    tls: Option<TlsConnector>,
+}
+
+#[derive(Debug)]
+enum Outgoing {
+    Data(Vec<u8>),
+    Close,
+}
+
+impl StreamTransport {
+    /// Create a transport without connections.
+    pub fn new() -> StreamTransport {
+        let (events_tx, events) = unbounded_channel();
+        StreamTransport {
+            connections: HashMap::new(),
+            next_id: 0,
+            events_tx,
+            events,
+            #[cfg(feature = "transport-tls")]
+            tls: None,
+        }
+    }
+
+    /// Use `config` for the TLS connections opened by `send`. The server
+    /// certificate is checked against the host of the `ConnectionKey`,
+    /// which has to be a domain name.
+    /// ([RFC3261: Section 26.3.1, Transport and Network Layer Security](https://tools.ietf.org/html/rfc3261#section-26.3.1))
+    #[cfg(feature = "transport-tls")]
+    pub fn set_tls_config(&mut self, config: Arc<ClientConfig>) {
+        self.tls = Some(TlsConnector::from(config));
+    }
+
+    /// Determine if a connection to `key` is open.
+    pub fn is_connected(&self, key: &ConnectionKey) -> bool {
+        self.connections.contains_key(key)
+    }
+
+    /// Use `stream` for the connection to `key`, e.g. a TLS stream or a
+    /// connection accepted by the application, replacing the previous
+    /// connection to `key` if any.
+    pub fn add_stream<S>(&mut self, key: ConnectionKey, stream: S)
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let id = self.next_id;
+        self.next_id += 1;
+        let (reader, writer) = tokio::io::split(stream);
+        let (queue, pending) = unbounded_channel();
+        tokio::spawn(read_loop(reader, id, key.clone(), self.events_tx.clone()));
+        tokio::spawn(write_loop(
+            writer,
+            pending,
+            id,
+            key.clone(),
+            self.events_tx.clone(),
+        ));
+        self.connections.insert(key, (id, queue));
+    }
+
+    /// Accept a TCP connection on `listener`, returning the key its
+    /// messages are received with.
+    pub async fn accept(&mut self, listener: &mut TcpListener) -> IoResult<ConnectionKey> {
+        let (stream, peer) = listener.accept().await?;
+        let key = ConnectionKey::new(peer.ip().to_string(), peer.port(), Transport::Tcp);
+        self.add_stream(key.clone(), stream);
+        Ok(key)
+    }
+
+    /// Accept a TCP connection on `listener` and complete the TLS
+    /// handshake with `acceptor`, returning the key its messages are
+    /// received with.
+    #[cfg(feature = "transport-tls")]
+    pub async fn accept_tls(
+        &mut self,
+        listener: &mut TcpListener,
+        acceptor: &TlsAcceptor,
+    ) -> IoResult<ConnectionKey> {
+        let (stream, peer) = listener.accept().await?;
+        let stream = acceptor.accept(stream).await?;
+        let key = ConnectionKey::new(peer.ip().to_string(), peer.port(), Transport::Tls);
+        self.add_stream(key.clone(), stream);
+        Ok(key)
+    }
+
+    /// Queue `msg` to be written on the connection to `key`, opening it
+    /// first if `key` is a TCP destination without connection, or a TLS
+    /// one when the TLS configuration is set. Fails with an error of
+    /// kind `NotConnected` for other transports.
+    pub async fn send(&mut self, msg: &SipMessage, key: &ConnectionKey) -> IoResult<()> {
+        if !self.connections.contains_key(key) {
+            self.connect(key).await?;
+        }
+        let (_, queue) = &self.connections[key];
+        queue
+            .send(Outgoing::Data(msg.to_bytes()))
+            .map_err(|_| not_connected(key))
+    }
+
+    async fn connect(&mut self, key: &ConnectionKey) -> IoResult<()> {
+        match key.transport {
+            Transport::Tcp => {
+                let stream = TcpStream::connect((key.host.as_str(), key.port)).await?;
+                self.add_stream(key.clone(), stream);
+            },
+            #[cfg(feature = "transport-tls")]
+            Transport::Tls => {
+                let connector = self.tls.clone().ok_or_else(|| not_connected(key))?;
+                let name = DNSNameRef::try_from_ascii_str(&key.host).map_err(|_| {
+                    IoError::new(
+                        IoErrorKind::InvalidInput,
+                        format!("{} is not a valid TLS server name", key.host),
+                    )
+                })?;
+                let stream = TcpStream::connect((key.host.as_str(), key.port)).await?;
+                let stream = connector.connect(name, stream).await?;
+                self.add_stream(key.clone(), stream);
+            },
+            _ => return Err(not_connected(key)),
+        }
+        Ok(())
+    }
+
+    /// Close the connection to `key` once the messages queued are written,
+    /// by shutting down its sending side. Messages the peer sends before
+    /// closing its own side are still received.
+    pub fn close(&mut self, key: &ConnectionKey) {
+        if let Some((_, queue)) = self.connections.remove(key) {
+            let _ = queue.send(Outgoing::Close);
+        }
+    }
+
+    /// Wait for the next event of any connection.
+    pub async fn recv(&mut self) -> StreamEvent {
+        loop {
+            let (id, event) = match self.events.recv().await {
+                Some(event) => event,
+                None => unreachable!("the transport holds a sender"),
+            };
+            match event {
+                StreamEvent::HalfClosed(key) if !self.is_current(&key, id) => {
+                    // The peer finished closing a connection closed with
+                    // `close`, the one of a replaced connection is ignored.
+                    if self.connections.contains_key(&key) {
+                        continue;
+                    }
+                    return StreamEvent::Closed(key, None);
+                },
+                StreamEvent::Closed(key, error) => {
+                    if !self.is_current(&key, id) {
+                        continue;
+                    }
+                    self.connections.remove(&key);
+                    return StreamEvent::Closed(key, error);
+                },
+                event => return event,
+            }
+        }
+    }
+
+    /// Determine if the connection `id` is still the one used for `key`,
+    /// events of connections closed or replaced since are outdated.
+    fn is_current(&self, key: &ConnectionKey, id: u64) -> bool {
+        self.connections.get(key).map(|(current, _)| *current) == Some(id)
+    }
+}
+
+impl fmt::Debug for StreamTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StreamTransport")
+            .field("connections", &self.connections.keys())
+            .field("next_id", &self.next_id)
+            .finish()
+    }
+}
+
+impl Default for StreamTransport {
+    fn default() -> StreamTransport {
+        StreamTransport::new()
+    }
+}
+
+async fn read_loop<R: AsyncRead + Unpin>(
+    mut reader: R,
+    id: u64,
+    key: ConnectionKey,
+    events: UnboundedSender<(u64, StreamEvent)>,
+) {
+    let mut parser = SipParser::new();
+    let mut buffer = vec![0; 65535];
+    loop {
+        let amt = match reader.read(&mut buffer).await {
+            Ok(0) => {
+                let _ = events.send((id, StreamEvent::HalfClosed(key)));
+                return;
+            },
+            Ok(amt) => amt,
+            Err(e) => {
+                let _ = events.send((id, StreamEvent::Closed(key, Some(e))));
+                return;
+            },
+        };
+        parser.feed(&buffer[..amt]);
+        loop {
+            let result = parser.next_message();
+            if parser.take_keep_alive() {
+                let _ = events.send((id, StreamEvent::KeepAlive(key.clone())));
+            }
+            let event = match result {
+                Ok(Some(msg)) => StreamEvent::Message(Box::new(msg), key.clone()),
+                Ok(None) => break,
+                Err(e) if e.kind() == IoErrorKind::ConnectionAborted => {
+                    let _ = events.send((id, StreamEvent::Closed(key, Some(e))));
+                    return;
+                },
+                Err(e) => StreamEvent::Invalid(key.clone(), e),
+            };
+            let _ = events.send((id, event));
+        }
+    }
+}
+
+async fn write_loop<W: AsyncWrite + Unpin>(
+    mut writer: W,
+    mut pending: UnboundedReceiver<Outgoing>,
+    id: u64,
+    key: ConnectionKey,
+    events: UnboundedSender<(u64, StreamEvent)>,
+) {
+    while let Some(outgoing) = pending.recv().await {
+        let result = match outgoing {
+            Outgoing::Data(data) => writer.write_all(&data).await,
+            Outgoing::Close => {
+                let _ = writer.shutdown().await;
+                return;
+            },
+        };
+        if let Err(e) = result {
+            let _ = events.send((id, StreamEvent::Closed(key, Some(e))));
+            return;
+        }
+    }
+}
+
+fn not_connected(key: &ConnectionKey) -> IoError {
+    IoError::new(
+        IoErrorKind::NotConnected,
+        format!(
+            "no {} connection to {}:{}",
+            key.transport, key.host, key.port
+        ),
+    )
+}
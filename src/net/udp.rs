@@ -0,0 +1,109 @@
+use tokio::net::{ToSocketAddrs, UdpSocket};
+
+use std::{
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+    net::SocketAddr,
+};
+
+use crate::core::{parse_packet, ParsedPacket, SipMessage};
+
+/// The size above which a request must not be sent over UDP when the
+/// MTU of the path is unknown.
+/// ([RFC3261: Section 18.1.1, Sending Requests](https://tools.ietf.org/html/rfc3261#section-18.1.1))
+pub const UDP_MTU_LIMIT: usize = 1300;
+
+/// Sends and receives SIP messages as UDP datagrams.
+///
+/// Requests larger than `UDP_MTU_LIMIT` are refused by `send_to` with
+/// an error of kind `InvalidInput`, they have to be sent over TCP
+/// instead. Responses are sent whatever their size. Received datagrams
+/// are returned with their source address, to be recorded on received
+/// requests with `SipMessage::set_received`. Messages are sent with
+/// compact header names after `set_compact_headers(true)`.
+/// # Examples
+///
+/// ```no_run
+/// use libsip::{net::UdpTransport, core::ParsedPacket};
+///
+/// # async fn run() -> std::io::Result<()> {
+/// let mut transport = UdpTransport::bind("0.0.0.0:5060").await?;
+/// loop {
+///     if let (ParsedPacket::Sip(msg), source) = transport.recv_from().await? {
+///         println!("{} from {}", msg, source);
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct UdpTransport {
+    socket: UdpSocket,
+    buffer: Vec<u8>,
+    compact_headers: bool,
+}
+
+impl UdpTransport {
+    /// Create a transport listening on `addr`.
+    pub async fn bind<A: ToSocketAddrs>(addr: A) -> IoResult<UdpTransport> {
+        Ok(UdpTransport::from_socket(UdpSocket::bind(addr).await?))
+    }
+
+    /// Create a transport on an already bound `socket`.
+    pub fn from_socket(socket: UdpSocket) -> UdpTransport {
+        UdpTransport {
+            socket,
+            buffer: vec![0; 65535],
+            compact_headers: false,
+        }
+    }
+
+    /// Send messages with the compact form of the header names to keep
+    /// datagrams small, see `SipMessage::write_compact_to`.
+    pub fn set_compact_headers(&mut self, compact: bool) {
+        self.compact_headers = compact;
+    }
+
+    /// The address the transport is listening on.
+    pub fn local_addr(&self) -> IoResult<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Determine if `msg` is a request too large to be sent over UDP,
+    /// i.e. that has to be sent over a congestion controlled transport
+    /// such as TCP.
+    pub fn requires_tcp(msg: &SipMessage) -> bool {
+        msg.is_request() && msg.to_bytes().len() > UDP_MTU_LIMIT
+    }
+
+    /// Send `msg` in a single datagram to `target`. Fails without sending
+    /// anything if `msg` is a request larger than `UDP_MTU_LIMIT`.
+    pub async fn send_to(&mut self, msg: &SipMessage, target: SocketAddr) -> IoResult<()> {
+        let data = if self.compact_headers {
+            msg.to_compact_bytes()
+        } else {
+            msg.to_bytes()
+        };
+        if msg.is_request() && data.len() > UDP_MTU_LIMIT {
+            return Err(IoError::new(
+                IoErrorKind::InvalidInput,
+                format!(
+                    "request of {} bytes exceeds the UDP limit of {} bytes, send it over TCP",
+                    data.len(),
+                    UDP_MTU_LIMIT
+                ),
+            ));
+        }
+        self.socket.send_to(&data, target).await?;
+        Ok(())
+    }
+
+    /// Receive the next datagram, returning it with its source address.
+    /// Datagrams that are neither a keep-alive, STUN nor a valid SIP
+    /// message result in an error of kind `InvalidData`, after which
+    /// the transport can still be used.
+    pub async fn recv_from(&mut self) -> IoResult<(ParsedPacket, SocketAddr)> {
+        let (amt, source) = self.socket.recv_from(&mut self.buffer).await?;
+        let packet = parse_packet(&self.buffer[..amt])
+            .map_err(|e| IoError::new(IoErrorKind::InvalidData, e))?;
+        Ok((packet, source))
+    }
+}
@@ -0,0 +1,312 @@
+use sha::{
+    sha1::Sha1,
+    utils::{Digest, DigestExt},
+};
+
+use std::{
+    convert::{TryFrom, TryInto},
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+};
+
+use crate::core::{stream::DEFAULT_MAX_MESSAGE_SIZE, SipMessage};
+
+/// The WebSocket subprotocol of SIP, negotiated with the
+/// `Sec-WebSocket-Protocol` header of the opening handshake.
+pub const WS_PROTOCOL: &str = "sip";
+
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xa;
+
+/// Compute the `Sec-WebSocket-Accept` value answering the
+/// `Sec-WebSocket-Key` of an opening handshake.
+/// ([RFC6455: Section 4.2.2, Sending the Server's Opening Handshake](https://tools.ietf.org/html/rfc6455#section-4.2.2))
+/// # Examples
+///
+/// ```
+/// use libsip::net::ws::accept_key;
+///
+/// assert_eq!("s3pPLMBiTxaQ9kYGzzhZRbK+xOo=", accept_key("dGhlIHNhbXBsZSBub25jZQ=="));
+/// ```
+pub fn accept_key(key: &str) -> String {
+    let digest = Sha1::default()
+        .digest(format!("{}{}", key.trim(), ACCEPT_GUID).as_bytes())
+        .to_bytes();
+    base64::encode(digest)
+}
+
+/// A WebSocket message received by a `WsFramer`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum WsFrame {
+    /// A text or binary message, carrying a single SIP message.
+    Sip(Box<SipMessage>),
+    /// A ping, to be answered with `WsFramer::encode_pong`.
+    Ping(Vec<u8>),
+    Pong(Vec<u8>),
+    /// The peer is closing the connection, with its status code if any.
+    Close(Option<u16>),
+}
+
+/// Frames SIP messages over an established WebSocket connection, once
+/// the opening handshake negotiated the `sip` subprotocol.
+/// ([RFC7118: Section 5, SIP Encoding](https://tools.ietf.org/html/rfc7118#section-5))
+///
+/// Every SIP message is carried in its own WebSocket message, text when
+/// it is valid UTF-8 and binary otherwise. Received data is added with
+/// `feed` and taken out, possibly reassembled from fragments, with
+/// `next_frame`. The framer doesn't perform any I/O, so it can be used
+/// with whatever stream carries the connection. Messages longer than the
+/// maximum message size, 65535 bytes by default, are rejected.
+/// # Examples
+///
+/// ```
+/// use libsip::{net::ws::{WsFrame, WsFramer}, SipMessage};
+///
+/// let msg = SipMessage::parse(b"SIP/2.0 200 OK\r\n\r\n").unwrap();
+/// let mut server = WsFramer::server();
+/// server.feed(&WsFramer::client().encode(&msg));
+/// assert_eq!(Some(WsFrame::Sip(Box::new(msg))), server.next_frame().unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct WsFramer {
+    masked: bool,
+    buffer: Vec<u8>,
+    fragments: Vec<u8>,
+    /// Whether a message was started by a frame without the FIN bit.
+    fragmented: bool,
+    max_message_size: usize,
+}
+
+impl Default for WsFramer {
+    fn default() -> WsFramer {
+        WsFramer {
+            masked: false,
+            buffer: vec![],
+            fragments: vec![],
+            fragmented: false,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+        }
+    }
+}
+
+impl WsFramer {
+    /// Create the framer of a client, masking the frames it encodes and
+    /// expecting the frames of the server to be unmasked.
+    pub fn client() -> WsFramer {
+        WsFramer {
+            masked: true,
+            ..Default::default()
+        }
+    }
+
+    /// Create the framer of a server, whose frames aren't masked, while
+    /// the frames of the client have to be.
+    pub fn server() -> WsFramer {
+        WsFramer::default()
+    }
+
+    /// Set the size limit of a message, and of every frame.
+    pub fn set_max_message_size(&mut self, size: usize) {
+        self.max_message_size = size;
+    }
+
+    /// Encode `msg` in a single frame.
+    pub fn encode(&self, msg: &SipMessage) -> Vec<u8> {
+        let data = msg.to_bytes();
+        let opcode = if std::str::from_utf8(&data).is_ok() {
+            OPCODE_TEXT
+        } else {
+            OPCODE_BINARY
+        };
+        self.encode_frame(opcode, &data)
+    }
+
+    /// Encode the pong answering a ping with `payload`.
+    pub fn encode_pong(&self, payload: &[u8]) -> Vec<u8> {
+        self.encode_frame(OPCODE_PONG, payload)
+    }
+
+    /// Encode a close frame with the status `code`, e.g. 1000 for
+    /// a normal closure.
+    pub fn encode_close(&self, code: u16) -> Vec<u8> {
+        self.encode_frame(OPCODE_CLOSE, &code.to_be_bytes())
+    }
+
+    fn encode_frame(&self, opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask_bit = if self.masked { 0x80 } else { 0 };
+        let mut frame = vec![0x80 | opcode];
+        match payload.len() {
+            len if len < 126 => frame.push(mask_bit | len as u8),
+            len if len <= usize::from(u16::MAX) => {
+                frame.push(mask_bit | 126);
+                frame.extend_from_slice(&(len as u16).to_be_bytes());
+            },
+            len => {
+                frame.push(mask_bit | 127);
+                frame.extend_from_slice(&(len as u64).to_be_bytes());
+            },
+        }
+        if self.masked {
+            let key = rand::random::<[u8; 4]>();
+            frame.extend_from_slice(&key);
+            frame.extend(apply_mask(key, payload));
+        } else {
+            frame.extend_from_slice(payload);
+        }
+        frame
+    }
+
+    /// Add received data to the end of the buffer.
+    pub fn feed(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Take the next complete message out of the buffer. Returns `Ok(None)`
+    /// when more data is needed. Fails with an error of kind `InvalidData`
+    /// if a message isn't a valid SIP message or uses an unknown opcode.
+    ///
+    /// A frame with an invalid length, a message longer than the maximum
+    /// message size, or frames breaking the protocol also fail with an
+    /// error of kind `InvalidData`: frames of the client that aren't masked
+    /// and frames of the server that are, continuation frames that don't
+    /// continue a message and new messages started before the previous one
+    /// is complete. The buffer is cleared as the following frames can't be
+    /// found or trusted anymore, and the connection has to be closed.
+    /// ([RFC6455: Section 5.1, Overview](https://tools.ietf.org/html/rfc6455#section-5.1))
+    pub fn next_frame(&mut self) -> IoResult<Option<WsFrame>> {
+        loop {
+            let (fin, opcode, payload) = match self.take_frame()? {
+                Some(frame) => frame,
+                None => return Ok(None),
+            };
+            match opcode {
+                OPCODE_CONTINUATION | OPCODE_TEXT | OPCODE_BINARY => {
+                    if opcode == OPCODE_CONTINUATION && !self.fragmented {
+                        return Err(self.protocol_error(
+                            "WebSocket continuation frame without a message to continue",
+                        ));
+                    }
+                    if opcode != OPCODE_CONTINUATION && self.fragmented {
+                        return Err(self.protocol_error(
+                            "WebSocket message started before the previous one is complete",
+                        ));
+                    }
+                    if self.fragments.len() + payload.len() > self.max_message_size {
+                        return Err(self.protocol_error("WebSocket message is too long"));
+                    }
+                    self.fragments.extend_from_slice(&payload);
+                    self.fragmented = !fin;
+                    if !fin {
+                        continue;
+                    }
+                    let data = std::mem::take(&mut self.fragments);
+                    return SipMessage::parse(&data)
+                        .map(|msg| Some(WsFrame::Sip(Box::new(msg))))
+                        .map_err(|e| IoError::new(IoErrorKind::InvalidData, e));
+                },
+                OPCODE_CLOSE => {
+                    let code = payload
+                        .get(..2)
+                        .map(|code| u16::from_be_bytes([code[0], code[1]]));
+                    return Ok(Some(WsFrame::Close(code)));
+                },
+                OPCODE_PING => return Ok(Some(WsFrame::Ping(payload))),
+                OPCODE_PONG => return Ok(Some(WsFrame::Pong(payload))),
+                _ => {
+                    return Err(IoError::new(
+                        IoErrorKind::InvalidData,
+                        format!("unknown WebSocket opcode {}", opcode),
+                    ))
+                },
+            }
+        }
+    }
+
+    /// Remove the first complete frame from the buffer, returning its
+    /// FIN bit, opcode and unmasked payload. A 64 bit length must have its
+    /// most significant bit unset, and only the frames of the client are
+    /// masked.
+    /// ([RFC6455: Section 5.2, Base Framing Protocol](https://tools.ietf.org/html/rfc6455#section-5.2))
+    fn take_frame(&mut self) -> IoResult<Option<(bool, u8, Vec<u8>)>> {
+        let header = match self.buffer.get(..2) {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        let fin = header[0] & 0x80 != 0;
+        let opcode = header[0] & 0x0f;
+        let masked = header[1] & 0x80 != 0;
+        if masked == self.masked {
+            return Err(if masked {
+                self.protocol_error("WebSocket frame of the server is masked")
+            } else {
+                self.protocol_error("WebSocket frame of the client isn't masked")
+            });
+        }
+        let (len, mut pos) = match header[1] & 0x7f {
+            126 => match self.buffer.get(2..4).and_then(|len| len.try_into().ok()) {
+                Some(len) => (u64::from(u16::from_be_bytes(len)), 4),
+                None => return Ok(None),
+            },
+            127 => match self.buffer.get(2..10).and_then(|len| len.try_into().ok()) {
+                Some(len) => (u64::from_be_bytes(len), 10),
+                None => return Ok(None),
+            },
+            len => (u64::from(len), 2),
+        };
+        if len & (1 << 63) != 0 {
+            return Err(self.protocol_error("WebSocket frame length has its top bit set"));
+        }
+        let len = match usize::try_from(len) {
+            Ok(len) if len <= self.max_message_size => len,
+            _ => return Err(self.protocol_error("WebSocket frame is too long")),
+        };
+        let key = if masked {
+            match self
+                .buffer
+                .get(pos..pos + 4)
+                .and_then(|key| key.try_into().ok())
+            {
+                Some(key) => {
+                    pos += 4;
+                    Some(key)
+                },
+                None => return Ok(None),
+            }
+        } else {
+            None
+        };
+        let end = match pos.checked_add(len) {
+            Some(end) => end,
+            None => return Err(self.protocol_error("WebSocket frame is too long")),
+        };
+        let payload = match self.buffer.get(pos..end) {
+            Some(payload) => payload,
+            None => return Ok(None),
+        };
+        let payload = match key {
+            Some(key) => apply_mask(key, payload).collect(),
+            None => payload.to_vec(),
+        };
+        self.buffer.drain(..end);
+        Ok(Some((fin, opcode, payload)))
+    }
+
+    fn protocol_error(&mut self, error: &str) -> IoError {
+        self.buffer.clear();
+        self.fragments.clear();
+        self.fragmented = false;
+        IoError::new(IoErrorKind::InvalidData, error)
+    }
+}
+
+fn apply_mask(key: [u8; 4], payload: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    payload
+        .iter()
+        .enumerate()
+        .map(move |(i, byte)| byte ^ key[i % 4])
+}
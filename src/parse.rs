@@ -1,6 +1,6 @@
 use nom::{
     branch::alt,
-    bytes::complete::{take_until, take_while},
+    bytes::complete::take_while,
     character::{complete::char as parse_char, *},
     combinator::map_res,
     error::{ErrorKind, ParseError},
@@ -17,27 +17,26 @@ pub fn slice_to_string<'a, E: ParseError<&'a [u8]>>(slice: &'a [u8]) -> Result<S
     if slice.is_empty() {
         Err(E::from_error_kind(slice, ErrorKind::Eof))
     } else {
-        Ok(String::from_utf8(Vec::from(slice))
-            .map_err(|_| E::from_error_kind(slice, ErrorKind::IsNot))?)
+        String::from_utf8(Vec::from(slice)).map_err(|_| E::from_error_kind(slice, ErrorKind::IsNot))
     }
 }
 
 pub fn slice_to_string_nullable(slice: &[u8]) -> Result<String, IoError> {
-    Ok(String::from_utf8(Vec::from(slice))
-        .map_err(|_| IoError::new(IoErrorKind::InvalidInput, "Failed to parse utf8 string"))?)
+    String::from_utf8(Vec::from(slice))
+        .map_err(|_| IoError::new(IoErrorKind::InvalidInput, "Failed to parse utf8 string"))
 }
 
 /// Parse unsigned 16 bit integer using `Parse::parse`.
 pub fn parse_u16<'a, E: ParseError<&'a [u8]>>(slice: &'a [u8]) -> Result<u16, E> {
-    Ok(::std::str::from_utf8(slice)
+    ::std::str::from_utf8(slice)
         .map_err(|_| E::from_error_kind(slice, ErrorKind::IsNot))?
         .parse()
-        .map_err(|_| E::from_error_kind(slice, ErrorKind::IsNot))?)
+        .map_err(|_| E::from_error_kind(slice, ErrorKind::IsNot))
 }
 
 /// Parse unsigned 8 bit integer using `Parse::parse`.
 pub fn parse_u8(slice: &[u8]) -> Result<u8, IoError> {
-    Ok(::std::str::from_utf8(slice)
+    ::std::str::from_utf8(slice)
         .map_err(|_| {
             IoError::new(
                 IoErrorKind::InvalidInput,
@@ -45,12 +44,12 @@ pub fn parse_u8(slice: &[u8]) -> Result<u8, IoError> {
             )
         })?
         .parse()
-        .map_err(|_| IoError::new(IoErrorKind::InvalidInput, "Failed to parse u8 integer"))?)
+        .map_err(|_| IoError::new(IoErrorKind::InvalidInput, "Failed to parse u8 integer"))
 }
 
 /// Parse unsigned 32 bit integer using `Parse::parse`.
 pub fn parse_u32(slice: &[u8]) -> Result<u32, IoError> {
-    Ok(::std::str::from_utf8(slice)
+    ::std::str::from_utf8(slice)
         .map_err(|_| {
             IoError::new(
                 IoErrorKind::InvalidInput,
@@ -58,15 +57,28 @@ pub fn parse_u32(slice: &[u8]) -> Result<u32, IoError> {
             )
         })?
         .parse()
-        .map_err(|_| IoError::new(IoErrorKind::InvalidInput, "Failed to parse u32 integer"))?)
+        .map_err(|_| IoError::new(IoErrorKind::InvalidInput, "Failed to parse u32 integer"))
+}
+
+/// Parse unsigned 64 bit integer using `Parse::parse`.
+pub fn parse_u64(slice: &[u8]) -> Result<u64, IoError> {
+    ::std::str::from_utf8(slice)
+        .map_err(|_| {
+            IoError::new(
+                IoErrorKind::InvalidInput,
+                "Failed to parse utf8 u64 integer",
+            )
+        })?
+        .parse()
+        .map_err(|_| IoError::new(IoErrorKind::InvalidInput, "Failed to parse u64 integer"))
 }
 
 /// Parse input as an f32 using `Parse::parse`.
 pub fn parse_f32(slice: &[u8]) -> Result<f32, IoError> {
-    Ok(::std::str::from_utf8(slice)
+    ::std::str::from_utf8(slice)
         .map_err(|_| IoError::new(IoErrorKind::InvalidInput, "Failed to parse utf8 f32"))?
         .parse()
-        .map_err(|_| IoError::new(IoErrorKind::InvalidInput, "Failed to parse f32"))?)
+        .map_err(|_| IoError::new(IoErrorKind::InvalidInput, "Failed to parse f32"))
 }
 
 /// Parse Input as a vector of bytes.
@@ -78,7 +90,7 @@ pub fn parse_byte_vec<'a, E: ParseError<&'a [u8]>>(
 
 pub fn parse_ip_address<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
-) -> IResult<&[u8], Ipv4Addr, E> {
+) -> IResult<&'a [u8], Ipv4Addr, E> {
     let (input, byte1) = map_res(take_while(is_digit), parse_u8)(input)?;
     let (input, _) = parse_char('.')(input)?;
     let (input, byte2) = map_res(take_while(is_digit), parse_u8)(input)?;
@@ -100,12 +112,42 @@ pub fn parse_possibly_quoted_string<'a, E: ParseError<&'a [u8]>>(
     alt::<_, _, E, _>(alts)(input)
 }
 
+/// Parses "quoted-string" ([RFC3261: Page 222, "quoted-string"](https://tools.ietf.org/html/rfc3261#page-222)),
+/// replacing every quoted-pair (e.g. `\"`) with the character it escapes.
+/// # Examples
+///
+/// ```
+/// use libsip::parse::parse_quoted_string;
+/// use nom::error::VerboseError;
+/// assert_eq!(
+///     parse_quoted_string::<VerboseError<_>>(b"\"say \\\"hello\\\" \\\\o/\";x"),
+///     Ok((";x".as_bytes(), String::from("say \"hello\" \\o/")))
+/// );
+/// ```
 pub fn parse_quoted_string<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], String, E> {
-    let (input, _) = parse_char('"')(input)?;
-    let (input, out) = map_res(take_until("\""), slice_to_string_nullable)(input)?;
-    let (input, _) = parse_char('"')(input)?;
+    let (mut input, _) = parse_char('"')(input)?;
+    let mut out = vec![];
+    loop {
+        match input {
+            [b'"', rest @ ..] => {
+                input = rest;
+                break;
+            },
+            [b'\\', chr, rest @ ..] => {
+                out.push(*chr);
+                input = rest;
+            },
+            [chr, rest @ ..] if *chr != b'\\' => {
+                out.push(*chr);
+                input = rest;
+            },
+            _ => return Err(nom::Err::Error(E::from_error_kind(input, ErrorKind::Char))),
+        }
+    }
+    let out = slice_to_string_nullable(&out)
+        .map_err(|_| nom::Err::Error(E::from_error_kind(input, ErrorKind::MapRes)))?;
     Ok((input, out))
 }
 
@@ -121,3 +163,17 @@ pub fn parse_quoted_string<'a, E: ParseError<&'a [u8]>>(
 pub fn is_token(chr: u8) -> bool {
     is_alphanumeric(chr) || "-.!%*_+`'~".contains(char::from(chr))
 }
+
+/// Checks if a given character can be part of a word ([RFC3261: Page 221, "word"](https://tools.ietf.org/html/rfc3261#page-221))
+/// # Examples
+///
+/// ```
+/// use libsip::parse::is_word;
+/// assert!(is_word('a' as u8));
+/// assert!(is_word('<' as u8));
+/// assert!(!is_word('@' as u8));
+/// assert!(!is_word(',' as u8));
+/// ```
+pub fn is_word(chr: u8) -> bool {
+    is_token(chr) || "()<>:\\\"/[]?{}".contains(char::from(chr))
+}
@@ -0,0 +1,166 @@
+//! The mechanical parts of forwarding messages as a stateless proxy.
+//! ([RFC3261: Section 16, Proxy Behavior](https://tools.ietf.org/html/rfc3261#section-16))
+//!
+//! A forwarded request has its Max-Forwards decremented with
+//! `decrement_max_forwards`, the proxy's own Via pushed with `push_via`
+//! and, for the proxy to stay on the path of the dialog, a Record-Route
+//! added with `add_record_route`. The responses are forwarded after
+//! removing that Via with `pop_via`.
+
+use std::io::Result as IoResult;
+
+use crate::{
+    transaction::key::MAGIC_COOKIE, Header, NamedHeader, ResponseGenerator, SipMessage, StatusCode,
+    Uri, ViaHeader,
+};
+
+/// The Max-Forwards added to requests without one.
+pub const DEFAULT_MAX_FORWARDS: u32 = 70;
+
+/// Decrement the Max-Forwards of `request` before forwarding it, adding
+/// one of 70 when it has none. Returns the 483 (Too Many Hops) response
+/// to send back instead of forwarding the request when it is already 0.
+/// ([RFC3261: Section 16.3, Request Validation](https://tools.ietf.org/html/rfc3261#section-16.3))
+/// # Examples
+///
+/// ```
+/// use libsip::{headers::HeaderKind, proxy::decrement_max_forwards, Header, SipMessage};
+///
+/// let mut request = SipMessage::parse(
+///     b"OPTIONS sip:bob@example.com SIP/2.0\r\n\
+///     Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+///     Max-Forwards: 1\r\n\
+///     To: <sip:bob@example.com>\r\n\
+///     From: <sip:alice@example.com>;tag=1928301774\r\n\
+///     Call-ID: a84b4c76e66710\r\n\
+///     CSeq: 1 OPTIONS\r\n\r\n",
+/// )
+/// .unwrap();
+/// assert_eq!(None, decrement_max_forwards(&mut request).unwrap());
+/// assert_eq!(Some(&Header::MaxForwards(0)), request.header(HeaderKind::MaxForwards));
+///
+/// let response = decrement_max_forwards(&mut request).unwrap().unwrap();
+/// assert_eq!(Some(483), response.status_code());
+/// ```
+pub fn decrement_max_forwards(request: &mut SipMessage) -> IoResult<Option<SipMessage>> {
+    let max_forwards = request
+        .headers_mut()
+        .0
+        .iter_mut()
+        .find_map(|header| match header {
+            Header::MaxForwards(value) => Some(value),
+            _ => None,
+        });
+    match max_forwards {
+        Some(0) => Ok(Some(
+            ResponseGenerator::reply_to(request, StatusCode::TOO_MANY_HOPS).build()?,
+        )),
+        Some(value) => {
+            *value -= 1;
+            Ok(None)
+        },
+        None => {
+            request
+                .headers_mut()
+                .push(Header::MaxForwards(DEFAULT_MAX_FORWARDS));
+            Ok(None)
+        },
+    }
+}
+
+/// Insert `via`, representing the proxy, above the Via headers of a
+/// forwarded `request`. When `via` has no branch it is given the one
+/// computed by `stateless_branch`, so `push_via` has to be called once
+/// the Request-URI of the forwarded request is set.
+/// ([RFC3261: Section 16.6, Request Forwarding](https://tools.ietf.org/html/rfc3261#section-16.6))
+pub fn push_via(request: &mut SipMessage, mut via: ViaHeader) {
+    if via.branch().is_none() {
+        via.set_param("branch", Some(stateless_branch(request)));
+    }
+    insert_first(request, Header::Via(vec![via]), |header| {
+        matches!(header, Header::Via(_))
+    });
+}
+
+/// Compute the branch of the Via a stateless proxy adds to `request`.
+/// The same branch is computed for the retransmissions of a request, and
+/// for the CANCEL and the ACK of a non-2xx response matching an INVITE,
+/// which a stateless proxy can't tell apart from new requests otherwise.
+///
+/// When the topmost Via has an RFC3261 branch, the branch is a hash of
+/// that branch, its sent-by and the Request-URI, which differs when a
+/// request spirals back with a new Request-URI. Otherwise it is a hash of
+/// the Request-URI, topmost Via, To tag, From tag, Call-ID and CSeq number.
+/// ([RFC3261: Section 16.11, Stateless Proxy](https://tools.ietf.org/html/rfc3261#section-16.11))
+pub fn stateless_branch(request: &SipMessage) -> String {
+    let uri = request.uri().map(Uri::to_string).unwrap_or_default();
+    let top_via = request.top_via();
+    let data = match top_via.and_then(ViaHeader::branch) {
+        Some(branch) if branch.starts_with(MAGIC_COOKIE) => {
+            let sent_by = top_via.map(|via| via.host.to_string());
+            format!("{}\n{}\n{}", branch, sent_by.unwrap_or_default(), uri)
+        },
+        _ => format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            uri,
+            top_via.map(ViaHeader::to_string).unwrap_or_default(),
+            request.to().and_then(NamedHeader::tag).unwrap_or_default(),
+            request
+                .from()
+                .and_then(NamedHeader::tag)
+                .unwrap_or_default(),
+            request.call_id().map(String::as_str).unwrap_or_default(),
+            request.cseq().map(|(seq, _)| seq).unwrap_or_default(),
+        ),
+    };
+    format!("{}{:x}", MAGIC_COOKIE, md5::compute(data))
+}
+
+/// Remove the topmost Via of a `response`, the one of the proxy the
+/// response was received by, returning it. The response is forwarded to
+/// the sender of the Via left on top, and is discarded when there is none.
+/// When the topmost Via header lists several values only the first one
+/// is removed.
+/// ([RFC3261: Section 16.7, Response Processing](https://tools.ietf.org/html/rfc3261#section-16.7))
+pub fn pop_via(response: &mut SipMessage) -> Option<ViaHeader> {
+    let headers = &mut response.headers_mut().0;
+    let index = headers.iter().position(|header| match header {
+        Header::Via(vias) => !vias.is_empty(),
+        _ => false,
+    })?;
+    let via = match &mut headers[index] {
+        Header::Via(vias) => vias.remove(0),
+        _ => return None,
+    };
+    if let Header::Via(vias) = &headers[index] {
+        if vias.is_empty() {
+            headers.remove(index);
+        }
+    }
+    Some(via)
+}
+
+/// Insert a Record-Route of `uri` above the Record-Route headers of
+/// a forwarded `request`, with the `lr` parameter so that the requests
+/// of the dialog are loose routed through the proxy.
+/// ([RFC3261: Section 16.6, Request Forwarding](https://tools.ietf.org/html/rfc3261#section-16.6))
+pub fn add_record_route(request: &mut SipMessage, uri: Uri) {
+    let uri = if uri.is_loose_route() {
+        uri
+    } else {
+        uri.lr_param()
+    };
+    insert_first(
+        request,
+        Header::RecordRoute(vec![NamedHeader::new(uri)]),
+        |header| matches!(header, Header::RecordRoute(_)),
+    );
+}
+
+/// Insert `header` before the first header matching `same`, or at the
+/// end of the headers when there is none.
+fn insert_first<F: Fn(&Header) -> bool>(msg: &mut SipMessage, header: Header, same: F) {
+    let headers = &mut msg.headers_mut().0;
+    let index = headers.iter().position(same).unwrap_or(headers.len());
+    headers.insert(index, header);
+}
@@ -1,4 +1,8 @@
-use crate::*;
+use crate::{
+    build::{BuildError, REQUEST_HEADERS},
+    headers::HeaderKind,
+    *,
+};
 
 use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
 
@@ -13,6 +17,8 @@ pub struct RequestGenerator {
     version: Version,
     headers: Headers,
     body: Vec<u8>,
+    default_via: Option<ViaHeader>,
+    suppress_content_length: bool,
 }
 
 impl RequestGenerator {
@@ -24,6 +30,8 @@ impl RequestGenerator {
             version: Version::default(),
             headers: Headers::new(),
             body: vec![],
+            default_via: None,
+            suppress_content_length: false,
         }
     }
 
@@ -70,28 +78,192 @@ impl RequestGenerator {
         self
     }
 
-    /// Build the sip request.
-    pub fn build(self) -> IoResult<SipMessage> {
-        if let Some(method) = self.method {
-            if let Some(uri) = self.uri {
-                Ok(SipMessage::Request {
-                    method,
-                    uri,
-                    version: self.version,
-                    headers: self.headers,
-                    body: self.body,
-                })
-            } else {
-                Err(IoError::new(
-                    IoErrorKind::InvalidInput,
-                    "`uri` method call required",
-                ))
+    /// Create a generator for the CANCEL of the INVITE `invite`, with the
+    /// same Request-URI, Call-ID, From, To, Route headers and CSeq number
+    /// and a single Via header equal to the topmost Via of the INVITE.
+    /// ([RFC3261: Section 9.1, Client Behavior](https://tools.ietf.org/html/rfc3261#section-9.1))
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::*;
+    ///
+    /// let invite = SipMessage::parse(
+    ///     b"INVITE sip:bob@example.com SIP/2.0\r\n\
+    ///     Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+    ///     From: <sip:alice@example.com>;tag=1928301774\r\n\
+    ///     To: <sip:bob@example.com>\r\n\
+    ///     Call-ID: a84b4c76e66710\r\n\
+    ///     CSeq: 314159 INVITE\r\n\r\n",
+    /// )
+    /// .unwrap();
+    /// let cancel = RequestGenerator::cancel_of(&invite).unwrap().build().unwrap();
+    /// assert_eq!(Some(&Method::Cancel), cancel.method());
+    /// assert_eq!(Some((314159, &Method::Cancel)), cancel.cseq());
+    /// assert_eq!(invite.top_via(), cancel.top_via());
+    /// ```
+    pub fn cancel_of(invite: &SipMessage) -> IoResult<RequestGenerator> {
+        let missing = |name: &str| {
+            IoError::new(
+                IoErrorKind::InvalidInput,
+                format!("INVITE doesn't contain a {} header", name),
+            )
+        };
+        let uri = invite
+            .uri()
+            .ok_or_else(|| IoError::new(IoErrorKind::InvalidInput, "Expected a SIP request"))?;
+        let via = invite.top_via().ok_or_else(|| missing("Via"))?;
+        let from = invite.from().ok_or_else(|| missing("From"))?;
+        let to = invite.to().ok_or_else(|| missing("To"))?;
+        let call_id = invite.call_id().ok_or_else(|| missing("Call-ID"))?;
+        let (seq, _) = invite.cseq().ok_or_else(|| missing("CSeq"))?;
+
+        let mut generator = RequestGenerator::new()
+            .method(Method::Cancel)
+            .uri(uri.clone())
+            .header(Header::Via(vec![via.clone()]))
+            .header(Header::MaxForwards(70))
+            .header(Header::From(from.clone()))
+            .header(Header::To(to.clone()))
+            .header(Header::CallId(call_id.clone()))
+            .header(Header::CSeq(seq, Method::Cancel));
+        for header in invite.headers().iter() {
+            if let Header::Route(_) = header {
+                generator = generator.header(header.clone());
             }
-        } else {
-            Err(IoError::new(
+        }
+        Ok(generator.header(Header::ContentLength(0)))
+    }
+
+    /// Create a generator for the ACK of the final `response` to the
+    /// INVITE `invite`.
+    ///
+    /// The ACK of a non-2xx response is part of the INVITE transaction:
+    /// it is sent to the Request-URI of the INVITE with its topmost Via
+    /// and Route headers. ([RFC3261: Section 17.1.1.3, Construction of the ACK Request](https://tools.ietf.org/html/rfc3261#section-17.1.1.3))
+    ///
+    /// The ACK of a 2xx response is a new transaction within the dialog,
+    /// with a new branch. It is sent to the Contact of the response using
+    /// the route set from its Record-Route headers, in reverse order, the
+    /// same way as the other requests of the dialog, see `route_target`.
+    /// ([RFC3261: Section 13.2.2.4, 2xx Responses](https://tools.ietf.org/html/rfc3261#section-13.2.2.4))
+    pub fn ack_of(invite: &SipMessage, response: &SipMessage) -> IoResult<RequestGenerator> {
+        let missing = |name: &str| {
+            IoError::new(
+                IoErrorKind::InvalidInput,
+                format!("message doesn't contain a {} header", name),
+            )
+        };
+        let invite_uri = invite
+            .uri()
+            .ok_or_else(|| IoError::new(IoErrorKind::InvalidInput, "Expected a SIP request"))?;
+        let status = response
+            .status()
+            .ok_or_else(|| IoError::new(IoErrorKind::InvalidInput, "Expected a SIP response"))?;
+        if !status.is_final() {
+            return Err(IoError::new(
                 IoErrorKind::InvalidInput,
-                "`method` method call required",
-            ))
+                "Provisional responses are not acknowledged",
+            ));
+        }
+        let via = invite.top_via().ok_or_else(|| missing("Via"))?;
+        let from = invite.from().ok_or_else(|| missing("From"))?;
+        let to = response.to().ok_or_else(|| missing("To"))?;
+        let call_id = invite.call_id().ok_or_else(|| missing("Call-ID"))?;
+        let (seq, _) = invite.cseq().ok_or_else(|| missing("CSeq"))?;
+
+        let (uri, via, routes) = if status.is_success() {
+            let contact = response
+                .headers()
+                .iter()
+                .find_map(|header| match header {
+                    Header::Contact(contacts) => {
+                        contacts.first().map(|contact| contact.uri.clone())
+                    },
+                    _ => None,
+                })
+                .ok_or_else(|| missing("Contact"))?;
+            let RouteTarget { uri, routes, .. } = route_target(&uac_route_set(response), &contact);
+            let via =
+                ViaHeader::new(via.host.clone(), via.transport).branch_param(generate_branch());
+            (uri, via, routes)
+        } else {
+            (
+                invite_uri.clone(),
+                via.clone(),
+                invite.headers().route_set(),
+            )
+        };
+
+        let mut generator = RequestGenerator::new()
+            .method(Method::Ack)
+            .uri(uri)
+            .header(Header::Via(vec![via]))
+            .header(Header::MaxForwards(70))
+            .header(Header::From(from.clone()))
+            .header(Header::To(to.clone()))
+            .header(Header::CallId(call_id.clone()))
+            .header(Header::CSeq(seq, Method::Ack));
+        if !routes.is_empty() {
+            generator = generator.header(Header::Route(routes));
+        }
+        Ok(generator.header(Header::ContentLength(0)))
+    }
+
+    /// Set the Via inserted as the topmost header, with a new branch
+    /// from `generate_branch`, when no Via header is given.
+    pub fn default_via(mut self, via: ViaHeader) -> RequestGenerator {
+        self.default_via = Some(via);
+        self
+    }
+
+    /// Don't set the Content-Length header when `build` is called,
+    /// leaving the headers as they were given.
+    pub fn suppress_content_length(mut self) -> RequestGenerator {
+        self.suppress_content_length = true;
+        self
+    }
+
+    /// Add `header` to the request header list if it is `Some`, leaving
+    /// missing mandatory headers to be reported by `build`.
+    pub fn header_opt(mut self, header: Option<Header>) -> RequestGenerator {
+        if let Some(header) = header {
+            self.headers.push(header);
+        }
+        self
+    }
+
+    /// Build the sip request, checking that the Via, Max-Forwards, From,
+    /// To, Call-ID and CSeq headers are present. The Content-Length header
+    /// is set to the length of the body unless `suppress_content_length`
+    /// was called.
+    pub fn build(mut self) -> Result<SipMessage, BuildError> {
+        if !self.suppress_content_length {
+            let length = Header::ContentLength(self.body.len() as u32);
+            self.headers.replace(HeaderKind::ContentLength, length);
+        }
+        let msg = self.build_unchecked()?;
+        BuildError::check_headers(msg.headers(), &REQUEST_HEADERS)?;
+        Ok(msg)
+    }
+
+    /// Build the sip request without checking its headers, e.g. for
+    /// tests or requests that are completed later on. The `default_via`
+    /// is still inserted.
+    pub fn build_unchecked(mut self) -> Result<SipMessage, BuildError> {
+        let method = self.method.ok_or(BuildError::MissingMethod)?;
+        let uri = self.uri.ok_or(BuildError::MissingUri)?;
+        if let Some(via) = self.default_via {
+            if self.headers.via().is_none() {
+                let via = via.branch_param(generate_branch());
+                self.headers.0.insert(0, Header::Via(vec![via]));
+            }
         }
+        Ok(SipMessage::Request {
+            method,
+            uri,
+            version: self.version,
+            headers: self.headers,
+            body: self.body,
+        })
     }
 }
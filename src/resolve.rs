@@ -0,0 +1,347 @@
+//! Locating the servers a request to a URI is sent to.
+//! ([RFC3263: Locating SIP Servers](https://tools.ietf.org/html/rfc3263))
+//!
+//! `resolve` selects the transport from the NAPTR records of the domain,
+//! the port and hosts from its SRV records and the addresses from the
+//! A/AAAA records of these hosts, returning every target in the order
+//! they have to be tried. The DNS queries are made through a `DnsBackend`,
+//! `StaticDns` answering them from records given in advance and
+//! `SystemDns` with the address lookup of the standard library.
+
+use std::{
+    collections::HashMap,
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+    net::{IpAddr, SocketAddr, ToSocketAddrs},
+};
+
+use crate::{
+    core::Transport,
+    uri::{Host, Uri, UriSchema, DEFAULT_SIPS_PORT},
+};
+
+/// A NAPTR record, mapping a domain to the SRV name of a service.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NaptrRecord {
+    pub order: u16,
+    pub preference: u16,
+    /// `s` when the replacement is the name of SRV records.
+    pub flags: String,
+    /// The service, e.g. `SIP+D2U` for SIP over UDP.
+    pub service: String,
+    pub replacement: String,
+}
+
+/// An SRV record, giving the host and port of a service.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SrvRecord {
+    pub priority: u16,
+    pub weight: u16,
+    pub port: u16,
+    /// The host providing the service, `.` when it isn't available.
+    pub target: String,
+}
+
+/// A server to send a request to.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Target {
+    pub transport: Transport,
+    pub addr: SocketAddr,
+}
+
+/// Performs the DNS queries needed by `resolve`. Names without records
+/// result in an empty list, errors abort the resolution.
+pub trait DnsBackend {
+    /// Query the NAPTR records of `name`.
+    fn naptr(&self, name: &str) -> IoResult<Vec<NaptrRecord>>;
+
+    /// Query the SRV records of `name`, e.g. `_sip._udp.example.com`.
+    fn srv(&self, name: &str) -> IoResult<Vec<SrvRecord>>;
+
+    /// Query the A and AAAA records of `host`.
+    fn lookup_ip(&self, host: &str) -> IoResult<Vec<IpAddr>>;
+}
+
+/// A `DnsBackend` answering from the records it was given, e.g. to
+/// configure the servers of a domain without DNS.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct StaticDns {
+    naptr: HashMap<String, Vec<NaptrRecord>>,
+    srv: HashMap<String, Vec<SrvRecord>>,
+    ip: HashMap<String, Vec<IpAddr>>,
+}
+
+impl StaticDns {
+    pub fn new() -> StaticDns {
+        StaticDns::default()
+    }
+
+    /// Add a NAPTR record to `name`.
+    pub fn add_naptr<S: Into<String>>(mut self, name: S, record: NaptrRecord) -> Self {
+        self.naptr.entry(name.into()).or_default().push(record);
+        self
+    }
+
+    /// Add an SRV record to `name`.
+    pub fn add_srv<S: Into<String>>(mut self, name: S, record: SrvRecord) -> Self {
+        self.srv.entry(name.into()).or_default().push(record);
+        self
+    }
+
+    /// Add an A or AAAA record to `host`.
+    pub fn add_ip<S: Into<String>>(mut self, host: S, ip: IpAddr) -> Self {
+        self.ip.entry(host.into()).or_default().push(ip);
+        self
+    }
+}
+
+impl DnsBackend for StaticDns {
+    fn naptr(&self, name: &str) -> IoResult<Vec<NaptrRecord>> {
+        Ok(self.naptr.get(name).cloned().unwrap_or_default())
+    }
+
+    fn srv(&self, name: &str) -> IoResult<Vec<SrvRecord>> {
+        Ok(self.srv.get(name).cloned().unwrap_or_default())
+    }
+
+    fn lookup_ip(&self, host: &str) -> IoResult<Vec<IpAddr>> {
+        Ok(self.ip.get(host).cloned().unwrap_or_default())
+    }
+}
+
+/// A `DnsBackend` using the resolver of the system through the standard
+/// library, which only looks up addresses: domains are treated as having
+/// no NAPTR and SRV records.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct SystemDns;
+
+impl DnsBackend for SystemDns {
+    fn naptr(&self, _name: &str) -> IoResult<Vec<NaptrRecord>> {
+        Ok(vec![])
+    }
+
+    fn srv(&self, _name: &str) -> IoResult<Vec<SrvRecord>> {
+        Ok(vec![])
+    }
+
+    fn lookup_ip(&self, host: &str) -> IoResult<Vec<IpAddr>> {
+        Ok((host, 0).to_socket_addrs()?.map(|addr| addr.ip()).collect())
+    }
+}
+
+/// Determine the targets a request to `uri` is sent to, in the order
+/// they have to be tried when the previous ones fail. The host of the
+/// `maddr` parameter is used instead of the one of `uri` if present.
+///
+/// - With a numeric host, it is the only target.
+/// - With a port, the addresses of the host are used with that port.
+/// - With a `transport` parameter, the SRV records of that transport
+///   are used, or the addresses of the host on the default port.
+/// - Otherwise the transports are taken from the NAPTR records of the
+///   host, or from the SRV records of TLS, TCP and UDP if it has none.
+///   UDP is used for `sip` uris and TLS for `sips` uris when neither
+///   exist.
+///
+/// SRV records are ordered by priority, then from the highest weight.
+/// Fails with an error of kind `NotFound` if there is no target, and of
+/// kind `InvalidInput` for absolute uris which have no host.
+/// # Examples
+///
+/// ```
+/// use libsip::{resolve::{resolve, SrvRecord, StaticDns, Target}, uri::parse_uri, Transport};
+/// use nom::error::VerboseError;
+///
+/// let dns = StaticDns::new()
+///     .add_srv("_sip._udp.example.com", SrvRecord {
+///         priority: 0,
+///         weight: 0,
+///         port: 5080,
+///         target: "sip.example.com".into(),
+///     })
+///     .add_ip("sip.example.com", "192.0.2.1".parse().unwrap());
+/// let uri = parse_uri::<VerboseError<&[u8]>>(b"sip:bob@example.com").unwrap().1;
+/// assert_eq!(
+///     vec![Target { transport: Transport::Udp, addr: "192.0.2.1:5080".parse().unwrap() }],
+///     resolve(&uri, &dns).unwrap()
+/// );
+/// ```
+pub fn resolve<B: DnsBackend>(uri: &Uri, dns: &B) -> IoResult<Vec<Target>> {
+    if let Some(absolute) = uri.as_absolute() {
+        return Err(IoError::new(
+            IoErrorKind::InvalidInput,
+            format!("Cannot resolve the absolute uri {}", absolute),
+        ));
+    }
+    let secure = uri.schema == Some(UriSchema::Sips);
+    let domain = uri.maddr().unwrap_or(&uri.host);
+    let transport = uri.transport().map(|transport| {
+        if secure {
+            secured(transport)
+        } else {
+            transport
+        }
+    });
+    let default_transport = if secure {
+        Transport::Tls
+    } else {
+        Transport::Udp
+    };
+
+    let numeric = |ip: IpAddr| {
+        let transport = transport.unwrap_or(default_transport);
+        let port = domain.port().unwrap_or_else(|| transport.default_port());
+        Ok(vec![Target {
+            transport,
+            addr: SocketAddr::new(ip, port),
+        }])
+    };
+    let name = match domain.host() {
+        Host::Ipv4(ip) => return numeric(ip.into()),
+        Host::Ipv6(ip) => return numeric(ip.into()),
+        Host::Domain(name) => name,
+    };
+
+    let targets = if let Some(port) = domain.port() {
+        address_targets(dns, &name, transport.unwrap_or(default_transport), port)?
+    } else if let Some(transport) = transport {
+        let targets = srv_targets(dns, &name, transport)?;
+        if targets.is_empty() {
+            address_targets(dns, &name, transport, transport.default_port())?
+        } else {
+            targets
+        }
+    } else {
+        let mut targets = naptr_targets(dns, &name, secure)?;
+        if targets.is_empty() {
+            let candidates: &[Transport] = if secure {
+                &[Transport::Tls]
+            } else {
+                &[Transport::Tls, Transport::Tcp, Transport::Udp]
+            };
+            for transport in candidates {
+                targets.extend(srv_targets(dns, &name, *transport)?);
+            }
+        }
+        if targets.is_empty() {
+            address_targets(
+                dns,
+                &name,
+                default_transport,
+                default_transport.default_port(),
+            )?
+        } else {
+            targets
+        }
+    };
+
+    if targets.is_empty() {
+        Err(IoError::new(
+            IoErrorKind::NotFound,
+            format!("no server found for {}", name),
+        ))
+    } else {
+        Ok(targets)
+    }
+}
+
+/// The transport of a `sips` uri for the `transport` parameter, TLS
+/// being used on top of TCP, SCTP and WebSocket.
+fn secured(transport: Transport) -> Transport {
+    match transport {
+        Transport::Tcp | Transport::Udp => Transport::Tls,
+        Transport::Sctp => Transport::TlsSctp,
+        Transport::Ws => Transport::Wss,
+        transport => transport,
+    }
+}
+
+/// The transport of a NAPTR service.
+/// ([RFC3263: Section 4.1](https://tools.ietf.org/html/rfc3263#section-4.1)
+/// and [RFC7118: Section 6](https://tools.ietf.org/html/rfc7118#section-6))
+fn service_transport(service: &str) -> Option<Transport> {
+    match service.to_ascii_uppercase().as_str() {
+        "SIP+D2U" => Some(Transport::Udp),
+        "SIP+D2T" => Some(Transport::Tcp),
+        "SIPS+D2T" => Some(Transport::Tls),
+        "SIP+D2S" => Some(Transport::Sctp),
+        "SIPS+D2S" => Some(Transport::TlsSctp),
+        "SIP+D2W" => Some(Transport::Ws),
+        "SIPS+D2W" => Some(Transport::Wss),
+        _ => None,
+    }
+}
+
+/// The prefix of the SRV name of `transport`, e.g. `_sip._udp`.
+fn srv_prefix(transport: Transport) -> Option<&'static str> {
+    match transport {
+        Transport::Udp => Some("_sip._udp"),
+        Transport::Tcp => Some("_sip._tcp"),
+        Transport::Tls => Some("_sips._tcp"),
+        Transport::Sctp => Some("_sip._sctp"),
+        Transport::TlsSctp => Some("_sips._sctp"),
+        Transport::Ws | Transport::Wss => None,
+    }
+}
+
+fn naptr_targets<B: DnsBackend>(dns: &B, name: &str, secure: bool) -> IoResult<Vec<Target>> {
+    let mut records: Vec<(NaptrRecord, Transport)> = dns
+        .naptr(name)?
+        .into_iter()
+        .filter(|record| record.flags.eq_ignore_ascii_case("s"))
+        .filter_map(|record| {
+            let transport = service_transport(&record.service)?;
+            Some((record, transport))
+        })
+        .filter(|(_, transport)| !secure || transport.default_port() == DEFAULT_SIPS_PORT)
+        .collect();
+    records.sort_by_key(|(record, _)| (record.order, record.preference));
+
+    let mut targets = vec![];
+    for (record, transport) in records {
+        let srv = dns.srv(record.replacement.trim_end_matches('.'))?;
+        targets.extend(srv_records_targets(dns, srv, transport)?);
+    }
+    Ok(targets)
+}
+
+fn srv_targets<B: DnsBackend>(dns: &B, name: &str, transport: Transport) -> IoResult<Vec<Target>> {
+    match srv_prefix(transport) {
+        Some(prefix) => {
+            let srv = dns.srv(&format!("{}.{}", prefix, name))?;
+            srv_records_targets(dns, srv, transport)
+        },
+        None => Ok(vec![]),
+    }
+}
+
+fn srv_records_targets<B: DnsBackend>(
+    dns: &B,
+    mut records: Vec<SrvRecord>,
+    transport: Transport,
+) -> IoResult<Vec<Target>> {
+    records.sort_by_key(|record| (record.priority, std::cmp::Reverse(record.weight)));
+    let mut targets = vec![];
+    for record in records {
+        let host = record.target.trim_end_matches('.');
+        if host.is_empty() {
+            continue;
+        }
+        targets.extend(address_targets(dns, host, transport, record.port)?);
+    }
+    Ok(targets)
+}
+
+fn address_targets<B: DnsBackend>(
+    dns: &B,
+    host: &str,
+    transport: Transport,
+    port: u16,
+) -> IoResult<Vec<Target>> {
+    Ok(dns
+        .lookup_ip(host)?
+        .into_iter()
+        .map(|ip| Target {
+            transport,
+            addr: SocketAddr::new(ip, port),
+        })
+        .collect())
+}
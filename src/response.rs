@@ -1,6 +1,8 @@
-use crate::*;
-
-use std::io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult};
+use crate::{
+    build::{BuildError, RESPONSE_HEADERS},
+    headers::HeaderKind,
+    *,
+};
 
 /// Sip Response Generator. When build is called the struct
 /// is consumed and produces a SipMessage::Response variant.
@@ -12,6 +14,7 @@ pub struct ResponseGenerator {
     version: Version,
     headers: Headers,
     body: Vec<u8>,
+    suppress_content_length: bool,
 }
 
 impl ResponseGenerator {
@@ -22,12 +25,56 @@ impl ResponseGenerator {
             version: Version::default(),
             headers: Headers::new(),
             body: vec![],
+            suppress_content_length: false,
         }
     }
 
-    /// Set the response status code.
-    pub fn code(mut self, code: u32) -> ResponseGenerator {
-        self.code = Some(code);
+    /// Create a generator for a response to `request` with `code`,
+    /// copying its Via, From, To, Call-ID, CSeq and Record-Route headers.
+    /// The To tag is added by `build` when needed.
+    /// ([RFC3261: Section 8.2.6.2, Headers and Tags](https://tools.ietf.org/html/rfc3261#section-8.2.6.2))
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::*;
+    ///
+    /// let req = SipMessage::parse(
+    ///     b"OPTIONS sip:bob@example.com SIP/2.0\r\n\
+    ///     Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+    ///     From: <sip:alice@example.com>;tag=1928301774\r\n\
+    ///     To: <sip:bob@example.com>\r\n\
+    ///     Call-ID: a84b4c76e66710\r\n\
+    ///     CSeq: 63104 OPTIONS\r\n\r\n",
+    /// )
+    /// .unwrap();
+    /// let res = ResponseGenerator::reply_to(&req, StatusCode::OK).build().unwrap();
+    /// assert_eq!(req.call_id(), res.call_id());
+    /// assert!(res.to().unwrap().tag().is_some());
+    /// ```
+    pub fn reply_to<C: Into<StatusCode>>(request: &SipMessage, code: C) -> ResponseGenerator {
+        let headers = request
+            .headers()
+            .iter()
+            .filter(|header| {
+                matches!(
+                    header,
+                    Header::Via(_)
+                        | Header::From(_)
+                        | Header::To(_)
+                        | Header::CallId(_)
+                        | Header::CSeq(..)
+                        | Header::RecordRoute(_)
+                )
+            })
+            .cloned()
+            .collect();
+        ResponseGenerator::new().code(code).headers(headers)
+    }
+
+    /// Set the response status code, either a `StatusCode` or its
+    /// numeric value.
+    pub fn code<C: Into<StatusCode>>(mut self, code: C) -> ResponseGenerator {
+        self.code = Some(code.into().as_u32());
         self
     }
 
@@ -55,6 +102,18 @@ impl ResponseGenerator {
         &mut self.headers
     }
 
+    /// Add the RSeq header with `rseq` and `Require: 100rel`, making this
+    /// provisional response reliable, but only when `request_headers`
+    /// list `100rel` in a Supported or Require header.
+    /// ([RFC3262: Section 3, UAS Behavior](https://tools.ietf.org/html/rfc3262#section-3))
+    pub fn rseq_if_supported(mut self, request_headers: &Headers, rseq: u32) -> ResponseGenerator {
+        if request_headers.supports("100rel") {
+            self.headers.push(Header::Require(vec!["100rel".into()]));
+            self.headers.push(Header::RSeq(rseq));
+        }
+        self
+    }
+
     /// Set the sip response body. This completely replaces
     /// the current response body.
     pub fn body(mut self, body: Vec<u8>) -> ResponseGenerator {
@@ -62,21 +121,55 @@ impl ResponseGenerator {
         self
     }
 
-    /// Create the Sip response.
-    pub fn build(self) -> IoResult<SipMessage> {
-        if let Some(code) = self.code {
-            let res = SipMessage::Response {
-                code,
-                version: self.version,
-                headers: self.headers,
-                body: self.body,
-            };
-            Ok(res)
-        } else {
-            Err(IoError::new(
-                IoErrorKind::InvalidInput,
-                "ResponseGenerator requires `code` method be called.",
-            ))
+    /// Don't set the Content-Length header when `build` is called,
+    /// leaving the headers as they were given.
+    pub fn suppress_content_length(mut self) -> ResponseGenerator {
+        self.suppress_content_length = true;
+        self
+    }
+
+    /// Add `header` to the response header list if it is `Some`, leaving
+    /// missing mandatory headers to be reported by `build`.
+    pub fn header_opt(mut self, header: Option<Header>) -> ResponseGenerator {
+        if let Some(header) = header {
+            self.headers.push(header);
+        }
+        self
+    }
+
+    /// Create the Sip response, checking that the Via, From, To, Call-ID
+    /// and CSeq headers are present. A tag is added to the To header of
+    /// every response other than 100 (Trying) if it doesn't already have one
+    /// and the Content-Length header is set to the length of the body unless
+    /// `suppress_content_length` was called.
+    pub fn build(mut self) -> Result<SipMessage, BuildError> {
+        if !self.suppress_content_length {
+            let length = Header::ContentLength(self.body.len() as u32);
+            self.headers.replace(HeaderKind::ContentLength, length);
+        }
+        let msg = self.build_unchecked()?;
+        BuildError::check_headers(msg.headers(), &RESPONSE_HEADERS)?;
+        Ok(msg)
+    }
+
+    /// Create the Sip response without checking its headers, e.g. for
+    /// tests or responses that are completed later on.
+    pub fn build_unchecked(mut self) -> Result<SipMessage, BuildError> {
+        let code = self.code.ok_or(BuildError::MissingCode)?;
+        if code != 100 {
+            for header in self.headers.0.iter_mut() {
+                if let Header::To(to) = header {
+                    if to.tag().is_none() {
+                        to.set_tag(generate_tag());
+                    }
+                }
+            }
         }
+        Ok(SipMessage::Response {
+            code,
+            version: self.version,
+            headers: self.headers,
+            body: self.body,
+        })
     }
 }
@@ -0,0 +1,381 @@
+//! The Session Description Protocol, describing the media streams of
+//! a session in the body of INVITE requests and their responses.
+//! ([RFC4566: SDP: Session Description Protocol](https://tools.ietf.org/html/rfc4566))
+//!
+//! `SessionDescription::parse` reads a body and the `Display`
+//! implementation writes it back, one `<type>=<value>` line per field.
+//! The time zone (`z=`), encryption key (`k=`) and repeat time (`r=`)
+//! lines are skipped when parsing.
+
+pub mod parse;
+pub use self::parse::parse_sdp;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::{
+    fmt,
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+    net::IpAddr,
+};
+
+use nom::error::VerboseError;
+
+/// A session description, the session level fields followed by the
+/// description of every media stream.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SessionDescription {
+    /// The protocol version (`v=`), always 0.
+    pub version: u8,
+    pub origin: Origin,
+    /// The session name (`s=`), `-` when the session has none.
+    pub session_name: String,
+    pub info: Option<String>,
+    pub uri: Option<String>,
+    pub emails: Vec<String>,
+    pub phones: Vec<String>,
+    /// The connection data used by media without their own.
+    pub connection: Option<Connection>,
+    pub bandwidths: Vec<Bandwidth>,
+    pub timing: Vec<Timing>,
+    pub attributes: Vec<Attribute>,
+    pub media: Vec<MediaDescription>,
+}
+
+/// The originator of a session and its identifier (`o=`).
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Origin {
+    /// The user name on the originating host, `-` if it has none.
+    pub username: String,
+    pub session_id: u64,
+    /// Incremented every time the session description is modified.
+    pub session_version: u64,
+    pub net_type: String,
+    pub addr_type: String,
+    pub address: String,
+}
+
+/// The address media are received at (`c=`), e.g. `IN IP4 192.0.2.1`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Connection {
+    pub net_type: String,
+    pub addr_type: String,
+    pub address: String,
+}
+
+/// The bandwidth proposed for a session or media (`b=`), in kilobits
+/// per second for the `AS` and `CT` types.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bandwidth {
+    pub bw_type: String,
+    pub bandwidth: u64,
+}
+
+/// The start and stop times of a session (`t=`), as NTP timestamps.
+/// `0 0` for a permanent session.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Timing {
+    pub start: u64,
+    pub stop: u64,
+}
+
+/// An attribute of a session or media (`a=`).
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Attribute {
+    /// A flag without value, e.g. `sendrecv`.
+    Property(String),
+    /// A named value, e.g. `rtpmap:0 PCMU/8000`.
+    Value(String, String),
+}
+
+/// The description of a media stream (`m=`) and of its fields.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MediaDescription {
+    /// The media type, e.g. `audio` or `video`.
+    pub media: String,
+    /// The port media are received on, 0 for a rejected stream.
+    pub port: u16,
+    pub num_ports: Option<u16>,
+    /// The transport protocol, e.g. `RTP/AVP`.
+    pub protocol: String,
+    /// The media formats, the RTP payload types for RTP.
+    pub formats: Vec<String>,
+    pub info: Option<String>,
+    pub connection: Option<Connection>,
+    pub bandwidths: Vec<Bandwidth>,
+    pub attributes: Vec<Attribute>,
+}
+
+impl SessionDescription {
+    /// Create a session description without media, originated by `origin`.
+    pub fn new(origin: Origin) -> SessionDescription {
+        SessionDescription {
+            version: 0,
+            origin,
+            session_name: "-".into(),
+            info: None,
+            uri: None,
+            emails: vec![],
+            phones: vec![],
+            connection: None,
+            bandwidths: vec![],
+            timing: vec![Timing::default()],
+            attributes: vec![],
+            media: vec![],
+        }
+    }
+
+    /// Create an offer of a single audio stream received at `address` on
+    /// `port`, with the PCMU, PCMA and telephone-event payload types.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::sdp::SessionDescription;
+    ///
+    /// let offer = SessionDescription::audio_offer("192.0.2.1".parse().unwrap(), 49170);
+    /// assert_eq!(49170, offer.media[0].port);
+    /// assert_eq!(Some("PCMU/8000"), offer.media[0].rtpmap("0"));
+    /// assert_eq!(offer, SessionDescription::parse(offer.to_string().as_bytes()).unwrap());
+    /// ```
+    pub fn audio_offer(address: IpAddr, port: u16) -> SessionDescription {
+        let connection = Connection::new(address);
+        let mut sdp = SessionDescription::new(Origin {
+            username: "-".into(),
+            session_id: u64::from(rand::random::<u32>()),
+            session_version: 1,
+            net_type: connection.net_type.clone(),
+            addr_type: connection.addr_type.clone(),
+            address: connection.address.clone(),
+        });
+        sdp.connection = Some(connection);
+        let mut audio = MediaDescription::new("audio", port, "RTP/AVP");
+        audio.formats = vec!["0".into(), "8".into(), "101".into()];
+        audio.attributes = vec![
+            Attribute::Value("rtpmap".into(), "0 PCMU/8000".into()),
+            Attribute::Value("rtpmap".into(), "8 PCMA/8000".into()),
+            Attribute::Value("rtpmap".into(), "101 telephone-event/8000".into()),
+            Attribute::Value("fmtp".into(), "101 0-15".into()),
+            Attribute::Property("sendrecv".into()),
+        ];
+        sdp.media.push(audio);
+        sdp
+    }
+
+    /// Parse a whole session description, e.g. the body of an INVITE.
+    pub fn parse(input: &[u8]) -> IoResult<SessionDescription> {
+        match parse_sdp::<VerboseError<&[u8]>>(input) {
+            Ok((_, sdp)) => Ok(sdp),
+            Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+                let line = e
+                    .errors
+                    .first()
+                    .map(|(remains, _)| {
+                        let offset = input.len() - remains.len();
+                        String::from_utf8_lossy(&input[offset..])
+                            .lines()
+                            .next()
+                            .unwrap_or_default()
+                            .to_string()
+                    })
+                    .unwrap_or_default();
+                Err(IoError::new(
+                    IoErrorKind::InvalidData,
+                    format!("invalid session description line: {}", line),
+                ))
+            },
+            Err(nom::Err::Incomplete(_)) => Err(IoError::new(
+                IoErrorKind::UnexpectedEof,
+                "incomplete session description",
+            )),
+        }
+    }
+
+    /// The value of the first session level attribute `name`, empty for
+    /// a property.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        find_attribute(&self.attributes, name)
+    }
+
+    /// The connection data of `media`, its own or the session one.
+    pub fn media_connection<'a>(&'a self, media: &'a MediaDescription) -> Option<&'a Connection> {
+        media.connection.as_ref().or(self.connection.as_ref())
+    }
+}
+
+impl Connection {
+    /// Create the connection data of an IPv4 or IPv6 `address`.
+    pub fn new(address: IpAddr) -> Connection {
+        Connection {
+            net_type: "IN".into(),
+            addr_type: if address.is_ipv4() { "IP4" } else { "IP6" }.into(),
+            address: address.to_string(),
+        }
+    }
+}
+
+impl MediaDescription {
+    /// Create the description of a `media` stream received on `port`
+    /// with the transport `protocol`, without formats.
+    pub fn new<M: Into<String>, P: Into<String>>(
+        media: M,
+        port: u16,
+        protocol: P,
+    ) -> MediaDescription {
+        MediaDescription {
+            media: media.into(),
+            port,
+            num_ports: None,
+            protocol: protocol.into(),
+            formats: vec![],
+            info: None,
+            connection: None,
+            bandwidths: vec![],
+            attributes: vec![],
+        }
+    }
+
+    /// The value of the first attribute `name` of this media, empty for
+    /// a property.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        find_attribute(&self.attributes, name)
+    }
+
+    /// The encoding of the RTP payload type `format` given by its
+    /// `rtpmap` attribute, e.g. `PCMU/8000`.
+    pub fn rtpmap(&self, format: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find_map(|attribute| match attribute {
+                Attribute::Value(name, value) if name == "rtpmap" => {
+                    let mut parts = value.splitn(2, ' ');
+                    if parts.next() == Some(format) {
+                        parts.next()
+                    } else {
+                        None
+                    }
+                },
+                _ => None,
+            })
+    }
+}
+
+fn find_attribute<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a str> {
+    attributes.iter().find_map(|attribute| match attribute {
+        Attribute::Property(property) if property == name => Some(""),
+        Attribute::Value(key, value) if key == name => Some(value.as_str()),
+        _ => None,
+    })
+}
+
+impl fmt::Display for SessionDescription {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "v={}\r\n", self.version)?;
+        write!(f, "o={}\r\n", self.origin)?;
+        write!(f, "s={}\r\n", self.session_name)?;
+        if let Some(info) = &self.info {
+            write!(f, "i={}\r\n", info)?;
+        }
+        if let Some(uri) = &self.uri {
+            write!(f, "u={}\r\n", uri)?;
+        }
+        for email in &self.emails {
+            write!(f, "e={}\r\n", email)?;
+        }
+        for phone in &self.phones {
+            write!(f, "p={}\r\n", phone)?;
+        }
+        if let Some(connection) = &self.connection {
+            write!(f, "c={}\r\n", connection)?;
+        }
+        for bandwidth in &self.bandwidths {
+            write!(f, "b={}\r\n", bandwidth)?;
+        }
+        for timing in &self.timing {
+            write!(f, "t={}\r\n", timing)?;
+        }
+        for attribute in &self.attributes {
+            write!(f, "a={}\r\n", attribute)?;
+        }
+        for media in &self.media {
+            write!(f, "{}", media)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {} {}",
+            self.username,
+            self.session_id,
+            self.session_version,
+            self.net_type,
+            self.addr_type,
+            self.address
+        )
+    }
+}
+
+impl fmt::Display for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", self.net_type, self.addr_type, self.address)
+    }
+}
+
+impl fmt::Display for Bandwidth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.bw_type, self.bandwidth)
+    }
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.start, self.stop)
+    }
+}
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Attribute::Property(name) => write!(f, "{}", name),
+            Attribute::Value(name, value) => write!(f, "{}:{}", name, value),
+        }
+    }
+}
+
+impl fmt::Display for MediaDescription {
+    /// Writes the `m=` line followed by the fields of this media.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m={} {}", self.media, self.port)?;
+        if let Some(num_ports) = self.num_ports {
+            write!(f, "/{}", num_ports)?;
+        }
+        write!(f, " {}", self.protocol)?;
+        for format in &self.formats {
+            write!(f, " {}", format)?;
+        }
+        write!(f, "\r\n")?;
+        if let Some(info) = &self.info {
+            write!(f, "i={}\r\n", info)?;
+        }
+        if let Some(connection) = &self.connection {
+            write!(f, "c={}\r\n", connection)?;
+        }
+        for bandwidth in &self.bandwidths {
+            write!(f, "b={}\r\n", bandwidth)?;
+        }
+        for attribute in &self.attributes {
+            write!(f, "a={}\r\n", attribute)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_till, take_while1, take_while_m_n},
+    character::{
+        complete::{char, digit1},
+        *,
+    },
+    combinator::{all_consuming, map_res, opt},
+    error::{ErrorKind, ParseError},
+    multi::many0,
+    sequence::preceded,
+    IResult,
+};
+
+use super::*;
+use crate::parse::{parse_u16, parse_u64, parse_u8, slice_to_string};
+
+/// Parse a session description, one `<type>=<value>` line at a time.
+/// Lines may end with CRLF or LF alone. Fails on the first line that
+/// is malformed or out of order.
+/// # Examples
+///
+/// ```
+/// use libsip::sdp::parse_sdp;
+/// use nom::error::VerboseError;
+///
+/// let (_, sdp) = parse_sdp::<VerboseError<&[u8]>>(
+///     b"v=0\r\n\
+///     o=alice 2890844526 2890844526 IN IP4 atlanta.com\r\n\
+///     s=-\r\n\
+///     c=IN IP4 192.0.2.101\r\n\
+///     t=0 0\r\n\
+///     m=audio 49172 RTP/AVP 0\r\n\
+///     a=rtpmap:0 PCMU/8000\r\n",
+/// )
+/// .unwrap();
+/// assert_eq!("alice", sdp.origin.username);
+/// assert_eq!(Some("PCMU/8000"), sdp.media[0].rtpmap("0"));
+/// ```
+pub fn parse_sdp<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], SessionDescription, E> {
+    let (input, version) = parse_line(b'v', map_res(digit1, parse_u8))(input)?;
+    let (input, origin) = parse_line(b'o', parse_origin)(input)?;
+    let (input, session_name) = parse_line(b's', parse_text)(input)?;
+    let mut sdp = SessionDescription::new(origin);
+    sdp.version = version;
+    sdp.session_name = session_name;
+    sdp.timing = vec![];
+
+    let mut input = input;
+    while !input.is_empty() {
+        let (rest, (kind, value)) = parse_sdp_line(input)?;
+        let invalid = || nom::Err::Error(E::from_error_kind(input, ErrorKind::Verify));
+        let text = || String::from_utf8_lossy(value).to_string();
+        if kind == b'm' {
+            let media = parse_value(value, parse_media).ok_or_else(invalid)?;
+            sdp.media.push(media);
+        } else if let Some(media) = sdp.media.last_mut() {
+            match kind {
+                b'i' => media.info = Some(text()),
+                b'c' => {
+                    media.connection =
+                        Some(parse_value(value, parse_connection).ok_or_else(invalid)?)
+                },
+                b'b' => media
+                    .bandwidths
+                    .push(parse_value(value, parse_bandwidth).ok_or_else(invalid)?),
+                b'a' => media
+                    .attributes
+                    .push(parse_value(value, parse_attribute).ok_or_else(invalid)?),
+                b'k' => {},
+                _ => return Err(invalid()),
+            }
+        } else {
+            match kind {
+                b'i' => sdp.info = Some(text()),
+                b'u' => sdp.uri = Some(text()),
+                b'e' => sdp.emails.push(text()),
+                b'p' => sdp.phones.push(text()),
+                b'c' => {
+                    sdp.connection = Some(parse_value(value, parse_connection).ok_or_else(invalid)?)
+                },
+                b'b' => sdp
+                    .bandwidths
+                    .push(parse_value(value, parse_bandwidth).ok_or_else(invalid)?),
+                b't' => sdp
+                    .timing
+                    .push(parse_value(value, parse_timing).ok_or_else(invalid)?),
+                b'a' => sdp
+                    .attributes
+                    .push(parse_value(value, parse_attribute).ok_or_else(invalid)?),
+                b'r' | b'z' | b'k' => {},
+                _ => return Err(invalid()),
+            }
+        }
+        input = rest;
+    }
+    Ok((input, sdp))
+}
+
+/// Parse a line of any type, returning the type and the raw value.
+pub fn parse_sdp_line<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], (u8, &'a [u8]), E> {
+    let (input, kind) = take_while_m_n(1, 1, is_alphabetic)(input)?;
+    let (input, _) = char('=')(input)?;
+    let (input, value) = take_till(|chr| chr == b'\r' || chr == b'\n')(input)?;
+    let (input, _) = opt(alt((tag("\r\n"), tag("\n"))))(input)?;
+    Ok((input, (kind[0], value)))
+}
+
+/// Parse a line of the type `kind`, its value with `parser`.
+fn parse_line<'a, O, E, F>(
+    kind: u8,
+    mut parser: F,
+) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], O, E>
+where
+    E: ParseError<&'a [u8]>,
+    F: FnMut(&'a [u8]) -> IResult<&'a [u8], O, E>,
+{
+    move |input: &'a [u8]| {
+        let (rest, (found, value)) = parse_sdp_line(input)?;
+        if found != kind {
+            return Err(nom::Err::Error(E::from_error_kind(input, ErrorKind::Char)));
+        }
+        let (_, output) = all_consuming(&mut parser)(value)
+            .map_err(|_| nom::Err::Error(E::from_error_kind(input, ErrorKind::Verify)))?;
+        Ok((rest, output))
+    }
+}
+
+fn parse_value<'a, O, F>(value: &'a [u8], parser: F) -> Option<O>
+where
+    F: FnMut(&'a [u8]) -> IResult<&'a [u8], O, (&'a [u8], ErrorKind)>,
+{
+    all_consuming(parser)(value).ok().map(|(_, output)| output)
+}
+
+fn parse_text<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], String, E> {
+    Ok((
+        &input[input.len()..],
+        String::from_utf8_lossy(input).to_string(),
+    ))
+}
+
+fn parse_field<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], String, E> {
+    map_res(take_while1(|chr| chr != b' '), slice_to_string::<E>)(input)
+}
+
+/// Parse the value of an `o=` line.
+pub fn parse_origin<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Origin, E> {
+    let (input, username) = parse_field(input)?;
+    let (input, session_id) = preceded(char(' '), map_res(digit1, parse_u64))(input)?;
+    let (input, session_version) = preceded(char(' '), map_res(digit1, parse_u64))(input)?;
+    let (input, connection) = preceded(char(' '), parse_connection)(input)?;
+    Ok((
+        input,
+        Origin {
+            username,
+            session_id,
+            session_version,
+            net_type: connection.net_type,
+            addr_type: connection.addr_type,
+            address: connection.address,
+        },
+    ))
+}
+
+/// Parse the value of a `c=` line.
+pub fn parse_connection<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Connection, E> {
+    let (input, net_type) = parse_field(input)?;
+    let (input, addr_type) = preceded(char(' '), parse_field)(input)?;
+    let (input, address) = preceded(char(' '), parse_field)(input)?;
+    Ok((
+        input,
+        Connection {
+            net_type,
+            addr_type,
+            address,
+        },
+    ))
+}
+
+/// Parse the value of a `b=` line.
+pub fn parse_bandwidth<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Bandwidth, E> {
+    let (input, bw_type) =
+        map_res(take_while1(is_alphanumeric_or_dash), slice_to_string::<E>)(input)?;
+    let (input, bandwidth) = preceded(char(':'), map_res(digit1, parse_u64))(input)?;
+    Ok((input, Bandwidth { bw_type, bandwidth }))
+}
+
+/// Parse the value of a `t=` line.
+pub fn parse_timing<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Timing, E> {
+    let (input, start) = map_res(digit1, parse_u64)(input)?;
+    let (input, stop) = preceded(char(' '), map_res(digit1, parse_u64))(input)?;
+    Ok((input, Timing { start, stop }))
+}
+
+/// Parse the value of an `a=` line.
+pub fn parse_attribute<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Attribute, E> {
+    let (input, name) = map_res(
+        take_while1(|chr| chr != b':' && chr != b' '),
+        slice_to_string::<E>,
+    )(input)?;
+    match char::<_, E>(':')(input) {
+        Ok((value, _)) => Ok((
+            &value[value.len()..],
+            Attribute::Value(name, String::from_utf8_lossy(value).to_string()),
+        )),
+        Err(_) => Ok((input, Attribute::Property(name))),
+    }
+}
+
+/// Parse the value of an `m=` line.
+pub fn parse_media<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], MediaDescription, E> {
+    let (input, media) = parse_field(input)?;
+    let (input, port) = preceded(char(' '), map_res(digit1, parse_u16::<E>))(input)?;
+    let (input, num_ports) = opt(preceded(char('/'), map_res(digit1, parse_u16::<E>)))(input)?;
+    let (input, protocol) = preceded(char(' '), parse_field)(input)?;
+    let (input, formats) = many0(preceded(char(' '), parse_field))(input)?;
+    let mut description = MediaDescription::new(media, port, protocol);
+    description.num_ports = num_ports;
+    description.formats = formats;
+    Ok((input, description))
+}
+
+fn is_alphanumeric_or_dash(chr: u8) -> bool {
+    is_alphanumeric(chr) || chr == b'-'
+}
@@ -0,0 +1,255 @@
+use std::time::Duration;
+
+use crate::{
+    transaction::{SipTimers, Timer, TransactionAction},
+    Method, RequestGenerator, SipMessage,
+};
+
+/// The states of a client transaction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ClientState {
+    /// An INVITE was sent and no response received yet.
+    Calling,
+    /// A non-INVITE request was sent and no response received yet.
+    Trying,
+    /// A provisional response was received.
+    Proceeding,
+    /// A final response was received, retransmissions of it are absorbed.
+    Completed,
+    Terminated,
+}
+
+/// The client side of a transaction, following the state machines of
+/// [RFC3261: Section 17.1.1, INVITE Client Transaction](https://tools.ietf.org/html/rfc3261#section-17.1.1)
+/// and [Section 17.1.2, Non-INVITE Client Transaction](https://tools.ietf.org/html/rfc3261#section-17.1.2).
+///
+/// Responses have to be matched to the transaction before being given to
+/// `on_response`. Timers firing in a state where they don't apply are
+/// ignored, so they never have to be cancelled.
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     transaction::{ClientState, ClientTransaction, Timer, TransactionAction},
+///     SipMessage,
+/// };
+///
+/// let request = SipMessage::parse(b"OPTIONS sip:bob@example.com SIP/2.0\r\n\r\n").unwrap();
+/// let mut transaction = ClientTransaction::new(request.clone(), false);
+/// let actions = transaction.start();
+/// assert_eq!(TransactionAction::Send(request.clone()), actions[0]);
+///
+/// let actions = transaction.on_timer(Timer::E);
+/// assert_eq!(TransactionAction::Send(request), actions[0]);
+///
+/// let response = SipMessage::parse(b"SIP/2.0 200 OK\r\n\r\n").unwrap();
+/// let actions = transaction.on_response(response.clone());
+/// assert_eq!(TransactionAction::PassToTu(response), actions[0]);
+/// assert_eq!(ClientState::Completed, transaction.state());
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClientTransaction {
+    request: SipMessage,
+    reliable: bool,
+    timers: SipTimers,
+    state: ClientState,
+    interval: Duration,
+    ack: Option<SipMessage>,
+}
+
+impl ClientTransaction {
+    /// Create the transaction of `request`, which is sent over a reliable
+    /// transport, like TCP, if `reliable` is true. Call `start` to send it.
+    pub fn new(request: SipMessage, reliable: bool) -> ClientTransaction {
+        let state = if request.method() == Some(&Method::Invite) {
+            ClientState::Calling
+        } else {
+            ClientState::Trying
+        };
+        ClientTransaction {
+            request,
+            reliable,
+            timers: SipTimers::default(),
+            state,
+            interval: SipTimers::default().t1,
+            ack: None,
+        }
+    }
+
+    /// Use `timers` instead of the default timer values.
+    pub fn timers(mut self, timers: SipTimers) -> ClientTransaction {
+        self.timers = timers;
+        self.interval = timers.t1;
+        self
+    }
+
+    /// The request of this transaction.
+    pub fn request(&self) -> &SipMessage {
+        &self.request
+    }
+
+    /// The current state of this transaction.
+    pub fn state(&self) -> ClientState {
+        self.state
+    }
+
+    /// Determine if this is an INVITE transaction.
+    pub fn is_invite(&self) -> bool {
+        self.request.method() == Some(&Method::Invite)
+    }
+
+    /// The ACK sent for a non-2xx final response to an INVITE.
+    pub fn ack(&self) -> Option<&SipMessage> {
+        self.ack.as_ref()
+    }
+
+    /// Send the request, starting the retransmit timer on unreliable
+    /// transports and the timeout timer.
+    pub fn start(&mut self) -> Vec<TransactionAction> {
+        let (retransmit, timeout) = if self.is_invite() {
+            (Timer::A, Timer::B)
+        } else {
+            (Timer::E, Timer::F)
+        };
+        let mut actions = vec![TransactionAction::Send(self.request.clone())];
+        if !self.reliable {
+            actions.push(TransactionAction::StartTimer(retransmit, self.interval));
+        }
+        actions.push(TransactionAction::StartTimer(
+            timeout,
+            self.timers.duration(timeout),
+        ));
+        actions
+    }
+
+    /// Handle a response received for this transaction.
+    pub fn on_response(&mut self, response: SipMessage) -> Vec<TransactionAction> {
+        let code = match response.status_code() {
+            Some(code) => code,
+            None => return vec![],
+        };
+        if self.is_invite() {
+            self.on_invite_response(response, code)
+        } else {
+            self.on_non_invite_response(response, code)
+        }
+    }
+
+    fn on_invite_response(&mut self, response: SipMessage, code: u32) -> Vec<TransactionAction> {
+        match self.state {
+            ClientState::Calling | ClientState::Proceeding => {
+                if code < 200 {
+                    self.state = ClientState::Proceeding;
+                    vec![TransactionAction::PassToTu(response)]
+                } else if code < 300 {
+                    self.state = ClientState::Terminated;
+                    vec![
+                        TransactionAction::PassToTu(response),
+                        TransactionAction::Terminated,
+                    ]
+                } else {
+                    self.ack = RequestGenerator::ack_of(&self.request, &response)
+                        .ok()
+                        .and_then(|ack| ack.build().ok());
+                    let mut actions = vec![];
+                    if let Some(ack) = &self.ack {
+                        actions.push(TransactionAction::Send(ack.clone()));
+                    }
+                    actions.push(TransactionAction::PassToTu(response));
+                    actions.extend(self.complete(Timer::D));
+                    actions
+                }
+            },
+            ClientState::Completed if code >= 300 => self
+                .ack
+                .iter()
+                .map(|ack| TransactionAction::Send(ack.clone()))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn on_non_invite_response(
+        &mut self,
+        response: SipMessage,
+        code: u32,
+    ) -> Vec<TransactionAction> {
+        match self.state {
+            ClientState::Trying | ClientState::Proceeding => {
+                if code < 200 {
+                    self.state = ClientState::Proceeding;
+                    vec![TransactionAction::PassToTu(response)]
+                } else {
+                    let mut actions = vec![TransactionAction::PassToTu(response)];
+                    actions.extend(self.complete(Timer::K));
+                    actions
+                }
+            },
+            _ => vec![],
+        }
+    }
+
+    /// Move to the Completed state, waiting for `timer` on unreliable
+    /// transports for retransmissions to be absorbed.
+    fn complete(&mut self, timer: Timer) -> Vec<TransactionAction> {
+        if self.reliable {
+            self.state = ClientState::Terminated;
+            vec![TransactionAction::Terminated]
+        } else {
+            self.state = ClientState::Completed;
+            vec![TransactionAction::StartTimer(
+                timer,
+                self.timers.duration(timer),
+            )]
+        }
+    }
+
+    /// Handle the expiration of `timer`.
+    pub fn on_timer(&mut self, timer: Timer) -> Vec<TransactionAction> {
+        match (timer, self.state) {
+            (Timer::A, ClientState::Calling) => {
+                self.interval *= 2;
+                vec![
+                    TransactionAction::Send(self.request.clone()),
+                    TransactionAction::StartTimer(Timer::A, self.interval),
+                ]
+            },
+            (Timer::E, ClientState::Trying) | (Timer::E, ClientState::Proceeding) => {
+                self.interval = if self.state == ClientState::Trying {
+                    std::cmp::min(self.interval * 2, self.timers.t2)
+                } else {
+                    self.timers.t2
+                };
+                vec![
+                    TransactionAction::Send(self.request.clone()),
+                    TransactionAction::StartTimer(Timer::E, self.interval),
+                ]
+            },
+            (Timer::B, ClientState::Calling)
+            | (Timer::F, ClientState::Trying)
+            | (Timer::F, ClientState::Proceeding) => {
+                self.state = ClientState::Terminated;
+                vec![TransactionAction::Timeout, TransactionAction::Terminated]
+            },
+            (Timer::D, ClientState::Completed) | (Timer::K, ClientState::Completed) => {
+                self.state = ClientState::Terminated;
+                vec![TransactionAction::Terminated]
+            },
+            _ => vec![],
+        }
+    }
+
+    /// Handle a failure of the transport sending the request.
+    pub fn on_transport_error(&mut self) -> Vec<TransactionAction> {
+        match self.state {
+            ClientState::Calling | ClientState::Trying | ClientState::Proceeding => {
+                self.state = ClientState::Terminated;
+                vec![
+                    TransactionAction::TransportError,
+                    TransactionAction::Terminated,
+                ]
+            },
+            _ => vec![],
+        }
+    }
+}
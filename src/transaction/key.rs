@@ -0,0 +1,129 @@
+use crate::{Domain, Method, SipMessage};
+
+/// The `z9hG4bK` prefix of branches generated following RFC3261.
+pub const MAGIC_COOKIE: &str = "z9hG4bK";
+
+/// Identifies the transaction a message belongs to, for finding the
+/// state machine that handles it.
+/// ([RFC3261: Section 17.1.3, Matching Responses to Client Transactions](https://tools.ietf.org/html/rfc3261#section-17.1.3)
+/// and [Section 17.2.3, Matching Requests to Server Transactions](https://tools.ietf.org/html/rfc3261#section-17.2.3))
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum TransactionKey {
+    /// The key of a message whose topmost Via branch has the magic
+    /// cookie. The method is the one of the request, or of the CSeq
+    /// for responses, with ACK replaced by INVITE so that the ACK of
+    /// a non-2xx response matches the INVITE transaction.
+    Rfc3261 {
+        branch: String,
+        sent_by: Domain,
+        method: Method,
+    },
+    /// The key of a request from an RFC2543 element, which doesn't
+    /// generate unique branches. Only retransmissions of the same request
+    /// have the same key.
+    Rfc2543 {
+        uri: String,
+        from_tag: Option<String>,
+        to_tag: Option<String>,
+        call_id: String,
+        cseq: u32,
+        method: Method,
+        via: String,
+    },
+}
+
+impl TransactionKey {
+    /// Compute the key of `msg`, returning `None` if it lacks the headers
+    /// needed: a Via for every message and, without a magic cookie,
+    /// a Call-ID and CSeq in a request.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::{transaction::TransactionKey, SipMessage};
+    ///
+    /// let invite = SipMessage::parse(
+    ///     b"INVITE sip:bob@example.com SIP/2.0\r\n\
+    ///     Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+    ///     CSeq: 1 INVITE\r\n\r\n",
+    /// )
+    /// .unwrap();
+    /// let response = SipMessage::parse(
+    ///     b"SIP/2.0 180 Ringing\r\n\
+    ///     Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+    ///     CSeq: 1 INVITE\r\n\r\n",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(TransactionKey::from_message(&invite), TransactionKey::from_message(&response));
+    /// ```
+    pub fn from_message(msg: &SipMessage) -> Option<TransactionKey> {
+        let via = msg.top_via()?;
+        let method = match msg.method() {
+            Some(method) => method.clone(),
+            None => msg.cseq()?.1.clone(),
+        };
+        let method = if method == Method::Ack {
+            Method::Invite
+        } else {
+            method
+        };
+        match via.branch() {
+            Some(branch) if branch.starts_with(MAGIC_COOKIE) => Some(TransactionKey::Rfc3261 {
+                branch: branch.to_string(),
+                sent_by: via.host.clone(),
+                method,
+            }),
+            _ => Some(TransactionKey::Rfc2543 {
+                uri: format!("{}", msg.uri()?),
+                from_tag: msg.from().and_then(|from| from.tag()).map(String::from),
+                to_tag: msg.to().and_then(|to| to.tag()).map(String::from),
+                call_id: msg.call_id()?.clone(),
+                cseq: msg.cseq()?.0,
+                method,
+                via: format!("{}", via),
+            }),
+        }
+    }
+}
+
+/// Identifies the dialog a message belongs to, from the point of view
+/// of the local user agent.
+/// ([RFC3261: Section 12, Dialogs](https://tools.ietf.org/html/rfc3261#section-12))
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct DialogId {
+    pub call_id: String,
+    pub local_tag: String,
+    pub remote_tag: String,
+}
+
+impl DialogId {
+    /// Compute the dialog ID of a received message. The local tag is
+    /// the To tag of a request and the From tag of a response. Returns
+    /// `None` if the message doesn't have a Call-ID and both tags.
+    pub fn incoming(msg: &SipMessage) -> Option<DialogId> {
+        let (local, remote) = if msg.is_request() {
+            (msg.to()?, msg.from()?)
+        } else {
+            (msg.from()?, msg.to()?)
+        };
+        Some(DialogId {
+            call_id: msg.call_id()?.clone(),
+            local_tag: local.tag()?.to_string(),
+            remote_tag: remote.tag()?.to_string(),
+        })
+    }
+
+    /// Compute the dialog ID of a message sent by the local user agent,
+    /// the reverse of `incoming`.
+    pub fn outgoing(msg: &SipMessage) -> Option<DialogId> {
+        let (local, remote) = if msg.is_request() {
+            (msg.from()?, msg.to()?)
+        } else {
+            (msg.to()?, msg.from()?)
+        };
+        Some(DialogId {
+            call_id: msg.call_id()?.clone(),
+            local_tag: local.tag()?.to_string(),
+            remote_tag: remote.tag()?.to_string(),
+        })
+    }
+}
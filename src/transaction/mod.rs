@@ -0,0 +1,79 @@
+//! SIP transactions as described in [RFC3261: Section 17, Transactions](https://tools.ietf.org/html/rfc3261#section-17).
+//!
+//! The transactions in this module don't own sockets or clocks. Received
+//! messages and expired timers are fed to them and they return the
+//! actions to take, so they can be driven by any transport and runtime.
+use std::time::Duration;
+
+use crate::SipMessage;
+
+pub mod client;
+pub use self::client::{ClientState, ClientTransaction};
+
+pub mod key;
+pub use self::key::{DialogId, TransactionKey};
+
+pub mod server;
+pub use self::server::{ServerState, ServerTransaction};
+
+pub mod timer;
+pub use self::timer::{SipTimers, TimerScheduler};
+
+/// Estimate of the round trip time. See `SipTimers` for using other values.
+pub const T1: Duration = Duration::from_millis(500);
+
+/// Maximum retransmit interval for non-INVITE requests and INVITE responses.
+pub const T2: Duration = Duration::from_secs(4);
+
+/// Maximum duration a message will remain in the network.
+pub const T4: Duration = Duration::from_secs(5);
+
+/// The transaction timers.
+/// ([RFC3261: Appendix A, Table of Timer Values](https://tools.ietf.org/html/rfc3261#appendix-A))
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Timer {
+    /// INVITE request retransmit interval, for unreliable transports.
+    A,
+    /// INVITE transaction timeout.
+    B,
+    /// Wait time for response retransmits of an INVITE.
+    D,
+    /// Non-INVITE request retransmit interval, for unreliable transports.
+    E,
+    /// Non-INVITE transaction timeout.
+    F,
+    /// INVITE response retransmit interval, for unreliable transports.
+    G,
+    /// Wait time for the ACK of a non-2xx final response to an INVITE.
+    H,
+    /// Wait time for ACK retransmits.
+    I,
+    /// Wait time for retransmits of a non-INVITE request.
+    J,
+    /// Wait time for response retransmits of a non-INVITE request.
+    K,
+    /// Delay after which an INVITE that wasn't answered yet gets a
+    /// 100 (Trying) response.
+    /// ([RFC3261: Section 17.2.1, INVITE Server Transaction](https://tools.ietf.org/html/rfc3261#section-17.2.1))
+    Trying,
+}
+
+/// Something the owner of a transaction has to do after feeding it
+/// a message or a timer.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TransactionAction {
+    /// Send the message to the transport, either for the first time or
+    /// as a retransmission.
+    Send(SipMessage),
+    /// Pass the received message to the transaction user.
+    PassToTu(SipMessage),
+    /// Start `Timer`, calling `on_timer` when it expires. A timer that is
+    /// started again replaces the previous one.
+    StartTimer(Timer, Duration),
+    /// Inform the transaction user that the transaction timed out.
+    Timeout,
+    /// Inform the transaction user that the transport failed.
+    TransportError,
+    /// The transaction is terminated and can be dropped.
+    Terminated,
+}
@@ -0,0 +1,227 @@
+use std::time::Duration;
+
+use crate::{
+    transaction::{SipTimers, Timer, TransactionAction},
+    Method, ResponseGenerator, SipMessage,
+};
+
+/// The states of a server transaction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ServerState {
+    /// A non-INVITE request was received and not answered yet.
+    Trying,
+    /// An INVITE was received or a provisional response was sent.
+    Proceeding,
+    /// A final response was sent, retransmissions of the request
+    /// are answered with it.
+    Completed,
+    /// The ACK for a non-2xx final response to an INVITE was received.
+    Confirmed,
+    Terminated,
+}
+
+/// The server side of a transaction, following the state machines of
+/// [RFC3261: Section 17.2.1, INVITE Server Transaction](https://tools.ietf.org/html/rfc3261#section-17.2.1)
+/// and [Section 17.2.2, Non-INVITE Server Transaction](https://tools.ietf.org/html/rfc3261#section-17.2.2).
+///
+/// Retransmissions of the request, and the ACK of a non-2xx final
+/// response, have to be matched to the transaction before being given
+/// to `on_request`. They are absorbed, replaying the last response sent.
+/// Timers firing in a state where they don't apply are ignored.
+/// # Examples
+///
+/// ```
+/// use libsip::{
+///     transaction::{ServerState, ServerTransaction, TransactionAction},
+///     SipMessage,
+/// };
+///
+/// let request = SipMessage::parse(b"OPTIONS sip:bob@example.com SIP/2.0\r\n\r\n").unwrap();
+/// let mut transaction = ServerTransaction::new(request.clone(), false);
+/// assert_eq!(vec![TransactionAction::PassToTu(request.clone())], transaction.start());
+///
+/// let response = SipMessage::parse(b"SIP/2.0 200 OK\r\n\r\n").unwrap();
+/// transaction.send_response(response.clone());
+/// assert_eq!(ServerState::Completed, transaction.state());
+/// assert_eq!(vec![TransactionAction::Send(response)], transaction.on_request(request));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct ServerTransaction {
+    request: SipMessage,
+    reliable: bool,
+    timers: SipTimers,
+    state: ServerState,
+    interval: Duration,
+    response: Option<SipMessage>,
+}
+
+impl ServerTransaction {
+    /// Create the transaction of the received `request`, which arrived
+    /// over a reliable transport, like TCP, if `reliable` is true. Call
+    /// `start` to pass it to the transaction user.
+    pub fn new(request: SipMessage, reliable: bool) -> ServerTransaction {
+        let state = if request.method() == Some(&Method::Invite) {
+            ServerState::Proceeding
+        } else {
+            ServerState::Trying
+        };
+        ServerTransaction {
+            request,
+            reliable,
+            timers: SipTimers::default(),
+            state,
+            interval: SipTimers::default().t1,
+            response: None,
+        }
+    }
+
+    /// Use `timers` instead of the default timer values.
+    pub fn timers(mut self, timers: SipTimers) -> ServerTransaction {
+        self.timers = timers;
+        self.interval = timers.t1;
+        self
+    }
+
+    /// The request of this transaction.
+    pub fn request(&self) -> &SipMessage {
+        &self.request
+    }
+
+    /// The current state of this transaction.
+    pub fn state(&self) -> ServerState {
+        self.state
+    }
+
+    /// Determine if this is an INVITE transaction.
+    pub fn is_invite(&self) -> bool {
+        self.request.method() == Some(&Method::Invite)
+    }
+
+    /// The last response sent by this transaction.
+    pub fn last_response(&self) -> Option<&SipMessage> {
+        self.response.as_ref()
+    }
+
+    /// Pass the request to the transaction user. For an INVITE the
+    /// `Trying` timer is started, a 100 (Trying) being sent when it
+    /// expires unless the transaction user responded first.
+    pub fn start(&mut self) -> Vec<TransactionAction> {
+        let mut actions = vec![TransactionAction::PassToTu(self.request.clone())];
+        if self.is_invite() {
+            actions.push(TransactionAction::StartTimer(
+                Timer::Trying,
+                self.timers.duration(Timer::Trying),
+            ));
+        }
+        actions
+    }
+
+    /// Handle a request matched to this transaction, either a
+    /// retransmission of the original request or an ACK.
+    pub fn on_request(&mut self, request: SipMessage) -> Vec<TransactionAction> {
+        let is_ack = request.method() == Some(&Method::Ack);
+        match self.state {
+            ServerState::Completed if is_ack && self.is_invite() => {
+                if self.reliable {
+                    self.state = ServerState::Terminated;
+                    vec![TransactionAction::Terminated]
+                } else {
+                    self.state = ServerState::Confirmed;
+                    vec![TransactionAction::StartTimer(
+                        Timer::I,
+                        self.timers.duration(Timer::I),
+                    )]
+                }
+            },
+            ServerState::Proceeding | ServerState::Completed if !is_ack => self.retransmit(),
+            _ => vec![],
+        }
+    }
+
+    /// Send `response`, generated by the transaction user.
+    pub fn send_response(&mut self, response: SipMessage) -> Vec<TransactionAction> {
+        let code = match response.status_code() {
+            Some(code) => code,
+            None => return vec![],
+        };
+        match self.state {
+            ServerState::Trying | ServerState::Proceeding => {},
+            _ => return vec![],
+        }
+        self.response = Some(response.clone());
+        let mut actions = vec![TransactionAction::Send(response)];
+        if code < 200 {
+            self.state = ServerState::Proceeding;
+        } else if self.is_invite() && code < 300 {
+            self.state = ServerState::Terminated;
+            actions.push(TransactionAction::Terminated);
+        } else if self.is_invite() {
+            self.state = ServerState::Completed;
+            if !self.reliable {
+                actions.push(TransactionAction::StartTimer(Timer::G, self.interval));
+            }
+            actions.push(TransactionAction::StartTimer(
+                Timer::H,
+                self.timers.duration(Timer::H),
+            ));
+        } else if self.reliable {
+            self.state = ServerState::Terminated;
+            actions.push(TransactionAction::Terminated);
+        } else {
+            self.state = ServerState::Completed;
+            actions.push(TransactionAction::StartTimer(
+                Timer::J,
+                self.timers.duration(Timer::J),
+            ));
+        }
+        actions
+    }
+
+    /// Handle the expiration of `timer`.
+    pub fn on_timer(&mut self, timer: Timer) -> Vec<TransactionAction> {
+        match (timer, self.state) {
+            (Timer::Trying, ServerState::Proceeding) if self.response.is_none() => {
+                match ResponseGenerator::reply_to(&self.request, 100).build() {
+                    Ok(trying) => self.send_response(trying),
+                    Err(_) => vec![],
+                }
+            },
+            (Timer::G, ServerState::Completed) => {
+                self.interval = std::cmp::min(self.interval * 2, self.timers.t2);
+                let mut actions = self.retransmit();
+                actions.push(TransactionAction::StartTimer(Timer::G, self.interval));
+                actions
+            },
+            (Timer::H, ServerState::Completed) => {
+                self.state = ServerState::Terminated;
+                vec![TransactionAction::Timeout, TransactionAction::Terminated]
+            },
+            (Timer::I, ServerState::Confirmed) | (Timer::J, ServerState::Completed) => {
+                self.state = ServerState::Terminated;
+                vec![TransactionAction::Terminated]
+            },
+            _ => vec![],
+        }
+    }
+
+    /// Handle a failure of the transport sending a response.
+    pub fn on_transport_error(&mut self) -> Vec<TransactionAction> {
+        match self.state {
+            ServerState::Terminated => vec![],
+            _ => {
+                self.state = ServerState::Terminated;
+                vec![
+                    TransactionAction::TransportError,
+                    TransactionAction::Terminated,
+                ]
+            },
+        }
+    }
+
+    fn retransmit(&self) -> Vec<TransactionAction> {
+        self.response
+            .iter()
+            .map(|response| TransactionAction::Send(response.clone()))
+            .collect()
+    }
+}
@@ -0,0 +1,171 @@
+use std::time::{Duration, Instant};
+
+use crate::transaction::{Timer, T1, T2, T4};
+
+/// The base timer values used by transactions, from which the value of
+/// every transaction timer is derived.
+/// ([RFC3261: Appendix A, Table of Timer Values](https://tools.ietf.org/html/rfc3261#appendix-A))
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SipTimers {
+    /// Estimate of the round trip time, 500ms by default.
+    pub t1: Duration,
+    /// Maximum retransmit interval for non-INVITE requests and INVITE
+    /// responses, 4s by default.
+    pub t2: Duration,
+    /// Maximum duration a message will remain in the network, 5s by default.
+    pub t4: Duration,
+}
+
+impl Default for SipTimers {
+    fn default() -> SipTimers {
+        SipTimers {
+            t1: T1,
+            t2: T2,
+            t4: T4,
+        }
+    }
+}
+
+impl SipTimers {
+    /// Create timers with the default values.
+    pub fn new() -> SipTimers {
+        SipTimers::default()
+    }
+
+    /// Set the value of T1.
+    pub fn t1(mut self, t1: Duration) -> SipTimers {
+        self.t1 = t1;
+        self
+    }
+
+    /// Set the value of T2.
+    pub fn t2(mut self, t2: Duration) -> SipTimers {
+        self.t2 = t2;
+        self
+    }
+
+    /// Set the value of T4.
+    pub fn t4(mut self, t4: Duration) -> SipTimers {
+        self.t4 = t4;
+        self
+    }
+
+    /// The initial duration of `timer` on an unreliable transport.
+    /// Timers A, E and G are doubled by the transactions on every
+    /// retransmission, E and G up to T2. Timer D is at least 32s.
+    /// # Examples
+    ///
+    /// ```
+    /// use libsip::transaction::{SipTimers, Timer};
+    /// use std::time::Duration;
+    ///
+    /// let timers = SipTimers::new().t1(Duration::from_millis(100));
+    /// assert_eq!(Duration::from_millis(6400), timers.duration(Timer::B));
+    /// assert_eq!(Duration::from_secs(5), timers.duration(Timer::K));
+    /// ```
+    pub fn duration(&self, timer: Timer) -> Duration {
+        match timer {
+            Timer::A | Timer::E | Timer::G => self.t1,
+            Timer::B | Timer::F | Timer::H | Timer::J => self.t1 * 64,
+            Timer::D => std::cmp::max(self.t1 * 64, Duration::from_secs(32)),
+            Timer::I | Timer::K => self.t4,
+            Timer::Trying => Duration::from_millis(200),
+        }
+    }
+}
+
+/// A list of pending timers, for driving transactions, registration
+/// refreshes or anything else that waits from a sync or async runtime.
+/// The caller supplies the current time: `next_deadline` tells it how
+/// long it may sleep and `poll` returns the timers that fired.
+/// # Examples
+///
+/// ```
+/// use libsip::transaction::{Timer, TimerScheduler};
+/// use std::time::{Duration, Instant};
+///
+/// let now = Instant::now();
+/// let mut scheduler = TimerScheduler::new();
+/// scheduler.schedule(("branch1", Timer::E), now, Duration::from_millis(500));
+/// scheduler.schedule(("branch1", Timer::F), now, Duration::from_secs(32));
+/// assert_eq!(Some(now + Duration::from_millis(500)), scheduler.next_deadline());
+///
+/// let fired = scheduler.poll(now + Duration::from_secs(1));
+/// assert_eq!(vec![("branch1", Timer::E)], fired);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimerScheduler<K> {
+    timers: Vec<(Instant, K)>,
+}
+
+impl<K> Default for TimerScheduler<K> {
+    fn default() -> TimerScheduler<K> {
+        TimerScheduler { timers: vec![] }
+    }
+}
+
+impl<K: PartialEq> TimerScheduler<K> {
+    /// Create an empty scheduler.
+    pub fn new() -> TimerScheduler<K> {
+        TimerScheduler::default()
+    }
+
+    /// Start the timer `key`, firing `after` the time `now`. A pending
+    /// timer with the same key is replaced.
+    pub fn schedule(&mut self, key: K, now: Instant, after: Duration) {
+        self.cancel(&key);
+        let deadline = now + after;
+        let index = self
+            .timers
+            .iter()
+            .position(|(other, _)| *other > deadline)
+            .unwrap_or(self.timers.len());
+        self.timers.insert(index, (deadline, key));
+    }
+
+    /// Stop the timer `key`, returning true if it was pending.
+    pub fn cancel(&mut self, key: &K) -> bool {
+        let len = self.timers.len();
+        self.timers.retain(|(_, other)| other != key);
+        len != self.timers.len()
+    }
+
+    /// Stop every timer matching `predicate`, e.g. the timers of
+    /// a terminated transaction.
+    pub fn cancel_where<F: FnMut(&K) -> bool>(&mut self, mut predicate: F) {
+        self.timers.retain(|(_, key)| !predicate(key));
+    }
+
+    /// The time at which the next timer fires.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.timers.first().map(|(deadline, _)| *deadline)
+    }
+
+    /// The time left until the next timer fires from `now`, zero if
+    /// a timer already expired.
+    pub fn next_timeout(&self, now: Instant) -> Option<Duration> {
+        self.next_deadline()
+            .map(|deadline| deadline.saturating_duration_since(now))
+    }
+
+    /// Remove and return the timers that expired at `now`, in the order
+    /// they expired.
+    pub fn poll(&mut self, now: Instant) -> Vec<K> {
+        let expired = self
+            .timers
+            .iter()
+            .take_while(|(deadline, _)| *deadline <= now)
+            .count();
+        self.timers.drain(..expired).map(|(_, key)| key).collect()
+    }
+
+    /// The number of pending timers.
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Determine if no timer is pending.
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+}
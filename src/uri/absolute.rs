@@ -0,0 +1,90 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use nom::{
+    bytes::complete::{take_while, take_while1, take_while_m_n},
+    character::complete::char,
+    combinator::recognize,
+    error::ParseError,
+    sequence::pair,
+    IResult,
+};
+
+use std::fmt;
+
+use crate::uri::{parse_schema, Uri};
+
+/// An absolute uri that is neither a sip, sips nor tel uri, such as
+/// `urn:service:sos` or `http://www.example.com/alice/photo.jpg`.
+/// Everything after the scheme is kept unparsed.
+/// ([RFC3261: Page 224, "absoluteURI"](https://tools.ietf.org/html/rfc3261#page-224))
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AbsoluteUri {
+    pub scheme: String,
+    /// The part of the uri after `scheme:`, e.g. `service:sos`.
+    pub rest: String,
+}
+
+impl AbsoluteUri {
+    pub fn new<S: Into<String>, R: Into<String>>(scheme: S, rest: R) -> AbsoluteUri {
+        AbsoluteUri {
+            scheme: scheme.into(),
+            rest: rest.into(),
+        }
+    }
+}
+
+impl fmt::Display for AbsoluteUri {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.scheme, self.rest)
+    }
+}
+
+/// Parse any absolute uri (`scheme ":" rest`), keeping everything after the
+/// schema unparsed. Used as a fallback for header values such as
+/// `<http://www.example.com/alice/photo.jpg>` or `<urn:service:sos>`,
+/// and for Request-URIs such as `urn:service:sos`.
+/// Parsing stops at whitespace, `<`, `>` and `"`. Sip, sips and tel uris
+/// are rejected, as are values like `example.com:5060` that are a host and
+/// port.
+/// ([RFC3261: Page 224, "absoluteURI"](https://tools.ietf.org/html/rfc3261#page-224))
+pub fn parse_absolute_uri<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Uri, E> {
+    parse_absolute_uri_until(b"<>\"", input)
+}
+
+/// Parse an absolute uri that is not enclosed in angle brackets, any
+/// parameters after `;` are left unparsed as they belong to the header.
+pub fn parse_absolute_uri_without_params<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Uri, E> {
+    parse_absolute_uri_until(b"<>\";,", input)
+}
+
+fn parse_absolute_uri_until<'a, E: ParseError<&'a [u8]>>(
+    stop: &'static [u8],
+    input: &'a [u8],
+) -> IResult<&'a [u8], Uri, E> {
+    let (input, schema) = recognize(pair(
+        take_while_m_n(1, 1, |chr: u8| chr.is_ascii_alphabetic()),
+        take_while(|chr: u8| chr.is_ascii_alphanumeric() || b"+-.".contains(&chr)),
+    ))(input)?;
+    let (input, _) = char(':')(input)?;
+    let (input, rest) =
+        take_while1(|chr: u8| chr.is_ascii_graphic() && !stop.contains(&chr))(input)?;
+    if matches!(parse_schema::<E>(schema), Ok((&[], _))) || rest[0].is_ascii_digit() {
+        return Err(nom::Err::Error(E::from_error_kind(
+            input,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    Ok((
+        input,
+        Uri::absolute(
+            String::from_utf8_lossy(schema),
+            String::from_utf8_lossy(rest),
+        ),
+    ))
+}
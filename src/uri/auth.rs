@@ -1,21 +1,31 @@
-use crate::parse::slice_to_string;
+use crate::{
+    parse::slice_to_string,
+    uri::escape::{is_password_char, is_user_char, unescape, write_escaped},
+};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use nom::{
-    bytes::complete::take_while,
-    character::{complete::char, is_alphanumeric},
-    combinator::{map_res, opt},
+    bytes::complete::{take_while, take_while1},
+    character::complete::char,
+    combinator::{map, map_res, opt},
     error::ParseError,
     IResult,
 };
 
 use std::fmt;
 
-/// URI Credentials
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+/// URI Credentials. The username and password are stored unescaped
+/// and escaped again when written.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UriAuth {
     pub username: String,
     pub password: Option<String>,
+    /// Leave the password out when writing this UriAuth, as recommended by
+    /// [RFC3261: Section 19.1.1](https://tools.ietf.org/html/rfc3261#section-19.1.1).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub redact_password: bool,
 }
 
 impl UriAuth {
@@ -24,6 +34,7 @@ impl UriAuth {
         UriAuth {
             username: username.into(),
             password: None,
+            redact_password: false,
         }
     }
 
@@ -32,14 +43,21 @@ impl UriAuth {
         self.password = Some(p.into());
         self
     }
+
+    /// Omit the password when this UriAuth is written. The password
+    /// is still available in the `password` field.
+    pub fn redact_password(mut self) -> UriAuth {
+        self.redact_password = true;
+        self
+    }
 }
 
 impl fmt::Display for UriAuth {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if let Some(pass) = &self.password {
-            write!(f, "{}:{}", &self.username, pass)?;
-        } else {
-            write!(f, "{}", &self.username)?;
+        write_escaped(&self.username, is_user_char, f)?;
+        if let (Some(pass), false) = (&self.password, self.redact_password) {
+            write!(f, ":")?;
+            write_escaped(pass, is_password_char, f)?;
         }
         Ok(())
     }
@@ -49,18 +67,35 @@ impl fmt::Display for UriAuth {
 pub fn parse_uriauth<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], UriAuth, E> {
-    let (input, username) = map_res(take_while(is_alphanumeric), slice_to_string::<E>)(input)?;
+    let (input, username) = map(
+        map_res(
+            take_while1(|chr| is_user_char(chr) || chr == b'%'),
+            slice_to_string::<E>,
+        ),
+        |username| unescape(&username),
+    )(input)?;
     let (input, password) = opt(parse_password::<E>)(input)?;
     let (input, _) = char('@')(input)?;
-    Ok((input, UriAuth { username, password }))
+    Ok((
+        input,
+        UriAuth {
+            username,
+            password,
+            redact_password: false,
+        },
+    ))
 }
 
-/// Currently this will only accept alphanumeric characters.
+/// Parse the password of a uri, replacing escape sequences.
 pub fn parse_password<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], String, E> {
     let (input, _) = char(':')(input)?;
-    Ok(map_res(take_while(is_alphanumeric), slice_to_string::<E>)(
-        input,
-    )?)
+    map(
+        map_res(
+            take_while(|chr| is_password_char(chr) || chr == b'%'),
+            slice_to_string::<E>,
+        ),
+        |password| unescape(&password),
+    )(input)
 }
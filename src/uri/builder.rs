@@ -0,0 +1,94 @@
+use crate::uri::{Domain, Uri, UriAuth, UriParam, UriSchema};
+
+/// Fluent builder for a `Uri`.
+/// # Examples
+///
+/// ```
+/// use libsip::{uri::UriBuilder, Transport};
+///
+/// let uri = UriBuilder::sip("example.com")
+///     .user("alice")
+///     .port(5061)
+///     .parameter("transport", "tcp")
+///     .build();
+/// assert_eq!(Some(Transport::Tcp), uri.transport());
+/// assert_eq!("sip:alice@example.com:5061;transport=TCP", format!("{}", uri));
+/// ```
+#[derive(Debug, Clone)]
+pub struct UriBuilder {
+    uri: Uri,
+}
+
+impl UriBuilder {
+    /// Create a new builder for a Uri with `schema` and `host`.
+    pub fn new<D: Into<Domain>>(schema: UriSchema, host: D) -> UriBuilder {
+        UriBuilder {
+            uri: Uri::new(schema, host.into()),
+        }
+    }
+
+    /// Create a new builder for a `sip` Uri.
+    pub fn sip<D: Into<Domain>>(host: D) -> UriBuilder {
+        UriBuilder::new(UriSchema::Sip, host)
+    }
+
+    /// Create a new builder for a `sips` Uri.
+    pub fn sips<D: Into<Domain>>(host: D) -> UriBuilder {
+        UriBuilder::new(UriSchema::Sips, host)
+    }
+
+    /// Set the username, keeping the password if there is one.
+    pub fn user<S: Into<String>>(mut self, user: S) -> UriBuilder {
+        let password = self.uri.auth.take().and_then(|auth| auth.password);
+        let mut auth = UriAuth::new(user);
+        auth.password = password;
+        self.uri.auth = Some(auth);
+        self
+    }
+
+    /// Set the password. This has no effect until a username is set.
+    pub fn password<S: Into<String>>(mut self, password: S) -> UriBuilder {
+        self.uri.auth = self.uri.auth.map(|auth| auth.password(password));
+        self
+    }
+
+    /// Set the port of the host.
+    pub fn port(mut self, port: u16) -> UriBuilder {
+        self.uri.host.set_port(Some(port));
+        self
+    }
+
+    /// Add a parameter with a value. Known parameters such as `transport`
+    /// are stored as their typed `UriParam` variant.
+    pub fn parameter<N: AsRef<str>, V: AsRef<str>>(mut self, name: N, value: V) -> UriBuilder {
+        let param = UriParam::from_key::<(&[u8], nom::error::ErrorKind)>(
+            name.as_ref().as_bytes(),
+            value.as_ref().as_bytes(),
+        )
+        .unwrap_or_else(|_| UriParam::Other(name.as_ref().into(), Some(value.as_ref().into())));
+        self.uri.parameters.push(param);
+        self
+    }
+
+    /// Add a parameter without a value, e.g. `lr`.
+    pub fn flag<N: AsRef<str>>(mut self, name: N) -> UriBuilder {
+        let param = match name.as_ref() {
+            "lr" => UriParam::Lr,
+            "rport" => UriParam::RPort(None),
+            name => UriParam::Other(name.into(), None),
+        };
+        self.uri.parameters.push(param);
+        self
+    }
+
+    /// Add a header to the headers section of the Uri.
+    pub fn header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> UriBuilder {
+        self.uri = self.uri.header(name, value);
+        self
+    }
+
+    /// Produce the Uri.
+    pub fn build(self) -> Uri {
+        self.uri
+    }
+}
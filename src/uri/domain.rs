@@ -1,18 +1,101 @@
-use nom::character::{is_alphanumeric, is_digit};
+use nom::character::{is_alphanumeric, is_digit, is_hex_digit};
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use std::{fmt, net::Ipv4Addr};
+use std::{
+    fmt,
+    net::{Ipv4Addr, Ipv6Addr},
+};
 
 use crate::parse::{parse_ip_address, parse_u16, slice_to_string};
 
-/// Domain address for a URI. Currently Only Ipv4 and
-/// domains are implemented.
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+/// Default port for `sip` uris and unsecured transports.
+pub const DEFAULT_SIP_PORT: u16 = 5060;
+
+/// Default port for `sips` uris.
+pub const DEFAULT_SIPS_PORT: u16 = 5061;
+
+/// Host part of a URI without the port.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Host {
+    Domain(String),
+    Ipv4(Ipv4Addr),
+    Ipv6(Ipv6Addr),
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Host::Domain(domain) => write!(f, "{}", domain),
+            Host::Ipv4(addr) => write!(f, "{}", addr),
+            Host::Ipv6(addr) => write!(f, "[{}]", addr),
+        }
+    }
+}
+
+/// Domain address for a URI, either an ip address or a domain name,
+/// with an optional port.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Domain {
     Ipv4(Ipv4Addr, Option<u16>),
+    /// IPv6 reference, written enclosed in brackets.
+    Ipv6(Ipv6Addr, Option<u16>),
     Domain(String, Option<u16>),
 }
 
+impl Domain {
+    /// Create a Domain from a `host` and optional `port`.
+    pub fn new(host: Host, port: Option<u16>) -> Domain {
+        match host {
+            Host::Domain(domain) => Domain::Domain(domain, port),
+            Host::Ipv4(addr) => Domain::Ipv4(addr, port),
+            Host::Ipv6(addr) => Domain::Ipv6(addr, port),
+        }
+    }
+
+    /// Retrieve the host without the port.
+    pub fn host(&self) -> Host {
+        match self {
+            Domain::Domain(domain, _) => Host::Domain(domain.clone()),
+            Domain::Ipv4(addr, _) => Host::Ipv4(*addr),
+            Domain::Ipv6(addr, _) => Host::Ipv6(*addr),
+        }
+    }
+
+    /// Retrieve the port if there is one.
+    pub fn port(&self) -> Option<u16> {
+        match self {
+            Domain::Domain(_, port) | Domain::Ipv4(_, port) | Domain::Ipv6(_, port) => *port,
+        }
+    }
+
+    /// Set the port, or remove it when `port` is `None`.
+    pub fn set_port(&mut self, port: Option<u16>) {
+        match self {
+            Domain::Domain(_, old) | Domain::Ipv4(_, old) | Domain::Ipv6(_, old) => *old = port,
+        }
+    }
+}
+
+/// Parse a host with an optional port, e.g. `example.com:5060`. Values
+/// that are not a valid host are kept as a domain name.
+impl From<&str> for Domain {
+    fn from(host: &str) -> Domain {
+        match parse_domain::<(&[u8], nom::error::ErrorKind)>(host.as_bytes()) {
+            Ok((&[], domain)) => domain,
+            _ => Domain::Domain(host.into(), None),
+        }
+    }
+}
+
+impl From<String> for Domain {
+    fn from(host: String) -> Domain {
+        Domain::from(host.as_str())
+    }
+}
+
 impl fmt::Display for Domain {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -23,6 +106,13 @@ impl fmt::Display for Domain {
                     write!(f, "{}", addr)
                 }
             },
+            Domain::Ipv6(addr, port) => {
+                if let Some(port) = port {
+                    write!(f, "[{}]:{}", addr, port)
+                } else {
+                    write!(f, "[{}]", addr)
+                }
+            },
             Domain::Domain(domain, port) => {
                 if let Some(port) = port {
                     write!(f, "{}:{}", domain, port)
@@ -36,7 +126,7 @@ impl fmt::Display for Domain {
 
 use nom::{
     branch::alt,
-    bytes::complete::take_while,
+    bytes::complete::{take_while, take_while1},
     character::complete::char,
     combinator::{map_res, opt},
     error::ParseError,
@@ -54,7 +144,26 @@ pub fn parse_port<'a, E: ParseError<&'a [u8]>>(
 }
 
 pub fn parse_domain<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Domain, E> {
-    alt((parse_ip_domain::<E>, parse_domain_domain::<E>))(input)
+    alt((
+        parse_ipv6_domain::<E>,
+        parse_ip_domain::<E>,
+        parse_domain_domain::<E>,
+    ))(input)
+}
+
+/// Parse an IPv6 reference enclosed in brackets, e.g. `[2001:db8::1]:5060`.
+pub fn parse_ipv6_domain<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Domain, E> {
+    let (input, _) = char('[')(input)?;
+    let (input, addr) = map_res(
+        take_while1::<_, _, E>(|item| is_hex_digit(item) || item == b':' || item == b'.'),
+        |item| String::from_utf8_lossy(item).parse::<Ipv6Addr>(),
+    )(input)?;
+    let (input, _) = char(']')(input)?;
+    let (input, _) = opt::<_, _, E, _>(char::<_, E>(':'))(input)?;
+    let (input, port) = parse_port::<E>(input)?;
+    Ok((input, Domain::Ipv6(addr, port)))
 }
 
 pub fn parse_ip_domain<'a, E: ParseError<&'a [u8]>>(
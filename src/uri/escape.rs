@@ -0,0 +1,72 @@
+use std::fmt;
+
+/// Replace every `%XX` escape sequence in `input` with the byte it represents.
+/// Malformed escape sequences are kept as they are.
+/// # Examples
+///
+/// ```
+/// use libsip::uri::unescape;
+/// assert_eq!(unescape("abc%40example.com%3Bto-tag%3D1"), "abc@example.com;to-tag=1");
+/// assert_eq!(unescape("100%"), "100%");
+/// ```
+pub fn unescape(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' && index + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[index + 1..index + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            if let Some(byte) = hex {
+                out.push(byte);
+                index += 3;
+                continue;
+            }
+        }
+        out.push(bytes[index]);
+        index += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+/// Write `input` escaping every byte for which `allowed` returns false.
+pub fn write_escaped(input: &str, allowed: fn(u8) -> bool, f: &mut fmt::Formatter) -> fmt::Result {
+    for byte in input.bytes() {
+        if allowed(byte) {
+            write!(f, "{}", byte as char)?;
+        } else {
+            write!(f, "%{:02X}", byte)?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks if a given character is unreserved ([RFC3261: Page 220, "unreserved"](https://tools.ietf.org/html/rfc3261#page-220))
+pub fn is_unreserved(chr: u8) -> bool {
+    chr.is_ascii_alphanumeric() || b"-_.!~*'()".contains(&chr)
+}
+
+/// Checks if a given character can be used unescaped in the name or value
+/// of a uri header ([RFC3261: Page 222, "hnv-unreserved"](https://tools.ietf.org/html/rfc3261#page-222))
+pub fn is_header_char(chr: u8) -> bool {
+    is_unreserved(chr) || b"[]/?:+$".contains(&chr)
+}
+
+/// Checks if a given character can be used unescaped in the user part
+/// of a uri ([RFC3261: Page 222, "user-unreserved"](https://tools.ietf.org/html/rfc3261#page-222))
+pub fn is_user_char(chr: u8) -> bool {
+    is_unreserved(chr) || b"&=+$,;?/".contains(&chr)
+}
+
+/// Checks if a given character can be used unescaped in the password
+/// of a uri ([RFC3261: Page 222, "password"](https://tools.ietf.org/html/rfc3261#page-222))
+pub fn is_password_char(chr: u8) -> bool {
+    is_unreserved(chr) || b"&=+$,".contains(&chr)
+}
+
+/// Checks if a given character can be used unescaped in the name or value
+/// of a uri parameter ([RFC3261: Page 222, "param-unreserved"](https://tools.ietf.org/html/rfc3261#page-222))
+pub fn is_param_char(chr: u8) -> bool {
+    is_unreserved(chr) || b"[]/:&+$".contains(&chr)
+}
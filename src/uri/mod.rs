@@ -1,14 +1,31 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use std::{fmt, io::Result as IoResult, str::FromStr};
+use std::{
+    fmt,
+    io::{Error as IoError, ErrorKind as IoErrorKind, Result as IoResult},
+    str::FromStr,
+};
 
-use nom::{character::complete::char, combinator::opt, error::ParseError, sequence::pair, IResult};
+use crate::core::Transport;
+
+use nom::{
+    bytes::complete::{take_while, take_while1},
+    character::complete::char,
+    combinator::opt,
+    error::{ParseError, VerboseError},
+    multi::separated_list1,
+    sequence::pair,
+    IResult,
+};
 
 pub mod schema;
 pub use self::schema::{parse_schema, UriSchema};
 
 pub mod domain;
-pub use self::domain::{parse_domain, parse_port, Domain};
+pub use self::domain::{
+    parse_domain, parse_ipv6_domain, parse_port, Domain, Host, DEFAULT_SIPS_PORT, DEFAULT_SIP_PORT,
+};
 
 pub mod params;
 pub use self::params::{parse_param, parse_params, UriParam};
@@ -16,13 +33,35 @@ pub use self::params::{parse_param, parse_params, UriParam};
 pub mod auth;
 pub use self::auth::{parse_uriauth, UriAuth};
 
+pub mod escape;
+pub use self::escape::unescape;
+
+pub mod builder;
+pub use self::builder::UriBuilder;
+
+pub mod tel;
+pub use self::tel::parse_telephone_subscriber;
+
+pub mod absolute;
+pub use self::absolute::{parse_absolute_uri, parse_absolute_uri_without_params, AbsoluteUri};
+
 /// Universal Rescource Identifier for libsip.
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Uri {
     pub schema: Option<UriSchema>,
+    /// The host of a sip or tel uri. Absolute uris have no host, use
+    /// `Uri::host` or `Uri::domain` which return None for them.
     pub host: Domain,
     pub auth: Option<UriAuth>,
     pub parameters: Vec<UriParam>,
+    /// Headers embedded in the uri after `?`, in the order they appear.
+    /// The values are stored unescaped.
+    pub headers: Vec<(String, String)>,
+    /// Set for absolute uris of any other scheme, see `Uri::absolute`.
+    /// The schema, host, auth, parameters and headers of such a uri are
+    /// left empty and it is written as this value alone.
+    pub absolute: Option<Box<AbsoluteUri>>,
 }
 
 impl Uri {
@@ -32,6 +71,8 @@ impl Uri {
             host,
             auth: None,
             parameters: vec![],
+            headers: vec![],
+            absolute: None,
         }
     }
 
@@ -42,6 +83,8 @@ impl Uri {
             host,
             auth: None,
             parameters: vec![],
+            headers: vec![],
+            absolute: None,
         }
     }
 
@@ -55,12 +98,70 @@ impl Uri {
         Uri::new(UriSchema::Sips, host)
     }
 
+    /// Create a new tel Uri for `number`, e.g. `+1-212-555-0101`.
+    /// The number is stored as the host of the Uri.
+    pub fn tel<S: Into<String>>(number: S) -> Uri {
+        Uri::new(UriSchema::Tel, Domain::Domain(number.into(), None))
+    }
+
+    /// Create a new absolute Uri that is neither a sip nor a tel uri,
+    /// e.g. `Uri::absolute("urn", "service:sos")`. Such a uri has no host,
+    /// its `host()` and `port()` are None and it can't be resolved.
+    pub fn absolute<S: Into<String>, R: Into<String>>(schema: S, rest: R) -> Uri {
+        Uri {
+            absolute: Some(Box::new(AbsoluteUri::new(schema, rest))),
+            ..Uri::new_schemaless(Domain::Domain(String::new(), None))
+        }
+    }
+
+    /// Determine if this is an absolute Uri parsed by `parse_absolute_uri`.
+    pub fn is_absolute(&self) -> bool {
+        self.absolute.is_some()
+    }
+
+    /// Retrieve the absolute uri this Uri holds, if it is one.
+    pub fn as_absolute(&self) -> Option<&AbsoluteUri> {
+        self.absolute.as_deref()
+    }
+
+    /// Determine if this is a tel Uri.
+    pub fn is_tel(&self) -> bool {
+        self.schema == Some(UriSchema::Tel)
+    }
+
+    /// Retrieve the number of a tel Uri without visual separators,
+    /// e.g. `+12125550101` for `tel:+1-212-555-0101`.
+    pub fn tel_number(&self) -> Option<String> {
+        match (&self.host, self.is_tel()) {
+            (Domain::Domain(number, _), true) => Some(
+                number
+                    .chars()
+                    .filter(|chr| !"-.()".contains(*chr))
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Determine if this is a tel Uri containing a global number.
+    pub fn is_global_number(&self) -> bool {
+        self.tel_number()
+            .is_some_and(|number| number.starts_with('+'))
+    }
+
     /// Add a `UriAuth` section to this Uri.
     pub fn auth(mut self, auth: UriAuth) -> Uri {
         self.auth = Some(auth);
         self
     }
 
+    /// Omit the password of this Uri when it is written, see
+    /// `UriAuth::redact_password`.
+    pub fn redact_password(mut self) -> Uri {
+        self.auth = self.auth.map(UriAuth::redact_password);
+        self
+    }
+
     /// Remove authentication if there is any.
     pub fn authless(mut self) -> Uri {
         self.auth = None;
@@ -80,6 +181,106 @@ impl Uri {
         self
     }
 
+    /// Set the `transport` parameter, replacing any previous value.
+    pub fn transport_param(self, transport: Transport) -> Uri {
+        self.replace_parameter(UriParam::Transport(transport))
+    }
+
+    /// Set the `user` parameter, replacing any previous value.
+    pub fn user_param<S: Into<String>>(self, user: S) -> Uri {
+        self.replace_parameter(UriParam::User(user.into()))
+    }
+
+    /// Add the loose routing flag `lr`.
+    pub fn lr_param(self) -> Uri {
+        self.replace_parameter(UriParam::Lr)
+    }
+
+    /// Set the `maddr` parameter, replacing any previous value.
+    pub fn maddr_param(self, maddr: Domain) -> Uri {
+        self.replace_parameter(UriParam::Maddr(maddr))
+    }
+
+    /// Set the `ttl` parameter, replacing any previous value.
+    pub fn ttl_param(self, ttl: u8) -> Uri {
+        self.replace_parameter(UriParam::Ttl(ttl))
+    }
+
+    fn replace_parameter(mut self, p: UriParam) -> Uri {
+        match self
+            .parameters
+            .iter_mut()
+            .find(|param| param.name() == p.name())
+        {
+            Some(param) => *param = p,
+            None => self.parameters.push(p),
+        }
+        self
+    }
+
+    /// Retrieve the first parameter called `name`.
+    pub fn param(&self, name: &str) -> Option<&UriParam> {
+        self.parameters.iter().find(|param| param.name() == name)
+    }
+
+    /// The value of the `transport` parameter.
+    pub fn transport(&self) -> Option<Transport> {
+        match self.param("transport") {
+            Some(UriParam::Transport(transport)) => Some(*transport),
+            _ => None,
+        }
+    }
+
+    /// The value of the `user` parameter.
+    pub fn user_type(&self) -> Option<&str> {
+        match self.param("user") {
+            Some(UriParam::User(user)) => Some(user),
+            _ => None,
+        }
+    }
+
+    /// Determine if this Uri contains the loose routing flag `lr`.
+    pub fn is_loose_route(&self) -> bool {
+        self.param("lr").is_some()
+    }
+
+    /// The value of the `maddr` parameter.
+    pub fn maddr(&self) -> Option<&Domain> {
+        match self.param("maddr") {
+            Some(UriParam::Maddr(maddr)) => Some(maddr),
+            _ => None,
+        }
+    }
+
+    /// The value of the `ttl` parameter.
+    pub fn ttl(&self) -> Option<u8> {
+        match self.param("ttl") {
+            Some(UriParam::Ttl(ttl)) => Some(*ttl),
+            _ => None,
+        }
+    }
+
+    /// Add a header to the headers section of this Uri.
+    pub fn header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Uri {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Add a list of headers. This will remove all old headers.
+    pub fn headers(mut self, headers: Vec<(String, String)>) -> Uri {
+        self.headers = headers;
+        self
+    }
+
+    /// Retrieve the unescaped value of the header `name` embedded in this Uri.
+    /// Header names are compared case insensitively.
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
     /// Remove the Schema if there is any.
     pub fn schemaless(mut self) -> Uri {
         self.schema = None;
@@ -92,15 +293,43 @@ impl Uri {
         self
     }
 
-    /// Set the host value.
-    pub fn host(&self) -> String {
-        format!("{}", self.host)
+    /// Retrieve the host of this Uri, None for absolute uris.
+    pub fn domain(&self) -> Option<&Domain> {
+        if self.is_absolute() {
+            None
+        } else {
+            Some(&self.host)
+        }
+    }
+
+    /// Retrieve the formatted host of this Uri, None for absolute uris.
+    pub fn host(&self) -> Option<String> {
+        self.domain().map(|host| format!("{}", host))
+    }
+
+    /// Retrieve the port of this Uri if there is one.
+    pub fn port(&self) -> Option<u16> {
+        self.domain().and_then(Domain::port)
+    }
+
+    /// Retrieve the port of this Uri, or the default port for its schema
+    /// and transport when none is set: 5061 for `sips` uris and secure
+    /// transports such as `;transport=tls`, 5060 otherwise.
+    pub fn port_or_default(&self) -> u16 {
+        self.port()
+            .unwrap_or_else(|| match (self.schema, self.transport()) {
+                (Some(UriSchema::Sips), _) => DEFAULT_SIPS_PORT,
+                (_, Some(transport)) => transport.default_port(),
+                (_, None) => DEFAULT_SIP_PORT,
+            })
     }
 
     /// Retrieve a formatted string containing host and parameters.
-    /// This can be used in the Via header.
+    /// This can be used in the Via header. Fails for absolute uris.
     pub fn host_and_params(&self) -> IoResult<String> {
-        let mut host = self.host();
+        let mut host = self
+            .host()
+            .ok_or_else(|| IoError::new(IoErrorKind::InvalidInput, "absolute uris have no host"))?;
         for param in &self.parameters {
             host += &format!("{}", param);
         }
@@ -110,6 +339,9 @@ impl Uri {
 
 impl fmt::Display for Uri {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(absolute) = &self.absolute {
+            return write!(f, "{}", absolute);
+        }
         if let Some(schema) = self.schema {
             write!(f, "{}:", schema)?;
         }
@@ -121,38 +353,86 @@ impl fmt::Display for Uri {
         for param in &self.parameters {
             write!(f, "{}", param)?;
         }
+        for (index, (name, value)) in self.headers.iter().enumerate() {
+            write!(f, "{}", if index == 0 { '?' } else { '&' })?;
+            escape::write_escaped(name, escape::is_header_char, f)?;
+            write!(f, "=")?;
+            escape::write_escaped(value, escape::is_header_char, f)?;
+        }
         Ok(())
     }
 }
 
 pub fn parse_uri<'a, E: ParseError<&'a [u8]>>(input: &'a [u8]) -> IResult<&'a [u8], Uri, E> {
+    let (input, mut uri) = parse_uri_without_params::<E>(input)?;
+    let (input, parameters) = parse_params::<E>(input)?;
+    let (input, headers) = opt(parse_uri_headers::<E>)(input)?;
+    uri.parameters = parameters;
+    uri.headers = headers.unwrap_or_default();
+    Ok((input, uri))
+}
+
+/// Parse the headers section of a uri, e.g. `?subject=project&priority=urgent`.
+pub fn parse_uri_headers<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Vec<(String, String)>, E> {
+    let (input, _) = char('?')(input)?;
+    separated_list1(char('&'), parse_uri_header)(input)
+}
+
+fn parse_uri_header<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], (String, String), E> {
+    let is_escaped_header_char = |chr| escape::is_header_char(chr) || chr == b'%';
+    let (input, name) = take_while1(is_escaped_header_char)(input)?;
+    let (input, _) = char('=')(input)?;
+    let (input, value) = take_while(is_escaped_header_char)(input)?;
+    Ok((
+        input,
+        (
+            unescape(&String::from_utf8_lossy(name)),
+            unescape(&String::from_utf8_lossy(value)),
+        ),
+    ))
+}
+
+/// Parse a uri leaving any parameters that follow it unparsed. Used for
+/// header values where the uri is not enclosed in angle brackets, in that
+/// case the parameters belong to the header and not to the uri.
+pub fn parse_uri_without_params<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Uri, E> {
     let (input, schema) = opt(pair(parse_schema::<E>, char(':')))(input)?;
+    if let Some((UriSchema::Tel, _)) = schema {
+        let (input, host) = parse_telephone_subscriber::<E>(input)?;
+        return Ok((input, Uri::new(UriSchema::Tel, host)));
+    }
     let (input, auth) = opt(parse_uriauth::<E>)(input)?;
     let (input, host) = parse_domain::<E>(input)?;
-    let (input, parameters) = parse_params::<E>(input)?;
     Ok((
         input,
         Uri {
             schema: schema.map(|item| item.0),
             host,
-            parameters,
+            parameters: vec![],
+            headers: vec![],
             auth,
+            absolute: None,
         },
     ))
 }
 
+/// Parse a complete Uri, e.g. `sip:alice@example.com;transport=tcp`.
 impl FromStr for Uri {
-    type Err = nom::Err<nom::error::ErrorKind>;
+    type Err = IoError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(parse_uri(s.as_bytes()).map_err(map_error)?.1)
-    }
-}
-
-fn map_error(n: nom::Err<(&[u8], nom::error::ErrorKind)>) -> nom::Err<nom::error::ErrorKind> {
-    match n {
-        nom::Err::Error((_, a)) => nom::Err::Error(a),
-        nom::Err::Failure((_, b)) => nom::Err::Failure(b),
-        nom::Err::Incomplete(a) => nom::Err::Incomplete(a),
+        match parse_uri::<VerboseError<&[u8]>>(s.as_bytes()) {
+            Ok((&[], uri)) => Ok(uri),
+            _ => Err(IoError::new(
+                IoErrorKind::InvalidInput,
+                format!("Failed to parse uri: {}", s),
+            )),
+        }
     }
 }
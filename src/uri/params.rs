@@ -1,30 +1,41 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+
 use std::fmt;
 
 use crate::{
     core::{parse_transport, Transport},
-    parse::is_token,
-    uri::{parse_domain, parse_port, Domain},
+    parse::parse_u8,
+    uri::{
+        escape::{is_param_char, unescape, write_escaped},
+        parse_domain, parse_port, Domain,
+    },
 };
 
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while},
-    character::is_alphabetic,
+    bytes::complete::{tag, take_while, take_while1},
     combinator::map,
     error::ParseError,
     IResult,
 };
 
-/// Uri Parameters.
-///
-/// TODO: Expand this enum. Similar to `libsip::Header`
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+/// Uri Parameters ([RFC3261: Section 19.1.1, SIP and SIPS URI Components](https://tools.ietf.org/html/rfc3261#section-19.1.1)).
+/// Parameters without a variant, or with a value that could not be
+/// parsed, are kept as `Other`.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum UriParam {
     Transport(Transport),
     Branch(String),
     Received(Domain),
     RPort(Option<u16>),
+    /// The `user` parameter, e.g. `phone` or `ip`.
+    User(String),
+    /// The loose routing flag `lr`.
+    Lr,
+    Maddr(Domain),
+    Ttl(u8),
     Other(String, Option<String>),
 }
 
@@ -34,21 +45,50 @@ impl UriParam {
         key: &'a [u8],
         value: &'a [u8],
     ) -> Result<UriParam, nom::Err<E>> {
+        let other = || {
+            UriParam::Other(
+                unescape(&String::from_utf8_lossy(key)),
+                Some(unescape(&String::from_utf8_lossy(value))),
+            )
+        };
         match key {
-            b"rport" => Ok(UriParam::RPort(parse_port::<E>(&value)?.1)),
-            b"transport" => Ok(UriParam::Transport(parse_transport::<E>(&value)?.1)),
+            b"rport" => Ok(UriParam::RPort(parse_port::<E>(value)?.1)),
+            b"transport" => Ok(match parse_transport::<E>(value) {
+                Ok((&[], transport)) => UriParam::Transport(transport),
+                _ => other(),
+            }),
             b"branch" => Ok(UriParam::Branch(
                 String::from_utf8(value.to_vec()).expect("Utf-8 Error"),
             )),
             b"received" => {
                 //let mut data = value.to_vec();
                 //data.push(b' ');
-                Ok(UriParam::Received(parse_domain::<E>(&value)?.1))
+                Ok(UriParam::Received(parse_domain::<E>(value)?.1))
             },
-            _method => Ok(UriParam::Other(
-                String::from_utf8_lossy(key).to_string(),
-                Some(String::from_utf8_lossy(value).to_string()),
-            )),
+            b"user" => Ok(UriParam::User(String::from_utf8_lossy(value).to_string())),
+            b"maddr" => Ok(match parse_domain::<E>(value) {
+                Ok((&[], domain)) => UriParam::Maddr(domain),
+                _ => other(),
+            }),
+            b"ttl" => Ok(parse_u8(value)
+                .map(UriParam::Ttl)
+                .unwrap_or_else(|_| other())),
+            _method => Ok(other()),
+        }
+    }
+
+    /// Get the name of this parameter.
+    pub fn name(&self) -> &str {
+        match self {
+            UriParam::Transport(_) => "transport",
+            UriParam::Branch(_) => "branch",
+            UriParam::Received(_) => "received",
+            UriParam::RPort(_) => "rport",
+            UriParam::User(_) => "user",
+            UriParam::Lr => "lr",
+            UriParam::Maddr(_) => "maddr",
+            UriParam::Ttl(_) => "ttl",
+            UriParam::Other(key, _) => key,
         }
     }
 }
@@ -61,8 +101,19 @@ impl fmt::Display for UriParam {
             UriParam::Received(branch) => write!(f, ";received={}", branch),
             UriParam::RPort(Some(value)) => write!(f, ";rport={}", value),
             UriParam::RPort(None) => write!(f, ";rport"),
-            UriParam::Other(key, Some(value)) => write!(f, ";{}={}", key, value),
-            UriParam::Other(key, None) => write!(f, ";{}", key),
+            UriParam::User(user) => write!(f, ";user={}", user),
+            UriParam::Lr => write!(f, ";lr"),
+            UriParam::Maddr(maddr) => write!(f, ";maddr={}", maddr),
+            UriParam::Ttl(ttl) => write!(f, ";ttl={}", ttl),
+            UriParam::Other(key, value) => {
+                write!(f, ";")?;
+                write_escaped(key, is_param_char, f)?;
+                if let Some(value) = value {
+                    write!(f, "=")?;
+                    write_escaped(value, is_param_char, f)?;
+                }
+                Ok(())
+            },
         }
     }
 }
@@ -80,21 +131,28 @@ pub fn parse_named_param<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], UriParam, E> {
     let (input, _) = tag(";")(input)?;
-    let (input, key) = take_while(is_alphabetic)(input)?;
+    let (input, key) = take_while1(is_escaped_param_char)(input)?;
     let (input, _) = tag("=")(input)?;
-    let (input, value) = take_while(is_token)(input)?;
-    UriParam::from_key::<E>(key, value).and_then(|item| Ok((input, item)))
+    let (input, value) = take_while(is_escaped_param_char)(input)?;
+    UriParam::from_key::<E>(key, value).map(|item| (input, item))
 }
 
 pub fn parse_single_param<'a, E: ParseError<&'a [u8]>>(
     input: &'a [u8],
 ) -> IResult<&'a [u8], UriParam, E> {
     let (input, _) = tag(";")(input)?;
-    let (input, key) = take_while(is_alphabetic)(input)?;
-    Ok((
-        input,
-        UriParam::Other(String::from_utf8_lossy(key).into(), None),
-    ))
+    let (input, key) = take_while1(is_escaped_param_char)(input)?;
+    match key {
+        b"lr" => Ok((input, UriParam::Lr)),
+        _ => Ok((
+            input,
+            UriParam::Other(unescape(&String::from_utf8_lossy(key)), None),
+        )),
+    }
+}
+
+fn is_escaped_param_char(chr: u8) -> bool {
+    is_param_char(chr) || chr == b'%'
 }
 
 /// Parse multiple uri parameters.
@@ -104,7 +162,7 @@ pub fn parse_params<'a, E: ParseError<&'a [u8]>>(
     let mut results = vec![];
     let mut data = input;
 
-    while let Ok((remains, param)) = parse_param::<E>(&data) {
+    while let Ok((remains, param)) = parse_param::<E>(data) {
         results.push(param);
         data = remains;
     }
@@ -0,0 +1,39 @@
+use nom::{
+    bytes::complete::take_while1, character::is_hex_digit, combinator::map_res, error::ParseError,
+    IResult,
+};
+
+use crate::{parse::slice_to_string, uri::Domain};
+
+/// Checks if a given character is a visual separator in a telephone
+/// number ([RFC3966: Page 5, "visual-separator"](https://tools.ietf.org/html/rfc3966#page-5))
+pub fn is_visual_separator(chr: u8) -> bool {
+    b"-.()".contains(&chr)
+}
+
+/// Checks if a given character can be part of a local or global
+/// telephone number ([RFC3966: Page 5, "phonedigit-hex"](https://tools.ietf.org/html/rfc3966#page-5))
+pub fn is_phonedigit_hex(chr: u8) -> bool {
+    is_hex_digit(chr) || is_visual_separator(chr) || chr == b'*' || chr == b'#'
+}
+
+/// Parse the number of a tel uri, either a global number starting with `+`
+/// or a local number. Visual separators are kept so the number is written
+/// back as it was received.
+/// ([RFC3966: Page 5, "telephone-subscriber"](https://tools.ietf.org/html/rfc3966#page-5))
+pub fn parse_telephone_subscriber<'a, E: ParseError<&'a [u8]>>(
+    input: &'a [u8],
+) -> IResult<&'a [u8], Domain, E> {
+    let (rest, number) = map_res(
+        take_while1(|chr| chr == b'+' || is_phonedigit_hex(chr)),
+        slice_to_string::<E>,
+    )(input)?;
+    let digits = number.strip_prefix('+').unwrap_or(&number);
+    if digits.is_empty() || digits.contains('+') || !digits.bytes().any(|c| c.is_ascii_hexdigit()) {
+        return Err(nom::Err::Error(E::from_error_kind(
+            input,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+    Ok((rest, Domain::Domain(number, None)))
+}
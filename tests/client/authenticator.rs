@@ -0,0 +1,137 @@
+use libsip::*;
+
+use std::io::ErrorKind;
+
+fn request() -> SipMessage {
+    SipMessage::parse(
+        b"INVITE sip:bob@biloxi.com SIP/2.0\r\n\
+        Via: SIP/2.0/UDP pc33.atlanta.com;branch=z9hG4bK776asdhds\r\n\
+        Call-ID: a84b4c76e66710@pc33.atlanta.com\r\n\
+        CSeq: 314159 INVITE\r\n\
+        Content-Length: 0\r\n\r\n",
+    )
+    .unwrap()
+}
+
+fn challenged(challenges: &str) -> SipMessage {
+    let response = format!(
+        "SIP/2.0 407 Proxy Authentication Required\r\n\
+        Via: SIP/2.0/UDP pc33.atlanta.com;branch=z9hG4bK776asdhds\r\n\
+        Call-ID: a84b4c76e66710@pc33.atlanta.com\r\n\
+        CSeq: 314159 INVITE\r\n\
+        {}\
+        Content-Length: 0\r\n\r\n",
+        challenges
+    );
+    SipMessage::parse(response.as_bytes()).unwrap()
+}
+
+fn credentials(msg: &SipMessage) -> Vec<(bool, String, String)> {
+    msg.headers()
+        .iter()
+        .filter_map(|header| match header {
+            Header::Authorization(auth) => Some((false, auth)),
+            Header::ProxyAuthorization(auth) => Some((true, auth)),
+            _ => None,
+        })
+        .map(|(proxy, auth)| (proxy, auth.1["realm"].clone(), auth.1["username"].clone()))
+        .collect()
+}
+
+#[test]
+fn multiple_realms() {
+    let mut authenticator = Authenticator::new()
+        .credentials("alice", "secret")
+        .realm_credentials("biloxi.com", "alice@atlanta.com", "other");
+    let response = challenged(
+        "Proxy-Authenticate: Digest realm=\"atlanta.com\", nonce=\"abc\", qop=\"auth\"\r\n\
+        WWW-Authenticate: Digest realm=\"biloxi.com\", nonce=\"def\", qop=\"auth\"\r\n",
+    );
+    let resend = authenticator.resend(&request(), &response).unwrap();
+
+    assert_eq!(Some((314160, &Method::Invite)), resend.cseq());
+    assert_eq!(request().call_id(), resend.call_id());
+    assert_ne!(
+        Some("z9hG4bK776asdhds"),
+        resend.top_via().and_then(|via| via.branch())
+    );
+    assert_eq!(
+        vec![
+            (true, "atlanta.com".to_string(), "alice".to_string()),
+            (
+                false,
+                "biloxi.com".to_string(),
+                "alice@atlanta.com".to_string()
+            ),
+        ],
+        credentials(&resend)
+    );
+
+    // Only the credentials of the challenged realm are replaced.
+    let response = challenged(
+        "Proxy-Authenticate: Digest realm=\"atlanta.com\", nonce=\"ghi\", qop=\"auth\", stale=true\r\n",
+    );
+    let again = authenticator.resend(&resend, &response).unwrap();
+    assert_eq!(Some((314161, &Method::Invite)), again.cseq());
+    assert_eq!(
+        vec![
+            (
+                false,
+                "biloxi.com".to_string(),
+                "alice@atlanta.com".to_string()
+            ),
+            (true, "atlanta.com".to_string(), "alice".to_string()),
+        ],
+        credentials(&again)
+    );
+}
+
+#[test]
+fn rejected() {
+    let mut authenticator = Authenticator::new().credentials("alice", "wrong");
+    let response =
+        challenged("Proxy-Authenticate: Digest realm=\"atlanta.com\", nonce=\"abc\"\r\n");
+    let resend = authenticator.resend(&request(), &response).unwrap();
+    let error = authenticator.resend(&resend, &response).unwrap_err();
+    assert_eq!(ErrorKind::PermissionDenied, error.kind());
+}
+
+#[test]
+fn fresh_challenges() {
+    let mut authenticator = Authenticator::new().credentials("alice", "secret");
+    // Every new request is challenged again, with a new nonce.
+    for nonce in &["abc", "def"] {
+        let response = challenged(&format!(
+            "Proxy-Authenticate: Digest realm=\"atlanta.com\", nonce=\"{}\"\r\n",
+            nonce
+        ));
+        let resend = authenticator.resend(&request(), &response).unwrap();
+        assert_eq!(
+            vec![(true, "atlanta.com".to_string(), "alice".to_string())],
+            credentials(&resend)
+        );
+        let nonces: Vec<_> = resend
+            .headers()
+            .iter()
+            .filter_map(|header| match header {
+                Header::ProxyAuthorization(auth) => auth.nonce(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(vec![*nonce], nonces);
+    }
+}
+
+#[test]
+fn missing_credentials() {
+    let mut authenticator = Authenticator::new().realm_credentials("biloxi.com", "bob", "secret");
+    let response =
+        challenged("Proxy-Authenticate: Digest realm=\"atlanta.com\", nonce=\"abc\"\r\n");
+    let error = authenticator.resend(&request(), &response).unwrap_err();
+    assert_eq!(ErrorKind::PermissionDenied, error.kind());
+
+    let error = authenticator
+        .resend(&request(), &challenged(""))
+        .unwrap_err();
+    assert_eq!(ErrorKind::InvalidInput, error.kind());
+}
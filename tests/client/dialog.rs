@@ -0,0 +1,202 @@
+use libsip::*;
+
+const INVITE: &[u8] = b"INVITE sip:bob@example.com SIP/2.0\r\n\
+    Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+    From: Alice <sip:alice@example.com>;tag=1928301774\r\n\
+    To: Bob <sip:bob@example.com>\r\n\
+    Call-ID: a84b4c76e66710\r\n\
+    CSeq: 314159 INVITE\r\n\
+    Contact: <sip:alice@pc33.example.com>\r\n\
+    Record-Route: <sip:p2.example.com;lr>, <sip:p1.example.com;lr>\r\n\r\n";
+
+const OK: &[u8] = b"SIP/2.0 200 OK\r\n\
+    Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+    Record-Route: <sip:p2.example.com;lr>, <sip:p1.example.com;lr>\r\n\
+    From: Alice <sip:alice@example.com>;tag=1928301774\r\n\
+    To: Bob <sip:bob@example.com>;tag=a6c85cf\r\n\
+    Call-ID: a84b4c76e66710\r\n\
+    CSeq: 314159 INVITE\r\n\
+    Contact: <sip:bob@192.0.2.4>\r\n\r\n";
+
+fn route_hosts(msg: &SipMessage) -> Vec<Domain> {
+    msg.headers()
+        .route_set()
+        .into_iter()
+        .map(|route| route.uri.host)
+        .collect()
+}
+
+#[test]
+fn uac_bye() {
+    let invite = SipMessage::parse(INVITE).unwrap();
+    let ok = SipMessage::parse(OK).unwrap();
+    let mut dialog = Dialog::from_uac(&invite, &ok).unwrap();
+    let bye = dialog.bye(&HeaderWriteConfig::default()).unwrap();
+    assert_eq!(314160, dialog.local_seq);
+
+    assert_eq!(Some(&Method::Bye), bye.method());
+    assert_eq!(
+        Some(&Uri::sip(ip_domain!(192, 0, 2, 4)).auth(uri_auth!("bob"))),
+        bye.uri()
+    );
+    assert_eq!(Some("1928301774"), bye.from().unwrap().tag());
+    assert_eq!(Some("a6c85cf"), bye.to().unwrap().tag());
+    assert_eq!(Some(&"a84b4c76e66710".to_string()), bye.call_id());
+    assert_eq!(Some((314160, &Method::Bye)), bye.cseq());
+    assert_eq!(
+        vec![domain!("p1.example.com"), domain!("p2.example.com")],
+        route_hosts(&bye)
+    );
+    let via = bye.top_via().unwrap();
+    assert_eq!(domain!("pc33.example.com"), via.host);
+    assert_ne!(Some("z9hG4bK776asdhds"), via.branch());
+}
+
+#[test]
+fn uas_bye() {
+    let invite = SipMessage::parse(INVITE).unwrap();
+    let ok = SipMessage::parse(OK).unwrap();
+    let via = ViaHeader::new(domain!("192.0.2.4"), Transport::Tcp);
+    let mut dialog = Dialog::from_uas(&invite, &ok, via).unwrap();
+    assert_eq!(Some(314159), dialog.remote_seq);
+    let bye = dialog.bye(&HeaderWriteConfig::default()).unwrap();
+
+    assert_eq!(
+        Some(&Uri::sip(domain!("pc33.example.com")).auth(uri_auth!("alice"))),
+        bye.uri()
+    );
+    assert_eq!(Some("a6c85cf"), bye.from().unwrap().tag());
+    assert_eq!(Some("1928301774"), bye.to().unwrap().tag());
+    assert_eq!(Some((1, &Method::Bye)), bye.cseq());
+    assert_eq!(
+        vec![domain!("p2.example.com"), domain!("p1.example.com")],
+        route_hosts(&bye)
+    );
+    assert_eq!(Transport::Tcp, bye.top_via().unwrap().transport);
+
+    dialog.via = None;
+    let error = dialog.bye(&HeaderWriteConfig::default()).unwrap_err();
+    assert!(error.to_string().contains("Via"));
+}
+
+#[test]
+fn strict_route() {
+    let invite = SipMessage::parse(INVITE).unwrap();
+    let ok = SipMessage::parse(OK).unwrap();
+    let mut dialog = Dialog::from_uac(&invite, &ok).unwrap();
+    dialog.route_set = vec![NamedHeader::new(Uri::sip(domain!("strict.example.com")))];
+    let req = dialog.new_request(Method::Info).build().unwrap();
+    assert_eq!(Some(&Uri::sip(domain!("strict.example.com"))), req.uri());
+    assert_eq!(vec![ip_domain!(192, 0, 2, 4)], route_hosts(&req));
+}
+
+#[test]
+fn early_dialog() {
+    let invite = SipMessage::parse(INVITE).unwrap();
+    let ringing = SipMessage::parse(
+        b"SIP/2.0 180 Ringing\r\n\
+        Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+        From: Alice <sip:alice@example.com>;tag=1928301774\r\n\
+        To: Bob <sip:bob@example.com>;tag=a6c85cf\r\n\
+        Call-ID: a84b4c76e66710\r\n\
+        CSeq: 314159 INVITE\r\n\
+        Contact: <sip:bob@192.0.2.9>\r\n\r\n",
+    )
+    .unwrap();
+    let mut dialog = Dialog::from_uac(&invite, &ringing).unwrap();
+    assert_eq!(DialogState::Early, dialog.state);
+    assert!(dialog.route_set.is_empty());
+
+    dialog.on_response(&SipMessage::parse(OK).unwrap());
+    assert_eq!(DialogState::Confirmed, dialog.state);
+    assert_eq!(
+        Uri::sip(ip_domain!(192, 0, 2, 4)).auth(uri_auth!("bob")),
+        dialog.remote_target
+    );
+    assert_eq!(2, dialog.route_set.len());
+}
+
+#[test]
+fn incoming_requests() {
+    let invite = SipMessage::parse(INVITE).unwrap();
+    let ok = SipMessage::parse(OK).unwrap();
+    let via = ViaHeader::new(domain!("192.0.2.4"), Transport::Udp);
+    let mut dialog = Dialog::from_uas(&invite, &ok, via).unwrap();
+    let request = |cseq: u32, method: &str, to_tag: &str| {
+        SipMessage::parse(
+            format!(
+                "{} sip:bob@192.0.2.4 SIP/2.0\r\n\
+                From: Alice <sip:alice@example.com>;tag=1928301774\r\n\
+                To: Bob <sip:bob@example.com>;tag={}\r\n\
+                Call-ID: a84b4c76e66710\r\n\
+                CSeq: {} {}\r\n\
+                Contact: <sip:alice@192.0.2.1>\r\n\r\n",
+                method, to_tag, cseq, method
+            )
+            .as_bytes(),
+        )
+        .unwrap()
+    };
+
+    assert!(dialog.matches(&request(314160, "BYE", "a6c85cf")));
+    assert!(!dialog.matches(&request(314160, "BYE", "other")));
+
+    assert!(dialog.on_request(&request(314159, "ACK", "a6c85cf")));
+    assert!(dialog.on_request(&request(314160, "INFO", "a6c85cf")));
+    assert_eq!(Some(314160), dialog.remote_seq);
+    assert_eq!(
+        Uri::sip(domain!("pc33.example.com")).auth(uri_auth!("alice")),
+        dialog.remote_target
+    );
+    assert!(!dialog.on_request(&request(314159, "INFO", "a6c85cf")));
+
+    assert!(dialog.on_request(&request(314161, "INVITE", "a6c85cf")));
+    assert_eq!(
+        Uri::sip(ip_domain!(192, 0, 2, 1)).auth(uri_auth!("alice")),
+        dialog.remote_target
+    );
+}
+
+#[test]
+fn dialog_id() {
+    let invite = SipMessage::parse(INVITE).unwrap();
+    let ok = SipMessage::parse(OK).unwrap();
+    let mut dialog = Dialog::from_uac(&invite, &ok).unwrap();
+    assert_eq!(transaction::DialogId::incoming(&ok), dialog.id());
+    let bye = dialog.bye(&HeaderWriteConfig::default()).unwrap();
+    assert_eq!(transaction::DialogId::outgoing(&bye), dialog.id());
+}
+
+#[test]
+fn route_sets() {
+    let invite = SipMessage::parse(INVITE).unwrap();
+    let ok = SipMessage::parse(OK).unwrap();
+    let hosts = |routes: Vec<NamedHeader>| {
+        routes
+            .into_iter()
+            .map(|route| route.uri.host)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        vec![domain!("p1.example.com"), domain!("p2.example.com")],
+        hosts(uac_route_set(&ok))
+    );
+    assert_eq!(
+        vec![domain!("p2.example.com"), domain!("p1.example.com")],
+        hosts(uas_route_set(&invite))
+    );
+
+    let remote_target = Uri::sip(ip_domain!(192, 0, 2, 4));
+    let target = route_target(&uac_route_set(&ok), &remote_target);
+    assert_eq!(remote_target, target.uri);
+    assert_eq!(
+        vec![domain!("p1.example.com"), domain!("p2.example.com")],
+        hosts(target.routes)
+    );
+    assert_eq!(domain!("p1.example.com"), target.next_hop.host);
+
+    let target = route_target(&[], &remote_target);
+    assert_eq!(remote_target, target.uri);
+    assert!(target.routes.is_empty());
+    assert_eq!(remote_target, target.next_hop);
+}
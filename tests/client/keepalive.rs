@@ -0,0 +1,52 @@
+use libsip::{core::ParsedPacket, *};
+
+use std::time::{Duration, Instant};
+
+#[test]
+fn crlf() {
+    let now = Instant::now();
+    let mut keep_alive = FlowKeepAlive::new(Transport::Tcp);
+    assert_eq!(None, keep_alive.poll(now));
+    keep_alive.start(now);
+    let next = keep_alive.next_at().unwrap();
+    assert!(next >= now + Duration::from_secs(95) && next <= now + Duration::from_secs(120));
+
+    assert_eq!(Some(b"\r\n\r\n".to_vec()), keep_alive.poll(next));
+    assert_eq!(None, keep_alive.poll(next));
+    assert!(!keep_alive.is_failed(next + Duration::from_secs(9)));
+    assert!(keep_alive.is_failed(next + Duration::from_secs(10)));
+    assert!(keep_alive.on_packet(&ParsedPacket::KeepAlive));
+    assert!(!keep_alive.is_failed(next + Duration::from_secs(10)));
+    assert!(!keep_alive.on_packet(&ParsedPacket::KeepAlive));
+
+    keep_alive.stop();
+    assert_eq!(None, keep_alive.next_at());
+}
+
+#[test]
+fn stun() {
+    let now = Instant::now();
+    let mut keep_alive = FlowKeepAlive::new(Transport::Udp).flow_timer(20);
+    keep_alive.start(now);
+    let next = keep_alive.next_at().unwrap();
+    assert!(next >= now + Duration::from_secs(16) && next <= now + Duration::from_secs(20));
+
+    let request = keep_alive.poll(next).unwrap();
+    assert_eq!(20, request.len());
+    assert_eq!([0x00, 0x01, 0x00, 0x00], request[..4]);
+    assert!(matches!(
+        core::parse_packet(&request),
+        Ok(ParsedPacket::MaybeStun(_))
+    ));
+    assert!(!keep_alive.on_packet(&ParsedPacket::KeepAlive));
+
+    let mut other = request.clone();
+    other[0..2].copy_from_slice(&[0x01, 0x01]);
+    other[19] ^= 0xff;
+    assert!(!keep_alive.on_packet(&ParsedPacket::MaybeStun(other)));
+
+    let mut response = request;
+    response[0..2].copy_from_slice(&[0x01, 0x01]);
+    assert!(keep_alive.on_packet(&ParsedPacket::MaybeStun(response)));
+    assert!(!keep_alive.is_failed(next + Duration::from_secs(10)));
+}
@@ -0,0 +1,188 @@
+use libsip::*;
+
+fn writer() -> MessageWriter {
+    MessageWriter::new(Uri::sip(domain!("example.com")).auth(uri_auth!("alice")))
+}
+
+fn via() -> Header {
+    Header::Via(vec![ViaHeader::new(
+        ip_domain!(192, 168, 0, 2, 5060),
+        Transport::Tcp,
+    )])
+}
+
+#[test]
+fn message_schema() {
+    let cfg = HeaderWriteConfig::default();
+    let to = Uri::sips(domain!("example.com")).auth(uri_auth!("bob"));
+    let msg = writer()
+        .write_message(b"hi".to_vec(), to.clone(), Some(via()), &cfg)
+        .unwrap();
+    if let SipMessage::Request { uri, .. } = msg {
+        assert_eq!(to, uri);
+    } else {
+        panic!("expected a request");
+    }
+
+    let to = Uri::new_schemaless(domain!("example.com")).auth(uri_auth!("bob"));
+    let msg = writer()
+        .write_message(b"hi".to_vec(), to.clone(), Some(via()), &cfg)
+        .unwrap();
+    if let SipMessage::Request { uri, .. } = msg {
+        assert_eq!(to.schema(UriSchema::Sip), uri);
+    } else {
+        panic!("expected a request");
+    }
+}
+
+#[test]
+fn write_invite() {
+    let cfg = HeaderWriteConfig::default();
+    let mut writer = writer();
+    let to = Uri::sip(domain!("example.com")).auth(uri_auth!("bob"));
+    let sdp = b"v=0\r\no=alice 2890844526 2890844526 IN IP4 192.168.0.2\r\n".to_vec();
+    let msg = writer
+        .write_invite(to.clone(), sdp.clone(), Some(via()), &cfg)
+        .unwrap();
+
+    assert_eq!(Some(&Method::Invite), msg.method());
+    assert_eq!(Some(&to), msg.uri());
+    assert_eq!(Some((1, &Method::Invite)), msg.cseq());
+    assert!(msg.from().unwrap().tag().is_some());
+    assert_eq!(
+        Some(&Header::ContentType(ContentType::sdp())),
+        msg.header(headers::HeaderKind::ContentType)
+    );
+    assert_eq!(
+        Some(&Header::ContentLength(sdp.len() as u32)),
+        msg.header(headers::HeaderKind::ContentLength)
+    );
+    assert_eq!(
+        Some(&Header::Contact(vec![NamedHeader::new(
+            Uri::sip(ip_domain!(192, 168, 0, 2, 5060)).auth(uri_auth!("alice"))
+        )])),
+        msg.header(headers::HeaderKind::Contact)
+    );
+    assert!(msg.headers().supports("replaces"));
+    assert!(msg.header(headers::HeaderKind::Allow).is_some());
+    assert_eq!(&sdp, msg.body());
+    assert!(msg.validate(core::Role::Uas).is_empty());
+}
+
+#[test]
+fn cseq_per_method() {
+    let cfg = HeaderWriteConfig::default();
+    let mut writer = writer();
+    let to = Uri::sip(domain!("example.com")).auth(uri_auth!("bob"));
+    writer
+        .write_invite(to.clone(), vec![], Some(via()), &cfg)
+        .unwrap();
+    let msg = writer
+        .write_message(b"hi".to_vec(), to.clone(), Some(via()), &cfg)
+        .unwrap();
+    assert_eq!(Some((1, &Method::Message)), msg.cseq());
+    let msg = writer
+        .write_message(b"hi".to_vec(), to, Some(via()), &cfg)
+        .unwrap();
+    assert_eq!(Some((2, &Method::Message)), msg.cseq());
+
+    assert_eq!(Header::CSeq(2, Method::Message), writer.cseq());
+    assert_eq!(
+        Header::CSeq(1, Method::Invite),
+        writer.cseq_for(Method::Invite)
+    );
+    assert_eq!(Header::CSeq(0, Method::Info), writer.cseq_for(Method::Info));
+    assert_eq!(2, writer.next_cseq(Method::Invite));
+}
+
+#[test]
+fn default_via() {
+    let cfg = HeaderWriteConfig::default();
+    let to = Uri::sip(domain!("example.com")).auth(uri_auth!("bob"));
+    let mut writer = writer().via(ViaHeader::new(
+        ip_domain!(192, 168, 0, 2, 5060),
+        Transport::Udp,
+    ));
+    let first = writer
+        .write_message(b"hi".to_vec(), to.clone(), None, &cfg)
+        .unwrap();
+    let second = writer
+        .write_message(b"hi".to_vec(), to.clone(), None, &cfg)
+        .unwrap();
+    let branch = |msg: &SipMessage| msg.top_via().unwrap().branch().unwrap().to_string();
+    assert!(branch(&first).starts_with("z9hG4bK"));
+    assert_ne!(branch(&first), branch(&second));
+    assert!(first.top_via().unwrap().has_rport());
+
+    let invite = writer.write_invite(to, vec![], None, &cfg).unwrap();
+    assert_eq!(
+        ip_domain!(192, 168, 0, 2, 5060),
+        invite.top_via().unwrap().host
+    );
+    assert!(invite.validate(core::validate::Role::Uas).is_empty());
+}
+
+#[test]
+fn options() {
+    let cfg = HeaderWriteConfig::default();
+    let mut writer = writer();
+    let to = Uri::sip(domain!("example.com")).auth(uri_auth!("bob"));
+    let options = writer.write_options(to.clone(), Some(via()), &cfg).unwrap();
+    assert_eq!(Some(&Method::Options), options.method());
+    assert_eq!(Some(&to), options.uri());
+    assert_eq!(Some((1, &Method::Options)), options.cseq());
+    assert_eq!(
+        Some(&Header::Accept(vec![ContentType::sdp()])),
+        options.header(headers::HeaderKind::Accept)
+    );
+    assert!(options.headers().supports("replaces"));
+    assert!(options.header(headers::HeaderKind::Allow).is_some());
+    assert!(options.body().is_empty());
+    let options = writer.write_options(to, Some(via()), &cfg).unwrap();
+    assert_eq!(Some((2, &Method::Options)), options.cseq());
+
+    let options = SipMessage::parse(&options.to_bytes()).unwrap();
+    let response = options_response(&options, &cfg).unwrap();
+    assert_eq!(Some(200), response.status_code());
+    assert_eq!(options.call_id(), response.call_id());
+    assert_eq!(Some((2, &Method::Options)), response.cseq());
+    assert!(response.to().unwrap().tag().is_some());
+    assert_eq!(
+        Some(&Header::Allow(cfg.allowed_methods.clone().unwrap())),
+        response.header(headers::HeaderKind::Allow)
+    );
+    for tag in SUPPORTED_EXTENSIONS {
+        assert!(response.headers().supports(tag));
+    }
+}
+
+#[test]
+fn info_dtmf() {
+    let cfg = HeaderWriteConfig::default();
+    let mut writer = writer();
+    let to = Uri::sip(domain!("example.com")).auth(uri_auth!("bob"));
+    let info = writer
+        .write_info_dtmf(to.clone(), '#', 160, Some(via()), &cfg)
+        .unwrap();
+    assert_eq!(Some(&Method::Info), info.method());
+    assert_eq!(Some((1, &Method::Info)), info.cseq());
+    assert_eq!(
+        Some(&Header::ContentType(ContentType::dtmf_relay())),
+        info.header(headers::HeaderKind::ContentType)
+    );
+    let info = SipMessage::parse(&info.to_bytes()).unwrap();
+    assert_eq!(
+        DtmfRelay {
+            signal: '#',
+            duration: 160
+        },
+        DtmfRelay::parse(info.body()).unwrap()
+    );
+
+    assert!(writer
+        .write_info_dtmf(to, 'x', 160, Some(via()), &cfg)
+        .is_err());
+    assert_eq!('B', DtmfRelay::parse(b"signal=b\r\n").unwrap().signal);
+    assert!(DtmfRelay::parse(b"Duration=160\r\n").is_err());
+    assert!(DtmfRelay::parse(b"Signal=12\r\n").is_err());
+}
@@ -0,0 +1,9 @@
+mod authenticator;
+mod dialog;
+mod keepalive;
+mod messaging;
+mod publication;
+mod registration;
+mod session_timer;
+mod subscription;
+mod transfer;
@@ -0,0 +1,121 @@
+use libsip::{headers::EventType, *};
+
+use std::time::{Duration, Instant};
+
+fn manager() -> PublicationManager {
+    PublicationManager::new(
+        Uri::sip(domain!("example.com")).auth(uri_auth!("alice")),
+        EventType::Presence,
+        ViaHeader::new(ip_domain!(192, 168, 0, 2, 5060), Transport::Udp),
+    )
+    .expires(600)
+}
+
+fn pidf() -> ContentType {
+    ContentType::new("application", "pidf+xml")
+}
+
+fn reply(request: &SipMessage, code: u32, headers: Vec<Header>) -> SipMessage {
+    ResponseGenerator::reply_to(request, code)
+        .headers(headers)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn publish() {
+    let cfg = HeaderWriteConfig::default();
+    let mut publication = manager();
+    assert!(publication.refresh(&cfg).is_err());
+
+    let initial = publication
+        .publish(pidf(), b"<presence/>".to_vec(), &cfg)
+        .unwrap();
+    assert_eq!(Some(&Method::Publish), initial.method());
+    assert_eq!(PublicationStatus::Publishing, publication.status());
+    assert_eq!(None, initial.headers().sip_if_match());
+    assert_eq!(
+        Some(Header::Event(EventType::Presence, GenericParams::new())),
+        initial.headers().event()
+    );
+    assert_eq!(Some(Header::Expires(600)), initial.headers().expires());
+    assert_eq!(b"<presence/>".to_vec(), *initial.body());
+
+    let now = Instant::now();
+    publication.handle_response(
+        &reply(
+            &initial,
+            200,
+            vec![Header::SipETag("dx200xyz".into()), Header::Expires(400)],
+        ),
+        now,
+    );
+    assert_eq!(PublicationStatus::Published, publication.status());
+    assert_eq!(Some("dx200xyz"), publication.etag());
+    assert_eq!(
+        Some(now + Duration::from_secs(200)),
+        publication.refresh_at()
+    );
+    assert!(!publication.needs_refresh(now));
+
+    let refresh = publication.refresh(&cfg).unwrap();
+    assert_eq!(
+        Some(Header::SipIfMatch("dx200xyz".into())),
+        refresh.headers().sip_if_match()
+    );
+    assert!(refresh.body().is_empty());
+    assert_eq!(initial.call_id(), refresh.call_id());
+    assert_eq!(Some((2, &Method::Publish)), refresh.cseq());
+    publication.handle_response(
+        &reply(
+            &refresh,
+            200,
+            vec![Header::SipETag("kwj449x".into()), Header::Expires(600)],
+        ),
+        now,
+    );
+    assert_eq!(Some("kwj449x"), publication.etag());
+
+    let modify = publication
+        .publish(pidf(), b"<presence><note/></presence>".to_vec(), &cfg)
+        .unwrap();
+    assert_eq!(
+        Some(Header::SipIfMatch("kwj449x".into())),
+        modify.headers().sip_if_match()
+    );
+    assert!(!modify.body().is_empty());
+
+    let remove = publication.remove(&cfg).unwrap();
+    assert_eq!(Some(Header::Expires(0)), remove.headers().expires());
+    publication.handle_response(&reply(&remove, 200, vec![Header::Expires(0)]), now);
+    assert_eq!(PublicationStatus::Unpublished, publication.status());
+    assert_eq!(None, publication.etag());
+    assert_eq!(None, publication.refresh_at());
+}
+
+#[test]
+fn failures() {
+    let cfg = HeaderWriteConfig::default();
+    let mut publication = manager();
+    let now = Instant::now();
+    let initial = publication.publish(pidf(), vec![], &cfg).unwrap();
+    publication.handle_response(&reply(&initial, 423, vec![Header::MinExpires(1800)]), now);
+    assert_eq!(PublicationStatus::Failed(423), publication.status());
+    let initial = publication.publish(pidf(), vec![], &cfg).unwrap();
+    assert_eq!(Some(Header::Expires(1800)), initial.headers().expires());
+
+    publication.handle_response(
+        &reply(&initial, 200, vec![Header::SipETag("a".into())]),
+        now,
+    );
+    assert_eq!(
+        Some(now + Duration::from_secs(900)),
+        publication.refresh_at()
+    );
+    let refresh = publication.refresh(&cfg).unwrap();
+    publication.handle_response(&reply(&refresh, 412, vec![]), now);
+    assert_eq!(PublicationStatus::Failed(412), publication.status());
+    assert_eq!(None, publication.etag());
+    let initial = publication.publish(pidf(), vec![], &cfg).unwrap();
+    assert_eq!(None, initial.headers().sip_if_match());
+}
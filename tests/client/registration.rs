@@ -0,0 +1,236 @@
+use libsip::*;
+
+use std::time::{Duration, Instant};
+
+fn manager() -> RegistrationManager {
+    let account = Uri::sip(domain!("example.com")).auth(uri_auth!("alice"));
+    let local = Uri::sip(ip_domain!(192, 168, 0, 2, 5060));
+    RegistrationManager::new(account, local)
+}
+
+#[test]
+fn interval_too_brief() {
+    let cfg = HeaderWriteConfig::default();
+    let mut reg = manager();
+    let response = ResponseGenerator::new()
+        .code(423)
+        .header(Header::MinExpires(3600))
+        .build_unchecked()
+        .unwrap();
+    let request = reg.handle_interval_too_brief(&cfg, &response).unwrap();
+    assert_eq!(3600, reg.expires());
+    assert_eq!(Some(Header::Expires(3600)), request.headers().expires());
+}
+
+#[test]
+fn interval_too_brief_invalid_response() {
+    let cfg = HeaderWriteConfig::default();
+    let mut reg = manager();
+    let ok = ResponseGenerator::new()
+        .code(200)
+        .header(Header::MinExpires(3600))
+        .build_unchecked()
+        .unwrap();
+    assert!(reg.handle_interval_too_brief(&cfg, &ok).is_err());
+    let missing = ResponseGenerator::new()
+        .code(423)
+        .build_unchecked()
+        .unwrap();
+    assert!(reg.handle_interval_too_brief(&cfg, &missing).is_err());
+    assert_eq!(60, reg.expires());
+}
+
+fn response(code: u32, headers: Vec<Header>) -> SipMessage {
+    ResponseGenerator::new()
+        .code(code)
+        .headers(headers)
+        .build_unchecked()
+        .unwrap()
+}
+
+#[test]
+fn registration_state() {
+    let cfg = HeaderWriteConfig::default();
+    let mut reg = manager();
+    assert_eq!(RegistrationState::Unregistered, reg.state());
+    let first = reg.get_request(&cfg).unwrap();
+    let second = reg.get_request(&cfg).unwrap();
+    assert_eq!(RegistrationState::Registering, reg.state());
+    assert_ne!(first.top_via(), second.top_via());
+
+    let now = Instant::now();
+    let contact =
+        NamedHeader::new(Uri::sip(ip_domain!(192, 168, 0, 2, 5060))).param("expires", Some("1800"));
+    let ok = response(
+        200,
+        vec![Header::Contact(vec![contact]), Header::Expires(3600)],
+    );
+    assert_eq!(None, reg.handle_response(&cfg, &ok, now).unwrap());
+    assert_eq!(RegistrationState::Registered { expires: 1800 }, reg.state());
+    assert_eq!(Some(Duration::from_secs(900)), reg.refresh_interval());
+    assert!(!reg.needs_refresh(now + Duration::from_secs(899)));
+    assert!(reg.needs_refresh(now + Duration::from_secs(900)));
+
+    reg.get_unregister_request(&cfg).unwrap();
+    assert_eq!(RegistrationState::Unregistering, reg.state());
+    reg.handle_response(&cfg, &response(200, vec![]), now)
+        .unwrap();
+    assert_eq!(RegistrationState::Unregistered, reg.state());
+    assert_eq!(None, reg.refresh_at());
+}
+
+#[test]
+fn registered_contacts() {
+    let cfg = HeaderWriteConfig::default();
+    let mut reg = manager();
+    reg.get_request(&cfg).unwrap();
+    let ok = SipMessage::parse(
+        b"SIP/2.0 200 OK\r\n\
+        Contact: <sip:bob@192.168.0.9>;expires=60, <sip:192.168.0.2:5060>;expires=30\r\n\
+        Expires: 3600\r\n\r\n",
+    )
+    .unwrap();
+    assert_eq!(2, ok.headers().contacts().len());
+    reg.handle_response(&cfg, &ok, Instant::now()).unwrap();
+    assert_eq!(RegistrationState::Registered { expires: 30 }, reg.state());
+}
+
+fn digest(realm: &str, nonce: &str) -> AuthHeader {
+    AuthHeader(
+        AuthSchema::Digest,
+        vec![("realm", realm), ("nonce", nonce), ("qop", "auth")]
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+    )
+}
+
+#[test]
+fn registration_challenge() {
+    let cfg = HeaderWriteConfig::default();
+    let mut reg = manager();
+    reg.username("alice");
+    reg.password("secret");
+    reg.get_request(&cfg).unwrap();
+    let challenge = || {
+        response(
+            407,
+            vec![Header::ProxyAuthenticate(digest("example.com", "abc"))],
+        )
+    };
+
+    let request = reg
+        .handle_response(&cfg, &challenge(), Instant::now())
+        .unwrap()
+        .unwrap();
+    assert!(request
+        .headers()
+        .iter()
+        .any(|header| matches!(header, Header::ProxyAuthorization(_))));
+    assert_eq!(RegistrationState::Registering, reg.state());
+
+    assert_eq!(
+        None,
+        reg.handle_response(&cfg, &challenge(), Instant::now())
+            .unwrap()
+    );
+    assert_eq!(RegistrationState::Failed(407), reg.state());
+}
+
+/// Whether `request` carries an Authorization and a Proxy-Authorization header.
+fn credentials(request: &SipMessage) -> (bool, bool) {
+    let headers = request.headers();
+    (
+        headers
+            .iter()
+            .any(|header| matches!(header, Header::Authorization(_))),
+        headers
+            .iter()
+            .any(|header| matches!(header, Header::ProxyAuthorization(_))),
+    )
+}
+
+#[test]
+fn proxy_and_registrar_challenges() {
+    let cfg = HeaderWriteConfig::default();
+    let mut reg = manager();
+    reg.username("alice");
+    reg.password("secret");
+    reg.get_request(&cfg).unwrap();
+    let now = Instant::now();
+
+    let proxy = response(
+        407,
+        vec![Header::ProxyAuthenticate(digest(
+            "proxy.example.com",
+            "abc",
+        ))],
+    );
+    let request = reg.handle_response(&cfg, &proxy, now).unwrap().unwrap();
+    assert_eq!((false, true), credentials(&request));
+
+    // The registrar challenges the request the proxy let through.
+    let registrar = response(
+        401,
+        vec![Header::WwwAuthenticate(digest("example.com", "def"))],
+    );
+    let request = reg.handle_response(&cfg, &registrar, now).unwrap().unwrap();
+    assert_eq!((true, true), credentials(&request));
+    assert_eq!(RegistrationState::Registering, reg.state());
+
+    let ok = response(200, vec![Header::Expires(3600)]);
+    reg.handle_response(&cfg, &ok, now).unwrap();
+    assert_eq!(RegistrationState::Registered { expires: 3600 }, reg.state());
+}
+
+#[test]
+fn registration_failure() {
+    let cfg = HeaderWriteConfig::default();
+    let mut reg = manager();
+    reg.get_request(&cfg).unwrap();
+    let now = Instant::now();
+    assert_eq!(
+        None,
+        reg.handle_response(&cfg, &response(100, vec![]), now)
+            .unwrap()
+    );
+    assert_eq!(RegistrationState::Registering, reg.state());
+    reg.handle_response(&cfg, &response(403, vec![]), now)
+        .unwrap();
+    assert_eq!(RegistrationState::Failed(403), reg.state());
+
+    let too_brief = response(423, vec![Header::MinExpires(600)]);
+    let request = reg.handle_response(&cfg, &too_brief, now).unwrap().unwrap();
+    assert_eq!(Some(Header::Expires(600)), request.headers().expires());
+}
+
+#[test]
+fn outbound() {
+    let cfg = HeaderWriteConfig::default();
+    let mut reg = manager();
+    let instance = "urn:uuid:00000000-0000-1000-8000-000a95a0e128";
+    reg.outbound(instance, 1);
+    let request = reg.get_request(&cfg).unwrap();
+    let contact = request.headers().contacts().remove(0);
+    assert_eq!(Some(instance), contact.instance());
+    assert_eq!(Some(1), contact.reg_id());
+    assert!(request.headers().supports("outbound"));
+
+    let ok = response(
+        200,
+        vec![
+            Header::Require(vec!["outbound".into()]),
+            Header::FlowTimer(30),
+            Header::Expires(3600),
+        ],
+    );
+    reg.handle_response(&cfg, &ok, Instant::now()).unwrap();
+    assert!(reg.is_outbound());
+    let interval = reg.keep_alive(Transport::Tcp).interval();
+    assert!(interval >= Duration::from_secs(24) && interval <= Duration::from_secs(30));
+
+    let mut plain = manager();
+    plain.get_request(&cfg).unwrap();
+    plain.handle_response(&cfg, &ok, Instant::now()).unwrap();
+    assert!(!plain.is_outbound());
+}
@@ -0,0 +1,52 @@
+use libsip::{
+    headers::{Refresher, SessionExpires},
+    *,
+};
+
+use std::time::Duration;
+
+#[test]
+fn from_headers() {
+    let mut headers = Headers::new();
+    assert_eq!(None, SessionTimer::from_headers(&headers));
+    headers.push(Header::SessionExpires(SessionExpires::new(1800)));
+    let timer = SessionTimer::from_headers(&headers).unwrap();
+    assert_eq!(1800, timer.interval);
+    assert_eq!(Refresher::Uac, timer.refresher);
+    assert!(timer.is_refresher(true));
+    assert!(!timer.is_refresher(false));
+}
+
+#[test]
+fn intervals() {
+    let timer = SessionTimer {
+        interval: 1800,
+        refresher: Refresher::Uas,
+    };
+    assert_eq!(Duration::from_secs(900), timer.refresh_interval());
+    assert_eq!(Duration::from_secs(1768), timer.expiration_interval());
+    let timer = SessionTimer {
+        interval: 90,
+        refresher: Refresher::Uas,
+    };
+    assert_eq!(Duration::from_secs(45), timer.refresh_interval());
+    assert_eq!(Duration::from_secs(60), timer.expiration_interval());
+}
+
+#[test]
+fn request_header() {
+    assert_eq!(
+        Header::SessionExpires(SessionExpires::new(1800)),
+        SessionTimer::request_header(1800, None)
+    );
+    assert_eq!(
+        Header::SessionExpires(SessionExpires::new(90)),
+        SessionTimer::request_header(30, None)
+    );
+    let mut headers = Headers::new();
+    headers.push(Header::MinSE(3600));
+    assert_eq!(
+        Header::SessionExpires(SessionExpires::new(3600)),
+        SessionTimer::request_header(1800, Some(&headers))
+    );
+}
@@ -0,0 +1,159 @@
+use libsip::{headers::EventType, *};
+
+use std::time::{Duration, Instant};
+
+fn manager() -> SubscriptionManager {
+    SubscriptionManager::new(
+        Uri::sip(domain!("example.com")).auth(uri_auth!("alice")),
+        Uri::sip(domain!("example.com")).auth(uri_auth!("alice")),
+        EventType::MessageSummary,
+        ViaHeader::new(ip_domain!(192, 168, 0, 2, 5060), Transport::Udp),
+    )
+    .expires(600)
+}
+
+fn notify(subscribe: &SipMessage, cseq: u32, event: &str, state: &str) -> SipMessage {
+    SipMessage::parse(
+        format!(
+            "NOTIFY sip:alice@192.168.0.2:5060 SIP/2.0\r\n\
+            Via: SIP/2.0/UDP voicemail.example.com;branch=z9hG4bK{}\r\n\
+            Max-Forwards: 70\r\n\
+            From: <sip:alice@example.com>;tag=notifier\r\n\
+            To: <sip:alice@example.com>;tag={}\r\n\
+            Call-ID: {}\r\n\
+            CSeq: {} NOTIFY\r\n\
+            Contact: <sip:voicemail.example.com>\r\n\
+            Event: {}\r\n\
+            {}\
+            Content-Length: 0\r\n\r\n",
+            cseq,
+            subscribe.from().unwrap().tag().unwrap(),
+            subscribe.call_id().unwrap(),
+            cseq,
+            event,
+            state
+        )
+        .as_bytes(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn subscribe() {
+    let cfg = HeaderWriteConfig::default();
+    let mut sub = manager();
+    assert_eq!(&SubscriptionStatus::Idle, sub.status());
+    let subscribe = sub.subscribe(&cfg).unwrap();
+    assert_eq!(&SubscriptionStatus::Subscribing, sub.status());
+    assert_eq!(Some(&Method::Subscribe), subscribe.method());
+    assert_eq!(
+        Some(Header::Event(
+            EventType::MessageSummary,
+            GenericParams::new()
+        )),
+        subscribe.headers().event()
+    );
+    assert_eq!(Some(Header::Expires(600)), subscribe.headers().expires());
+    assert!(sub.dialog().is_none());
+
+    let now = Instant::now();
+    let ok = ResponseGenerator::reply_to(&subscribe, 200)
+        .header(Header::Expires(300))
+        .header(Header::Contact(vec![NamedHeader::new(Uri::sip(domain!(
+            "voicemail.example.com"
+        )))]))
+        .build()
+        .unwrap();
+    sub.handle_response(&subscribe, &ok, now);
+    assert_eq!(&SubscriptionStatus::Pending, sub.status());
+    assert_eq!(Some(now + Duration::from_secs(150)), sub.refresh_at());
+    assert!(sub.needs_refresh(now + Duration::from_secs(150)));
+
+    let refresh = sub.subscribe(&cfg).unwrap();
+    assert_eq!(subscribe.call_id(), refresh.call_id());
+    assert_eq!(Some((2, &Method::Subscribe)), refresh.cseq());
+    assert_eq!(
+        Some(&Uri::sip(domain!("voicemail.example.com"))),
+        refresh.uri()
+    );
+    assert_eq!(ok.to().unwrap().tag(), refresh.to().unwrap().tag());
+}
+
+#[test]
+fn notify_states() {
+    let cfg = HeaderWriteConfig::default();
+    let mut sub = manager();
+    let subscribe = sub.subscribe(&cfg).unwrap();
+    let now = Instant::now();
+
+    let response = sub
+        .handle_notify(
+            &notify(
+                &subscribe,
+                1,
+                "message-summary",
+                "Subscription-State: active;expires=500\r\n",
+            ),
+            now,
+        )
+        .unwrap();
+    assert_eq!(Some(200), response.status_code());
+    assert_eq!(&SubscriptionStatus::Active, sub.status());
+    assert_eq!(Some(now + Duration::from_secs(250)), sub.refresh_at());
+    assert_eq!(Some("notifier"), sub.dialog().unwrap().remote.tag());
+    assert_eq!(Some(1), sub.dialog().unwrap().remote_seq);
+
+    let response = sub
+        .handle_notify(
+            &notify(&subscribe, 2, "presence", "Subscription-State: active\r\n"),
+            now,
+        )
+        .unwrap();
+    assert_eq!(Some(489), response.status_code());
+    let response = sub
+        .handle_notify(&notify(&subscribe, 2, "message-summary", ""), now)
+        .unwrap();
+    assert_eq!(Some(400), response.status_code());
+
+    let unsubscribe = sub.unsubscribe(&cfg).unwrap();
+    assert_eq!(Some(Header::Expires(0)), unsubscribe.headers().expires());
+    assert_eq!(None, sub.refresh_at());
+    sub.handle_notify(
+        &notify(
+            &subscribe,
+            3,
+            "message-summary",
+            "Subscription-State: terminated;reason=timeout\r\n",
+        ),
+        now,
+    )
+    .unwrap();
+    assert_eq!(
+        &SubscriptionStatus::Terminated {
+            reason: Some("timeout".into()),
+            retry_after: None,
+        },
+        sub.status()
+    );
+}
+
+#[test]
+fn unknown_notify() {
+    let cfg = HeaderWriteConfig::default();
+    let mut sub = manager();
+    sub.subscribe(&cfg).unwrap();
+    let other = manager().subscribe(&cfg).unwrap();
+    let response = sub
+        .handle_notify(
+            &notify(
+                &other,
+                1,
+                "message-summary",
+                "Subscription-State: active\r\n",
+            ),
+            Instant::now(),
+        )
+        .unwrap();
+    assert_eq!(Some(481), response.status_code());
+    assert_eq!(&SubscriptionStatus::Subscribing, sub.status());
+}
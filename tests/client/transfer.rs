@@ -0,0 +1,119 @@
+use libsip::{headers::ReplacesHeader, *};
+
+const INVITE: &[u8] = b"INVITE sip:bob@example.com SIP/2.0\r\n\
+    Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+    From: Alice <sip:alice@example.com>;tag=1928301774\r\n\
+    To: Bob <sip:bob@example.com>\r\n\
+    Call-ID: a84b4c76e66710\r\n\
+    CSeq: 314159 INVITE\r\n\
+    Contact: <sip:alice@pc33.example.com>\r\n\r\n";
+
+const OK: &[u8] = b"SIP/2.0 200 OK\r\n\
+    Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+    From: Alice <sip:alice@example.com>;tag=1928301774\r\n\
+    To: Bob <sip:bob@example.com>;tag=a6c85cf\r\n\
+    Call-ID: a84b4c76e66710\r\n\
+    CSeq: 314159 INVITE\r\n\
+    Contact: <sip:bob@192.0.2.4>\r\n\r\n";
+
+fn dialog() -> Dialog {
+    let invite = SipMessage::parse(INVITE).unwrap();
+    let ok = SipMessage::parse(OK).unwrap();
+    Dialog::from_uac(&invite, &ok).unwrap()
+}
+
+fn notify(sipfrag: &str) -> SipMessage {
+    SipMessage::parse(
+        format!(
+            "NOTIFY sip:alice@pc33.example.com SIP/2.0\r\n\
+            Event: refer\r\n\
+            Subscription-State: active;expires=60\r\n\
+            Content-Type: message/sipfrag;version=2.0\r\n\
+            Content-Length: {}\r\n\r\n{}",
+            sipfrag.len(),
+            sipfrag
+        )
+        .as_bytes(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn blind_transfer() {
+    let mut dialog = dialog();
+    let target = Uri::sip(domain!("example.org")).auth(uri_auth!("carol"));
+    let refer = dialog
+        .refer(target.clone(), None, &HeaderWriteConfig::default())
+        .unwrap();
+    assert_eq!(Some(&Method::Refer), refer.method());
+    assert_eq!(Some((314160, &Method::Refer)), refer.cseq());
+    assert_eq!(Some("a6c85cf"), refer.to().unwrap().tag());
+    assert_eq!(
+        Some(Header::ReferTo(NamedHeader::new(target))),
+        refer.headers().refer_to()
+    );
+    assert_eq!(
+        Some(Header::ReferredBy(NamedHeader::new(
+            Uri::sip(domain!("example.com")).auth(uri_auth!("alice"))
+        ))),
+        refer.headers().referred_by()
+    );
+}
+
+#[test]
+fn attended_transfer() {
+    let consultation = dialog();
+    let replaces = consultation.replaces().unwrap();
+    assert_eq!(
+        ReplacesHeader::new("a84b4c76e66710", "a6c85cf", "1928301774"),
+        replaces
+    );
+
+    let mut dialog = dialog();
+    let target = Uri::sip(domain!("example.com")).auth(uri_auth!("bob"));
+    let refer = dialog
+        .refer(target, Some(&replaces), &HeaderWriteConfig::default())
+        .unwrap();
+    let refer = SipMessage::parse(&refer.to_bytes()).unwrap();
+    let refer_to = match refer.headers().refer_to() {
+        Some(Header::ReferTo(refer_to)) => refer_to,
+        _ => panic!("REFER without Refer-To"),
+    };
+    assert_eq!(
+        Ok(replaces),
+        refer_to
+            .uri
+            .header_value("Replaces")
+            .unwrap()
+            .parse::<ReplacesHeader>()
+            .map_err(|err| err.to_string())
+    );
+}
+
+#[test]
+fn progress() {
+    let progress = TransferProgress::from_notify(&notify("SIP/2.0 100 Trying\r\n")).unwrap();
+    assert!(!progress.is_final());
+    assert_eq!(Some("Trying".to_string()), progress.status_line().reason);
+
+    let progress = TransferProgress::from_notify(&notify(
+        "SIP/2.0 486 Busy Here\r\nWarning: 399 example.org \"Busy\"\r\n",
+    ))
+    .unwrap();
+    assert!(matches!(&progress, TransferProgress::Failed(line) if line.code == 486));
+
+    let progress = TransferProgress::from_notify(&notify("SIP/2.0 200 OK")).unwrap();
+    assert!(matches!(progress, TransferProgress::Succeeded(_)));
+
+    assert!(TransferProgress::from_notify(&notify("INVITE")).is_err());
+    let ok = SipMessage::parse(OK).unwrap();
+    assert!(TransferProgress::from_notify(&ok).is_err());
+}
+
+#[test]
+fn sipfrag() {
+    let line = parse_sipfrag(b"SIP/2.0 603 Declined\r\n").unwrap();
+    assert_eq!(603, line.code);
+    assert_eq!(Some("Declined".to_string()), line.reason);
+    assert!(parse_sipfrag(b"").is_err());
+}
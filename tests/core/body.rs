@@ -0,0 +1,105 @@
+use libsip::{
+    core::{Body, BodyPart, Multipart},
+    headers::HeaderKind,
+    sdp::SessionDescription,
+    *,
+};
+
+fn message(content_type: &str, body: &str) -> SipMessage {
+    let msg = format!(
+        "INVITE sip:bob@biloxi.com SIP/2.0\r\n\
+        Content-Type: {}\r\n\
+        Content-Length: {}\r\n\r\n{}",
+        content_type,
+        body.len(),
+        body
+    );
+    SipMessage::parse(msg.as_bytes()).unwrap()
+}
+
+const SDP: &str = "v=0\r\n\
+    o=alice 2890844526 2890844526 IN IP4 atlanta.com\r\n\
+    s=-\r\n\
+    c=IN IP4 192.0.2.101\r\n\
+    t=0 0\r\n\
+    m=audio 49172 RTP/AVP 0\r\n\
+    a=rtpmap:0 PCMU/8000\r\n";
+
+#[test]
+fn sdp() {
+    let msg = message("application/sdp", SDP);
+    match msg.typed_body() {
+        Some(Body::Sdp(sdp)) => assert_eq!(49172, sdp.media[0].port),
+        body => panic!("expected an SDP body, got {:?}", body),
+    }
+    // An invalid description is kept as raw bytes.
+    let msg = message("application/sdp", "v=0\r\n");
+    assert_eq!(Some(Body::Binary(b"v=0\r\n".to_vec())), msg.typed_body());
+}
+
+#[test]
+fn sipfrag_and_text() {
+    let msg = message("message/sipfrag;version=2.0", "SIP/2.0 200 OK");
+    match msg.typed_body() {
+        Some(Body::SipFrag(line)) => assert_eq!(200, line.code),
+        body => panic!("expected a sipfrag body, got {:?}", body),
+    }
+    let msg = message("text/plain;charset=utf-8", "Hello");
+    assert_eq!(Some(Body::Text("Hello".into())), msg.typed_body());
+    let msg = message("application/octet-stream", "Hello");
+    assert_eq!(Some(Body::Binary(b"Hello".to_vec())), msg.typed_body());
+    assert_eq!(None, message("text/plain", "").typed_body());
+}
+
+#[test]
+fn multipart() {
+    let body = format!(
+        "--boundary1\r\n\
+        Content-Type: application/sdp\r\n\r\n\
+        {}\r\n\
+        --boundary1\r\n\
+        Content-Type: text/plain\r\n\
+        Content-Disposition: render\r\n\r\n\
+        Hello\r\n\
+        --boundary1--\r\n",
+        SDP
+    );
+    let msg = message("multipart/mixed;boundary=boundary1", &body);
+    let multipart = match msg.typed_body() {
+        Some(Body::Multipart(multipart)) => multipart,
+        body => panic!("expected a multipart body, got {:?}", body),
+    };
+    assert_eq!("mixed", multipart.subtype);
+    assert_eq!(2, multipart.parts.len());
+    assert_eq!(
+        Body::Sdp(Box::new(SessionDescription::parse(SDP.as_bytes()).unwrap())),
+        multipart.parts[0].body
+    );
+    assert_eq!(Body::Text("Hello".into()), multipart.parts[1].body);
+    assert!(multipart.parts[1]
+        .headers
+        .get(HeaderKind::ContentDisposition)
+        .is_some());
+    assert_eq!(body.as_bytes(), &multipart.to_bytes()[..]);
+}
+
+#[test]
+fn set_body() {
+    let mut msg = message("text/plain", "Hello");
+    let multipart = Multipart::mixed(vec![
+        BodyPart::new(Body::Text("Hi".into())),
+        BodyPart::new(Body::Binary(vec![0, 1, 2])),
+    ]);
+    msg.set_body(Body::Multipart(multipart.clone()));
+    assert_eq!(
+        Some(&Header::ContentType(multipart.content_type())),
+        msg.header(HeaderKind::ContentType)
+    );
+    assert_eq!(
+        Some(Header::ContentLength(msg.body().len() as u32)),
+        msg.headers().content_length()
+    );
+
+    let msg = SipMessage::parse(&msg.to_bytes()).unwrap();
+    assert_eq!(Some(Body::Multipart(multipart)), msg.typed_body());
+}
@@ -0,0 +1,88 @@
+use libsip::{core::StartLineRef, *};
+
+#[test]
+fn borrowed_request() {
+    let input = b"INVITE sip:bob@example.com SIP/2.0\r\n\
+        Via: SIP/2.0/UDP 10.0.0.1:5060;branch=z9hG4bK776asdhds\r\n\
+        f: <sip:alice@example.com>;tag=1928301774\r\n\
+        Subject: folded\r\n  subject\r\n\
+        Call-ID: a84b4c76e66710\r\n\
+        Content-Length: 4\r\n\r\nv=0\nextra";
+    let msg = parse_message_borrowed(input).unwrap();
+    assert!(msg.is_request());
+    assert_eq!(
+        StartLineRef::Request {
+            method: "INVITE",
+            uri: "sip:bob@example.com",
+            version: "SIP/2.0"
+        },
+        msg.start_line
+    );
+    assert_eq!(5, msg.headers.len());
+    assert_eq!(
+        Some("<sip:alice@example.com>;tag=1928301774"),
+        msg.header("From")
+    );
+    assert_eq!(Some("folded\r\n  subject"), msg.header("subject"));
+    assert_eq!(Some("a84b4c76e66710"), msg.header("CALL-ID"));
+    assert_eq!(b"v=0\n", msg.body);
+    assert_eq!(&input[..input.len() - 5], msg.as_bytes());
+
+    let owned = msg.to_owned().unwrap();
+    assert!(matches!(
+        owned,
+        SipMessage::Request {
+            method: Method::Invite,
+            ..
+        }
+    ));
+    assert_eq!(b"v=0\n", owned.body().as_slice());
+    assert_eq!(
+        Header::CallId("a84b4c76e66710".into()),
+        msg.headers[3].to_owned().unwrap()
+    );
+}
+
+#[test]
+fn borrowed_response() {
+    let input =
+        b"SIP/2.0 180 Ringing\r\nVia: SIP/2.0/UDP 10.0.0.1\r\nVia: SIP/2.0/TCP 10.0.0.2\r\n\r\n";
+    let msg = parse_message_borrowed(input).unwrap();
+    assert!(msg.is_response());
+    assert_eq!(Some(180), msg.status_code());
+    assert_eq!(None, msg.uri());
+    assert_eq!(
+        vec!["SIP/2.0/UDP 10.0.0.1", "SIP/2.0/TCP 10.0.0.2"],
+        msg.header_values("Via").collect::<Vec<_>>()
+    );
+    assert!(msg.body.is_empty());
+    assert_eq!(Some(180), msg.to_owned().unwrap().status_code());
+}
+
+#[test]
+fn borrowed_errors() {
+    assert_eq!(
+        Err(ParseError::UnsupportedVersion { offset: 27 }),
+        parse_message_borrowed(b"INVITE sip:bob@example.com SIP/3.0\r\n\r\n")
+    );
+    assert!(matches!(
+        parse_message_borrowed(b"SIP/2.0 OK\r\n\r\n"),
+        Err(ParseError::StartLine { offset: 8, .. })
+    ));
+    assert!(matches!(
+        parse_message_borrowed(b"SIP/2.0 200 OK\r\nCall-ID: 1234\r\nBad Header\r\n\r\n"),
+        Err(ParseError::Header {
+            line: 3,
+            offset: 31,
+            ..
+        })
+    ));
+    assert_eq!(
+        Err(ParseError::Incomplete { needed: Some(3) }),
+        parse_message_borrowed(b"SIP/2.0 200 OK\r\nContent-Length: 5\r\n\r\nab")
+    );
+    assert_eq!(
+        Err(ParseError::Incomplete { needed: None }),
+        parse_message_borrowed(b"SIP/2.0 200 OK\r\nCall-ID: 1234\r\n")
+    );
+}
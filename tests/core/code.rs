@@ -0,0 +1,33 @@
+use libsip::*;
+
+#[test]
+fn status_codes() {
+    assert_eq!(StatusCode(486), StatusCode::BUSY_HERE);
+    assert_eq!(Some("Busy Here"), StatusCode::BUSY_HERE.reason_phrase());
+    assert_eq!(Some("OK"), StatusCode::OK.reason_phrase());
+    assert_eq!(None, StatusCode(499).reason_phrase());
+    assert_eq!("183", format!("{}", StatusCode::SESSION_PROGRESS));
+    assert_eq!(StatusCode::NOT_FOUND, 404);
+
+    assert!(StatusCode::RINGING.is_provisional());
+    assert!(!StatusCode::RINGING.is_final());
+    assert!(StatusCode::ACCEPTED.is_success());
+    assert!(StatusCode::MOVED_TEMPORARILY.is_redirect());
+    assert!(StatusCode::UNAUTHORIZED.is_client_error());
+    assert!(StatusCode::SERVICE_UNAVAILABLE.is_server_error());
+    assert!(StatusCode::DECLINE.is_global_failure());
+    assert!(StatusCode::DECLINE.is_final());
+}
+
+#[test]
+fn write_status_code() {
+    let res = ResponseGenerator::new()
+        .code(StatusCode::TEMPORARILY_UNAVAILABLE)
+        .build_unchecked()
+        .unwrap();
+    assert_eq!(Some(480), res.status_code());
+    assert_eq!(
+        "SIP/2.0 480 Temporarily Unavailable\r\n\r\n",
+        format!("{}", res)
+    );
+}
@@ -0,0 +1,62 @@
+use libsip::{core::config::parse_message_with_config, *};
+
+const BARE_LF: &[u8] = b"INVITE sip:bob@example.com SIP/2.0 \nCall-ID: 1234@example.com  \nContent-Length: 4\t\n\nv=0\n";
+
+#[test]
+fn lenient_message() {
+    assert!(parse_message_with_config(BARE_LF, &ParseConfig::strict()).is_err());
+
+    let msg = parse_message_with_config(BARE_LF, &ParseConfig::lenient()).unwrap();
+    assert_eq!(
+        Some(Header::CallId("1234@example.com".into())),
+        msg.headers().call_id()
+    );
+    assert_eq!(b"v=0\n", msg.body().as_slice());
+
+    let config = ParseConfig {
+        allow_bare_lf: true,
+        allow_trailing_whitespace: false,
+    };
+    let msg = parse_message_with_config(b"SIP/2.0 200 OK\nCall-ID: 1234@example.com\n\n", &config)
+        .unwrap();
+    assert_eq!(Some(200), msg.status_code());
+}
+
+#[test]
+fn normalize_head() {
+    let config = ParseConfig::lenient();
+    assert_eq!(Some(84), config.find_head_end(BARE_LF));
+    assert_eq!(
+        b"SIP/2.0 200 OK\r\nSubject: hi\r\n\r\n".to_vec(),
+        config.normalize_head(b"SIP/2.0 200 OK  \r\nSubject: hi\t\n\n")
+    );
+    assert_eq!(None, ParseConfig::strict().find_head_end(BARE_LF));
+}
+
+#[test]
+fn lenient_stream() {
+    let mut parser = SipParser::with_config(ParseConfig::lenient());
+    parser.feed(&BARE_LF[..20]);
+    assert!(parser.next_message().unwrap().is_none());
+    parser.feed(&BARE_LF[20..]);
+    parser.feed(b"SIP/2.0 100 Trying\n\n");
+    let msg = parser.next_message().unwrap().unwrap();
+    assert_eq!(b"v=0\n", msg.body().as_slice());
+    assert_eq!(
+        Some(100),
+        parser.next_message().unwrap().unwrap().status_code()
+    );
+}
+
+#[test]
+fn lenient_error_offsets() {
+    let config = ParseConfig::lenient();
+    let input = b"SIP/2.0 200 OK  \nCall-ID: 1234@example.com\t\nBad Header\n\n";
+    let error = SipMessage::parse_with_config(input, &config).unwrap_err();
+    assert!(matches!(error, ParseError::Header { line: 3, .. }));
+    assert_eq!(Some(44), error.offset());
+
+    let error = SipMessage::parse_with_config(b"INVITE  sip:bob@example.com SIP/2.0\n\n", &config)
+        .unwrap_err();
+    assert!(matches!(error, ParseError::StartLine { offset: 7, .. }));
+}
@@ -0,0 +1,36 @@
+use libsip::*;
+
+#[test]
+fn parse_errors() {
+    let msg =
+        SipMessage::parse(b"INVITE sip:bob@example.com SIP/2.0\r\nContent-Length: 3\r\n\r\nabcdef")
+            .unwrap();
+    assert_eq!(b"abc", msg.body().as_slice());
+
+    let err = SipMessage::parse(b"INVITE sip:bob@example.com SIP/3.0\r\n\r\n").unwrap_err();
+    assert_eq!(ParseError::UnsupportedVersion { offset: 27 }, err);
+
+    let err = SipMessage::parse(b"SIP/2.0 OK\r\n\r\n").unwrap_err();
+    assert!(matches!(err, ParseError::StartLine { offset: 8, .. }));
+
+    let err =
+        SipMessage::parse(b"SIP/2.0 200 OK\r\nCall-ID: 1234@example.com\r\nBad Header\r\n\r\n")
+            .unwrap_err();
+    assert_eq!(Some(43), err.offset(), "unexpected error: {}", err);
+    if let ParseError::Header { name, line, .. } = &err {
+        assert_eq!("Bad Header", name);
+        assert_eq!(3, *line);
+    } else {
+        panic!("expected a header error, got {:?}", err);
+    }
+    assert!(format!("{}", err).starts_with("invalid Bad Header header on line 3 at byte 43"));
+
+    let err = SipMessage::parse(b"SIP/2.0 200 OK\r\nContent-Length: 5\r\n\r\nab").unwrap_err();
+    assert_eq!(ParseError::Incomplete { needed: Some(3) }, err);
+
+    let err = SipMessage::parse(b"SIP/2.0 200 OK\r\nCall-ID: 1234@example.com\r\n").unwrap_err();
+    assert_eq!(ParseError::Incomplete { needed: None }, err);
+
+    let err: Box<dyn std::error::Error> = Box::new(err);
+    assert_eq!("incomplete message", err.to_string());
+}
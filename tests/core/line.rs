@@ -0,0 +1,93 @@
+use libsip::{core::line::*, *};
+
+use nom::error::VerboseError;
+
+#[test]
+fn request_line() {
+    let remains = vec![];
+    let line = RequestLine {
+        method: Method::Invite,
+        uri: Uri::sip(domain!("example.com")).auth(uri_auth!("bob")),
+        version: Version::default(),
+    };
+    assert_eq!(
+        Ok((remains.as_ref(), line.clone())),
+        parse_request_line::<VerboseError<&[u8]>>(b"INVITE sip:bob@example.com SIP/2.0\r\n")
+    );
+    assert_eq!("INVITE sip:bob@example.com SIP/2.0", format!("{}", line));
+
+    assert!(matches!(
+        parse_request_line::<VerboseError<&[u8]>>(b"INVITE sip:bob@example.com SIP/1.0\r\n"),
+        Err(nom::Err::Failure(_))
+    ));
+    assert!(matches!(
+        parse_request_line::<VerboseError<&[u8]>>(b"INVITE sip:bob@example.com\r\n"),
+        Err(nom::Err::Error(_))
+    ));
+}
+
+#[test]
+fn absolute_request_uri() {
+    let remains = vec![];
+    let line = RequestLine {
+        method: Method::Invite,
+        uri: Uri::absolute("urn", "service:sos"),
+        version: Version::default(),
+    };
+    assert_eq!(
+        Ok((remains.as_ref(), line.clone())),
+        parse_request_line::<VerboseError<&[u8]>>(b"INVITE urn:service:sos SIP/2.0\r\n")
+    );
+    assert_eq!("INVITE urn:service:sos SIP/2.0", format!("{}", line));
+
+    let msg = SipMessage::parse(
+        b"INVITE urn:service:sos SIP/2.0\r\n\
+        Via: SIP/2.0/UDP 10.0.0.1;branch=z9hG4bK776asdhds\r\n\
+        Content-Length: 0\r\n\r\n",
+    )
+    .unwrap();
+    assert_eq!(Some(&line.uri), msg.uri());
+}
+
+#[test]
+fn status_line() {
+    let remains = vec![];
+    let line = StatusLine {
+        version: Version::default(),
+        code: 486,
+        reason: Some("Busy Here (DND)".into()),
+    };
+    assert_eq!(
+        Ok((remains.as_ref(), line.clone())),
+        parse_status_line::<VerboseError<&[u8]>>(b"SIP/2.0 486 Busy Here (DND)\r\n")
+    );
+    assert_eq!("SIP/2.0 486 Busy Here (DND)", format!("{}", line));
+
+    let line = StatusLine {
+        version: Version::default(),
+        code: 200,
+        reason: None,
+    };
+    assert_eq!(
+        Ok((remains.as_ref(), line)),
+        parse_status_line::<VerboseError<&[u8]>>(b"SIP/2.0 200\r\n")
+    );
+
+    assert!(matches!(
+        parse_status_line::<VerboseError<&[u8]>>(b"SIP/3.0 200 OK\r\n"),
+        Err(nom::Err::Failure(_))
+    ));
+    assert!(matches!(
+        parse_message::<VerboseError<&[u8]>>(b"SIP/3.0 200 OK\r\n\r\n"),
+        Err(nom::Err::Failure(_))
+    ));
+}
+
+#[test]
+fn extension_method() {
+    let (_, line) =
+        parse_request_line::<VerboseError<&[u8]>>(b"INFORMATION sip:example.com SIP/2.0\r\n")
+            .unwrap();
+    assert_eq!(Method::Extension("INFORMATION".into()), line.method);
+    assert_eq!("INFORMATION sip:example.com SIP/2.0", format!("{}", line));
+}
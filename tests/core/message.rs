@@ -9,7 +9,7 @@ fn read_message() {
     let req = RequestGenerator::new()
         .uri(uri)
         .method(Method::Register)
-        .build()
+        .build_unchecked()
         .unwrap();
     assert_eq!(
         Ok((remains.as_ref(), req)),
@@ -31,10 +31,108 @@ fn read_complex() {
         .method(Method::Register)
         .headers(vec![Header::Expires(10), Header::ContentLength(5)])
         .body(vec![b'6'; 5])
-        .build()
+        .build_unchecked()
         .unwrap();
     assert_eq!(Ok((
         remains.as_ref(), req)),
         parse_message::<VerboseError<&[u8]>>(b"REGISTER sip:user@example.com;rport;new;Some=Param;Other SIP/2.0\r\nExpires: 10\r\nContent-Length: 5\r\n\r\n66666")
     );
 }
+
+#[test]
+fn read_content_length() {
+    let input = b"SIP/2.0 200 OK\r\nContent-Length: 5\r\n\r\nhelloSIP/2.0 100 Trying\r\n\r\n";
+    let (remains, msg) = parse_message::<VerboseError<&[u8]>>(input).unwrap();
+    assert_eq!(b"hello", msg.body().as_slice());
+    assert_eq!(b"SIP/2.0 100 Trying\r\n\r\n", remains);
+
+    assert!(matches!(
+        parse_message::<VerboseError<&[u8]>>(b"SIP/2.0 200 OK\r\nContent-Length: 5\r\n\r\nhel"),
+        Err(nom::Err::Incomplete(nom::Needed::Size(size))) if size.get() == 2
+    ));
+
+    let (remains, msg) =
+        parse_message::<VerboseError<&[u8]>>(b"SIP/2.0 200 OK\r\n\r\nhello").unwrap();
+    assert_eq!(b"hello", msg.body().as_slice());
+    assert!(remains.is_empty());
+}
+
+#[test]
+fn datagram_truncation() {
+    let input = b"SIP/2.0 200 OK\r\nContent-Length: 5\r\n\r\nhello world";
+    let msg = SipMessage::parse(input).unwrap();
+    assert_eq!(b"hello", msg.body().as_slice());
+
+    let (msg, len) = SipMessage::parse_with_len(input).unwrap();
+    assert_eq!(b"hello", msg.body().as_slice());
+    assert_eq!(b" world", &input[len..]);
+
+    let input = b"SIP/2.0 200 OK\r\n\r\nhello world";
+    let (msg, len) = SipMessage::parse_with_len(input).unwrap();
+    assert_eq!(b"hello world", msg.body().as_slice());
+    assert_eq!(input.len(), len);
+}
+
+#[test]
+fn write_binary_body() {
+    let body = vec![0x89, b'P', b'N', b'G', 0x00, 0xff];
+    let req = RequestGenerator::new()
+        .uri(Uri::sip(domain!("example.com")))
+        .method(Method::Message)
+        .header(Header::ContentLength(body.len() as u32))
+        .body(body.clone())
+        .build_unchecked()
+        .unwrap();
+    let bytes = req.to_bytes();
+    assert!(bytes.starts_with(b"MESSAGE sip:example.com SIP/2.0\r\nContent-Length: 6\r\n\r\n"));
+    assert!(bytes.ends_with(&body));
+
+    let mut out = vec![];
+    req.write_to(&mut out).unwrap();
+    assert_eq!(bytes, out);
+    assert_eq!(Ok(req), SipMessage::parse(&bytes));
+}
+
+#[test]
+fn message_accessors() {
+    let mut req = SipMessage::parse(
+        b"INVITE sip:bob@example.com SIP/2.0\r\n\
+        Via: SIP/2.0/UDP proxy.example.com;branch=z9hG4bK2\r\n\
+        Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK1\r\n\
+        From: <sip:alice@example.com>;tag=1928301774\r\n\
+        To: <sip:bob@example.com>\r\n\
+        Call-ID: a84b4c76e66710\r\n\
+        CSeq: 314159 INVITE\r\n\
+        Max-Forwards: 70\r\n\r\n",
+    )
+    .unwrap();
+    assert_eq!(Some(&Method::Invite), req.method());
+    assert_eq!(
+        Some(&Uri::sip(domain!("example.com")).auth(uri_auth!("bob"))),
+        req.uri()
+    );
+    assert_eq!(None, req.status());
+    assert_eq!(Some(&"a84b4c76e66710".to_string()), req.call_id());
+    assert_eq!(Some((314159, &Method::Invite)), req.cseq());
+    assert_eq!(Some("1928301774"), req.from().and_then(|from| from.tag()));
+    assert_eq!(None, req.to().unwrap().tag());
+    assert_eq!(2, req.via_stack().len());
+    assert_eq!(Some("z9hG4bK2"), req.top_via().unwrap().branch());
+
+    req.set_header(Header::MaxForwards(69));
+    assert_eq!(
+        Some(&Header::MaxForwards(69)),
+        req.header(headers::HeaderKind::MaxForwards)
+    );
+    assert_eq!(7, req.headers().iter().count());
+    assert_eq!(2, req.remove_header(headers::HeaderKind::Via).len());
+    assert!(req.via_stack().is_empty());
+
+    let res = ResponseGenerator::new()
+        .code(StatusCode::RINGING)
+        .build_unchecked()
+        .unwrap();
+    assert_eq!(Some(StatusCode::RINGING), res.status());
+    assert_eq!(None, res.method());
+    assert_eq!(None, res.uri());
+}
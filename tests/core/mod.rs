@@ -1,4 +1,18 @@
+mod body;
+mod borrowed;
+mod code;
+mod config;
+mod error;
+mod line;
 mod message;
+mod packet;
+mod preserve;
 mod request;
 mod response;
+#[cfg(feature = "arbitrary")]
+mod round_trip;
+#[cfg(feature = "serde")]
+mod serialize;
+mod stream;
+mod validate;
 mod version;
@@ -0,0 +1,26 @@
+use libsip::core::packet::{parse_packet, ParsedPacket};
+use libsip::*;
+
+#[test]
+fn packets() {
+    assert_eq!(Ok(ParsedPacket::KeepAlive), parse_packet(b"\r\n\r\n"));
+    assert_eq!(Ok(ParsedPacket::KeepAlive), parse_packet(b"\r\n"));
+
+    let binding_request = [
+        0x00, 0x01, 0x00, 0x00, 0x21, 0x12, 0xa4, 0x42, 0xb7, 0xe7, 0xa7, 0x01, 0xbc, 0x34, 0xd6,
+        0x86, 0xfa, 0x87, 0xdf, 0xae,
+    ];
+    assert_eq!(
+        Ok(ParsedPacket::MaybeStun(binding_request.to_vec())),
+        parse_packet(&binding_request)
+    );
+
+    let msg = parse_packet(b"OPTIONS sip:example.com SIP/2.0\r\nMax-Forwards: 70\r\n\r\n");
+    assert!(matches!(msg, Ok(ParsedPacket::Sip(msg)) if msg.method() == Some(&Method::Options)));
+
+    assert!(matches!(
+        parse_packet(b"OPTIONS sip:example.com SIP/2.0\r\nBad Header\r\n\r\n"),
+        Err(ParseError::Header { .. })
+    ));
+    assert!(parse_packet(b"").is_err());
+}
@@ -0,0 +1,49 @@
+use libsip::*;
+
+const INVITE: &[u8] = b"INVITE sip:bob@example.com SIP/2.0\r\n\
+    via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+    To:<sip:bob@example.com>\r\n\
+    f: Alice <sip:alice@example.com>;tag=1928301774\r\n\
+    X-Signature: abc==\r\n\
+    Subject: lunch\r\n  tomorrow\r\n\
+    CSeq:   314159 INVITE\r\n\
+    Content-Length: 4\r\n\r\nv=0\n";
+
+#[test]
+fn preserved_round_trip() {
+    let msg = PreservedMessage::parse(INVITE).unwrap();
+    assert_eq!(
+        Some(&Header::Subject("lunch tomorrow".into())),
+        msg.message().headers().get(headers::HeaderKind::Subject)
+    );
+    assert_eq!(INVITE.to_vec(), msg.to_bytes());
+    assert_ne!(INVITE.to_vec(), msg.message().to_bytes());
+    assert_eq!(SipMessage::parse(INVITE).unwrap(), msg.into_message());
+}
+
+#[test]
+fn preserved_changes() {
+    let mut msg = PreservedMessage::parse(INVITE).unwrap();
+    msg.message_mut()
+        .headers_mut()
+        .push(Header::MaxForwards(69));
+    msg.message_mut().headers_mut().0.remove(4);
+    let bytes = String::from_utf8(msg.to_bytes()).unwrap();
+    assert!(bytes.starts_with("INVITE sip:bob@example.com SIP/2.0\r\nvia: SIP/2.0/UDP"));
+    assert!(!bytes.contains("Subject"));
+    assert!(bytes.contains("X-Signature: abc==\r\nCSeq:   314159 INVITE\r\n"));
+    assert!(bytes.ends_with("Content-Length: 4\r\nMax-Forwards: 69\r\n\r\nv=0\n"));
+
+    let mut res = PreservedMessage::parse(b"SIP/2.0 200 Okay\r\nCall-ID: 1\r\n\r\n").unwrap();
+    assert_eq!(
+        b"SIP/2.0 200 Okay\r\nCall-ID: 1\r\n\r\n".to_vec(),
+        res.to_bytes()
+    );
+    if let SipMessage::Response { code, .. } = res.message_mut() {
+        *code = 486;
+    }
+    assert_eq!(
+        b"SIP/2.0 486 Busy Here\r\nCall-ID: 1\r\n\r\n".to_vec(),
+        res.to_bytes()
+    );
+}
@@ -1,4 +1,4 @@
-use libsip::*;
+use libsip::{headers::HeaderKind, *};
 
 use nom::error::VerboseError;
 
@@ -8,7 +8,7 @@ fn write_simple() {
     let req = RequestGenerator::new()
         .uri(uri)
         .method(Method::Register)
-        .build()
+        .build_unchecked()
         .unwrap();
     assert_eq!(
         "REGISTER sip:user@example.com SIP/2.0\r\n\r\n".to_string(),
@@ -24,7 +24,7 @@ fn write_complex() {
         .method(Method::Register)
         .headers(vec![Header::Expires(10), Header::ContentLength(5)])
         .body(vec![b'5'; 5])
-        .build()
+        .build_unchecked()
         .unwrap();
     assert_eq!(
         "REGISTER sip:example.com SIP/2.0\r\nExpires: 10\r\nContent-Length: 5\r\n\r\n55555"
@@ -40,7 +40,7 @@ fn read_simple() {
     let req = RequestGenerator::new()
         .uri(uri)
         .method(Method::Register)
-        .build()
+        .build_unchecked()
         .unwrap();
     assert_eq!(
         Ok((remains.as_ref(), req)),
@@ -57,7 +57,264 @@ fn read_complex() {
         .method(Method::Register)
         .headers(vec![Header::Expires(10), Header::ContentLength(5)])
         .body(vec![b'6'; 5])
-        .build()
+        .build_unchecked()
         .unwrap();
     assert_eq!(Ok((remains.as_ref(), req)), parse_request::<VerboseError<&[u8]>>(b"REGISTER sip:user@example.com SIP/2.0\r\nExpires: 10\r\nContent-Length: 5\r\n\r\n66666"));
 }
+
+#[test]
+fn cancel_of() {
+    let invite = SipMessage::parse(
+        b"INVITE sip:bob@example.com SIP/2.0\r\n\
+        Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+        Max-Forwards: 70\r\n\
+        Route: <sip:proxy.example.com;lr>\r\n\
+        From: Alice <sip:alice@example.com>;tag=1928301774\r\n\
+        To: Bob <sip:bob@example.com>\r\n\
+        Call-ID: a84b4c76e66710\r\n\
+        CSeq: 314159 INVITE\r\n\
+        Require: 100rel\r\n\
+        Content-Type: application/sdp\r\n\
+        Content-Length: 4\r\n\r\nv=0\n",
+    )
+    .unwrap();
+    let cancel = RequestGenerator::cancel_of(&invite)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        "CANCEL sip:bob@example.com SIP/2.0\r\n\
+        Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+        Max-Forwards: 70\r\n\
+        From: Alice <sip:alice@example.com>;tag=1928301774\r\n\
+        To: Bob <sip:bob@example.com>\r\n\
+        Call-ID: a84b4c76e66710\r\n\
+        CSeq: 314159 CANCEL\r\n\
+        Route: <sip:proxy.example.com;lr>\r\n\
+        Content-Length: 0\r\n\r\n",
+        format!("{}", cancel)
+    );
+
+    let res = ResponseGenerator::new()
+        .code(200)
+        .build_unchecked()
+        .unwrap();
+    assert!(RequestGenerator::cancel_of(&res).is_err());
+}
+
+fn ack_invite() -> SipMessage {
+    SipMessage::parse(
+        b"INVITE sip:bob@example.com SIP/2.0\r\n\
+        Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+        Route: <sip:proxy.example.com;lr>\r\n\
+        From: <sip:alice@example.com>;tag=1928301774\r\n\
+        To: <sip:bob@example.com>\r\n\
+        Call-ID: a84b4c76e66710\r\n\
+        CSeq: 314159 INVITE\r\n\r\n",
+    )
+    .unwrap()
+}
+
+#[test]
+fn ack_of_failure() {
+    let res = SipMessage::parse(
+        b"SIP/2.0 486 Busy Here\r\n\
+        Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+        From: <sip:alice@example.com>;tag=1928301774\r\n\
+        To: <sip:bob@example.com>;tag=a6c85cf\r\n\
+        Call-ID: a84b4c76e66710\r\n\
+        CSeq: 314159 INVITE\r\n\r\n",
+    )
+    .unwrap();
+    let ack = RequestGenerator::ack_of(&ack_invite(), &res)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        "ACK sip:bob@example.com SIP/2.0\r\n\
+        Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+        Max-Forwards: 70\r\n\
+        From: sip:alice@example.com;tag=1928301774\r\n\
+        To: sip:bob@example.com;tag=a6c85cf\r\n\
+        Call-ID: a84b4c76e66710\r\n\
+        CSeq: 314159 ACK\r\n\
+        Route: <sip:proxy.example.com;lr>\r\n\
+        Content-Length: 0\r\n\r\n",
+        format!("{}", ack)
+    );
+}
+
+#[test]
+fn ack_of_success() {
+    let res = SipMessage::parse(
+        b"SIP/2.0 200 OK\r\n\
+        Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+        Record-Route: <sip:p2.example.com;lr>\r\n\
+        Record-Route: <sip:p1.example.com;lr>\r\n\
+        From: <sip:alice@example.com>;tag=1928301774\r\n\
+        To: <sip:bob@example.com>;tag=a6c85cf\r\n\
+        Call-ID: a84b4c76e66710\r\n\
+        CSeq: 314159 INVITE\r\n\
+        Contact: <sip:bob@192.0.2.4>\r\n\r\n",
+    )
+    .unwrap();
+    let ack = RequestGenerator::ack_of(&ack_invite(), &res)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        Some(&Uri::sip(ip_domain!(192, 0, 2, 4)).auth(uri_auth!("bob"))),
+        ack.uri()
+    );
+    let via = ack.top_via().unwrap();
+    assert_eq!(domain!("pc33.example.com"), via.host);
+    assert_ne!(Some("z9hG4bK776asdhds"), via.branch());
+    assert!(via.branch().unwrap().starts_with("z9hG4bK"));
+    assert_eq!(Some("a6c85cf"), ack.to().unwrap().tag());
+    assert_eq!(Some((314159, &Method::Ack)), ack.cseq());
+    let routes: Vec<_> = ack
+        .headers()
+        .route_set()
+        .into_iter()
+        .map(|route| route.uri.host)
+        .collect();
+    assert_eq!(
+        vec![domain!("p1.example.com"), domain!("p2.example.com")],
+        routes
+    );
+
+    let ringing = ResponseGenerator::new()
+        .code(180)
+        .build_unchecked()
+        .unwrap();
+    assert!(RequestGenerator::ack_of(&ack_invite(), &ringing).is_err());
+}
+
+#[test]
+fn ack_of_success_strict_route() {
+    let res = SipMessage::parse(
+        b"SIP/2.0 200 OK\r\n\
+        Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+        Record-Route: <sip:p2.example.com;lr>\r\n\
+        Record-Route: <sip:p1.example.com>\r\n\
+        From: <sip:alice@example.com>;tag=1928301774\r\n\
+        To: <sip:bob@example.com>;tag=a6c85cf\r\n\
+        Call-ID: a84b4c76e66710\r\n\
+        CSeq: 314159 INVITE\r\n\
+        Contact: <sip:bob@192.0.2.4>\r\n\r\n",
+    )
+    .unwrap();
+    let ack = RequestGenerator::ack_of(&ack_invite(), &res)
+        .unwrap()
+        .build()
+        .unwrap();
+    // The strict router p1 is the Request-URI, the Contact the last route.
+    assert_eq!(Some(&Uri::sip(domain!("p1.example.com"))), ack.uri());
+    let routes: Vec<_> = ack
+        .headers()
+        .route_set()
+        .into_iter()
+        .map(|route| route.uri)
+        .collect();
+    assert_eq!(
+        vec![
+            Uri::sip(domain!("p2.example.com")).lr_param(),
+            Uri::sip(ip_domain!(192, 0, 2, 4)).auth(uri_auth!("bob")),
+        ],
+        routes
+    );
+}
+
+#[test]
+fn build_missing_headers() {
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("bob"));
+    let res = RequestGenerator::new()
+        .method(Method::Options)
+        .uri(uri.clone())
+        .header(Header::CallId("1234@example.com".into()))
+        .header_opt(None)
+        .build();
+    assert_eq!(
+        Err(BuildError::MissingHeaders(vec![
+            HeaderKind::Via,
+            HeaderKind::MaxForwards,
+            HeaderKind::From,
+            HeaderKind::To,
+            HeaderKind::CSeq,
+        ])),
+        res
+    );
+    assert_eq!(
+        "missing mandatory headers: Via, Max-Forwards, From, To, CSeq",
+        format!("{}", res.unwrap_err())
+    );
+
+    let res = RequestGenerator::new().uri(uri).build();
+    assert_eq!(Err(BuildError::MissingMethod), res);
+}
+
+#[test]
+fn build_content_length() {
+    let generator = || {
+        RequestGenerator::new()
+            .method(Method::Message)
+            .uri(Uri::sip(domain!("example.com")).auth(uri_auth!("bob")))
+            .header(Header::Via(vec![ViaHeader::new(
+                domain!("pc33.example.com"),
+                Transport::Udp,
+            )]))
+            .header(Header::MaxForwards(70))
+            .header(Header::ContentLength(100))
+            .header(Header::From(named_header!(
+                (Uri::sip(domain!("example.com")).auth(uri_auth!("alice")))
+            )))
+            .header(Header::To(named_header!(
+                (Uri::sip(domain!("example.com")).auth(uri_auth!("bob")))
+            )))
+            .header(Header::CallId("1234@example.com".into()))
+            .header(Header::CSeq(1, Method::Message))
+            .body(b"hello".to_vec())
+    };
+
+    let req = generator().build().unwrap();
+    assert_eq!(
+        vec![&Header::ContentLength(5)],
+        req.headers().get_all(HeaderKind::ContentLength)
+    );
+    assert_eq!(Some(&Header::ContentLength(5)), req.headers().0.get(2));
+
+    let req = generator().suppress_content_length().build().unwrap();
+    assert_eq!(
+        Some(Header::ContentLength(100)),
+        req.headers().content_length()
+    );
+}
+
+#[test]
+fn default_via() {
+    let via = ViaHeader::new(domain!("pc33.example.com"), Transport::Udp);
+    let generator = || {
+        RequestGenerator::new()
+            .method(Method::Options)
+            .uri(Uri::sip(domain!("example.com")))
+            .default_via(via.clone())
+            .header(Header::MaxForwards(70))
+    };
+
+    let first = generator().build_unchecked().unwrap();
+    let second = generator().build_unchecked().unwrap();
+    let branch = |msg: &SipMessage| msg.top_via().unwrap().branch().unwrap().to_string();
+    assert_eq!(
+        Some(HeaderKind::Via),
+        first.headers().0.first().map(Header::kind)
+    );
+    assert!(branch(&first).starts_with("z9hG4bK"));
+    assert_ne!(branch(&first), branch(&second));
+
+    let given = via.clone().branch_param("z9hG4bK1234");
+    let req = generator()
+        .header(Header::Via(vec![given.clone()]))
+        .build_unchecked()
+        .unwrap();
+    assert_eq!(vec![&given], req.via_stack());
+}
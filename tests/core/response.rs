@@ -1,10 +1,13 @@
-use libsip::*;
+use libsip::{headers::HeaderKind, *};
 
 use nom::error::VerboseError;
 
 #[test]
 fn write_simple() {
-    let req = ResponseGenerator::new().code(200).build().unwrap();
+    let req = ResponseGenerator::new()
+        .code(200)
+        .build_unchecked()
+        .unwrap();
     assert_eq!("SIP/2.0 200 OK\r\n\r\n".to_string(), format!("{}", req));
 }
 
@@ -15,7 +18,7 @@ fn write_complex() {
         .header(Header::Expires(10))
         .header(Header::ContentLength(5))
         .body(vec![b'5'; 5])
-        .build()
+        .build_unchecked()
         .unwrap();
     assert_eq!(
         "SIP/2.0 180 Ringing\r\nExpires: 10\r\nContent-Length: 5\r\n\r\n55555".to_string(),
@@ -26,7 +29,10 @@ fn write_complex() {
 #[test]
 fn read_simple() {
     let remains = vec![];
-    let req = ResponseGenerator::new().code(200).build().unwrap();
+    let req = ResponseGenerator::new()
+        .code(200)
+        .build_unchecked()
+        .unwrap();
     assert_eq!(
         Ok((remains.as_ref(), req)),
         parse_response::<VerboseError<&[u8]>>(b"SIP/2.0 200 OK\r\n\r\n")
@@ -41,7 +47,7 @@ fn read_complex() {
         .header(Header::Expires(10))
         .header(Header::ContentLength(5))
         .body(vec![b'5'; 5])
-        .build()
+        .build_unchecked()
         .unwrap();
     assert_eq!(
         Ok((remains.as_ref(), req)),
@@ -50,3 +56,124 @@ fn read_complex() {
         )
     );
 }
+
+#[test]
+fn rseq_if_supported() {
+    let mut request_headers = Headers::new();
+    request_headers.push(Header::Supported(vec!["timer".into(), "100rel".into()]));
+    let res = ResponseGenerator::new()
+        .code(183)
+        .rseq_if_supported(&request_headers, 1)
+        .build_unchecked()
+        .unwrap();
+    assert_eq!(
+        "SIP/2.0 183 Session Progress\r\nRequire: 100rel\r\nRSeq: 1\r\n\r\n".to_string(),
+        format!("{}", res)
+    );
+
+    let res = ResponseGenerator::new()
+        .code(183)
+        .rseq_if_supported(&Headers::new(), 1)
+        .build_unchecked()
+        .unwrap();
+    assert_eq!(
+        "SIP/2.0 183 Session Progress\r\n\r\n".to_string(),
+        format!("{}", res)
+    );
+}
+
+#[test]
+fn to_tag() {
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("bob"));
+    let res = ResponseGenerator::new()
+        .code(180)
+        .header(Header::To(named_header!((uri.clone()))))
+        .build_unchecked()
+        .unwrap();
+    let tag = match res.headers().to() {
+        Some(Header::To(to)) => to.tag().map(String::from),
+        _ => None,
+    };
+    assert!(tag.is_some());
+
+    let res = ResponseGenerator::new()
+        .code(200)
+        .header(Header::To(
+            named_header!((uri.clone())).param("tag", Some("1234")),
+        ))
+        .build_unchecked()
+        .unwrap();
+    assert_eq!(
+        Some(Header::To(
+            named_header!((uri.clone())).param("tag", Some("1234"))
+        )),
+        res.headers().to()
+    );
+
+    let res = ResponseGenerator::new()
+        .code(100)
+        .header(Header::To(named_header!((uri.clone()))))
+        .build_unchecked()
+        .unwrap();
+    assert_eq!(Some(Header::To(named_header!(uri))), res.headers().to());
+}
+
+#[test]
+fn reply_to() {
+    let req = SipMessage::parse(
+        b"INVITE sip:bob@example.com SIP/2.0\r\n\
+        Via: SIP/2.0/UDP proxy.example.com;branch=z9hG4bK2\r\n\
+        Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK1\r\n\
+        Max-Forwards: 69\r\n\
+        Record-Route: <sip:proxy.example.com;lr>\r\n\
+        From: <sip:alice@example.com>;tag=1928301774\r\n\
+        To: <sip:bob@example.com>\r\n\
+        Call-ID: a84b4c76e66710\r\n\
+        CSeq: 314159 INVITE\r\n\
+        Content-Type: application/sdp\r\n\
+        Content-Length: 4\r\n\r\nv=0\n",
+    )
+    .unwrap();
+    let res = ResponseGenerator::reply_to(&req, StatusCode::RINGING)
+        .build()
+        .unwrap();
+    let tag = res.to().unwrap().tag().unwrap().to_string();
+    assert_eq!(
+        format!(
+            "SIP/2.0 180 Ringing\r\n\
+            Via: SIP/2.0/UDP proxy.example.com;branch=z9hG4bK2\r\n\
+            Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK1\r\n\
+            Record-Route: <sip:proxy.example.com;lr>\r\n\
+            From: sip:alice@example.com;tag=1928301774\r\n\
+            To: sip:bob@example.com;tag={}\r\n\
+            Call-ID: a84b4c76e66710\r\n\
+            CSeq: 314159 INVITE\r\n\
+            Content-Length: 0\r\n\r\n",
+            tag
+        ),
+        format!("{}", res)
+    );
+
+    let trying = ResponseGenerator::reply_to(&req, 100).build().unwrap();
+    assert_eq!(None, trying.to().unwrap().tag());
+}
+
+#[test]
+fn build_missing_headers() {
+    let request =
+        SipMessage::parse(b"OPTIONS sip:bob@example.com SIP/2.0\r\nCall-ID: 1234\r\n\r\n").unwrap();
+    let res = ResponseGenerator::new()
+        .code(200)
+        .header_opt(request.headers().call_id())
+        .header_opt(request.headers().cseq())
+        .build();
+    assert_eq!(
+        Err(BuildError::MissingHeaders(vec![
+            HeaderKind::Via,
+            HeaderKind::From,
+            HeaderKind::To,
+            HeaderKind::CSeq,
+        ])),
+        res
+    );
+}
@@ -0,0 +1,20 @@
+use libsip::{parse_header, uri::parse_uri, Header, SipMessage, Uri};
+use nom::error::VerboseError;
+use quickcheck::quickcheck;
+
+quickcheck! {
+    fn uri(uri: Uri) -> bool {
+        let written = uri.to_string();
+        parse_uri::<VerboseError<&[u8]>>(written.as_bytes()) == Ok((&[][..], uri))
+    }
+
+    fn header(header: Header) -> bool {
+        let written = format!("{}\r\n", header);
+        parse_header::<VerboseError<&[u8]>>(written.as_bytes()).map(|(_, parsed)| parsed)
+            == Ok(header)
+    }
+
+    fn message(msg: SipMessage) -> bool {
+        SipMessage::parse(&msg.to_bytes()) == Ok(msg)
+    }
+}
@@ -0,0 +1,59 @@
+use libsip::{
+    core::Body,
+    headers::{parse::parse_auth_challenge, DigestCredentials},
+    *,
+};
+use nom::error::VerboseError;
+
+const INVITE: &[u8] = b"INVITE sip:bob@example.com SIP/2.0\r\n\
+    Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds;rport\r\n\
+    Max-Forwards: 70\r\n\
+    From: Alice <sip:alice@example.com>;tag=1928301774\r\n\
+    To: Bob <sip:bob@example.com>\r\n\
+    Call-ID: a84b4c76e66710\r\n\
+    CSeq: 314159 INVITE\r\n\
+    Contact: <sip:alice@pc33.example.com;transport=tcp>\r\n\
+    Record-Route: <sip:p1.example.com;lr>\r\n\
+    Proxy-Authorization: Digest username=\"alice\", realm=\"example.com\", nonce=\"abc\"\r\n\
+    Content-Type: application/sdp\r\n\
+    Content-Length: 92\r\n\r\n\
+    v=0\r\n\
+    o=alice 2890844526 2890844526 IN IP4 pc33.example.com\r\n\
+    s=-\r\n\
+    c=IN IP4 192.0.2.1\r\n\
+    t=0 0\r\n";
+
+#[test]
+fn message() {
+    let msg = SipMessage::parse(INVITE).unwrap();
+    let json = serde_json::to_string(&msg).unwrap();
+    assert_eq!(msg, serde_json::from_str::<SipMessage>(&json).unwrap());
+}
+
+#[test]
+fn body() {
+    let body = SipMessage::parse(INVITE).unwrap().typed_body().unwrap();
+    assert!(matches!(body, Body::Sdp(_)));
+    let json = serde_json::to_string(&body).unwrap();
+    assert_eq!(body, serde_json::from_str::<Body>(&json).unwrap());
+}
+
+#[test]
+fn credentials() {
+    let (_, challenge) = parse_auth_challenge::<VerboseError<&[u8]>>(
+        b"Digest realm=\"example.com\", nonce=\"abc\", qop=\"auth\"",
+    )
+    .unwrap();
+    let mut credentials = DigestCredentials::new("alice", "secret");
+    credentials.on_challenge(&challenge, None);
+    let uri = Uri::sip(domain!("example.com"));
+    credentials.authorize(&Method::Register, &uri, b"").unwrap();
+
+    let json = serde_json::to_string(&credentials).unwrap();
+    assert!(!json.contains("secret"));
+    let mut restored = serde_json::from_str::<DigestCredentials>(&json).unwrap();
+    assert_ne!(credentials, restored);
+    restored.set_password("secret");
+    assert_eq!(credentials, restored);
+    assert_eq!(1, restored.nonce_count());
+}
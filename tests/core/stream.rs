@@ -0,0 +1,118 @@
+use libsip::*;
+
+use std::io::ErrorKind;
+
+const INVITE: &[u8] = b"INVITE sip:bob@example.com SIP/2.0\r\nCall-ID: 1234@example.com\r\nContent-Length: 4\r\n\r\nv=0\n";
+
+#[test]
+fn split_reads() {
+    let mut parser = SipParser::new();
+    for chunk in INVITE.chunks(7) {
+        assert!(parser.next_message().unwrap().is_none());
+        parser.feed(chunk);
+    }
+    let msg = parser.next_message().unwrap().unwrap();
+    assert!(msg.is_request());
+    assert_eq!(b"v=0\n", msg.body().as_slice());
+    assert_eq!(
+        Some(Header::CallId("1234@example.com".into())),
+        msg.headers().call_id()
+    );
+    assert!(parser.remaining().is_empty());
+    assert!(parser.next_message().unwrap().is_none());
+}
+
+#[test]
+fn multiple_messages() {
+    let mut parser = SipParser::new();
+    parser.feed(b"\r\n\r\n");
+    parser.feed(INVITE);
+    parser.feed(b"SIP/2.0 100 Trying\r\n\r\n");
+    parser.feed(INVITE);
+    parser.feed(b"SIP/2.0 200");
+
+    assert!(parser.next_message().unwrap().unwrap().is_request());
+    assert_eq!(
+        Some(100),
+        parser.next_message().unwrap().unwrap().status_code()
+    );
+    assert!(parser.next_message().unwrap().unwrap().is_request());
+    assert!(parser.next_message().unwrap().is_none());
+    assert_eq!(b"SIP/2.0 200", parser.remaining());
+}
+
+#[test]
+fn keep_alive() {
+    let mut parser = SipParser::new();
+    parser.feed(b"\r\n\r\n");
+    assert!(parser.next_message().unwrap().is_none());
+    assert!(parser.take_keep_alive());
+    assert!(!parser.take_keep_alive());
+
+    // The empty line ending headers received on their own isn't one.
+    let head_end = INVITE.windows(4).position(|w| w == b"\r\n\r\n").unwrap() + 2;
+    parser.feed(&INVITE[..head_end]);
+    assert!(parser.next_message().unwrap().is_none());
+    parser.feed(&INVITE[head_end..]);
+    assert!(parser.next_message().unwrap().unwrap().is_request());
+    assert!(!parser.take_keep_alive());
+
+    parser.feed(b"\r\n");
+    parser.feed(INVITE);
+    assert!(parser.next_message().unwrap().unwrap().is_request());
+    assert!(parser.take_keep_alive());
+}
+
+#[test]
+fn invalid_message() {
+    let mut parser = SipParser::new();
+    parser.feed(b"garbage\r\nContent-Length: 0\r\n\r\n");
+    parser.feed(b"SIP/2.0 100 Trying\r\n\r\n");
+    let error = parser.next_message().unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert_eq!(
+        Some(100),
+        parser.next_message().unwrap().unwrap().status_code()
+    );
+
+    parser.feed(b"garbage\r\n\r\n");
+    parser.feed(b"SIP/2.0 100 Trying\r\n\r\n");
+    let error = parser.next_message().unwrap_err();
+    assert_eq!(ErrorKind::ConnectionAborted, error.kind());
+    assert!(parser.remaining().is_empty());
+}
+
+#[test]
+fn invalid_message_body() {
+    let mut parser = SipParser::new();
+    parser.feed(b"OPTIONS sip:a@b SIP/3.0\r\nContent-Length: 5\r\n\r\nhel");
+    assert!(parser.next_message().unwrap().is_none());
+    parser.feed(b"lo");
+    parser.feed(b"OPTIONS sip:a@b SIP/2.0\r\nContent-Length: 0\r\n\r\n");
+    let error = parser.next_message().unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    let msg = parser.next_message().unwrap().unwrap();
+    assert_eq!(Some(&Method::Options), msg.method());
+    assert!(parser.remaining().is_empty());
+}
+
+#[test]
+fn max_message_size() {
+    let mut parser = SipParser::new();
+    parser.set_max_message_size(64);
+    parser.feed(b"SIP/2.0 200 OK\r\nContent-Length: 100\r\n\r\n");
+    let error = parser.next_message().unwrap_err();
+    assert_eq!(ErrorKind::ConnectionAborted, error.kind());
+    assert!(parser.remaining().is_empty());
+
+    parser.feed(b"SIP/2.0 200 OK\r\nContent-Length: 18446744073709551615\r\n\r\n");
+    let error = parser.next_message().unwrap_err();
+    assert_eq!(ErrorKind::ConnectionAborted, error.kind());
+    assert!(parser.remaining().is_empty());
+
+    parser.feed(b"SIP/2.0 200 OK\r\n");
+    assert!(parser.next_message().unwrap().is_none());
+    parser.feed(&[b'a'; 64]);
+    let error = parser.next_message().unwrap_err();
+    assert_eq!(ErrorKind::ConnectionAborted, error.kind());
+}
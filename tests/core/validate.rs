@@ -0,0 +1,94 @@
+use libsip::{
+    core::validate::{Role, ValidationError},
+    headers::HeaderKind,
+    *,
+};
+
+fn invite(branch: &str, extra: &str) -> SipMessage {
+    SipMessage::parse(
+        format!(
+            "INVITE sip:bob@example.com SIP/2.0\r\n\
+            Via: SIP/2.0/UDP pc33.example.com;branch={}\r\n\
+            From: <sip:alice@example.com>;tag=1928301774\r\n\
+            To: <sip:bob@example.com>\r\n\
+            Call-ID: a84b4c76e66710\r\n\
+            CSeq: 314159 INVITE\r\n\
+            {}\r\n",
+            branch, extra
+        )
+        .as_bytes(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn valid_request() {
+    let req = invite(
+        "z9hG4bK1",
+        "Max-Forwards: 70\r\nContent-Length: 3\r\n\r\nabc",
+    );
+    assert!(req.validate(Role::Uas).is_empty());
+    assert!(req.validate(Role::Proxy).is_empty());
+}
+
+#[test]
+fn invalid_request() {
+    let req = invite("z9hG4bK1", "Max-Forwards: 0\r\nCSeq: 1 BYE\r\n");
+    let errors = req.validate(Role::Proxy);
+    assert!(errors.contains(&ValidationError::DuplicateHeader(HeaderKind::CSeq)));
+    assert!(errors.contains(&ValidationError::TooManyHops));
+    assert!(!req
+        .validate(Role::Uas)
+        .contains(&ValidationError::TooManyHops));
+
+    let mut req = invite("z9hG4bK1", "");
+    req.set_header(Header::CSeq(1, Method::Bye));
+    req.body_mut().extend(b"abc");
+    req.set_header(Header::ContentLength(2));
+    assert_eq!(
+        vec![
+            ValidationError::MissingHeader(HeaderKind::MaxForwards),
+            ValidationError::CSeqMethodMismatch {
+                method: Method::Invite,
+                cseq_method: Method::Bye
+            },
+            ValidationError::ContentLengthMismatch {
+                content_length: 2,
+                body_length: 3
+            },
+        ],
+        req.validate(Role::Uas)
+    );
+}
+
+#[test]
+fn response_vias() {
+    let res = SipMessage::parse(
+        b"SIP/2.0 200 OK\r\n\
+        Via: SIP/2.0/UDP proxy.example.com;branch=z9hG4bK2\r\n\
+        Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK1\r\n\
+        From: <sip:alice@example.com>;tag=1928301774\r\n\
+        To: <sip:bob@example.com>;tag=a6c85cf\r\n\
+        Call-ID: a84b4c76e66710\r\n\
+        CSeq: 314159 INVITE\r\n\r\n",
+    )
+    .unwrap();
+    assert!(res.validate(Role::Proxy).is_empty());
+    assert_eq!(vec![ValidationError::MultipleVias], res.validate(Role::Uac));
+}
+
+#[test]
+fn merged_requests() {
+    let first = invite("z9hG4bK1", "Max-Forwards: 70\r\n");
+    let retransmission = invite("z9hG4bK1", "Max-Forwards: 70\r\n");
+    let merged = invite("z9hG4bK2", "Max-Forwards: 69\r\n");
+    assert!(merged.is_merged_with(&first));
+    assert!(!retransmission.is_merged_with(&first));
+    assert_eq!(
+        vec![ValidationError::MergedRequest],
+        merged.validate_against(Role::Uas, vec![&first])
+    );
+    assert!(retransmission
+        .validate_against(Role::Uas, vec![&first])
+        .is_empty());
+}
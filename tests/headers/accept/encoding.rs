@@ -1,5 +1,5 @@
 use libsip::{
-    headers::{parse::parse_accept_encoding_header, ContentType},
+    headers::{encoding::parse_accept_encoding_header, ContentCoding, WeightedCoding},
     Header,
 };
 
@@ -7,9 +7,14 @@ use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::AcceptEncoding(ContentType::Sdp);
+    let header = Header::AcceptEncoding(vec![WeightedCoding::new(ContentCoding::Gzip)]);
+    assert_eq!("Accept-Encoding: gzip".to_string(), format!("{}", header));
+    let header = Header::AcceptEncoding(vec![
+        WeightedCoding::new(ContentCoding::Gzip).q(0.5),
+        WeightedCoding::new(ContentCoding::Identity),
+    ]);
     assert_eq!(
-        "Accept-Encoding: application/sdp".to_string(),
+        "Accept-Encoding: gzip;q=0.5,identity".to_string(),
         format!("{}", header)
     );
 }
@@ -17,9 +22,24 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::AcceptEncoding(ContentType::Sdp);
+    let header = Header::AcceptEncoding(vec![WeightedCoding::new(ContentCoding::Gzip)]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_accept_encoding_header::<VerboseError<&[u8]>>(b"Accept-Encoding: application/sdp")
+        parse_accept_encoding_header::<VerboseError<&[u8]>>(b"Accept-Encoding: gzip\r\n")
+    );
+    let header = Header::AcceptEncoding(vec![
+        WeightedCoding::new(ContentCoding::Other("br".into())).q(0.8),
+        WeightedCoding::new(ContentCoding::Identity),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_accept_encoding_header::<VerboseError<&[u8]>>(
+            b"Accept-Encoding: br;q=0.8, Identity\r\n"
+        )
+    );
+    let header = Header::AcceptEncoding(vec![]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_accept_encoding_header::<VerboseError<&[u8]>>(b"Accept-Encoding: \r\n")
     );
 }
@@ -1,5 +1,5 @@
 use libsip::{
-    headers::{parse::parse_accept_language_header, Language},
+    headers::{language::parse_accept_language_header, Language, LanguageRange, LanguageTag},
     Header,
 };
 
@@ -7,16 +7,42 @@ use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::AcceptLanguage(Language::English);
+    let header = Header::AcceptLanguage(vec![LanguageRange::new(Language::English)]);
     assert_eq!("Accept-Language: en".to_string(), format!("{}", header));
+    let header = Header::AcceptLanguage(vec![
+        LanguageRange::new(Language::Danish),
+        LanguageRange::new(LanguageTag::new(Language::English).subtag("GB")).q(0.8),
+        LanguageRange::any().q(0.1),
+    ]);
+    assert_eq!(
+        "Accept-Language: da,en-GB;q=0.8,*;q=0.1".to_string(),
+        format!("{}", header)
+    );
 }
 
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::AcceptLanguage(Language::English);
+    let header = Header::AcceptLanguage(vec![LanguageRange::new(Language::English)]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_accept_language_header::<VerboseError<&[u8]>>(b"Accept-Language: en\r\n")
+    );
+    let header = Header::AcceptLanguage(vec![
+        LanguageRange::new(Language::Danish),
+        LanguageRange::new(LanguageTag::new(Language::English).subtag("GB")).q(0.8),
+        LanguageRange::new(Language::Other("tlh".into())).q(0.5),
+        LanguageRange::any().q(0.1),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_accept_language_header::<VerboseError<&[u8]>>(
+            b"Accept-Language: da, en-GB;q=0.8, tlh;q=0.5, *;q=0.1\r\n"
+        )
+    );
+    let header = Header::AcceptLanguage(vec![]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_accept_language_header::<VerboseError<&[u8]>>(b"Accept-Language: en")
+        parse_accept_language_header::<VerboseError<&[u8]>>(b"Accept-Language: \r\n")
     );
 }
@@ -7,16 +7,27 @@ use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::Accept(vec![Method::Invite, Method::Options]);
-    assert_eq!("Accept: INVITE,OPTIONS".to_string(), format!("{}", header));
+    let header = Header::Accept(vec![ContentType::sdp(), ContentType::plain_text()]);
+    assert_eq!(
+        "Accept: application/sdp,text/plain".to_string(),
+        format!("{}", header)
+    );
 }
 
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::Accept(vec![Method::Register, Method::Invite]);
+    let header = Header::Accept(vec![
+        ContentType::sdp(),
+        ContentType::new("text", "*").param("q", Some("0.5")),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_accept_header::<VerboseError<&[u8]>>(b"Accept: application/sdp, text/*;q=0.5\r\n")
+    );
+    let header = Header::Accept(vec![]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_accept_header::<VerboseError<&[u8]>>(b"Accept: REGISTER, INVITE\r\n")
+        parse_accept_header::<VerboseError<&[u8]>>(b"Accept: \r\n")
     );
 }
@@ -19,3 +19,21 @@ fn read() {
         parse_allow_header::<VerboseError<&[u8]>>(b"Allow: REGISTER,INVITE\r\n")
     );
 }
+
+#[test]
+fn extension_methods() {
+    let remains = vec![];
+    let header = Header::Allow(vec![
+        Method::Invite,
+        Method::Update,
+        Method::Extension("FOO".into()),
+        Method::Info,
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header.clone())),
+        parse_allow_header::<VerboseError<&[u8]>>(b"Allow: INVITE, update,FOO , INFO\r\n")
+    );
+    assert_eq!("Allow: INVITE,UPDATE,FOO,INFO", format!("{}", header));
+    assert_eq!(Ok(Method::PRack), "prack".parse());
+    assert_eq!(Err(()), "NOT A METHOD".parse::<Method>());
+}
@@ -0,0 +1,103 @@
+use libsip::{headers::parse::*, *};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let header = Header::CallId("call@id.com".into());
+    assert_eq!("i: call@id.com".to_string(), format!("{:#}", header));
+
+    let header = Header::ContentLength(5);
+    assert_eq!("l: 5".to_string(), format!("{:#}", header));
+
+    let header = Header::Via(vec![ViaHeader::new(domain!("example.com"), Transport::Udp)]);
+    assert_eq!(
+        "v: SIP/2.0/UDP example.com".to_string(),
+        format!("{:#}", header)
+    );
+
+    let header = Header::CSeq(1, Method::Invite);
+    assert_eq!("CSeq: 1 INVITE".to_string(), format!("{:#}", header));
+
+    let req = RequestGenerator::new()
+        .uri(Uri::sip(domain!("example.com")))
+        .method(Method::Register)
+        .header(Header::Supported(vec!["timer".into()]))
+        .header(Header::ContentLength(0))
+        .build_unchecked()
+        .unwrap();
+    assert_eq!(
+        "REGISTER sip:example.com SIP/2.0\r\nk: timer\r\nl: 0\r\n\r\n".to_string(),
+        format!("{:#}", req)
+    );
+}
+
+#[test]
+fn write_bytes() {
+    let res = ResponseGenerator::new()
+        .code(200)
+        .header(Header::From(NamedHeader::new(Uri::sip(domain!(
+            "example.com"
+        )))))
+        .header(Header::ContentLength(2))
+        .body(vec![0xff, 0x00])
+        .build_unchecked()
+        .unwrap();
+    assert_eq!(
+        b"SIP/2.0 200 OK\r\nf: sip:example.com\r\nl: 2\r\n\r\n\xff\x00".to_vec(),
+        res.to_compact_bytes()
+    );
+    assert_eq!(Ok(res.clone()), SipMessage::parse(&res.to_compact_bytes()));
+
+    let mut out = vec![];
+    res.headers().write_to(&mut out, true).unwrap();
+    assert_eq!(b"f: sip:example.com\r\nl: 2\r\n".to_vec(), out);
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("guy"));
+    assert_eq!(
+        Ok((remains.as_ref(), Header::From(named_header!(uri)))),
+        parse_from_header::<VerboseError<&[u8]>>(b"f: <sip:guy@example.com>\r\n")
+    );
+
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("guy"));
+    assert_eq!(
+        Ok((remains.as_ref(), Header::To(named_header!(uri)))),
+        parse_to_header::<VerboseError<&[u8]>>(b"t: <sip:guy@example.com>\r\n")
+    );
+
+    assert_eq!(
+        Ok((remains.as_ref(), Header::CallId("call@id.com".into()))),
+        parse_callid_header::<VerboseError<&[u8]>>(b"i: call@id.com\r\n")
+    );
+
+    assert_eq!(
+        Ok((remains.as_ref(), Header::ContentLength(10))),
+        parse_content_length_header::<VerboseError<&[u8]>>(b"l: 10\r\n")
+    );
+
+    let header = ViaHeader::new(domain!("example.com"), Transport::Udp);
+    assert_eq!(
+        Ok((remains.as_ref(), Header::Via(vec![header]))),
+        parse_via_header::<VerboseError<&[u8]>>(b"v: SIP/2.0/UDP example.com\r\n")
+    );
+
+    let req = RequestGenerator::new()
+        .uri(Uri::sip(domain!("example.com")))
+        .method(Method::Register)
+        .headers(vec![
+            Header::Subject("hello".into()),
+            Header::ContentLength(0),
+        ])
+        .build_unchecked()
+        .unwrap();
+    assert_eq!(
+        Ok((remains.as_ref(), req)),
+        parse_message::<VerboseError<&[u8]>>(
+            b"REGISTER sip:example.com SIP/2.0\r\ns: hello\r\nl: 0\r\n\r\n"
+        )
+    );
+}
@@ -1,12 +1,20 @@
-use libsip::{headers::parse::parse_content_disposition_header, Header};
+use libsip::{
+    headers::{
+        disposition::parse_content_disposition_header, ContentDisposition, DispositionType,
+        Handling,
+    },
+    Header,
+};
 
 use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::ContentDisposition("<http://www.example.com/sounds/moo.wav>".into());
+    let header = Header::ContentDisposition(
+        ContentDisposition::new(DispositionType::Render).handling(Handling::Optional),
+    );
     assert_eq!(
-        "Content-Disposition: <http://www.example.com/sounds/moo.wav>".to_string(),
+        "Content-Disposition: render;handling=optional".to_string(),
         format!("{}", header)
     );
 }
@@ -14,11 +22,31 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::ContentDisposition("<http://www.example.com/sounds/moo.wav>".into());
+    let header = Header::ContentDisposition(
+        ContentDisposition::new(DispositionType::Icon).handling(Handling::Required),
+    );
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_content_disposition_header::<VerboseError<&[u8]>>(
-            b"Content-Disposition: <http://www.example.com/sounds/moo.wav>\r\n"
+            b"Content-Disposition: ICON;handling=REQUIRED\r\n"
         )
     );
+    let header = Header::ContentDisposition(
+        ContentDisposition::new(DispositionType::Other("attachment".into()))
+            .param("filename", Some("moo.wav")),
+    );
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_content_disposition_header::<VerboseError<&[u8]>>(
+            b"Content-Disposition: attachment;filename=moo.wav\r\n"
+        )
+    );
+}
+
+#[test]
+fn handling() {
+    assert!(!ContentDisposition::new(DispositionType::Session).is_optional());
+    assert!(ContentDisposition::new(DispositionType::Alert)
+        .handling(Handling::Optional)
+        .is_optional());
 }
@@ -1,5 +1,5 @@
 use libsip::{
-    headers::{parse::parse_content_encoding_header, ContentType},
+    headers::{encoding::parse_content_encoding_header, ContentCoding},
     Header,
 };
 
@@ -7,9 +7,12 @@ use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::ContentEncoding(ContentType::Sdp);
+    let header = Header::ContentEncoding(vec![ContentCoding::Gzip]);
+    assert_eq!("Content-Encoding: gzip".to_string(), format!("{}", header));
+    assert_eq!("e: gzip".to_string(), format!("{:#}", header));
+    let header = Header::ContentEncoding(vec![ContentCoding::Gzip, ContentCoding::Deflate]);
     assert_eq!(
-        "Content-Encoding: application/sdp".to_string(),
+        "Content-Encoding: gzip,deflate".to_string(),
         format!("{}", header)
     );
 }
@@ -17,9 +20,17 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::ContentEncoding(ContentType::Sdp);
+    let header = Header::ContentEncoding(vec![ContentCoding::Gzip]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_content_encoding_header::<VerboseError<&[u8]>>(b"Content-Encoding: application/sdp")
+        parse_content_encoding_header::<VerboseError<&[u8]>>(b"Content-Encoding: gzip\r\n")
+    );
+    let header = Header::ContentEncoding(vec![
+        ContentCoding::Gzip,
+        ContentCoding::Other("x-custom".into()),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_content_encoding_header::<VerboseError<&[u8]>>(b"e: gzip, x-custom\r\n")
     );
 }
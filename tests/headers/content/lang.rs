@@ -1,5 +1,5 @@
 use libsip::{
-    headers::{parse::parse_content_language_header, Language},
+    headers::{language::parse_content_language_header, Language, LanguageTag},
     Header,
 };
 
@@ -7,16 +7,38 @@ use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::ContentLanguage(Language::English);
+    let header = Header::ContentLanguage(vec![Language::English.into()]);
     assert_eq!("Content-Language: en".to_string(), format!("{}", header));
+    let header = Header::ContentLanguage(vec![
+        LanguageTag::new(Language::Chinese)
+            .subtag("Hant")
+            .subtag("TW"),
+        Language::French.into(),
+    ]);
+    assert_eq!(
+        "Content-Language: zh-Hant-TW,fr".to_string(),
+        format!("{}", header)
+    );
 }
 
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::ContentLanguage(Language::English);
+    let header = Header::ContentLanguage(vec![Language::English.into()]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_content_language_header::<VerboseError<&[u8]>>(b"Content-Language: en\r\n")
+    );
+    let header = Header::ContentLanguage(vec![
+        LanguageTag::new(Language::Chinese)
+            .subtag("Hant")
+            .subtag("TW"),
+        Language::French.into(),
+    ]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_content_language_header::<VerboseError<&[u8]>>(b"Content-Language: en")
+        parse_content_language_header::<VerboseError<&[u8]>>(
+            b"Content-Language: zh-Hant-TW, fr\r\n"
+        )
     );
 }
@@ -4,7 +4,7 @@ use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::ContentType(ContentType::Sdp);
+    let header = Header::ContentType(ContentType::sdp());
     assert_eq!(
         "Content-Type: application/sdp".to_string(),
         format!("{}", header)
@@ -14,9 +14,55 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::ContentType(ContentType::Sdp);
+    let header = Header::ContentType(ContentType::sdp());
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_content_type_header::<VerboseError<&[u8]>>(b"Content-Type: application/sdp")
     );
 }
+
+#[test]
+fn write_params() {
+    let header = Header::ContentType(ContentType::plain_text().param("charset", Some("utf-8")));
+    assert_eq!(
+        "Content-Type: text/plain;charset=utf-8".to_string(),
+        format!("{}", header)
+    );
+}
+
+#[test]
+fn read_params() {
+    let remains = vec![];
+    let header = Header::ContentType(ContentType::plain_text().param("charset", Some("UTF-8")));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_content_type_header::<VerboseError<&[u8]>>(
+            b"Content-Type: Text/Plain; charset=UTF-8"
+        )
+    );
+    let header = Header::ContentType(
+        ContentType::multipart_mixed().param("boundary", Some("unique-boundary-1")),
+    );
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_content_type_header::<VerboseError<&[u8]>>(
+            b"Content-Type: multipart/mixed;boundary=\"unique-boundary-1\""
+        )
+    );
+    let header = Header::ContentType(ContentType::new("application", "pidf+xml"));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_content_type_header::<VerboseError<&[u8]>>(b"Content-Type: application/pidf+xml")
+    );
+}
+
+#[test]
+fn accessors() {
+    let ty = ContentType::multipart_mixed()
+        .param("boundary", Some("unique-boundary-1"))
+        .param("Charset", Some("utf-8"));
+    assert!(ty.is("Multipart", "Mixed"));
+    assert!(!ty.is("multipart", "related"));
+    assert_eq!(Some("unique-boundary-1"), ty.boundary());
+    assert_eq!(Some("utf-8"), ty.charset());
+}
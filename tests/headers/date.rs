@@ -1,19 +1,67 @@
-use libsip::{headers::parse::parse_date_header, *};
+use libsip::{
+    headers::{
+        date::{Month, SipDate, Weekday},
+        parse::parse_date_header,
+    },
+    *,
+};
 
 use nom::error::VerboseError;
 
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn example_date() -> SipDate {
+    SipDate {
+        weekday: Weekday::Sat,
+        day: 13,
+        month: Month::Nov,
+        year: 2010,
+        hour: 23,
+        minute: 29,
+        second: 0,
+    }
+}
+
 #[test]
 fn write() {
-    let header = Header::Date("wed 1 2001".into());
+    let header = Header::Date(DateHeader::Date(example_date()));
+    assert_eq!(
+        "Date: Sat, 13 Nov 2010 23:29:00 GMT".to_string(),
+        format!("{}", header)
+    );
+    let header = Header::Date(DateHeader::Invalid("wed 1 2001".into()));
     assert_eq!("Date: wed 1 2001".to_string(), format!("{}", header));
 }
 
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::Date("wed 1 2001".into());
+    let header = Header::Date(DateHeader::Date(example_date()));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_date_header::<VerboseError<&[u8]>>(b"Date: Sat, 13 Nov 2010 23:29:00 GMT\r\n")
+    );
+    let header = Header::Date(DateHeader::Invalid("wed 1 2001".into()));
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_date_header::<VerboseError<&[u8]>>(b"Date: wed 1 2001\r\n")
     );
+    let header = Header::Date(DateHeader::Invalid("Sat, 13 Nov 2010 25:29:00 GMT".into()));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_date_header::<VerboseError<&[u8]>>(b"Date: Sat, 13 Nov 2010 25:29:00 GMT\r\n")
+    );
+}
+
+#[test]
+fn timestamps() {
+    assert_eq!(1_289_690_940, example_date().unix_timestamp());
+    assert_eq!(example_date(), SipDate::from_unix_timestamp(1_289_690_940));
+    assert_eq!(
+        "Thu, 01 Jan 1970 00:00:00 GMT",
+        format!("{}", SipDate::from_unix_timestamp(0))
+    );
+    let time = UNIX_EPOCH + Duration::from_secs(1_289_690_940);
+    assert_eq!(example_date(), SipDate::from(time));
+    assert_eq!(time, SystemTime::from(example_date()));
 }
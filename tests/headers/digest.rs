@@ -0,0 +1,318 @@
+use libsip::{
+    headers::{
+        parse::parse_auth_challenge, AuthenticationInfo, ChallengeOutcome, DigestCredentials,
+    },
+    *,
+};
+
+use nom::error::VerboseError;
+
+fn challenge(input: &str) -> AuthHeader {
+    parse_auth_challenge::<VerboseError<&[u8]>>(input.as_bytes())
+        .unwrap()
+        .1
+}
+
+fn hex(input: String) -> String {
+    format!("{:x}", md5::compute(input))
+}
+
+#[test]
+fn qop_auth() {
+    let uri = Uri::sip(domain!("biloxi.com")).auth(uri_auth!("bob"));
+    let ctx = AuthContext {
+        user: "bob",
+        pass: "zanzibar",
+        nc: 1,
+        uri: &uri,
+    };
+    let challenge = challenge(
+        "Digest realm=\"biloxi.com\", qop=\"auth,auth-int\", \
+         nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", opaque=\"5ccc069c403ebaf9f0171e9517f40e41\"",
+    );
+    let credentials = ctx.respond(&challenge, &Method::Invite, b"v=0").unwrap();
+    let params = &credentials.1;
+    assert_eq!(AuthSchema::Digest, credentials.0);
+    assert_eq!("bob", params["username"]);
+    assert_eq!("biloxi.com", params["realm"]);
+    assert_eq!("sip:bob@biloxi.com", params["uri"]);
+    assert_eq!("auth", params["qop"]);
+    assert_eq!("00000001", params["nc"]);
+    assert_eq!("5ccc069c403ebaf9f0171e9517f40e41", params["opaque"]);
+    assert!(params.get("algorithm").is_none());
+
+    let ha1 = hex("bob:biloxi.com:zanzibar".into());
+    let ha2 = hex("INVITE:sip:bob@biloxi.com".into());
+    let expected = hex(format!(
+        "{}:dcd98b7102dd2f0e8b11d0f600bfb0c093:00000001:{}:auth:{}",
+        ha1, params["cnonce"], ha2
+    ));
+    assert_eq!(expected, params["response"]);
+}
+
+#[test]
+fn qop_auth_int() {
+    let uri = Uri::sip(domain!("biloxi.com")).auth(uri_auth!("bob"));
+    let ctx = AuthContext {
+        user: "bob",
+        pass: "zanzibar",
+        nc: 26,
+        uri: &uri,
+    };
+    let challenge =
+        challenge("Digest realm=\"biloxi.com\", nonce=\"abc\", qop=\"auth-int\", algorithm=MD5");
+    let credentials = ctx.respond(&challenge, &Method::Message, b"hello").unwrap();
+    let params = &credentials.1;
+    assert_eq!("auth-int", params["qop"]);
+    assert_eq!("0000001a", params["nc"]);
+    assert_eq!("MD5", params["algorithm"]);
+
+    let ha1 = hex("bob:biloxi.com:zanzibar".into());
+    let ha2 = hex(format!(
+        "MESSAGE:sip:bob@biloxi.com:{}",
+        hex("hello".into())
+    ));
+    let expected = hex(format!(
+        "{}:abc:0000001a:{}:auth-int:{}",
+        ha1, params["cnonce"], ha2
+    ));
+    assert_eq!(expected, params["response"]);
+}
+
+#[test]
+fn without_qop() {
+    let uri = Uri::sip(domain!("biloxi.com"));
+    let ctx = AuthContext {
+        user: "bob",
+        pass: "zanzibar",
+        nc: 1,
+        uri: &uri,
+    };
+    let credentials = ctx
+        .respond(
+            &challenge("Digest realm=\"biloxi.com\", nonce=\"abc\""),
+            &Method::Register,
+            b"",
+        )
+        .unwrap();
+    let params = &credentials.1;
+    assert!(params.get("qop").is_none());
+    assert!(params.get("cnonce").is_none());
+    assert!(params.get("nc").is_none());
+    let ha1 = hex("bob:biloxi.com:zanzibar".into());
+    let ha2 = hex("REGISTER:sip:biloxi.com".into());
+    assert_eq!(hex(format!("{}:abc:{}", ha1, ha2)), params["response"]);
+}
+
+#[test]
+fn invalid_challenges() {
+    let uri = Uri::sip(domain!("biloxi.com"));
+    let ctx = AuthContext {
+        user: "bob",
+        pass: "zanzibar",
+        nc: 1,
+        uri: &uri,
+    };
+    for input in &[
+        "Digest nonce=\"abc\"",
+        "Digest realm=\"biloxi.com\"",
+        "Digest realm=\"biloxi.com\", nonce=\"abc\", qop=\"unknown\"",
+        "Digest realm=\"biloxi.com\", nonce=\"abc\", algorithm=UNKNOWN",
+    ] {
+        assert!(ctx
+            .respond(&challenge(input), &Method::Register, b"")
+            .is_err());
+    }
+}
+
+#[test]
+fn algorithms() {
+    use libsip::headers::DigestAlgorithm;
+
+    assert_eq!(
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        DigestAlgorithm::Sha256.hash(b"abc")
+    );
+    assert_eq!(
+        "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23",
+        DigestAlgorithm::Sha512_256Sess.hash(b"abc")
+    );
+    assert_eq!(
+        "900150983cd24fb0d6963f7d28e17f72",
+        DigestAlgorithm::Md5.hash(b"abc")
+    );
+    assert_eq!(
+        Some(DigestAlgorithm::Sha512_256),
+        DigestAlgorithm::from_token("SHA-512-256")
+    );
+    assert_eq!("SHA-256-sess", DigestAlgorithm::Sha256Sess.to_string());
+    assert!(!DigestAlgorithm::Sha256.is_sess());
+}
+
+#[test]
+fn sha256() {
+    use libsip::headers::DigestAlgorithm;
+
+    let uri = Uri::sip(domain!("biloxi.com"));
+    let ctx = AuthContext {
+        user: "bob",
+        pass: "zanzibar",
+        nc: 1,
+        uri: &uri,
+    };
+    let challenge =
+        challenge("Digest realm=\"biloxi.com\", nonce=\"abc\", qop=\"auth\", algorithm=SHA-256");
+    assert_eq!(Some(DigestAlgorithm::Sha256), challenge.algorithm());
+    let credentials = ctx.respond(&challenge, &Method::Register, b"").unwrap();
+    let params = &credentials.1;
+    assert_eq!("SHA-256", params["algorithm"]);
+    assert_eq!(64, params["response"].len());
+
+    let hash = |data: String| DigestAlgorithm::Sha256.hash(data.as_bytes());
+    let ha1 = hash("bob:biloxi.com:zanzibar".into());
+    let ha2 = hash("REGISTER:sip:biloxi.com".into());
+    let expected = hash(format!(
+        "{}:abc:00000001:{}:auth:{}",
+        ha1, params["cnonce"], ha2
+    ));
+    assert_eq!(expected, params["response"]);
+}
+
+#[test]
+fn sess() {
+    use libsip::headers::DigestAlgorithm;
+
+    let uri = Uri::sip(domain!("biloxi.com"));
+    let ctx = AuthContext {
+        user: "bob",
+        pass: "zanzibar",
+        nc: 2,
+        uri: &uri,
+    };
+    let challenge = challenge(
+        "Digest realm=\"biloxi.com\", nonce=\"abc\", qop=\"auth\", algorithm=sha-512-256-sess",
+    );
+    let credentials = ctx.respond(&challenge, &Method::Invite, b"").unwrap();
+    let params = &credentials.1;
+    assert_eq!("SHA-512-256-sess", params["algorithm"]);
+
+    let hash = |data: String| DigestAlgorithm::Sha512_256.hash(data.as_bytes());
+    let ha1 = hash(format!(
+        "{}:abc:{}",
+        hash("bob:biloxi.com:zanzibar".into()),
+        params["cnonce"]
+    ));
+    let ha2 = hash("INVITE:sip:biloxi.com".into());
+    let expected = hash(format!(
+        "{}:abc:00000002:{}:auth:{}",
+        ha1, params["cnonce"], ha2
+    ));
+    assert_eq!(expected, params["response"]);
+}
+
+#[test]
+fn fixed_cnonce() {
+    let uri = Uri::sip(domain!("biloxi.com"));
+    let ctx = AuthContext {
+        user: "bob",
+        pass: "zanzibar",
+        nc: 1,
+        uri: &uri,
+    };
+    let challenge = challenge("Digest realm=\"biloxi.com\", nonce=\"abc\", qop=auth");
+    let credentials = ctx
+        .respond_with_cnonce(&challenge, &Method::Register, b"", "0a4f113b")
+        .unwrap();
+    assert_eq!("0a4f113b", credentials.1["cnonce"]);
+    let ha1 = hex("bob:biloxi.com:zanzibar".into());
+    let ha2 = hex("REGISTER:sip:biloxi.com".into());
+    assert_eq!(
+        hex(format!("{}:abc:00000001:0a4f113b:auth:{}", ha1, ha2)),
+        credentials.1["response"]
+    );
+}
+
+#[test]
+fn credentials() {
+    let uri = Uri::sip(domain!("biloxi.com"));
+    let mut credentials = DigestCredentials::new("bob", "zanzibar");
+    assert!(credentials.authorize(&Method::Register, &uri, b"").is_err());
+
+    let first = challenge("Digest realm=\"biloxi.com\", nonce=\"abc\", qop=auth");
+    assert_eq!(
+        ChallengeOutcome::Retry,
+        credentials.on_challenge(&first, None)
+    );
+    assert_eq!(
+        ChallengeOutcome::Retry,
+        credentials.on_challenge(&first, None)
+    );
+    let auth = credentials.authorize(&Method::Register, &uri, b"").unwrap();
+    assert_eq!("abc", auth.1["nonce"]);
+    assert_eq!(1, credentials.nonce_count());
+    assert_eq!(Some(auth.1["cnonce"].as_str()), credentials.cnonce());
+    credentials.authorize(&Method::Invite, &uri, b"").unwrap();
+    assert_eq!(2, credentials.nonce_count());
+
+    let stale = challenge("Digest realm=\"biloxi.com\", nonce=\"def\", qop=auth, stale=TRUE");
+    assert_eq!(
+        ChallengeOutcome::Stale,
+        credentials.on_challenge(&stale, Some(&auth))
+    );
+    assert_eq!(0, credentials.nonce_count());
+    let auth = credentials.authorize(&Method::Register, &uri, b"").unwrap();
+    assert_eq!("def", auth.1["nonce"]);
+    assert_eq!("00000001", auth.1["nc"]);
+
+    let other = challenge("Digest realm=\"biloxi.com\", nonce=\"ghi\", qop=auth");
+    // A new nonce for the same realm still rejects the credentials sent.
+    assert_eq!(
+        ChallengeOutcome::Rejected,
+        credentials.on_challenge(&other, Some(&auth))
+    );
+    assert_eq!(Some(&stale), credentials.challenge());
+    // A request sent without credentials is simply challenged again.
+    assert_eq!(
+        ChallengeOutcome::Retry,
+        credentials.on_challenge(&other, None)
+    );
+    assert_eq!(Some(&other), credentials.challenge());
+
+    credentials.set_nonce("jkl");
+    let auth = credentials.authorize(&Method::Register, &uri, b"").unwrap();
+    assert_eq!("jkl", auth.1["nonce"]);
+    assert_eq!("00000001", auth.1["nc"]);
+}
+
+#[test]
+fn authentication_info() {
+    let uri = Uri::sip(domain!("biloxi.com"));
+    let mut credentials = DigestCredentials::new("bob", "zanzibar");
+    credentials.on_challenge(
+        &challenge("Digest realm=\"biloxi.com\", nonce=\"abc\", qop=auth"),
+        None,
+    );
+    credentials.authorize(&Method::Register, &uri, b"").unwrap();
+    let cnonce = credentials.cnonce().unwrap().to_string();
+
+    let ha1 = hex("bob:biloxi.com:zanzibar".to_string());
+    let ha2 = hex(format!(":{}", uri));
+    let rspauth = hex(format!("{}:abc:00000001:{}:auth:{}", ha1, cnonce, ha2));
+    let mut info = AuthenticationInfo {
+        next_nonce: Some("def".into()),
+        qop: Some("auth".into()),
+        rspauth: Some(rspauth),
+        cnonce: Some(cnonce),
+        nc: Some(1),
+    };
+    assert!(credentials.verify(&info, &uri, b""));
+    let wrong = DigestCredentials::new("bob", "secret");
+    assert!(!wrong.verify(&info, &uri, b""));
+
+    credentials.on_authentication_info(&info);
+    assert_eq!(Some("def"), credentials.challenge().unwrap().nonce());
+    assert_eq!(0, credentials.nonce_count());
+
+    info.rspauth = None;
+    assert!(!credentials.verify(&info, &uri, b""));
+}
@@ -0,0 +1,70 @@
+use libsip::{
+    headers::{
+        event::{parse_allow_events_header, parse_event_header},
+        parse::parse_header,
+        EventType, GenericParams,
+    },
+    Header,
+};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let header = Header::Event(EventType::Presence, GenericParams::new());
+    assert_eq!("Event: presence".to_string(), format!("{}", header));
+    assert_eq!("o: presence".to_string(), format!("{:#}", header));
+
+    let mut params = GenericParams::new();
+    params.insert("id", Some("1234"));
+    let header = Header::Event(EventType::Refer, params);
+    assert_eq!("Event: refer;id=1234".to_string(), format!("{}", header));
+
+    let header = Header::AllowEvents(vec![
+        EventType::Presence,
+        EventType::MessageSummary,
+        EventType::Other("presence.winfo".into()),
+    ]);
+    assert_eq!(
+        "Allow-Events: presence,message-summary,presence.winfo".to_string(),
+        format!("{}", header)
+    );
+    assert_eq!(
+        "u: presence,message-summary,presence.winfo".to_string(),
+        format!("{:#}", header)
+    );
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let header = Header::Event(EventType::Dialog, GenericParams::new());
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_event_header::<VerboseError<&[u8]>>(b"Event: dialog\r\n")
+    );
+
+    let mut params = GenericParams::new();
+    params.insert("id", Some("1234"));
+    let header = Header::Event(EventType::MessageSummary, params);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_event_header::<VerboseError<&[u8]>>(b"o: message-summary;id=1234\r\n")
+    );
+
+    let header = Header::AllowEvents(vec![
+        EventType::Presence,
+        EventType::Dialog,
+        EventType::Other("conference".into()),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header.clone())),
+        parse_allow_events_header::<VerboseError<&[u8]>>(
+            b"Allow-Events: presence, dialog, conference\r\n"
+        )
+    );
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_header::<VerboseError<&[u8]>>(b"u: presence,dialog,conference\r\n")
+    );
+}
@@ -0,0 +1,19 @@
+use libsip::{headers::parse::parse_flow_timer_header, Header};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let header = Header::FlowTimer(120);
+    assert_eq!("Flow-Timer: 120".to_string(), format!("{}", header));
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let header = Header::FlowTimer(120);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_flow_timer_header::<VerboseError<&[u8]>>(b"Flow-Timer: 120\r\n")
+    );
+}
@@ -0,0 +1,50 @@
+use libsip::{headers::parse::parse_header, *};
+
+use nom::error::VerboseError;
+
+#[test]
+fn read() {
+    let remains = b"Content-Length: 0\r\n".to_vec();
+    let header = Header::Subject("I know you're there, pick up the phone".into());
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_header::<VerboseError<&[u8]>>(
+            b"Subject: I know you're there,\r\n         pick up the phone\r\nContent-Length: 0\r\n"
+        )
+    );
+
+    let remains = vec![];
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("bob"));
+    let header = Header::To(named_header!(uri).param("tag", Some("1234")));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_header::<VerboseError<&[u8]>>(b"To:\r\n <sip:bob@example.com>;tag=1234\r\n")
+    );
+
+    let header = ViaHeader::new(domain!("example.com"), Transport::Udp).branch_param("z9hG4bK");
+    assert_eq!(
+        Ok((remains.as_ref(), Header::Via(vec![header]))),
+        parse_header::<VerboseError<&[u8]>>(
+            b"Via: SIP/2.0/UDP example.com \r\n\t;branch=z9hG4bK\r\n"
+        )
+    );
+}
+
+#[test]
+fn read_message() {
+    let remains = vec![];
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("user"));
+    let req = RequestGenerator::new()
+        .method(Method::Options)
+        .uri(uri)
+        .header(Header::Subject("folded subject".into()))
+        .header(Header::ContentLength(0))
+        .build_unchecked()
+        .unwrap();
+    assert_eq!(
+        Ok((remains.as_ref(), req)),
+        parse_message::<VerboseError<&[u8]>>(
+            b"OPTIONS sip:user@example.com SIP/2.0\r\nSubject: folded\r\n  subject\r\nContent-Length: 0\r\n\r\n"
+        )
+    );
+}
@@ -0,0 +1,110 @@
+use libsip::{
+    headers::{
+        diversion::{parse_diversion_header, DiversionHeader},
+        history_info::{parse_history_info_header, HistoryInfoEntry},
+        ReasonHeader,
+    },
+    *,
+};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let alice = Uri::sip(domain!("example.com")).auth(uri_auth!("alice"));
+    let bob = Uri::sip(domain!("example.com"))
+        .auth(uri_auth!("bob"))
+        .header("Reason", "SIP;cause=302");
+    let header = Header::HistoryInfo(vec![
+        HistoryInfoEntry::new(named_header!(alice), "1"),
+        HistoryInfoEntry::new(named_header!(bob), "1.1"),
+    ]);
+    assert_eq!(
+        "History-Info: <sip:alice@example.com>;index=1,<sip:bob@example.com?Reason=SIP%3Bcause%3D302>;index=1.1".to_string(),
+        format!("{}", header)
+    );
+
+    let alice = Uri::sip(domain!("example.com")).auth(uri_auth!("alice"));
+    let header = Header::Diversion(vec![DiversionHeader::new(named_header!(alice))
+        .reason("no-answer")
+        .counter(1)]);
+    assert_eq!(
+        "Diversion: <sip:alice@example.com>;reason=no-answer;counter=1".to_string(),
+        format!("{}", header)
+    );
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let alice = Uri::sip(domain!("example.com")).auth(uri_auth!("alice"));
+    let bob = Uri::sip(domain!("example.com"))
+        .auth(uri_auth!("bob"))
+        .header("Reason", "SIP;cause=302");
+    let header = Header::HistoryInfo(vec![
+        HistoryInfoEntry::new(named_header!(alice), "1"),
+        HistoryInfoEntry::new(named_header!(bob), "1.1"),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_history_info_header::<VerboseError<&[u8]>>(
+            b"History-Info: <sip:alice@example.com>;index=1, <sip:bob@example.com?Reason=SIP%3Bcause%3D302>;index=1.1\r\n"
+        )
+    );
+    assert!(parse_history_info_header::<VerboseError<&[u8]>>(
+        b"History-Info: <sip:alice@example.com>\r\n"
+    )
+    .is_err());
+
+    let alice = Uri::sip(domain!("example.com")).auth(uri_auth!("alice"));
+    let header = Header::Diversion(vec![DiversionHeader::new(
+        named_header!(alice).param("privacy", Some("off")),
+    )
+    .reason("unconditional")
+    .counter(2)]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_diversion_header::<VerboseError<&[u8]>>(
+            b"Diversion: <sip:alice@example.com>;reason=unconditional;counter=2;privacy=off\r\n"
+        )
+    );
+}
+
+#[test]
+fn entries() {
+    let bob = Uri::sip(domain!("example.com"))
+        .auth(uri_auth!("bob"))
+        .header("Reason", "SIP;cause=302");
+    let entry = HistoryInfoEntry::new(named_header!(bob), "1.1.2");
+    assert_eq!("1.1.2", entry.index());
+    assert_eq!(Some(vec![1, 1, 2]), entry.index_levels());
+    assert_eq!(Some(ReasonHeader::sip(302)), entry.reason());
+
+    let headers = Headers(vec![
+        Header::HistoryInfo(vec![entry.clone()]),
+        Header::HistoryInfo(vec![entry.clone()]),
+    ]);
+    assert_eq!(vec![entry.clone(), entry], headers.history_info());
+    assert!(headers.diversion().is_empty());
+}
+
+#[test]
+fn param_order() {
+    let input = b"History-Info: <sip:a@example.com>;foo=1;index=1;rc=1\r\n";
+    let (_, header) = parse_history_info_header::<VerboseError<&[u8]>>(input).unwrap();
+    assert_eq!(
+        "History-Info: <sip:a@example.com>;foo=1;index=1;rc=1",
+        format!("{}", header)
+    );
+
+    let mut entry = match header {
+        Header::HistoryInfo(mut entries) => entries.remove(0),
+        header => panic!("unexpected header {:?}", header),
+    };
+    assert_eq!("1", entry.index());
+    entry.set_index("1.2");
+    assert_eq!(
+        "<sip:a@example.com>;foo=1;index=1.2;rc=1",
+        format!("{}", entry)
+    );
+}
@@ -0,0 +1,42 @@
+use libsip::{
+    headers::parse::{parse_p_asserted_identity_header, parse_p_preferred_identity_header},
+    *,
+};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let sip = Uri::sip(domain!("example.com")).auth(uri_auth!("alice"));
+    let bob = Uri::sip(domain!("example.com")).auth(uri_auth!("bob"));
+    let header =
+        Header::PAssertedIdentity(vec![named_header!(sip).name("Alice"), named_header!(bob)]);
+    assert_eq!(
+        "P-Asserted-Identity: Alice <sip:alice@example.com>,<sip:bob@example.com>".to_string(),
+        format!("{}", header)
+    );
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let sip = Uri::sip(domain!("example.com")).auth(uri_auth!("alice"));
+    let bob = Uri::sip(domain!("example.com")).auth(uri_auth!("bob"));
+    let header = Header::PAssertedIdentity(vec![
+        named_header!((sip.clone())).name("Alice"),
+        named_header!(bob),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_p_asserted_identity_header::<VerboseError<&[u8]>>(
+            b"P-Asserted-Identity: \"Alice\" <sip:alice@example.com>, <sip:bob@example.com>\r\n"
+        )
+    );
+    let header = Header::PPreferredIdentity(vec![named_header!(sip)]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_p_preferred_identity_header::<VerboseError<&[u8]>>(
+            b"P-Preferred-Identity: <sip:alice@example.com>\r\n"
+        )
+    );
+}
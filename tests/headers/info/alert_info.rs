@@ -1,10 +1,12 @@
-use libsip::{headers::parse::parse_alert_info_header, Header};
+use libsip::headers::{parse::parse_alert_info_header, Header, InfoHeader};
 
 use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::AlertInfo("<http://www.example.com/sounds/moo.wav>".into());
+    let header = Header::AlertInfo(vec![InfoHeader::new(
+        "http://www.example.com/sounds/moo.wav",
+    )]);
     assert_eq!(
         "Alert-Info: <http://www.example.com/sounds/moo.wav>".to_string(),
         format!("{}", header)
@@ -14,11 +16,23 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::AlertInfo("<http://www.example.com/sounds/moo.wav>".into());
+    let header = Header::AlertInfo(vec![InfoHeader::new(
+        "http://www.example.com/sounds/moo.wav",
+    )]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_alert_info_header::<VerboseError<&[u8]>>(
             b"Alert-Info: <http://www.example.com/sounds/moo.wav>\r\n"
         )
     );
+    let header = Header::AlertInfo(vec![
+        InfoHeader::new("http://www.example.com/sounds/moo.wav"),
+        InfoHeader::new("urn:alert:service:call-waiting"),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_alert_info_header::<VerboseError<&[u8]>>(
+            b"Alert-Info: <http://www.example.com/sounds/moo.wav>, <urn:alert:service:call-waiting>\r\n"
+        )
+    );
 }
@@ -1,12 +1,27 @@
-use libsip::{headers::parse::parse_authentication_info_header, Header};
+use libsip::{
+    headers::{parse::parse_authentication_info_header, AuthenticationInfo},
+    Header,
+};
 
 use nom::error::VerboseError;
 
+fn info() -> AuthenticationInfo {
+    AuthenticationInfo {
+        next_nonce: Some("47364c23432d2e131a5fb210812c".into()),
+        qop: Some("auth".into()),
+        rspauth: Some("6629fae49393a05397450978507c4ef1".into()),
+        cnonce: Some("0a4f113b".into()),
+        nc: Some(1),
+    }
+}
+
 #[test]
 fn write() {
-    let header = Header::AuthenticationInfo("<http://www.example.com/sounds/moo.wav>".into());
+    let header = Header::AuthenticationInfo(info());
     assert_eq!(
-        "Authentication-Info: <http://www.example.com/sounds/moo.wav>".to_string(),
+        "Authentication-Info: nextnonce=\"47364c23432d2e131a5fb210812c\", qop=auth, \
+         rspauth=\"6629fae49393a05397450978507c4ef1\", cnonce=\"0a4f113b\", nc=00000001"
+            .to_string(),
         format!("{}", header)
     );
 }
@@ -14,11 +29,27 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::AuthenticationInfo("<http://www.example.com/sounds/moo.wav>".into());
+    let header = Header::AuthenticationInfo(info());
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_authentication_info_header::<VerboseError<&[u8]>>(
+            b"Authentication-Info: nextnonce=\"47364c23432d2e131a5fb210812c\", qop=auth, \
+            rspauth=\"6629fae49393a05397450978507c4ef1\", cnonce=\"0a4f113b\", nc=00000001\r\n"
+        )
+    );
+}
+
+#[test]
+fn read_next_nonce() {
+    let remains = vec![];
+    let header = Header::AuthenticationInfo(AuthenticationInfo {
+        next_nonce: Some("abc".into()),
+        ..Default::default()
+    });
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_authentication_info_header::<VerboseError<&[u8]>>(
-            b"Authentication-Info: <http://www.example.com/sounds/moo.wav>\r\n"
+            b"Authentication-Info: nextnonce=\"abc\"\r\n"
         )
     );
 }
@@ -1,12 +1,15 @@
-use libsip::{headers::parse::parse_call_info_header, Header};
+use libsip::headers::{parse::parse_call_info_header, Header, InfoHeader};
 
 use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::CallInfo("<http://www.example.com/sounds/moo.wav>".into());
+    let header = Header::CallInfo(vec![
+        InfoHeader::new("http://wwww.example.com/alice/photo.jpg").param("purpose", Some("icon")),
+        InfoHeader::new("http://www.example.com/alice/").param("purpose", Some("info")),
+    ]);
     assert_eq!(
-        "Call-Info: <http://www.example.com/sounds/moo.wav>".to_string(),
+        "Call-Info: <http://wwww.example.com/alice/photo.jpg>;purpose=icon,<http://www.example.com/alice/>;purpose=info".to_string(),
         format!("{}", header)
     );
 }
@@ -14,11 +17,30 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::CallInfo("<http://www.example.com/sounds/moo.wav>".into());
+    let header = Header::CallInfo(vec![InfoHeader::new(
+        "http://www.example.com/sounds/moo.wav",
+    )]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_call_info_header::<VerboseError<&[u8]>>(
             b"Call-Info: <http://www.example.com/sounds/moo.wav>\r\n"
         )
     );
+    let header = Header::CallInfo(vec![
+        InfoHeader::new("http://wwww.example.com/alice/photo.jpg").param("purpose", Some("icon")),
+        InfoHeader::new("http://www.example.com/alice/").param("purpose", Some("info")),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_call_info_header::<VerboseError<&[u8]>>(
+            b"Call-Info: <http://wwww.example.com/alice/photo.jpg> ;purpose=icon, <http://www.example.com/alice/> ;purpose=info\r\n"
+        )
+    );
+}
+
+#[test]
+fn purpose() {
+    let value = InfoHeader::new("http://wwww.example.com/alice/photo.jpg");
+    assert_eq!(None, value.purpose());
+    assert_eq!(Some("icon"), value.param("purpose", Some("icon")).purpose());
 }
@@ -1,12 +1,14 @@
-use libsip::{headers::parse::parse_error_info_header, Header};
+use libsip::headers::{parse::parse_error_info_header, Header, Headers, InfoHeader};
 
 use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::ErrorInfo("<http://www.example.com/sounds/moo.wav>".into());
+    let header = Header::ErrorInfo(vec![InfoHeader::new(
+        "sip:not-in-service-recording@atlanta.com",
+    )]);
     assert_eq!(
-        "Error-Info: <http://www.example.com/sounds/moo.wav>".to_string(),
+        "Error-Info: <sip:not-in-service-recording@atlanta.com>".to_string(),
         format!("{}", header)
     );
 }
@@ -14,11 +16,42 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::ErrorInfo("<http://www.example.com/sounds/moo.wav>".into());
+    let header = Header::ErrorInfo(vec![InfoHeader::new(
+        "http://www.example.com/sounds/moo.wav",
+    )]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_error_info_header::<VerboseError<&[u8]>>(
             b"Error-Info: <http://www.example.com/sounds/moo.wav>\r\n"
         )
     );
+    let header = Header::ErrorInfo(vec![
+        InfoHeader::new("sip:not-in-service-recording@atlanta.com"),
+        InfoHeader::new("http://www.example.com/sounds/moo.wav").param("lang", Some("en")),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_error_info_header::<VerboseError<&[u8]>>(
+            b"Error-Info: <sip:not-in-service-recording@atlanta.com>, <http://www.example.com/sounds/moo.wav>;lang=en\r\n"
+        )
+    );
+}
+
+#[test]
+fn error_info() {
+    let mut headers = Headers::new();
+    headers.push(Header::ErrorInfo(vec![InfoHeader::new(
+        "sip:not-in-service-recording@atlanta.com",
+    )]));
+    headers.push(Header::ContentLength(0));
+    headers.push(Header::ErrorInfo(vec![InfoHeader::new(
+        "http://www.example.com/sounds/moo.wav",
+    )]));
+    assert_eq!(
+        vec![
+            InfoHeader::new("sip:not-in-service-recording@atlanta.com"),
+            InfoHeader::new("http://www.example.com/sounds/moo.wav"),
+        ],
+        headers.error_info()
+    );
 }
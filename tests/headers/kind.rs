@@ -0,0 +1,67 @@
+use libsip::{headers::HeaderKind, *};
+
+#[test]
+fn kind() {
+    assert_eq!(
+        HeaderKind::CallId,
+        Header::CallId("1@example.com".into()).kind()
+    );
+    assert_eq!(HeaderKind::CallId, HeaderKind::from_name("call-id"));
+    assert_eq!(HeaderKind::Via, HeaderKind::from_name("v"));
+    assert_eq!(
+        HeaderKind::MimeVersion,
+        HeaderKind::from_name("MIME-Version")
+    );
+    assert_eq!("WWW-Authenticate", HeaderKind::WwwAuthenticate.name());
+    assert_eq!(
+        HeaderKind::Other("X-Foo".into()),
+        HeaderKind::from_name("x-foo")
+    );
+    assert_ne!(HeaderKind::Other("Expires".into()), HeaderKind::Expires);
+    assert_eq!(
+        HeaderKind::Require,
+        Header::Other("Require".into(), "Softphone 1.0".into()).kind()
+    );
+}
+
+#[test]
+fn get_remove_replace() {
+    let mut headers = Headers(vec![
+        Header::Expires(10),
+        Header::Other("X-Foo".into(), "1".into()),
+        Header::ContentLength(0),
+        Header::Other("x-foo".into(), "2".into()),
+        Header::Expires(20),
+    ]);
+    assert_eq!(Some(&Header::Expires(10)), headers.get(HeaderKind::Expires));
+    assert_eq!(None, headers.get(HeaderKind::To));
+    assert_eq!(2, headers.get_all(HeaderKind::Other("X-FOO".into())).len());
+
+    headers.replace(HeaderKind::Expires, Header::Expires(30));
+    assert_eq!(
+        Headers(vec![
+            Header::Expires(30),
+            Header::Other("X-Foo".into(), "1".into()),
+            Header::ContentLength(0),
+            Header::Other("x-foo".into(), "2".into()),
+        ]),
+        headers
+    );
+
+    assert_eq!(
+        vec![
+            Header::Other("X-Foo".into(), "1".into()),
+            Header::Other("x-foo".into(), "2".into()),
+        ],
+        headers.remove(HeaderKind::Other("X-Foo".into()))
+    );
+    headers.replace(HeaderKind::MaxForwards, Header::MaxForwards(70));
+    assert_eq!(
+        Headers(vec![
+            Header::Expires(30),
+            Header::ContentLength(0),
+            Header::MaxForwards(70),
+        ]),
+        headers
+    );
+}
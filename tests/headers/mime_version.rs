@@ -4,16 +4,22 @@ use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::MimeVersion(1.0);
-    assert_eq!("MIME-Version: 1".to_string(), format!("{}", header));
+    let header = Header::MimeVersion(1, 0);
+    assert_eq!("MIME-Version: 1.0".to_string(), format!("{}", header));
 }
 
 #[test]
 fn read() {
-    let remains = vec![b' '];
-    let header = Header::MimeVersion(1.0);
+    let remains = vec![];
+    let header = Header::MimeVersion(1, 0);
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_mime_version_header::<VerboseError<&[u8]>>(b"MIME-Version: 1.0 ")
+        parse_mime_version_header::<VerboseError<&[u8]>>(b"MIME-Version: 1.0\r\n")
     );
+    let header = Header::MimeVersion(1, 10);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_mime_version_header::<VerboseError<&[u8]>>(b"MIME-Version: 1.10\r\n")
+    );
+    assert!(parse_mime_version_header::<VerboseError<&[u8]>>(b"MIME-Version: 1\r\n").is_err());
 }
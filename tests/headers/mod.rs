@@ -2,23 +2,40 @@ mod accept;
 mod allow;
 mod authorization;
 mod callid;
+mod compact;
 mod content;
 mod cseq;
 mod date;
+mod digest;
+mod event;
 mod expires;
+mod flow_timer;
+mod folding;
+mod history_info;
+mod identity;
 mod info;
+mod kind;
 mod max_forwards;
 mod mime_version;
 mod min_expires;
 mod named;
 mod organization;
+mod other;
+mod params;
+mod path;
 mod priority;
+mod privacy;
 mod proxy;
+mod reason;
 mod record_route;
+mod replaces;
 mod require;
 mod retry_after;
 mod route;
+mod rseq;
 mod server;
+mod session_expires;
+mod sip_etag;
 mod subject;
 mod supported;
 mod timestamp;
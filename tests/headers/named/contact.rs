@@ -1,25 +1,28 @@
-use libsip::{headers::parse::parse_contact_header, *};
+use libsip::{
+    headers::parse::{parse_contact_header, parse_contact_wildcard_header, parse_header},
+    *,
+};
 
 use nom::error::VerboseError;
 
 #[test]
 fn write() {
     let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("guy"));
-    let header = Header::Contact(named_header!(uri, "Guy"));
+    let header = Header::Contact(vec![named_header!(uri, "Guy")]);
     assert_eq!(
         "Contact: Guy <sip:guy@example.com>".to_string(),
         format!("{}", header)
     );
 
     let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("guy"));
-    let header = Header::Contact(named_header!(uri, "Guy With Face"));
+    let header = Header::Contact(vec![named_header!(uri, "Guy With Face")]);
     assert_eq!(
         "Contact: \"Guy With Face\" <sip:guy@example.com>".to_string(),
         format!("{}", header)
     );
 
     let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("guy"));
-    let header = Header::Contact(named_header!(uri));
+    let header = Header::Contact(vec![named_header!(uri)]);
     assert_eq!(
         "Contact: sip:guy@example.com".to_string(),
         format!("{}", header)
@@ -30,14 +33,14 @@ fn write() {
 fn read() {
     let remains = vec![];
     let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("guy"));
-    let header = Header::Contact(named_header!(uri, "Guy"));
+    let header = Header::Contact(vec![named_header!(uri, "Guy")]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_contact_header::<VerboseError<&[u8]>>(b"Contact: Guy <sip:guy@example.com>\r\n")
     );
 
     let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("guy"));
-    let header = Header::Contact(named_header!(uri, "Guy with face"));
+    let header = Header::Contact(vec![named_header!(uri, "Guy with face")]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_contact_header::<VerboseError<&[u8]>>(
@@ -46,9 +49,193 @@ fn read() {
     );
 
     let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("guy"));
-    let header = Header::Contact(named_header!(uri));
+    let header = Header::Contact(vec![named_header!(uri)]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_contact_header::<VerboseError<&[u8]>>(b"Contact: <sip:guy@example.com>\r\n")
     );
 }
+
+#[test]
+fn read_params() {
+    let remains = vec![];
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("guy"));
+    let header = Header::Contact(vec![NamedHeader::new(uri)
+        .param("q", Some("0.7"))
+        .param("expires", Some("3600"))]);
+    assert_eq!(
+        Ok((remains.as_ref(), header.clone())),
+        parse_contact_header::<VerboseError<&[u8]>>(
+            b"Contact: <sip:guy@example.com>;q=0.7;expires=3600\r\n"
+        )
+    );
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_contact_header::<VerboseError<&[u8]>>(
+            b"Contact: sip:guy@example.com;q=0.7;expires=3600\r\n"
+        )
+    );
+
+    let uri = Uri::sip(domain!("example.com"))
+        .auth(uri_auth!("guy"))
+        .parameter(UriParam::Transport(Transport::Tcp));
+    let header = Header::Contact(vec![NamedHeader::new(uri)
+        .name("Guy")
+        .param("+sip.instance", Some("<urn:uuid:1>"))]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_contact_header::<VerboseError<&[u8]>>(
+            b"Contact: Guy <sip:guy@example.com;transport=tcp>;+sip.instance=\"<urn:uuid:1>\"\r\n"
+        )
+    );
+}
+
+#[test]
+fn accessors() {
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("guy"));
+    let contact = NamedHeader::new(uri)
+        .param("q", Some("0.7"))
+        .param("expires", Some("3600"));
+    assert_eq!(Some(0.7), contact.q());
+    assert_eq!(Some(3600), contact.expires());
+    let contact = contact.param("expires", Some("never"));
+    assert_eq!(None, contact.expires());
+}
+
+#[test]
+fn wildcard() {
+    let remains = vec![];
+    assert_eq!(
+        "Contact: *".to_string(),
+        format!("{}", Header::ContactWildcard)
+    );
+    assert_eq!("m: *".to_string(), format!("{:#}", Header::ContactWildcard));
+    assert_eq!(
+        Ok((remains.as_ref(), Header::ContactWildcard)),
+        parse_contact_wildcard_header::<VerboseError<&[u8]>>(b"Contact: *\r\n")
+    );
+    assert_eq!(
+        Ok((remains.as_ref(), Header::ContactWildcard)),
+        parse_header::<VerboseError<&[u8]>>(b"m: *\r\n")
+    );
+}
+
+#[test]
+fn quoted_params() {
+    let remains = vec![];
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("guy"));
+    let header = Header::Contact(vec![
+        named_header!(uri).param("+sip.instance", Some("<urn:uuid:f81d4fae>"))
+    ]);
+    assert_eq!(
+        "Contact: sip:guy@example.com;+sip.instance=\"<urn:uuid:f81d4fae>\"".to_string(),
+        format!("{}", header)
+    );
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_contact_header::<VerboseError<&[u8]>>(
+            b"Contact: <sip:guy@example.com>;+sip.instance=\"<urn:uuid:f81d4fae>\"\r\n"
+        )
+    );
+
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("guy"));
+    let header = Header::Contact(vec![
+        named_header!(uri).param("text", Some("say \"hi\", \\o/"))
+    ]);
+    assert_eq!(
+        "Contact: sip:guy@example.com;text=\"say \\\"hi\\\", \\\\o/\"".to_string(),
+        format!("{}", header)
+    );
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_contact_header::<VerboseError<&[u8]>>(
+            b"Contact: <sip:guy@example.com>;text=\"say \\\"hi\\\", \\\\o/\"\r\n"
+        )
+    );
+}
+
+#[test]
+fn outbound_params() {
+    let remains = vec![];
+    let uri = Uri::sip(ip_domain!(192, 0, 2, 1)).auth(uri_auth!("alice"));
+    let mut contact = NamedHeader::new(uri);
+    contact.set_instance("urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6");
+    contact.set_reg_id(1);
+    let header = Header::Contact(vec![contact.clone()]);
+    let written = "Contact: sip:alice@192.0.2.1;+sip.instance=\"<urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6>\";reg-id=1";
+    assert_eq!(written.to_string(), format!("{}", header));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_contact_header::<VerboseError<&[u8]>>(format!("{}\r\n", written).as_bytes())
+    );
+    assert_eq!(
+        Some("urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6"),
+        contact.instance()
+    );
+    assert_eq!(Some(1), contact.reg_id());
+
+    let input = b"Contact: <sip:alice@192.0.2.1>;pub-gruu=\"sip:alice@example.com;gr=urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6\";temp-gruu=\"sip:tgruu.7hs==jd7vnzga5w7fajsc7-ajd6fabz0f8g5@example.com;gr\"\r\n";
+    let (_, header) = parse_contact_header::<VerboseError<&[u8]>>(input).unwrap();
+    if let Header::Contact(contacts) = header {
+        let contact = &contacts[0];
+        assert_eq!(
+            Some(
+                Uri::sip(domain!("example.com"))
+                    .auth(uri_auth!("alice"))
+                    .parameter(UriParam::Other(
+                        "gr".into(),
+                        Some("urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6".into())
+                    ))
+            ),
+            contact.pub_gruu()
+        );
+        assert!(contact.temp_gruu().is_some());
+    } else {
+        panic!("expected a Contact header");
+    }
+}
+
+#[test]
+fn instance_ids() {
+    let id = generate_instance_id();
+    assert!(id.starts_with("urn:uuid:"));
+    assert_eq!(45, id.len());
+    assert_eq!(Some('4'), id.chars().nth(23));
+    assert_ne!(id, generate_instance_id());
+
+    let id = instance_id_from_seed("00:11:22:33:44:55");
+    assert_eq!(id, instance_id_from_seed("00:11:22:33:44:55"));
+    assert_ne!(id, instance_id_from_seed("00:11:22:33:44:56"));
+    assert_eq!(Some('3'), id.chars().nth(23));
+}
+
+#[test]
+fn list() {
+    let remains = vec![];
+    let first = Uri::sip(domain!("x.com")).auth(uri_auth!("a"));
+    let second = Uri::sip(domain!("y.com")).auth(uri_auth!("b"));
+    let header = Header::Contact(vec![
+        named_header!(first).param("expires", Some("60")),
+        named_header!(second).param("expires", Some("30")),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header.clone())),
+        parse_header::<VerboseError<&[u8]>>(
+            b"Contact: <sip:a@x.com>;expires=60, <sip:b@y.com>;expires=30\r\n"
+        )
+    );
+    assert_eq!(
+        "Contact: sip:a@x.com;expires=60, sip:b@y.com;expires=30".to_string(),
+        format!("{}", header)
+    );
+    let mut headers = Headers::new();
+    headers.push(header);
+    assert_eq!(
+        vec![Some(60), Some(30)],
+        headers
+            .contacts()
+            .iter()
+            .map(NamedHeader::expires)
+            .collect::<Vec<_>>()
+    );
+}
@@ -59,3 +59,79 @@ fn read() {
         parse_from_header::<VerboseError<&[u8]>>(b"From: sip:unknown@127.0.0.1\r\n")
     );
 }
+
+#[test]
+fn display_names() {
+    let remains = vec![];
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("alice"));
+    let header = Header::From(named_header!((uri.clone()), "Alice (Sales) 📞"));
+    assert_eq!(
+        "From: \"Alice (Sales) 📞\" <sip:alice@example.com>".to_string(),
+        format!("{}", header)
+    );
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_from_header::<VerboseError<&[u8]>>(
+            "From: \"Alice (Sales) 📞\" <sip:alice@example.com>\r\n".as_bytes()
+        )
+    );
+
+    let header = Header::From(named_header!((uri.clone()), "Bob \"The\" Builder\\2"));
+    assert_eq!(
+        "From: \"Bob \\\"The\\\" Builder\\\\2\" <sip:alice@example.com>".to_string(),
+        format!("{}", header)
+    );
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_from_header::<VerboseError<&[u8]>>(
+            b"From: \"Bob \\\"The\\\" Builder\\\\2\" <sip:alice@example.com>\r\n"
+        )
+    );
+
+    let header = Header::From(named_header!((uri.clone()), "Alice Smith-2"));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_from_header::<VerboseError<&[u8]>>(
+            b"From: Alice   Smith-2<sip:alice@example.com>\r\n"
+        )
+    );
+
+    let header = Header::From(named_header!(uri).param("tag", Some("1")));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_from_header::<VerboseError<&[u8]>>(b"From: sip:alice@example.com;tag=1\r\n")
+    );
+}
+
+#[test]
+fn addr_spec() {
+    let remains = vec![];
+    let uri = Uri::sip(domain!("example.com", 5060)).auth(uri_auth!("bob"));
+    let header = Header::From(NamedHeader::new(uri).param("tag", Some("123")));
+    assert_eq!(
+        Ok((remains.as_ref(), header.clone())),
+        parse_from_header::<VerboseError<&[u8]>>(b"From: sip:bob@example.com:5060;tag=123\r\n")
+    );
+    assert_eq!(
+        "From: sip:bob@example.com:5060;tag=123".to_string(),
+        format!("{}", header)
+    );
+    if let Header::From(from) = header {
+        assert_eq!(Some("123"), from.tag());
+        assert!(from.uri.parameters.is_empty());
+    }
+
+    // Parameters inside angle brackets belong to the uri.
+    let uri = Uri::sip(domain!("example.com"))
+        .auth(uri_auth!("bob"))
+        .parameter(UriParam::Other("tag".into(), Some("123".into())));
+    let header = Header::From(NamedHeader::new(uri));
+    assert_eq!(
+        Ok((remains.as_ref(), header.clone())),
+        parse_from_header::<VerboseError<&[u8]>>(b"From: <sip:bob@example.com;tag=123>\r\n")
+    );
+    assert_eq!(
+        "From: <sip:bob@example.com;tag=123>".to_string(),
+        format!("{}", header)
+    );
+}
@@ -4,9 +4,9 @@ use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::InReplyTo("call@id.com".into());
+    let header = Header::InReplyTo(vec!["call@id.com".into(), "17320@saturn".into()]);
     assert_eq!(
-        "In-Reply-To: call@id.com".to_string(),
+        "In-Reply-To: call@id.com,17320@saturn".to_string(),
         format!("{}", header)
     );
 }
@@ -14,9 +14,23 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::InReplyTo("call@id.com".into());
+    let header = Header::InReplyTo(vec!["call@id.com".into()]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_in_reply_to_header::<VerboseError<&[u8]>>(b"In-Reply-To: call@id.com\r\n")
     );
+    let header = Header::InReplyTo(vec![
+        "70710@saturn.bell-tel.com".into(),
+        "17320@saturn.bell-tel.com".into(),
+        "a84b4c76e66710".into(),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_in_reply_to_header::<VerboseError<&[u8]>>(
+            b"In-Reply-To: 70710@saturn.bell-tel.com, 17320@saturn.bell-tel.com,a84b4c76e66710\r\n"
+        )
+    );
+    assert!(
+        parse_in_reply_to_header::<VerboseError<&[u8]>>(b"In-Reply-To: call@id@com\r\n").is_err()
+    );
 }
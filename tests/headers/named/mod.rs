@@ -1,5 +1,6 @@
 mod contact;
 mod from;
 mod in_reply_to;
+mod refer;
 mod reply_to;
 mod to;
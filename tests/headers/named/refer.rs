@@ -0,0 +1,71 @@
+use libsip::{
+    headers::parse::{parse_header, parse_refer_to_header, parse_referred_by_header},
+    *,
+};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("carol"));
+    let header = Header::ReferTo(named_header!(uri));
+    assert_eq!(
+        "Refer-To: sip:carol@example.com".to_string(),
+        format!("{}", header)
+    );
+    assert_eq!(
+        "r: sip:carol@example.com".to_string(),
+        format!("{:#}", header)
+    );
+
+    let uri = Uri::sip(domain!("example.org"))
+        .auth(uri_auth!("bob"))
+        .header("Replaces", "12345@host;to-tag=1;from-tag=2");
+    let header = Header::ReferTo(named_header!(uri));
+    assert_eq!(
+        "Refer-To: <sip:bob@example.org?Replaces=12345%40host%3Bto-tag%3D1%3Bfrom-tag%3D2>"
+            .to_string(),
+        format!("{}", header)
+    );
+
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("alice"));
+    let header = Header::ReferredBy(named_header!(uri, "Alice"));
+    assert_eq!(
+        "Referred-By: Alice <sip:alice@example.com>".to_string(),
+        format!("{}", header)
+    );
+    assert_eq!(
+        "b: Alice <sip:alice@example.com>".to_string(),
+        format!("{:#}", header)
+    );
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let uri = Uri::sip(domain!("example.org"))
+        .auth(uri_auth!("bob"))
+        .header("Replaces", "12345@host;to-tag=1;from-tag=2");
+    let header = Header::ReferTo(named_header!(uri));
+    assert_eq!(
+        Ok((remains.as_ref(), header.clone())),
+        parse_refer_to_header::<VerboseError<&[u8]>>(
+            b"Refer-To: <sip:bob@example.org?Replaces=12345%40host%3Bto-tag%3D1%3Bfrom-tag%3D2>\r\n"
+        )
+    );
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_header::<VerboseError<&[u8]>>(
+            b"r: <sip:bob@example.org?Replaces=12345%40host%3Bto-tag%3D1%3Bfrom-tag%3D2>\r\n"
+        )
+    );
+
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("alice"));
+    let header = Header::ReferredBy(named_header!(uri, "Alice"));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_referred_by_header::<VerboseError<&[u8]>>(
+            b"Referred-By: Alice <sip:alice@example.com>\r\n"
+        )
+    );
+}
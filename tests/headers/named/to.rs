@@ -1,4 +1,4 @@
-use libsip::{headers::parse::parse_to_header, *};
+use libsip::{headers::parse::parse_to_header, uri::AbsoluteUri, *};
 
 use nom::error::VerboseError;
 
@@ -54,3 +54,84 @@ fn read() {
         parse_to_header::<VerboseError<&[u8]>>(b"To: <sip:guy@example.com>\r\n")
     );
 }
+
+#[test]
+fn tag() {
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("bob"));
+    let mut header = NamedHeader::new(uri);
+    assert_eq!(None, header.tag());
+    header.set_tag("as7d9f8-1");
+    assert_eq!(Some("as7d9f8-1"), header.tag());
+    header.set_tag(generate_tag());
+    assert_ne!(Some("as7d9f8-1"), header.tag());
+    assert_ne!(generate_tag(), generate_tag());
+}
+
+#[test]
+fn tel() {
+    let remains = vec![];
+    let header =
+        Header::To(NamedHeader::new(Uri::tel("+1-212-555-0101")).param("tag", Some("a6c85cf")));
+    assert_eq!(
+        Ok((remains.as_ref(), header.clone())),
+        parse_to_header::<VerboseError<&[u8]>>(b"To: <tel:+1-212-555-0101>;tag=a6c85cf\r\n")
+    );
+    assert_eq!(
+        "To: tel:+1-212-555-0101;tag=a6c85cf".to_string(),
+        format!("{}", header)
+    );
+}
+
+#[test]
+fn absolute_uri() {
+    let remains = vec![];
+    let header = Header::To(NamedHeader::new(Uri::absolute("urn", "service:sos")));
+    assert_eq!(
+        Ok((remains.as_ref(), header.clone())),
+        parse_to_header::<VerboseError<&[u8]>>(b"To: <urn:service:sos>\r\n")
+    );
+    assert_eq!(
+        Ok((remains.as_ref(), header.clone())),
+        parse_to_header::<VerboseError<&[u8]>>(b"To: urn:service:sos\r\n")
+    );
+    assert_eq!("To: <urn:service:sos>".to_string(), format!("{}", header));
+
+    let uri = Uri::absolute("http", "//www.example.com/alice;photo");
+    assert!(uri.is_absolute());
+    assert_eq!(
+        Some(&AbsoluteUri::new("http", "//www.example.com/alice;photo")),
+        uri.as_absolute()
+    );
+    assert_eq!(None, uri.schema);
+    assert_eq!(None, uri.host());
+    assert_eq!(None, uri.domain());
+    assert_eq!(None, uri.port());
+    assert!(uri.host_and_params().is_err());
+    let header = Header::To(named_header!(uri, "Alice").param("tag", Some("1")));
+    assert_eq!(
+        Ok((remains.as_ref(), header.clone())),
+        parse_to_header::<VerboseError<&[u8]>>(
+            b"To: Alice <http://www.example.com/alice;photo>;tag=1\r\n"
+        )
+    );
+    assert_eq!(
+        "To: Alice <http://www.example.com/alice;photo>;tag=1".to_string(),
+        format!("{}", header)
+    );
+}
+
+#[test]
+fn absolute_uri_round_trip() {
+    let inputs: Vec<&[u8]> = vec![
+        b"To: <urn:service:sos;foo=bar>;tag=1\r\n",
+        b"To: <http://a.com/x?y=1>\r\n",
+        b"To: <http://a.com/x?y=1>;tag=1\r\n",
+    ];
+    for input in inputs {
+        let (_, header) = parse_to_header::<VerboseError<&[u8]>>(input).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&input[..input.len() - 2]),
+            format!("{}", header)
+        );
+    }
+}
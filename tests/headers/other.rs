@@ -0,0 +1,49 @@
+use libsip::{
+    headers::parse::{parse_header, parse_other_header},
+    *,
+};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let header = Header::Other("X-Custom-Header".into(), "some value".into());
+    assert_eq!(
+        "X-Custom-Header: some value".to_string(),
+        format!("{}", header)
+    );
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let header = Header::Other("x-serialNumber".into(), "A1:B2;c=3".into());
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_other_header::<VerboseError<&[u8]>>(b"x-serialNumber:A1:B2;c=3\r\n")
+    );
+    let header = Header::Other("P-Charging-Vector".into(), "icid-value=1234bc9876e".into());
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_header::<VerboseError<&[u8]>>(b"P-Charging-Vector: icid-value=1234bc9876e\r\n")
+    );
+}
+
+#[test]
+fn read_message() {
+    let remains = vec![];
+    let uri = Uri::sip(domain!("example.com")).auth(uri_auth!("user"));
+    let req = RequestGenerator::new()
+        .method(Method::Options)
+        .uri(uri)
+        .header(Header::Other("X-Foo".into(), "bar".into()))
+        .header(Header::ContentLength(0))
+        .build_unchecked()
+        .unwrap();
+    let input = b"OPTIONS sip:user@example.com SIP/2.0\r\nX-Foo: bar\r\nContent-Length: 0\r\n\r\n";
+    assert_eq!(
+        Ok((remains.as_ref(), req.clone())),
+        parse_message::<VerboseError<&[u8]>>(input)
+    );
+    assert_eq!(String::from_utf8_lossy(input), format!("{}", req));
+}
@@ -0,0 +1,84 @@
+use libsip::{headers::parse::parse_header, *};
+
+use nom::error::VerboseError;
+
+#[test]
+fn order() {
+    let mut params = GenericParams::new();
+    params.insert("lr", None::<String>);
+    params.insert("transport", Some("tcp"));
+    params.push("foo", Some("1"));
+    params.push("foo", Some("2"));
+    assert_eq!(
+        Some(Some("tcp".into())),
+        params.insert("transport", Some("udp"))
+    );
+    assert_eq!(
+        vec!["lr", "transport", "foo", "foo"],
+        params
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Some(&Some("udp".into())), params.get("transport"));
+    assert_eq!(
+        vec![&Some("1".to_string()), &Some("2".to_string())],
+        params.get_all("foo").collect::<Vec<_>>()
+    );
+    assert_eq!(Some(Some("1".into())), params.remove("foo"));
+    assert!(!params.contains_key("foo"));
+    assert_eq!(2, params.len());
+}
+
+#[test]
+fn case_insensitive() {
+    let mut params = GenericParams::new();
+    params.push("Expires", Some("60"));
+    assert_eq!(Some(&Some("60".into())), params.get("expires"));
+    assert_eq!(
+        Some(Some("60".into())),
+        params.insert("EXPIRES", Some("30"))
+    );
+    assert_eq!(
+        vec![("Expires", Some("30"))],
+        params
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_deref()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Some(Some("30".into())), params.remove("expires"));
+    assert!(params.is_empty());
+
+    let (_, header) =
+        parse_header::<VerboseError<&[u8]>>(b"Contact: <sip:a@x.com>;Expires=60\r\n").unwrap();
+    assert_eq!("Contact: sip:a@x.com;Expires=60", format!("{}", header));
+    match header {
+        Header::Contact(contacts) => assert_eq!(Some(60), contacts[0].expires()),
+        _ => panic!("expected a Contact header"),
+    }
+}
+
+#[test]
+fn round_trip() {
+    let inputs: Vec<&[u8]> = vec![
+        b"To: sip:bob@example.com;zeta=1;alpha;tag=as7d9f8-1;alpha=2\r\n",
+        b"Via: SIP/2.0/UDP 192.168.1.120;rport;received=10.0.0.1;branch=z9hG4bK7Q6y;x=1\r\n",
+        b"Contact: sip:guy@example.com;q=0.7;expires=3600;+sip.instance=\"<urn:uuid:1>\"\r\n",
+    ];
+    for input in inputs {
+        let (_, header) = parse_header::<VerboseError<&[u8]>>(input).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&input[..input.len() - 2]),
+            format!("{}", header)
+        );
+    }
+
+    // An empty value isn't a token, it is written back quoted.
+    let (_, header) =
+        parse_header::<VerboseError<&[u8]>>(b"Contact: sip:guy@example.com;expires=;q=0.5\r\n")
+            .unwrap();
+    assert_eq!(
+        "Contact: sip:guy@example.com;expires=\"\";q=0.5",
+        format!("{}", header)
+    );
+}
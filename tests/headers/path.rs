@@ -0,0 +1,61 @@
+use libsip::{
+    headers::parse::{parse_path_header, parse_service_route_header},
+    *,
+};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let uri = Uri::sip(domain!("p1.example.com")).lr_param();
+    let header = Header::Path(vec![named_header!((uri.clone()))]);
+    assert_eq!(
+        "Path: <sip:p1.example.com;lr>".to_string(),
+        format!("{}", header)
+    );
+    let header = Header::ServiceRoute(vec![named_header!(uri)]);
+    assert_eq!(
+        "Service-Route: <sip:p1.example.com;lr>".to_string(),
+        format!("{}", header)
+    );
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let p1 = Uri::sip(domain!("p1.example.com")).lr_param();
+    let p2 = Uri::sip(domain!("p2.example.com")).lr_param();
+    let header = Header::Path(vec![
+        named_header!((p1.clone())),
+        named_header!((p2.clone())),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_path_header::<VerboseError<&[u8]>>(
+            b"Path: <sip:p1.example.com;lr>,<sip:p2.example.com;lr>\r\n"
+        )
+    );
+    let header = Header::ServiceRoute(vec![named_header!(p1)]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_service_route_header::<VerboseError<&[u8]>>(
+            b"Service-Route: <sip:p1.example.com;lr>\r\n"
+        )
+    );
+}
+
+#[test]
+fn service_route_set() {
+    let p1 = Uri::sip(domain!("p1.example.com")).lr_param();
+    let p2 = Uri::sip(domain!("p2.example.com")).lr_param();
+    let headers = Headers(vec![
+        Header::ServiceRoute(vec![named_header!((p1.clone()))]),
+        Header::Path(vec![named_header!((p1.clone()))]),
+        Header::ServiceRoute(vec![named_header!((p2.clone()))]),
+    ]);
+    assert_eq!(
+        vec![named_header!((p1.clone())), named_header!(p2)],
+        headers.service_route_set()
+    );
+    assert_eq!(vec![named_header!(p1)], headers.path_set());
+}
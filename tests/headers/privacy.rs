@@ -0,0 +1,34 @@
+use libsip::{
+    headers::privacy::{parse_privacy_header, PrivacyToken},
+    *,
+};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let header = Header::Privacy(vec![
+        PrivacyToken::Header,
+        PrivacyToken::Session,
+        PrivacyToken::Other("history".into()),
+    ]);
+    assert_eq!(
+        "Privacy: header;session;history".to_string(),
+        format!("{}", header)
+    );
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let header = Header::Privacy(vec![
+        PrivacyToken::User,
+        PrivacyToken::Id,
+        PrivacyToken::None,
+        PrivacyToken::Other("history".into()),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_privacy_header::<VerboseError<&[u8]>>(b"Privacy: USER; id ;None;history\r\n")
+    );
+}
@@ -1,12 +1,16 @@
-use libsip::{headers::parse::parse_proxy_authenticate_header, Header};
+use libsip::{headers::parse::parse_proxy_authenticate_header, *};
 
 use nom::error::VerboseError;
 
+use std::collections::HashMap;
+
 #[test]
 fn write() {
-    let header = Header::ProxyAuthenticate("call@id.com".into());
+    let mut map = HashMap::new();
+    map.insert("realm".into(), "example.com".into());
+    let header = Header::ProxyAuthenticate(AuthHeader(AuthSchema::Digest, map));
     assert_eq!(
-        "Proxy-Authenticate: call@id.com".to_string(),
+        "Proxy-Authenticate: Digest realm=\"example.com\"".to_string(),
         format!("{}", header)
     );
 }
@@ -14,11 +18,14 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::ProxyAuthenticate("call@id.com".into());
+    let mut map = HashMap::new();
+    map.insert("realm".into(), "example.com".into());
+    map.insert("nonce".into(), "abc".into());
+    let header = Header::ProxyAuthenticate(AuthHeader(AuthSchema::Digest, map));
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_proxy_authenticate_header::<VerboseError<&[u8]>>(
-            b"Proxy-Authenticate: call@id.com\r\n"
+            b"Proxy-Authenticate: Digest realm=\"example.com\", nonce=\"abc\"\r\n"
         )
     );
 }
@@ -1,12 +1,17 @@
-use libsip::{headers::parse::parse_proxy_authorization_header, Header};
+use libsip::{headers::parse::parse_proxy_authorization_header, *};
 
 use nom::error::VerboseError;
 
+use std::collections::HashMap;
+
 #[test]
 fn write() {
-    let header = Header::ProxyAuthorization("call@id.com".into());
+    let mut map = HashMap::new();
+    map.insert("username".into(), "bob".into());
+    map.insert("realm".into(), "biloxi.com".into());
+    let header = Header::ProxyAuthorization(AuthHeader(AuthSchema::Digest, map));
     assert_eq!(
-        "Proxy-Authorization: call@id.com".to_string(),
+        "Proxy-Authorization: Digest username=\"bob\", realm=\"biloxi.com\"".to_string(),
         format!("{}", header)
     );
 }
@@ -14,11 +19,14 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::ProxyAuthorization("call@id.com".into());
+    let mut map = HashMap::new();
+    map.insert("username".into(), "bob".into());
+    map.insert("realm".into(), "biloxi.com".into());
+    let header = Header::ProxyAuthorization(AuthHeader(AuthSchema::Digest, map));
     assert_eq!(
         Ok((remains.as_ref(), header)),
         parse_proxy_authorization_header::<VerboseError<&[u8]>>(
-            b"Proxy-Authorization: call@id.com\r\n"
+            b"Proxy-Authorization: Digest username=\"bob\", realm=\"biloxi.com\"\r\n"
         )
     );
 }
@@ -1,12 +1,12 @@
-use libsip::{headers::parse::parse_proxy_require_header, Header};
+use libsip::{headers::parse::parse_proxy_require_header, Header, Headers};
 
 use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::ProxyRequire("call@id.com".into());
+    let header = Header::ProxyRequire(vec!["sec-agree".into()]);
     assert_eq!(
-        "Proxy-Require: call@id.com".to_string(),
+        "Proxy-Require: sec-agree".to_string(),
         format!("{}", header)
     );
 }
@@ -14,9 +14,12 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::ProxyRequire("call@id.com".into());
+    let header = Header::ProxyRequire(vec!["sec-agree".into(), "foo.bar".into()]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_proxy_require_header::<VerboseError<&[u8]>>(b"Proxy-Require: call@id.com\r\n")
+        parse_proxy_require_header::<VerboseError<&[u8]>>(b"Proxy-Require: sec-agree,foo.bar\r\n")
     );
+    let headers = Headers(vec![Header::ProxyRequire(vec!["sec-agree".into()])]);
+    assert!(headers.proxy_requires("sec-agree"));
+    assert!(!headers.requires("sec-agree"));
 }
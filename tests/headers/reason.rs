@@ -0,0 +1,33 @@
+use libsip::{
+    headers::reason::{parse_reason_header, ReasonHeader, ReasonProtocol},
+    *,
+};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let header = Header::Reason(vec![
+        ReasonHeader::sip(200).text("Call completed elsewhere"),
+        ReasonHeader::q850(16),
+    ]);
+    assert_eq!(
+        "Reason: SIP;cause=200;text=\"Call completed elsewhere\",Q.850;cause=16".to_string(),
+        format!("{}", header)
+    );
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let header = Header::Reason(vec![
+        ReasonHeader::sip(580).text("Precondition Failure"),
+        ReasonHeader::new(ReasonProtocol::Other("preemption".into())).cause(1),
+    ]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_reason_header::<VerboseError<&[u8]>>(
+            b"Reason: SIP ;cause=580 ;text=\"Precondition Failure\", preemption;cause=1\r\n"
+        )
+    );
+}
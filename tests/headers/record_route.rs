@@ -1,12 +1,13 @@
-use libsip::{headers::parse::parse_record_route_header, Header};
+use libsip::{headers::parse::parse_record_route_header, *};
 
 use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::RecordRoute("Softphone 1.0".into());
+    let uri = Uri::sip(domain!("p1.example.com")).lr_param();
+    let header = Header::RecordRoute(vec![named_header!(uri)]);
     assert_eq!(
-        "Record-Route: Softphone 1.0".to_string(),
+        "Record-Route: <sip:p1.example.com;lr>".to_string(),
         format!("{}", header)
     );
 }
@@ -14,9 +15,12 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::RecordRoute("Softphone 1.0".into());
+    let uri = Uri::sip(domain!("p1.example.com")).lr_param();
+    let header = Header::RecordRoute(vec![named_header!(uri).param("foo", Some("bar"))]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_record_route_header::<VerboseError<&[u8]>>(b"Record-Route: Softphone 1.0\r\n")
+        parse_record_route_header::<VerboseError<&[u8]>>(
+            b"Record-Route: <sip:p1.example.com;lr>;foo=bar\r\n"
+        )
     );
 }
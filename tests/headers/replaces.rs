@@ -0,0 +1,59 @@
+use libsip::{
+    headers::{replaces::parse_replaces_header, ReplacesHeader},
+    *,
+};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let header = Header::Replaces(ReplacesHeader::new(
+        "425928@bobster.example.org",
+        "7743",
+        "6472",
+    ));
+    assert_eq!(
+        "Replaces: 425928@bobster.example.org;to-tag=7743;from-tag=6472".to_string(),
+        format!("{}", header)
+    );
+    let header = Header::Replaces(
+        ReplacesHeader::new("98732@sip.example.com", "ff87ff", "r33th4x0r").early_only(),
+    );
+    assert_eq!(
+        "Replaces: 98732@sip.example.com;to-tag=ff87ff;from-tag=r33th4x0r;early-only".to_string(),
+        format!("{}", header)
+    );
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let header = Header::Replaces(ReplacesHeader::new(
+        "425928@bobster.example.org",
+        "7743",
+        "6472",
+    ));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_replaces_header::<VerboseError<&[u8]>>(
+            b"Replaces: 425928@bobster.example.org;to-tag=7743;from-tag=6472\r\n"
+        )
+    );
+    assert!(parse_replaces_header::<VerboseError<&[u8]>>(
+        b"Replaces: 425928@bobster.example.org;to-tag=7743\r\n"
+    )
+    .is_err());
+}
+
+#[test]
+fn from_refer_to() {
+    let uri = Uri::sip(domain!("example.org"))
+        .auth(uri_auth!("bob"))
+        .header("Replaces", "12345@host;to-tag=1;from-tag=2;early-only");
+    let replaces: ReplacesHeader = uri.header_value("Replaces").unwrap().parse().unwrap();
+    assert_eq!(
+        ReplacesHeader::new("12345@host", "1", "2").early_only(),
+        replaces
+    );
+    assert!("12345@host".parse::<ReplacesHeader>().is_err());
+}
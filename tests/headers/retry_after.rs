@@ -1,12 +1,23 @@
-use libsip::{headers::parse::parse_retry_after_header, Header};
+use libsip::{
+    headers::retry_after::{parse_retry_after_header, RetryAfter},
+    Header,
+};
 
 use nom::error::VerboseError;
 
+use std::time::Duration;
+
 #[test]
 fn write() {
-    let header = Header::RetryAfter("Softphone 1.0".into());
+    let header = Header::RetryAfter(RetryAfter::new(18000));
+    assert_eq!("Retry-After: 18000".to_string(), format!("{}", header));
+    let header = Header::RetryAfter(
+        RetryAfter::new(120)
+            .comment("I'm in a meeting")
+            .param("duration", Some("3600")),
+    );
     assert_eq!(
-        "Retry-After: Softphone 1.0".to_string(),
+        "Retry-After: 120 (I'm in a meeting);duration=3600".to_string(),
         format!("{}", header)
     );
 }
@@ -14,9 +25,22 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::RetryAfter("Softphone 1.0".into());
+    let header = Header::RetryAfter(RetryAfter::new(18000).param("duration", Some("3600")));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_retry_after_header::<VerboseError<&[u8]>>(b"Retry-After: 18000;duration=3600\r\n")
+    );
+    let header = Header::RetryAfter(RetryAfter::new(120).comment("I'm in a meeting"));
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_retry_after_header::<VerboseError<&[u8]>>(b"Retry-After: Softphone 1.0\r\n")
+        parse_retry_after_header::<VerboseError<&[u8]>>(b"Retry-After: 120 (I'm in a meeting)\r\n")
     );
 }
+
+#[test]
+fn accessors() {
+    let value = RetryAfter::new(120).param("duration", Some("3600"));
+    assert_eq!(Duration::from_secs(120), value.retry_in());
+    assert_eq!(Some(3600), value.duration());
+    assert_eq!(None, RetryAfter::new(120).duration());
+}
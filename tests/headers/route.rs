@@ -1,19 +1,54 @@
-use libsip::{headers::parse::parse_route_header, Header};
+use libsip::{headers::parse::parse_route_header, *};
 
 use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::Route("Softphone 1.0".into());
-    assert_eq!("Route: Softphone 1.0".to_string(), format!("{}", header));
+    let uri = Uri::sip(domain!("p1.example.com")).lr_param();
+    let header = Header::Route(vec![named_header!(uri)]);
+    assert_eq!(
+        "Route: <sip:p1.example.com;lr>".to_string(),
+        format!("{}", header)
+    );
+
+    let first = Uri::sip(domain!("p1.example.com")).lr_param();
+    let second = Uri::sip(domain!("p2.example.com"));
+    let header = Header::Route(vec![named_header!(first), named_header!(second)]);
+    assert_eq!(
+        "Route: <sip:p1.example.com;lr>,<sip:p2.example.com>".to_string(),
+        format!("{}", header)
+    );
 }
 
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::Route("Softphone 1.0".into());
+    let first = Uri::sip(domain!("p1.example.com")).lr_param();
+    let second = Uri::sip(domain!("p2.example.com", 5060));
+    let header = Header::Route(vec![named_header!(first), named_header!(second)]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_route_header::<VerboseError<&[u8]>>(b"Route: Softphone 1.0\r\n")
+        parse_route_header::<VerboseError<&[u8]>>(
+            b"Route: <sip:p1.example.com;lr>, <sip:p2.example.com:5060>\r\n"
+        )
     );
 }
+
+#[test]
+fn route_set() {
+    let first = Uri::sip(domain!("p1.example.com")).lr_param();
+    let second = Uri::sip(domain!("p2.example.com"));
+    let third = Uri::sip(domain!("p3.example.com"));
+    let expected: Vec<NamedHeader> = vec![
+        NamedHeader::new(first),
+        NamedHeader::new(second),
+        NamedHeader::new(third),
+    ];
+    let headers = Headers(vec![
+        Header::Route(expected[..2].to_vec()),
+        Header::ContentLength(0),
+        Header::Route(expected[2..].to_vec()),
+    ]);
+    assert_eq!(expected, headers.route_set());
+    assert!(headers.record_route_set().is_empty());
+}
@@ -0,0 +1,29 @@
+use libsip::{
+    headers::parse::{parse_rack_header, parse_rseq_header},
+    Header, Method,
+};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let header = Header::RSeq(988789);
+    assert_eq!("RSeq: 988789".to_string(), format!("{}", header));
+    let header = Header::RAck(776656, 1, Method::Invite);
+    assert_eq!("RAck: 776656 1 INVITE".to_string(), format!("{}", header));
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let header = Header::RSeq(988789);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_rseq_header::<VerboseError<&[u8]>>(b"RSeq: 988789\r\n")
+    );
+    let header = Header::RAck(776656, 1, Method::Invite);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_rack_header::<VerboseError<&[u8]>>(b"RAck: 776656 1 INVITE\r\n")
+    );
+}
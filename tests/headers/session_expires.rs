@@ -0,0 +1,42 @@
+use libsip::{
+    headers::{
+        parse::{parse_header, parse_min_se_header},
+        session_expires::parse_session_expires_header,
+        Refresher, SessionExpires,
+    },
+    Header,
+};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let header = Header::SessionExpires(SessionExpires::new(4000).refresher(Refresher::Uas));
+    assert_eq!(
+        "Session-Expires: 4000;refresher=uas".to_string(),
+        format!("{}", header)
+    );
+    assert_eq!("x: 4000;refresher=uas".to_string(), format!("{:#}", header));
+    let header = Header::MinSE(90);
+    assert_eq!("Min-SE: 90".to_string(), format!("{}", header));
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let header = Header::SessionExpires(SessionExpires::new(1800));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_session_expires_header::<VerboseError<&[u8]>>(b"Session-Expires: 1800\r\n")
+    );
+    let header = Header::SessionExpires(SessionExpires::new(4000).refresher(Refresher::Uac));
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_header::<VerboseError<&[u8]>>(b"x: 4000;refresher=uac\r\n")
+    );
+    let header = Header::MinSE(90);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_min_se_header::<VerboseError<&[u8]>>(b"Min-SE: 90\r\n")
+    );
+}
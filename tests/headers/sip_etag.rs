@@ -0,0 +1,34 @@
+use libsip::{
+    headers::parse::{parse_header, parse_sip_etag_header, parse_sip_if_match_header},
+    Header,
+};
+
+use nom::error::VerboseError;
+
+#[test]
+fn write() {
+    let header = Header::SipETag("dx200xyz".into());
+    assert_eq!("SIP-ETag: dx200xyz".to_string(), format!("{}", header));
+    let header = Header::SipIfMatch("dx200xyz".into());
+    assert_eq!("SIP-If-Match: dx200xyz".to_string(), format!("{}", header));
+}
+
+#[test]
+fn read() {
+    let remains = vec![];
+    let header = Header::SipETag("dx200xyz".into());
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_sip_etag_header::<VerboseError<&[u8]>>(b"SIP-ETag: dx200xyz\r\n")
+    );
+    let header = Header::SipIfMatch("kwj449x".into());
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_sip_if_match_header::<VerboseError<&[u8]>>(b"SIP-If-Match: kwj449x \r\n")
+    );
+    let header = Header::SipETag("dx200xyz".into());
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_header::<VerboseError<&[u8]>>(b"sip-etag: dx200xyz\r\n")
+    );
+}
@@ -14,9 +14,18 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::Supported(vec!["allowed".into(), "events".into()]);
+    let header = Header::Supported(vec!["100rel".into(), "sec-agree".into()]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_supported_header::<VerboseError<&[u8]>>(b"Supported: allowed, events\r\n")
+        parse_supported_header::<VerboseError<&[u8]>>(b"Supported: 100rel, sec-agree\r\n")
+    );
+}
+
+#[test]
+fn read_empty() {
+    let remains = vec![];
+    assert_eq!(
+        Ok((remains.as_ref(), Header::Supported(vec![]))),
+        parse_supported_header::<VerboseError<&[u8]>>(b"Supported: \r\n")
     );
 }
@@ -4,9 +4,9 @@ use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::Unsupported("Softphone 1.0".into());
+    let header = Header::Unsupported(vec!["100rel".into(), "timer".into()]);
     assert_eq!(
-        "Unsupported: Softphone 1.0".to_string(),
+        "Unsupported: 100rel,timer".to_string(),
         format!("{}", header)
     );
 }
@@ -14,9 +14,9 @@ fn write() {
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::Unsupported("Softphone 1.0".into());
+    let header = Header::Unsupported(vec!["100rel".into(), "timer".into()]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_unsupported_header::<VerboseError<&[u8]>>(b"Unsupported: Softphone 1.0\r\n")
+        parse_unsupported_header::<VerboseError<&[u8]>>(b"Unsupported: 100rel , timer\r\n")
     );
 }
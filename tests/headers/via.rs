@@ -2,73 +2,218 @@ use libsip::{headers::parse::parse_via_header, *};
 
 use nom::error::VerboseError;
 
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
 #[test]
 fn write() {
-    let header = ViaHeader {
-        version: Version::default(),
-        transport: Transport::Udp,
-        uri: Uri::new_schemaless(domain!("example.com")),
-    };
+    let header = ViaHeader::new(domain!("example.com"), Transport::Udp);
     assert_eq!(
         "Via: SIP/2.0/UDP example.com".to_string(),
         format!("{}", header)
     );
+
+    let header = ViaHeader::new(ip_domain!(192, 168, 1, 1, 5060), Transport::Tcp)
+        .branch_param("z9hG4bK7Q6y313Qrt6Uc");
+    assert_eq!(
+        "Via: SIP/2.0/TCP 192.168.1.1:5060;branch=z9hG4bK7Q6y313Qrt6Uc".to_string(),
+        format!("{}", header)
+    );
+
+    let header =
+        ViaHeader::new(domain!("example.com"), Transport::Udp).param("rport", None::<&str>);
+    assert_eq!(
+        "Via: SIP/2.0/UDP example.com;rport".to_string(),
+        format!("{}", header)
+    );
 }
 
 #[test]
 fn read() {
-    let remains = vec!['\r' as u8, '\n' as u8];
-    let header = ViaHeader {
-        version: Version::default(),
-        transport: Transport::Udp,
-        uri: Uri::new_schemaless(domain!("example.com")),
-    };
-    assert_eq!(
-        Ok((remains.as_ref(), Header::Via(header))),
+    let remains = vec![];
+    let header = ViaHeader::new(domain!("example.com"), Transport::Udp);
+    assert_eq!(
+        Ok((remains.as_ref(), Header::Via(vec![header]))),
         parse_via_header::<VerboseError<&[u8]>>(b"Via: SIP/2.0/UDP example.com\r\n")
     );
 
     let input = b"Via: SIP/2.0/UDP 192.168.1.120;rport;branch=z9hG4bK7Q6y313Qrt6Uc\r\n";
-    let remains = vec!['\r' as u8, '\n' as u8];
-    let header = ViaHeader {
-        version: Version::default(),
-        transport: Transport::Udp,
-        uri: Uri::new_schemaless(ip_domain!(192, 168, 1, 120))
-            .parameter(UriParam::RPort(None))
-            .parameter(UriParam::Branch("z9hG4bK7Q6y313Qrt6Uc".into())),
-    };
-    assert_eq!(
-        Ok((remains.as_ref(), Header::Via(header))),
+    let header = ViaHeader::new(ip_domain!(192, 168, 1, 120), Transport::Udp)
+        .param("rport", None::<&str>)
+        .branch_param("z9hG4bK7Q6y313Qrt6Uc");
+    assert_eq!(
+        Ok((remains.as_ref(), Header::Via(vec![header]))),
         parse_via_header::<VerboseError<&[u8]>>(input)
     );
 
-    let input = b"Via: SIP/2.0/UDP 192.168.1.1:5060;rport=5060;received=192.168.1.1;branch=8e7ec4e3d1e1380bc111f8723341ca70;transport=UDP\r\n";
-    let remains = vec!['\r' as u8, '\n' as u8];
-    let header = ViaHeader {
-        version: Version::default(),
-        transport: Transport::Udp,
-        uri: Uri::new_schemaless(ip_domain!(192, 168, 1, 1, 5060))
-            .parameter(UriParam::RPort(Some(5060)))
-            .parameter(UriParam::Received(ip_domain!(192, 168, 1, 1)))
-            .parameter(UriParam::Branch("8e7ec4e3d1e1380bc111f8723341ca70".into()))
-            .parameter(UriParam::Transport(Transport::Udp)),
-    };
+    let input = b"Via: SIP/2.0/UDP 192.168.1.1:5060;rport=5060;received=192.168.1.1;branch=8e7ec4e3d1e1380bc111f8723341ca70;ttl=16;maddr=224.2.0.1\r\n";
+    let header = ViaHeader::new(ip_domain!(192, 168, 1, 1, 5060), Transport::Udp)
+        .param("rport", Some("5060"))
+        .param("received", Some("192.168.1.1"))
+        .branch_param("8e7ec4e3d1e1380bc111f8723341ca70")
+        .param("ttl", Some("16"))
+        .param("maddr", Some("224.2.0.1"));
     assert_eq!(
-        Ok((remains.as_ref(), Header::Via(header))),
+        Ok((remains.as_ref(), Header::Via(vec![header]))),
         parse_via_header::<VerboseError<&[u8]>>(input)
     );
+}
+
+#[test]
+fn accessors() {
+    let input = b"Via: SIP/2.0/UDP 192.168.1.1:5060;rport=5062;received=10.0.0.1;branch=z9hG4bK776asdhds;ttl=16;maddr=224.2.0.1\r\n";
+    let (_, header) = parse_via_header::<VerboseError<&[u8]>>(input).unwrap();
+    if let Header::Via(vias) = header {
+        let via = &vias[0];
+        assert_eq!(via.host, ip_domain!(192, 168, 1, 1, 5060));
+        assert_eq!(via.branch(), Some("z9hG4bK776asdhds"));
+        assert_eq!(via.received(), Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))));
+        assert_eq!(via.rport(), Some(5062));
+        assert!(via.has_rport());
+        assert_eq!(via.ttl(), Some(16));
+        assert_eq!(via.maddr(), Some("224.2.0.1"));
+    } else {
+        panic!("expected a Via header");
+    }
 
-    let input = b"Via: SIP/2.0/UDP 192.168.1.120;branch=03395ed83a7b9502c671c769bbe369cb;received=192.168.1.76\r\n";
-    let remains = vec!['\r' as u8, '\n' as u8];
-    let header = ViaHeader {
-        version: Version::default(),
-        transport: Transport::Udp,
-        uri: Uri::new_schemaless(ip_domain!(192, 168, 1, 120))
-            .parameter(UriParam::Branch("03395ed83a7b9502c671c769bbe369cb".into()))
-            .parameter(UriParam::Received(ip_domain!(192, 168, 1, 76))),
-    };
+    let via = ViaHeader::new(domain!("example.com"), Transport::Udp);
+    assert_eq!(via.branch(), None);
+    assert_eq!(via.received(), None);
+    assert!(!via.has_rport());
+}
+
+#[test]
+fn ipv6() {
+    let remains = vec![];
+    let addr: Ipv6Addr = "2001:db8::1".parse().unwrap();
+    let input =
+        b"Via: SIP/2.0/UDP [2001:db8::1]:5060;received=2001:db8::9;branch=z9hG4bK776asdhds\r\n";
+    let header = ViaHeader::new(Domain::Ipv6(addr, Some(5060)), Transport::Udp)
+        .param("received", Some("2001:db8::9"))
+        .branch_param("z9hG4bK776asdhds");
     assert_eq!(
-        Ok((remains.as_ref(), Header::Via(header))),
+        Ok((remains.as_ref(), Header::Via(vec![header.clone()]))),
         parse_via_header::<VerboseError<&[u8]>>(input)
     );
+    assert_eq!(
+        "Via: SIP/2.0/UDP [2001:db8::1]:5060;received=2001:db8::9;branch=z9hG4bK776asdhds"
+            .to_string(),
+        format!("{}", header)
+    );
+    assert_eq!(
+        header.received(),
+        Some(IpAddr::V6("2001:db8::9".parse().unwrap()))
+    );
+}
+
+#[test]
+fn transports() {
+    let remains = vec![];
+    for (transport, token) in &[
+        (Transport::Tls, "TLS"),
+        (Transport::Ws, "WS"),
+        (Transport::Wss, "WSS"),
+        (Transport::Sctp, "SCTP"),
+        (Transport::TlsSctp, "TLS-SCTP"),
+    ] {
+        let header = ViaHeader::new(domain!("df7jal23ls0d.invalid"), *transport);
+        let input = format!("Via: SIP/2.0/{} df7jal23ls0d.invalid\r\n", token);
+        assert_eq!(format!("{}\r\n", header), input);
+        assert_eq!(
+            Ok((remains.as_ref(), Header::Via(vec![header]))),
+            parse_via_header::<VerboseError<&[u8]>>(input.as_bytes())
+        );
+    }
+
+    let uri = parse_uri::<VerboseError<&[u8]>>(b"sip:bob@example.com;transport=ws")
+        .unwrap()
+        .1;
+    assert_eq!(Some(Transport::Ws), uri.transport());
+
+    let uri = parse_uri::<VerboseError<&[u8]>>(b"sip:bob@example.com;transport=tls-sctp")
+        .unwrap()
+        .1;
+    assert_eq!(Some(Transport::TlsSctp), uri.transport());
+    assert_eq!("sip:bob@example.com;transport=TLS-SCTP", uri.to_string());
+}
+
+#[test]
+fn received() {
+    let source: SocketAddr = "192.0.2.1:4000".parse().unwrap();
+
+    let mut via = ViaHeader::new(ip_domain!(192, 0, 2, 1, 5060), Transport::Udp);
+    via.set_received(source);
+    assert_eq!(None, via.received());
+    assert_eq!(
+        Some("192.0.2.1:5060".parse().unwrap()),
+        via.response_destination()
+    );
+
+    let mut via = ViaHeader::new(domain!("pc33.example.com"), Transport::Udp);
+    assert_eq!(None, via.response_destination());
+    via.set_received(source);
+    assert_eq!(Some(source.ip()), via.received());
+    assert_eq!(
+        Some("192.0.2.1:5060".parse().unwrap()),
+        via.response_destination()
+    );
+
+    let mut via =
+        ViaHeader::new(ip_domain!(10, 0, 0, 1, 5060), Transport::Udp).param("rport", None::<&str>);
+    via.set_received(source);
+    assert_eq!(
+        "Via: SIP/2.0/UDP 10.0.0.1:5060;rport=4000;received=192.0.2.1",
+        format!("{}", via)
+    );
+    assert_eq!(Some(source), via.response_destination());
+
+    let via = via.param("maddr", Some("224.2.0.1"));
+    assert_eq!(
+        Some("224.2.0.1:5060".parse().unwrap()),
+        via.response_destination()
+    );
+
+    let mut request = SipMessage::parse(
+        b"OPTIONS sip:bob@example.com SIP/2.0\r\n\
+        Via: SIP/2.0/UDP 10.0.0.1;rport;branch=z9hG4bK776asdhds\r\n\
+        Via: SIP/2.0/UDP 10.0.0.2;rport;branch=z9hG4bK776asdhdt\r\n\r\n",
+    )
+    .unwrap();
+    request.set_received(source);
+    assert_eq!(Some(source), response_destination(&request));
+    assert_eq!(None, request.via_stack()[1].rport());
+}
+
+#[test]
+fn list() {
+    let remains = vec![];
+    let input = b"Via: SIP/2.0/UDP p1.example.com;branch=z9hG4bK1 , SIP/2.0/UDP pc33.example.com;branch=z9hG4bK2\r\n";
+    let first = ViaHeader::new(domain!("p1.example.com"), Transport::Udp).branch_param("z9hG4bK1");
+    let second =
+        ViaHeader::new(domain!("pc33.example.com"), Transport::Udp).branch_param("z9hG4bK2");
+    let header = Header::Via(vec![first.clone(), second.clone()]);
+    assert_eq!(
+        Ok((remains.as_ref(), header.clone())),
+        parse_via_header::<VerboseError<&[u8]>>(input)
+    );
+    assert_eq!(
+        "Via: SIP/2.0/UDP p1.example.com;branch=z9hG4bK1, SIP/2.0/UDP pc33.example.com;branch=z9hG4bK2",
+        format!("{}", header)
+    );
+
+    let response = SipMessage::parse(
+        b"SIP/2.0 200 OK\r\n\
+        Via: SIP/2.0/UDP p1.example.com;branch=z9hG4bK1, SIP/2.0/UDP pc33.example.com;branch=z9hG4bK2\r\n\
+        Via: SIP/2.0/UDP 10.0.0.1;branch=z9hG4bK3\r\n\
+        Content-Length: 0\r\n\r\n",
+    )
+    .unwrap();
+    assert_eq!(Some(&first), response.top_via());
+    assert_eq!(
+        vec![Some("z9hG4bK1"), Some("z9hG4bK2"), Some("z9hG4bK3")],
+        response
+            .via_stack()
+            .iter()
+            .map(|via| via.branch())
+            .collect::<Vec<_>>()
+    );
 }
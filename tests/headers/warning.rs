@@ -1,19 +1,49 @@
-use libsip::{headers::parse::parse_warning_header, Header};
+use libsip::{
+    headers::warning::{parse_warning_header, WarningValue},
+    Header,
+};
 
 use nom::error::VerboseError;
 
 #[test]
 fn write() {
-    let header = Header::Warning("Softphone 1.0".into());
-    assert_eq!("Warning: Softphone 1.0".to_string(), format!("{}", header));
+    let header = Header::Warning(vec![WarningValue::new(
+        307,
+        "isi.edu",
+        "Session parameter 'foo' not understood",
+    )]);
+    assert_eq!(
+        "Warning: 307 isi.edu \"Session parameter 'foo' not understood\"".to_string(),
+        format!("{}", header)
+    );
+    let header = Header::Warning(vec![
+        WarningValue::new(301, "isi.edu", "Incompatible network address type 'E.164'"),
+        WarningValue::new(399, "example.com:5060", "Softphone 1.0"),
+    ]);
+    assert_eq!(
+        "Warning: 301 isi.edu \"Incompatible network address type 'E.164'\",399 example.com:5060 \"Softphone 1.0\"".to_string(),
+        format!("{}", header)
+    );
 }
 
 #[test]
 fn read() {
     let remains = vec![];
-    let header = Header::Warning("Softphone 1.0".into());
+    let header = Header::Warning(vec![WarningValue::new(399, "example.com", "Softphone 1.0")]);
+    assert_eq!(
+        Ok((remains.as_ref(), header)),
+        parse_warning_header::<VerboseError<&[u8]>>(
+            b"Warning: 399 example.com \"Softphone 1.0\"\r\n"
+        )
+    );
+    let header = Header::Warning(vec![
+        WarningValue::new(301, "isi.edu", "Incompatible network address type 'E.164'"),
+        WarningValue::new(399, "example.com:5060", "Softphone 1.0"),
+    ]);
     assert_eq!(
         Ok((remains.as_ref(), header)),
-        parse_warning_header::<VerboseError<&[u8]>>(b"Warning: Softphone 1.0\r\n")
+        parse_warning_header::<VerboseError<&[u8]>>(
+            b"Warning: 301 isi.edu \"Incompatible network address type 'E.164'\", 399 example.com:5060 \"Softphone 1.0\"\r\n"
+        )
     );
 }
@@ -1,4 +1,7 @@
-use libsip::{headers::parse::parse_www_authenticate_header, *};
+use libsip::{
+    headers::{parse::parse_www_authenticate_header, DigestAlgorithm},
+    *,
+};
 use nom::error::VerboseError;
 
 use std::collections::HashMap;
@@ -27,3 +30,45 @@ fn read() {
         )
     );
 }
+
+#[test]
+fn challenge_params() {
+    let (_, header) = parse_www_authenticate_header::<VerboseError<&[u8]>>(
+        b"WWW-Authenticate: Digest realm=\"atlanta.com\", \
+        domain=\"sip:ss1.carrier.com sip:ss2.carrier.com\", qop=\"auth,auth-int\", \
+        nonce=\"f84f1cec41e6cbe5aea9c8e88d359\", opaque=\"\", stale=FALSE, algorithm=MD5\r\n",
+    )
+    .unwrap();
+    let challenge = match header {
+        Header::WwwAuthenticate(challenge) => challenge,
+        _ => panic!("expected a WWW-Authenticate header"),
+    };
+    assert_eq!(AuthSchema::Digest, challenge.0);
+    assert_eq!(Some("atlanta.com"), challenge.realm());
+    assert_eq!(Some("f84f1cec41e6cbe5aea9c8e88d359"), challenge.nonce());
+    assert_eq!(Some(""), challenge.opaque());
+    assert_eq!(vec!["auth", "auth-int"], challenge.qop());
+    assert_eq!(
+        vec!["sip:ss1.carrier.com", "sip:ss2.carrier.com"],
+        challenge.domain()
+    );
+    assert!(!challenge.stale());
+    assert_eq!(Some(DigestAlgorithm::Md5), challenge.algorithm());
+}
+
+#[test]
+fn challenges() {
+    let response = SipMessage::parse(
+        b"SIP/2.0 407 Proxy Authentication Required\r\n\
+        Proxy-Authenticate: Digest realm=\"proxy.com\", nonce=\"a\"\r\n\
+        WWW-Authenticate: Digest realm=\"example.com\", nonce=\"b\"\r\n\r\n",
+    )
+    .unwrap();
+    let realms: Vec<_> = response
+        .headers()
+        .challenges()
+        .iter()
+        .map(|challenge| challenge.realm())
+        .collect();
+    assert_eq!(vec![Some("proxy.com"), Some("example.com")], realms);
+}
@@ -0,0 +1,6 @@
+#[cfg(feature = "transport-tcp")]
+mod stream;
+#[cfg(feature = "transport-udp")]
+mod udp;
+#[cfg(feature = "transport-ws")]
+mod ws;
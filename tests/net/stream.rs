@@ -0,0 +1,181 @@
+use libsip::{
+    net::{ConnectionKey, StreamEvent, StreamTransport},
+    *,
+};
+
+use std::{io::ErrorKind, time::Duration};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpListener, TcpStream},
+    time::delay_for,
+};
+
+#[tokio::test]
+async fn request_and_response() {
+    let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let mut client = StreamTransport::new();
+    let mut server = StreamTransport::new();
+    let request =
+        SipMessage::parse(b"OPTIONS sip:bob@127.0.0.1 SIP/2.0\r\nMax-Forwards: 70\r\n\r\n")
+            .unwrap();
+    let key = ConnectionKey::new("127.0.0.1", port, Transport::Tcp);
+
+    client.send(&request, &key).await.unwrap();
+    client.send(&request, &key).await.unwrap();
+    assert!(client.is_connected(&key));
+    let remote = server.accept(&mut listener).await.unwrap();
+    for _ in 0..2 {
+        match server.recv().await {
+            StreamEvent::Message(msg, from) => {
+                assert_eq!(request, *msg);
+                assert_eq!(remote, from);
+            },
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+
+    let response = ResponseGenerator::reply_to(&request, StatusCode::OK)
+        .build_unchecked()
+        .unwrap();
+    server.send(&response, &remote).await.unwrap();
+    match client.recv().await {
+        StreamEvent::Message(msg, from) => {
+            assert_eq!(response, *msg);
+            assert_eq!(key, from);
+        },
+        event => panic!("unexpected event {:?}", event),
+    }
+
+    client.close(&key);
+    assert!(!client.is_connected(&key));
+    assert!(matches!(server.recv().await, StreamEvent::HalfClosed(from) if from == remote));
+    server.close(&remote);
+    assert!(matches!(client.recv().await, StreamEvent::Closed(from, None) if from == key));
+}
+
+#[tokio::test]
+async fn replaced_connection() {
+    let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut transport = StreamTransport::new();
+    let key = ConnectionKey::new("192.0.2.4", 5060, Transport::Tcp);
+
+    let first = TcpStream::connect(addr).await.unwrap();
+    transport.add_stream(key.clone(), listener.accept().await.unwrap().0);
+    let mut second = TcpStream::connect(addr).await.unwrap();
+    transport.add_stream(key.clone(), listener.accept().await.unwrap().0);
+
+    drop(first);
+    delay_for(Duration::from_millis(50)).await;
+    second
+        .write_all(b"OPTIONS sip:bob@127.0.0.1 SIP/2.0\r\nMax-Forwards: 70\r\n\r\n")
+        .await
+        .unwrap();
+    match transport.recv().await {
+        StreamEvent::Message(msg, from) => {
+            assert_eq!(Some(&Method::Options), msg.method());
+            assert_eq!(key, from);
+        },
+        event => panic!("unexpected event {:?}", event),
+    }
+    assert!(transport.is_connected(&key));
+}
+
+#[tokio::test]
+async fn keep_alive() {
+    let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut transport = StreamTransport::new();
+    let key = ConnectionKey::new("192.0.2.4", 5060, Transport::Tcp);
+    let mut peer = TcpStream::connect(addr).await.unwrap();
+    transport.add_stream(key.clone(), listener.accept().await.unwrap().0);
+
+    // The empty line ending headers sent on their own isn't a keep-alive.
+    peer.write_all(b"OPTIONS sip:bob@127.0.0.1 SIP/2.0\r\nMax-Forwards: 70\r\n")
+        .await
+        .unwrap();
+    delay_for(Duration::from_millis(50)).await;
+    peer.write_all(b"\r\n").await.unwrap();
+    assert!(matches!(transport.recv().await, StreamEvent::Message(..)));
+
+    peer.write_all(b"\r\n\r\n").await.unwrap();
+    assert!(matches!(transport.recv().await, StreamEvent::KeepAlive(from) if from == key));
+}
+
+#[tokio::test]
+async fn tls_without_config() {
+    let mut transport = StreamTransport::new();
+    let request =
+        SipMessage::parse(b"OPTIONS sip:bob@127.0.0.1 SIP/2.0\r\nMax-Forwards: 70\r\n\r\n")
+            .unwrap();
+    let error = transport
+        .send(
+            &request,
+            &ConnectionKey::new("127.0.0.1", 5061, Transport::Tls),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(ErrorKind::NotConnected, error.kind());
+}
+
+#[cfg(feature = "transport-tls")]
+#[tokio::test]
+async fn tls() {
+    use std::{io::Cursor, sync::Arc};
+    use tokio_rustls::{
+        rustls::{
+            internal::pemfile::{certs, pkcs8_private_keys},
+            ClientConfig, NoClientAuth, ServerConfig,
+        },
+        TlsAcceptor,
+    };
+
+    let mut server_config = ServerConfig::new(NoClientAuth::new());
+    let chain = certs(&mut Cursor::new(&include_bytes!("tls/cert.pem")[..])).unwrap();
+    let mut keys =
+        pkcs8_private_keys(&mut Cursor::new(&include_bytes!("tls/key.pem")[..])).unwrap();
+    server_config
+        .set_single_cert(chain, keys.remove(0))
+        .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(server_config));
+    let mut client_config = ClientConfig::new();
+    client_config
+        .root_store
+        .add_pem_file(&mut Cursor::new(&include_bytes!("tls/ca.pem")[..]))
+        .unwrap();
+
+    let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let mut client = StreamTransport::new();
+    client.set_tls_config(Arc::new(client_config));
+    let mut server = StreamTransport::new();
+    let request =
+        SipMessage::parse(b"OPTIONS sips:bob@localhost SIP/2.0\r\nMax-Forwards: 70\r\n\r\n")
+            .unwrap();
+    let key = ConnectionKey::new("localhost", port, Transport::Tls);
+
+    let (sent, accepted) = tokio::join!(
+        client.send(&request, &key),
+        server.accept_tls(&mut listener, &acceptor)
+    );
+    sent.unwrap();
+    let remote = accepted.unwrap();
+    assert_eq!(Transport::Tls, remote.transport);
+    match server.recv().await {
+        StreamEvent::Message(msg, from) => {
+            assert_eq!(request, *msg);
+            assert_eq!(remote, from);
+        },
+        event => panic!("unexpected event {:?}", event),
+    }
+
+    let error = client
+        .send(
+            &request,
+            &ConnectionKey::new("127.0.0.1", port, Transport::Tls),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(ErrorKind::InvalidInput, error.kind());
+}
@@ -0,0 +1,55 @@
+use libsip::{
+    core::ParsedPacket,
+    net::{UdpTransport, UDP_MTU_LIMIT},
+    *,
+};
+
+use std::io::ErrorKind;
+
+#[tokio::test]
+async fn send_and_receive() {
+    let mut alice = UdpTransport::bind("127.0.0.1:0").await.unwrap();
+    let mut bob = UdpTransport::bind("127.0.0.1:0").await.unwrap();
+    let options =
+        SipMessage::parse(b"OPTIONS sip:bob@127.0.0.1 SIP/2.0\r\nMax-Forwards: 70\r\n\r\n")
+            .unwrap();
+
+    alice
+        .send_to(&options, bob.local_addr().unwrap())
+        .await
+        .unwrap();
+    let (packet, source) = bob.recv_from().await.unwrap();
+    assert_eq!(ParsedPacket::Sip(Box::new(options.clone())), packet);
+    assert_eq!(alice.local_addr().unwrap(), source);
+
+    alice.set_compact_headers(true);
+    alice
+        .send_to(&options, bob.local_addr().unwrap())
+        .await
+        .unwrap();
+    let (packet, _) = bob.recv_from().await.unwrap();
+    assert_eq!(ParsedPacket::Sip(Box::new(options)), packet);
+}
+
+#[tokio::test]
+async fn oversized_request() {
+    let mut alice = UdpTransport::bind("127.0.0.1:0").await.unwrap();
+    let mut request =
+        SipMessage::parse(b"MESSAGE sip:bob@127.0.0.1 SIP/2.0\r\nMax-Forwards: 70\r\n\r\n")
+            .unwrap();
+    *request.body_mut() = vec![b'a'; UDP_MTU_LIMIT];
+    assert!(UdpTransport::requires_tcp(&request));
+
+    let error = alice
+        .send_to(&request, "127.0.0.1:5060".parse().unwrap())
+        .await
+        .unwrap_err();
+    assert_eq!(ErrorKind::InvalidInput, error.kind());
+
+    let response = ResponseGenerator::new()
+        .code(200)
+        .body(vec![b'a'; UDP_MTU_LIMIT])
+        .build_unchecked()
+        .unwrap();
+    assert!(!UdpTransport::requires_tcp(&response));
+}
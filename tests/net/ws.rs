@@ -0,0 +1,128 @@
+use libsip::{
+    net::{ws::accept_key, WsFrame, WsFramer},
+    *,
+};
+
+use std::io::ErrorKind;
+
+#[test]
+fn handshake() {
+    assert_eq!(
+        "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=",
+        accept_key("dGhlIHNhbXBsZSBub25jZQ==")
+    );
+}
+
+#[test]
+fn frames() {
+    let mut msg =
+        SipMessage::parse(b"MESSAGE sip:bob@example.com SIP/2.0\r\nMax-Forwards: 70\r\n\r\n")
+            .unwrap();
+    *msg.body_mut() = vec![b'a'; 300];
+    let client = WsFramer::client();
+    let mut server = WsFramer::server();
+
+    let frame = client.encode(&msg);
+    assert_eq!(0x81, frame[0]);
+    assert_eq!(0x80 | 126, frame[1]);
+    server.feed(&frame[..10]);
+    assert_eq!(None, server.next_frame().unwrap());
+    server.feed(&frame[10..]);
+    server.feed(&client.encode_close(1000));
+    assert_eq!(
+        Some(WsFrame::Sip(Box::new(msg))),
+        server.next_frame().unwrap()
+    );
+    assert_eq!(
+        Some(WsFrame::Close(Some(1000))),
+        server.next_frame().unwrap()
+    );
+    assert_eq!(None, server.next_frame().unwrap());
+
+    assert_eq!(vec![0x8a, 0x02, b'h', b'i'], server.encode_pong(b"hi"));
+}
+
+#[test]
+fn fragments() {
+    let mut framer = WsFramer::client();
+    framer.feed(b"\x01\x09SIP/2.0 2");
+    framer.feed(b"\x89\x00");
+    framer.feed(b"\x80\x0900 OK\r\n\r\n");
+    assert_eq!(Some(WsFrame::Ping(vec![])), framer.next_frame().unwrap());
+    match framer.next_frame().unwrap() {
+        Some(WsFrame::Sip(msg)) => assert_eq!(Some(200), msg.status_code()),
+        frame => panic!("unexpected frame {:?}", frame),
+    }
+
+    framer.feed(b"\x81\x03bad");
+    assert!(framer.next_frame().is_err());
+}
+
+#[test]
+fn invalid_lengths() {
+    let mut framer = WsFramer::client();
+    framer.feed(&[0x82, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    let error = framer.next_frame().unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+
+    framer.feed(&[0x82, 0x7f, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    let error = framer.next_frame().unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+
+    framer.feed(&[0x82, 0x7f, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(None, framer.next_frame().unwrap());
+    framer.feed(&[0, 5]);
+    assert_eq!(None, framer.next_frame().unwrap());
+
+    let mut framer = WsFramer::client();
+    framer.set_max_message_size(10);
+    framer.feed(b"\x01\x06SIP/2.");
+    framer.feed(b"\x80\x0a0 200 OK\r\n");
+    let error = framer.next_frame().unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    framer.feed(b"\x81\x0bSIP/2.0 200");
+    assert!(framer.next_frame().is_err());
+}
+
+#[test]
+fn unmasked_client_frames() {
+    let msg = SipMessage::parse(b"SIP/2.0 200 OK\r\n\r\n").unwrap();
+    let mut server = WsFramer::server();
+    server.feed(&WsFramer::server().encode(&msg));
+    let error = server.next_frame().unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert_eq!(None, server.next_frame().unwrap());
+
+    let mut client = WsFramer::client();
+    client.feed(&WsFramer::client().encode(&msg));
+    let error = client.next_frame().unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+}
+
+#[test]
+fn orphan_continuation() {
+    let mut framer = WsFramer::client();
+    framer.feed(b"\x80\x12SIP/2.0 200 OK\r\n\r\n");
+    let error = framer.next_frame().unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+
+    // A complete message doesn't leave anything to continue.
+    framer.feed(b"\x81\x12SIP/2.0 200 OK\r\n\r\n");
+    assert!(framer.next_frame().unwrap().is_some());
+    framer.feed(b"\x80\x00");
+    assert!(framer.next_frame().is_err());
+}
+
+#[test]
+fn interleaved_messages() {
+    let mut framer = WsFramer::client();
+    framer.feed(b"\x01\x09SIP/2.0 2");
+    framer.feed(b"\x81\x12SIP/2.0 200 OK\r\n\r\n");
+    let error = framer.next_frame().unwrap_err();
+    assert_eq!(ErrorKind::InvalidData, error.kind());
+    assert_eq!(None, framer.next_frame().unwrap());
+
+    // The message in progress is dropped along with the connection.
+    framer.feed(b"\x80\x0900 OK\r\n\r\n");
+    assert!(framer.next_frame().is_err());
+}
@@ -0,0 +1,135 @@
+use libsip::{
+    headers::HeaderKind,
+    proxy::{add_record_route, decrement_max_forwards, pop_via, push_via, stateless_branch},
+    *,
+};
+
+fn invite() -> SipMessage {
+    SipMessage::parse(
+        b"INVITE sip:bob@biloxi.com SIP/2.0\r\n\
+        Via: SIP/2.0/UDP pc33.atlanta.com;branch=z9hG4bK776asdhds\r\n\
+        Max-Forwards: 70\r\n\
+        Record-Route: <sip:p2.biloxi.com;lr>\r\n\
+        To: Bob <sip:bob@biloxi.com>\r\n\
+        From: Alice <sip:alice@atlanta.com>;tag=1928301774\r\n\
+        Call-ID: a84b4c76e66710@pc33.atlanta.com\r\n\
+        CSeq: 314159 INVITE\r\n\r\n",
+    )
+    .unwrap()
+}
+
+#[test]
+fn max_forwards() {
+    let mut request = invite();
+    assert_eq!(None, decrement_max_forwards(&mut request).unwrap());
+    assert_eq!(
+        Some(&Header::MaxForwards(69)),
+        request.header(HeaderKind::MaxForwards)
+    );
+
+    request.set_header(Header::MaxForwards(0));
+    let response = decrement_max_forwards(&mut request).unwrap().unwrap();
+    assert_eq!(Some(StatusCode::TOO_MANY_HOPS), response.status());
+    assert_eq!(request.top_via(), response.top_via());
+
+    request.remove_header(HeaderKind::MaxForwards);
+    assert_eq!(None, decrement_max_forwards(&mut request).unwrap());
+    assert_eq!(
+        Some(&Header::MaxForwards(70)),
+        request.header(HeaderKind::MaxForwards)
+    );
+}
+
+#[test]
+fn via() {
+    let mut request = invite();
+    push_via(
+        &mut request,
+        ViaHeader::new(domain!("p1.atlanta.com"), Transport::Udp),
+    );
+    let stack = request.via_stack();
+    assert_eq!(2, stack.len());
+    assert_eq!(domain!("p1.atlanta.com"), stack[0].host);
+    assert!(stack[0].branch().unwrap().starts_with("z9hG4bK"));
+    assert_eq!(domain!("pc33.atlanta.com"), stack[1].host);
+
+    let mut response = ResponseGenerator::reply_to(&request, StatusCode::OK)
+        .build()
+        .unwrap();
+    let popped = pop_via(&mut response).unwrap();
+    assert_eq!(domain!("p1.atlanta.com"), popped.host);
+    assert_eq!(invite().top_via(), response.top_via());
+    pop_via(&mut response).unwrap();
+    assert_eq!(None, pop_via(&mut response));
+}
+
+#[test]
+fn combined_via() {
+    let mut response = SipMessage::parse(
+        b"SIP/2.0 200 OK\r\n\
+        Via: SIP/2.0/UDP p1.atlanta.com;branch=z9hG4bK1, SIP/2.0/UDP pc33.atlanta.com;branch=z9hG4bK2\r\n\
+        Via: SIP/2.0/UDP pc34.atlanta.com;branch=z9hG4bK3\r\n\
+        Content-Length: 0\r\n\r\n",
+    )
+    .unwrap();
+    let popped = pop_via(&mut response).unwrap();
+    assert_eq!(domain!("p1.atlanta.com"), popped.host);
+    assert_eq!(
+        domain!("pc33.atlanta.com"),
+        response.top_via().unwrap().host
+    );
+    assert_eq!(2, response.via_stack().len());
+    pop_via(&mut response).unwrap();
+    assert_eq!(
+        domain!("pc34.atlanta.com"),
+        response.top_via().unwrap().host
+    );
+    assert_eq!(1, response.headers().get_all(HeaderKind::Via).len());
+}
+
+#[test]
+fn stateless_branches() {
+    let request = invite();
+    let branch = stateless_branch(&request);
+    assert!(branch.starts_with("z9hG4bK"));
+    assert_eq!(branch, stateless_branch(&invite()));
+
+    let mut forwarded = invite();
+    push_via(
+        &mut forwarded,
+        ViaHeader::new(domain!("p1.atlanta.com"), Transport::Udp),
+    );
+    assert_eq!(Some(branch.as_str()), forwarded.top_via().unwrap().branch());
+
+    let cancel = RequestGenerator::cancel_of(&request)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(branch, stateless_branch(&cancel));
+
+    let mut spiral = invite();
+    if let SipMessage::Request { uri, .. } = &mut spiral {
+        *uri = Uri::sip(domain!("192.0.2.4")).auth(uri_auth!("bob"));
+    }
+    assert_ne!(branch, stateless_branch(&spiral));
+
+    let mut other = invite();
+    other
+        .top_via_mut()
+        .unwrap()
+        .set_param("branch", Some("z9hG4bKother"));
+    assert_ne!(branch, stateless_branch(&other));
+}
+
+#[test]
+fn record_route() {
+    let mut request = invite();
+    add_record_route(&mut request, Uri::sip(domain!("p1.atlanta.com")));
+    let routes = request.headers().record_route_set();
+    assert_eq!(2, routes.len());
+    assert_eq!(
+        Uri::sip(domain!("p1.atlanta.com")).lr_param(),
+        routes[0].uri
+    );
+    assert_eq!(Uri::sip(domain!("p2.biloxi.com")).lr_param(), routes[1].uri);
+}
@@ -0,0 +1,166 @@
+use libsip::{
+    resolve::{resolve, NaptrRecord, SrvRecord, StaticDns, SystemDns, Target},
+    *,
+};
+
+use nom::error::VerboseError;
+
+use std::io::ErrorKind;
+
+fn uri(input: &str) -> Uri {
+    parse_uri::<VerboseError<&[u8]>>(input.as_bytes())
+        .unwrap()
+        .1
+}
+
+fn target(transport: Transport, addr: &str) -> Target {
+    Target {
+        transport,
+        addr: addr.parse().unwrap(),
+    }
+}
+
+fn naptr(order: u16, service: &str, replacement: &str) -> NaptrRecord {
+    NaptrRecord {
+        order,
+        preference: 50,
+        flags: "s".into(),
+        service: service.into(),
+        replacement: replacement.into(),
+    }
+}
+
+fn srv(priority: u16, weight: u16, port: u16, target: &str) -> SrvRecord {
+    SrvRecord {
+        priority,
+        weight,
+        port,
+        target: target.into(),
+    }
+}
+
+fn example_dns() -> StaticDns {
+    StaticDns::new()
+        .add_naptr(
+            "example.com",
+            naptr(90, "SIP+D2U", "_sip._udp.example.com."),
+        )
+        .add_naptr(
+            "example.com",
+            naptr(50, "SIP+D2T", "_sip._tcp.example.com."),
+        )
+        .add_naptr(
+            "example.com",
+            naptr(40, "SIPS+D2T", "_sips._tcp.example.com."),
+        )
+        .add_naptr("example.com", naptr(30, "SIP+D2X", "_sip._x.example.com."))
+        .add_srv("_sip._udp.example.com", srv(0, 0, 5060, "a.example.com."))
+        .add_srv("_sip._tcp.example.com", srv(1, 0, 5060, "a.example.com."))
+        .add_srv("_sip._tcp.example.com", srv(0, 0, 5070, "b.example.com."))
+        .add_srv("_sips._tcp.example.com", srv(0, 0, 5061, "b.example.com."))
+        .add_ip("a.example.com", "192.0.2.1".parse().unwrap())
+        .add_ip("b.example.com", "192.0.2.2".parse().unwrap())
+        .add_ip("b.example.com", "2001:db8::2".parse().unwrap())
+}
+
+#[test]
+fn naptr_srv_address() {
+    let dns = example_dns();
+    assert_eq!(
+        vec![
+            target(Transport::Tls, "192.0.2.2:5061"),
+            target(Transport::Tls, "[2001:db8::2]:5061"),
+            target(Transport::Tcp, "192.0.2.2:5070"),
+            target(Transport::Tcp, "[2001:db8::2]:5070"),
+            target(Transport::Tcp, "192.0.2.1:5060"),
+            target(Transport::Udp, "192.0.2.1:5060"),
+        ],
+        resolve(&uri("sip:bob@example.com"), &dns).unwrap()
+    );
+    assert_eq!(
+        vec![
+            target(Transport::Tls, "192.0.2.2:5061"),
+            target(Transport::Tls, "[2001:db8::2]:5061"),
+        ],
+        resolve(&uri("sips:bob@example.com"), &dns).unwrap()
+    );
+}
+
+#[test]
+fn transport_param() {
+    let dns = example_dns();
+    assert_eq!(
+        vec![target(Transport::Udp, "192.0.2.1:5060")],
+        resolve(&uri("sip:bob@example.com;transport=udp"), &dns).unwrap()
+    );
+
+    let dns = StaticDns::new().add_ip("example.org", "192.0.2.3".parse().unwrap());
+    assert_eq!(
+        vec![target(Transport::Tcp, "192.0.2.3:5060")],
+        resolve(&uri("sip:bob@example.org;transport=tcp"), &dns).unwrap()
+    );
+    assert_eq!(
+        vec![target(Transport::Tls, "192.0.2.3:5061")],
+        resolve(&uri("sips:bob@example.org;transport=tcp"), &dns).unwrap()
+    );
+}
+
+#[test]
+fn srv_without_naptr() {
+    let dns = StaticDns::new()
+        .add_srv("_sip._udp.example.org", srv(0, 10, 5060, "a.example.org"))
+        .add_srv("_sip._udp.example.org", srv(0, 60, 5062, "b.example.org"))
+        .add_srv("_sip._udp.example.org", srv(1, 0, 5060, "."))
+        .add_ip("a.example.org", "192.0.2.1".parse().unwrap())
+        .add_ip("b.example.org", "192.0.2.2".parse().unwrap());
+    assert_eq!(
+        vec![
+            target(Transport::Udp, "192.0.2.2:5062"),
+            target(Transport::Udp, "192.0.2.1:5060"),
+        ],
+        resolve(&uri("sip:bob@example.org"), &dns).unwrap()
+    );
+}
+
+#[test]
+fn addresses() {
+    let dns = StaticDns::new().add_ip("example.org", "192.0.2.3".parse().unwrap());
+    assert_eq!(
+        vec![target(Transport::Udp, "192.0.2.3:5060")],
+        resolve(&uri("sip:bob@example.org"), &dns).unwrap()
+    );
+    assert_eq!(
+        vec![target(Transport::Tls, "192.0.2.3:5061")],
+        resolve(&uri("sips:bob@example.org"), &dns).unwrap()
+    );
+    assert_eq!(
+        vec![target(Transport::Udp, "192.0.2.3:5080")],
+        resolve(
+            &uri("sip:bob@example.org:5080"),
+            &example_dns().add_ip("example.org", "192.0.2.3".parse().unwrap())
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        vec![target(Transport::Tcp, "192.0.2.9:5060")],
+        resolve(&uri("sip:bob@192.0.2.9;transport=tcp"), &dns).unwrap()
+    );
+    assert_eq!(
+        vec![target(Transport::Udp, "192.0.2.3:5060")],
+        resolve(&uri("sip:bob@192.0.2.9;maddr=example.org"), &dns).unwrap()
+    );
+
+    let error = resolve(&uri("sip:bob@example.net"), &dns).unwrap_err();
+    assert_eq!(ErrorKind::NotFound, error.kind());
+
+    let error = resolve(&Uri::absolute("urn", "service:sos"), &dns).unwrap_err();
+    assert_eq!(ErrorKind::InvalidInput, error.kind());
+}
+
+#[test]
+fn system() {
+    assert_eq!(
+        vec![target(Transport::Udp, "127.0.0.1:5060")],
+        resolve(&uri("sip:bob@127.0.0.1"), &SystemDns).unwrap()
+    );
+}
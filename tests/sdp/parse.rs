@@ -0,0 +1,134 @@
+use libsip::sdp::{
+    parse::{parse_attribute, parse_media},
+    *,
+};
+
+use nom::error::VerboseError;
+
+const OFFER: &str = "v=0\r\n\
+    o=jdoe 2890844526 2890842807 IN IP4 10.47.16.5\r\n\
+    s=SDP Seminar\r\n\
+    i=A Seminar on the session description protocol\r\n\
+    u=http://www.example.com/seminars/sdp.pdf\r\n\
+    e=j.doe@example.com (Jane Doe)\r\n\
+    c=IN IP4 224.2.17.12/127\r\n\
+    b=AS:128\r\n\
+    t=2873397496 2873404696\r\n\
+    a=recvonly\r\n\
+    m=audio 49170 RTP/AVP 0\r\n\
+    m=video 51372/2 RTP/AVP 99\r\n\
+    c=IN IP4 224.2.17.13/127\r\n\
+    a=rtpmap:99 h263-1998/90000\r\n";
+
+#[test]
+fn read() {
+    let sdp = SessionDescription::parse(OFFER.as_bytes()).unwrap();
+    assert_eq!(0, sdp.version);
+    assert_eq!(
+        Origin {
+            username: "jdoe".into(),
+            session_id: 2890844526,
+            session_version: 2890842807,
+            net_type: "IN".into(),
+            addr_type: "IP4".into(),
+            address: "10.47.16.5".into(),
+        },
+        sdp.origin
+    );
+    assert_eq!("SDP Seminar", sdp.session_name);
+    assert_eq!(vec!["j.doe@example.com (Jane Doe)".to_string()], sdp.emails);
+    assert_eq!(
+        vec![Bandwidth {
+            bw_type: "AS".into(),
+            bandwidth: 128
+        }],
+        sdp.bandwidths
+    );
+    assert_eq!(
+        vec![Timing {
+            start: 2873397496,
+            stop: 2873404696
+        }],
+        sdp.timing
+    );
+    assert_eq!(Some(""), sdp.attribute("recvonly"));
+
+    assert_eq!(2, sdp.media.len());
+    let audio = &sdp.media[0];
+    assert_eq!(
+        ("audio", 49170, None),
+        (audio.media.as_str(), audio.port, audio.num_ports)
+    );
+    assert_eq!(
+        Some("224.2.17.12/127"),
+        sdp.media_connection(audio).map(|c| c.address.as_str())
+    );
+    let video = &sdp.media[1];
+    assert_eq!(Some(2), video.num_ports);
+    assert_eq!(vec!["99".to_string()], video.formats);
+    assert_eq!(Some("h263-1998/90000"), video.rtpmap("99"));
+    assert_eq!(
+        Some("224.2.17.13/127"),
+        sdp.media_connection(video).map(|c| c.address.as_str())
+    );
+}
+
+#[test]
+fn write() {
+    let sdp = SessionDescription::parse(OFFER.as_bytes()).unwrap();
+    assert_eq!(OFFER, format!("{}", sdp));
+}
+
+#[test]
+fn line_feeds() {
+    let sdp =
+        SessionDescription::parse(b"v=0\no=- 1 1 IN IP6 ::1\ns=-\nt=0 0\nm=audio 0 RTP/AVP 0")
+            .unwrap();
+    assert_eq!("::1", sdp.origin.address);
+    assert_eq!(0, sdp.media[0].port);
+}
+
+#[test]
+fn invalid() {
+    assert!(SessionDescription::parse(b"o=- 1 1 IN IP4 0.0.0.0\r\ns=-\r\n").is_err());
+    let error = SessionDescription::parse(
+        b"v=0\r\no=- 1 1 IN IP4 0.0.0.0\r\ns=-\r\nt=0 0\r\nm=audio port RTP/AVP 0\r\n",
+    )
+    .unwrap_err();
+    assert_eq!(
+        "invalid session description line: m=audio port RTP/AVP 0",
+        error.to_string()
+    );
+}
+
+#[test]
+fn audio_offer() {
+    let offer = SessionDescription::audio_offer("2001:db8::1".parse().unwrap(), 4000);
+    let connection = offer.connection.as_ref().unwrap();
+    assert_eq!("IP6", connection.addr_type);
+    assert_eq!("2001:db8::1", connection.address);
+    let audio = &offer.media[0];
+    assert_eq!(vec!["0", "8", "101"], audio.formats);
+    assert_eq!(Some("telephone-event/8000"), audio.rtpmap("101"));
+    assert_eq!(Some(""), audio.attribute("sendrecv"));
+}
+
+#[test]
+fn attributes() {
+    assert_eq!(
+        Ok((&b""[..], Attribute::Value("fmtp".into(), "101 0-15".into()))),
+        parse_attribute::<VerboseError<&[u8]>>(b"fmtp:101 0-15")
+    );
+    assert_eq!(
+        Ok((&b""[..], Attribute::Property("sendonly".into()))),
+        parse_attribute::<VerboseError<&[u8]>>(b"sendonly")
+    );
+}
+
+#[test]
+fn media() {
+    let (_, media) = parse_media::<VerboseError<&[u8]>>(b"audio 49170 RTP/SAVP 0 8").unwrap();
+    assert_eq!("RTP/SAVP", media.protocol);
+    assert_eq!(vec!["0", "8"], media.formats);
+    assert_eq!("m=audio 49170 RTP/SAVP 0 8\r\n", format!("{}", media));
+}
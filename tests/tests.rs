@@ -1,3 +1,14 @@
+mod client;
 mod core;
 mod headers;
+#[cfg(any(
+    feature = "transport-udp",
+    feature = "transport-tcp",
+    feature = "transport-ws"
+))]
+mod net;
+mod proxy;
+mod resolve;
+mod sdp;
+mod transaction;
 mod uri;
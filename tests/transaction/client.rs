@@ -0,0 +1,194 @@
+use libsip::{
+    transaction::{ClientState, ClientTransaction, Timer, TransactionAction, T1, T2, T4},
+    *,
+};
+
+use std::time::Duration;
+
+fn request(method: &str) -> SipMessage {
+    SipMessage::parse(
+        format!(
+            "{} sip:bob@example.com SIP/2.0\r\n\
+            Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+            Max-Forwards: 70\r\n\
+            From: <sip:alice@example.com>;tag=1928301774\r\n\
+            To: <sip:bob@example.com>\r\n\
+            Call-ID: a84b4c76e66710\r\n\
+            CSeq: 1 {}\r\n\
+            Content-Length: 0\r\n\r\n",
+            method, method
+        )
+        .as_bytes(),
+    )
+    .unwrap()
+}
+
+fn response(code: u32, method: &str) -> SipMessage {
+    SipMessage::parse(
+        format!(
+            "SIP/2.0 {} Reason\r\n\
+            Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+            From: <sip:alice@example.com>;tag=1928301774\r\n\
+            To: <sip:bob@example.com>;tag=a6c85cf\r\n\
+            Call-ID: a84b4c76e66710\r\n\
+            CSeq: 1 {}\r\n\
+            Content-Length: 0\r\n\r\n",
+            code, method
+        )
+        .as_bytes(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn invite_retransmit() {
+    let invite = request("INVITE");
+    let mut transaction = ClientTransaction::new(invite.clone(), false);
+    assert_eq!(ClientState::Calling, transaction.state());
+    assert_eq!(
+        vec![
+            TransactionAction::Send(invite.clone()),
+            TransactionAction::StartTimer(Timer::A, T1),
+            TransactionAction::StartTimer(Timer::B, T1 * 64),
+        ],
+        transaction.start()
+    );
+    assert_eq!(
+        vec![
+            TransactionAction::Send(invite.clone()),
+            TransactionAction::StartTimer(Timer::A, T1 * 2),
+        ],
+        transaction.on_timer(Timer::A)
+    );
+    assert_eq!(
+        vec![
+            TransactionAction::Send(invite),
+            TransactionAction::StartTimer(Timer::A, T1 * 4),
+        ],
+        transaction.on_timer(Timer::A)
+    );
+
+    let ringing = response(180, "INVITE");
+    assert_eq!(
+        vec![TransactionAction::PassToTu(ringing.clone())],
+        transaction.on_response(ringing)
+    );
+    assert_eq!(ClientState::Proceeding, transaction.state());
+    assert!(transaction.on_timer(Timer::A).is_empty());
+    assert!(transaction.on_timer(Timer::B).is_empty());
+
+    let ok = response(200, "INVITE");
+    assert_eq!(
+        vec![
+            TransactionAction::PassToTu(ok.clone()),
+            TransactionAction::Terminated,
+        ],
+        transaction.on_response(ok)
+    );
+    assert_eq!(ClientState::Terminated, transaction.state());
+}
+
+#[test]
+fn invite_failure() {
+    let mut transaction = ClientTransaction::new(request("INVITE"), false);
+    transaction.start();
+    let busy = response(486, "INVITE");
+    let actions = transaction.on_response(busy.clone());
+    let ack = transaction.ack().cloned().unwrap();
+    assert_eq!(Some(&Method::Ack), ack.method());
+    assert_eq!(Some((1, &Method::Ack)), ack.cseq());
+    assert_eq!(
+        vec![
+            TransactionAction::Send(ack.clone()),
+            TransactionAction::PassToTu(busy.clone()),
+            TransactionAction::StartTimer(Timer::D, Duration::from_secs(32)),
+        ],
+        actions
+    );
+    assert_eq!(ClientState::Completed, transaction.state());
+    assert_eq!(
+        vec![TransactionAction::Send(ack)],
+        transaction.on_response(busy)
+    );
+    assert_eq!(
+        vec![TransactionAction::Terminated],
+        transaction.on_timer(Timer::D)
+    );
+
+    let mut transaction = ClientTransaction::new(request("INVITE"), true);
+    assert_eq!(2, transaction.start().len());
+    let actions = transaction.on_response(response(486, "INVITE"));
+    assert_eq!(Some(&TransactionAction::Terminated), actions.last());
+}
+
+#[test]
+fn invite_timeout() {
+    let mut transaction = ClientTransaction::new(request("INVITE"), false);
+    transaction.start();
+    assert_eq!(
+        vec![TransactionAction::Timeout, TransactionAction::Terminated],
+        transaction.on_timer(Timer::B)
+    );
+    assert!(transaction.on_response(response(200, "INVITE")).is_empty());
+}
+
+#[test]
+fn non_invite() {
+    let options = request("OPTIONS");
+    let mut transaction = ClientTransaction::new(options.clone(), false);
+    assert_eq!(ClientState::Trying, transaction.state());
+    transaction.start();
+    let intervals: Vec<_> = (0..4)
+        .map(|_| match transaction.on_timer(Timer::E).pop() {
+            Some(TransactionAction::StartTimer(Timer::E, interval)) => interval,
+            other => panic!("unexpected action {:?}", other),
+        })
+        .collect();
+    assert_eq!(vec![T1 * 2, T1 * 4, T2, T2], intervals);
+
+    transaction.on_response(response(100, "OPTIONS"));
+    assert_eq!(ClientState::Proceeding, transaction.state());
+    assert_eq!(
+        vec![
+            TransactionAction::Send(options),
+            TransactionAction::StartTimer(Timer::E, T2),
+        ],
+        transaction.on_timer(Timer::E)
+    );
+
+    let ok = response(200, "OPTIONS");
+    assert_eq!(
+        vec![
+            TransactionAction::PassToTu(ok.clone()),
+            TransactionAction::StartTimer(Timer::K, T4),
+        ],
+        transaction.on_response(ok.clone())
+    );
+    assert!(transaction.on_response(ok).is_empty());
+    assert!(transaction.on_timer(Timer::E).is_empty());
+    assert_eq!(
+        vec![TransactionAction::Terminated],
+        transaction.on_timer(Timer::K)
+    );
+}
+
+#[test]
+fn non_invite_reliable() {
+    let options = request("OPTIONS");
+    let mut transaction = ClientTransaction::new(options.clone(), true);
+    assert_eq!(
+        vec![
+            TransactionAction::Send(options),
+            TransactionAction::StartTimer(Timer::F, T1 * 64),
+        ],
+        transaction.start()
+    );
+    assert_eq!(
+        vec![
+            TransactionAction::TransportError,
+            TransactionAction::Terminated,
+        ],
+        transaction.on_transport_error()
+    );
+    assert_eq!(ClientState::Terminated, transaction.state());
+}
@@ -0,0 +1,108 @@
+use libsip::{
+    transaction::{DialogId, TransactionKey},
+    *,
+};
+
+fn message(start: &str, branch: &str, cseq: &str, to_tag: &str) -> SipMessage {
+    SipMessage::parse(
+        format!(
+            "{}\r\n\
+            Via: SIP/2.0/UDP pc33.example.com:5060;branch={}\r\n\
+            From: <sip:alice@example.com>;tag=1928301774\r\n\
+            To: <sip:bob@example.com>{}\r\n\
+            Call-ID: a84b4c76e66710\r\n\
+            CSeq: {}\r\n\r\n",
+            start, branch, to_tag, cseq
+        )
+        .as_bytes(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn rfc3261_key() {
+    let invite = message(
+        "INVITE sip:bob@example.com SIP/2.0",
+        "z9hG4bK776",
+        "1 INVITE",
+        "",
+    );
+    let key = TransactionKey::from_message(&invite).unwrap();
+    assert_eq!(
+        TransactionKey::Rfc3261 {
+            branch: "z9hG4bK776".into(),
+            sent_by: domain!("pc33.example.com", 5060),
+            method: Method::Invite,
+        },
+        key
+    );
+
+    let response = message("SIP/2.0 486 Busy Here", "z9hG4bK776", "1 INVITE", ";tag=1");
+    assert_eq!(Some(&key), TransactionKey::from_message(&response).as_ref());
+    let ack = message(
+        "ACK sip:bob@example.com SIP/2.0",
+        "z9hG4bK776",
+        "1 ACK",
+        ";tag=1",
+    );
+    assert_eq!(Some(&key), TransactionKey::from_message(&ack).as_ref());
+    let cancel = message(
+        "CANCEL sip:bob@example.com SIP/2.0",
+        "z9hG4bK776",
+        "1 CANCEL",
+        "",
+    );
+    assert_ne!(Some(&key), TransactionKey::from_message(&cancel).as_ref());
+    let other = message(
+        "INVITE sip:bob@example.com SIP/2.0",
+        "z9hG4bK777",
+        "1 INVITE",
+        "",
+    );
+    assert_ne!(Some(&key), TransactionKey::from_message(&other).as_ref());
+}
+
+#[test]
+fn rfc2543_key() {
+    let invite = message("INVITE sip:bob@example.com SIP/2.0", "1234", "1 INVITE", "");
+    let key = TransactionKey::from_message(&invite).unwrap();
+    assert!(matches!(key, TransactionKey::Rfc2543 { cseq: 1, .. }));
+    assert_eq!(Some(key), TransactionKey::from_message(&invite.clone()));
+
+    let next = message("INVITE sip:bob@example.com SIP/2.0", "1234", "2 INVITE", "");
+    assert_ne!(
+        TransactionKey::from_message(&invite),
+        TransactionKey::from_message(&next)
+    );
+
+    let missing = SipMessage::parse(b"OPTIONS sip:bob@example.com SIP/2.0\r\n\r\n").unwrap();
+    assert_eq!(None, TransactionKey::from_message(&missing));
+}
+
+#[test]
+fn dialog_id() {
+    let bye = message(
+        "BYE sip:bob@example.com SIP/2.0",
+        "z9hG4bK1",
+        "2 BYE",
+        ";tag=a6c85cf",
+    );
+    let id = DialogId {
+        call_id: "a84b4c76e66710".into(),
+        local_tag: "a6c85cf".into(),
+        remote_tag: "1928301774".into(),
+    };
+    assert_eq!(Some(id.clone()), DialogId::incoming(&bye));
+
+    let ok = message("SIP/2.0 200 OK", "z9hG4bK1", "2 BYE", ";tag=a6c85cf");
+    assert_eq!(Some(id), DialogId::outgoing(&ok));
+    assert_eq!(DialogId::outgoing(&bye), DialogId::incoming(&ok));
+
+    let invite = message(
+        "INVITE sip:bob@example.com SIP/2.0",
+        "z9hG4bK1",
+        "1 INVITE",
+        "",
+    );
+    assert_eq!(None, DialogId::incoming(&invite));
+}
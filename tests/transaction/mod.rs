@@ -0,0 +1,4 @@
+mod client;
+mod key;
+mod server;
+mod timer;
@@ -0,0 +1,170 @@
+use libsip::{
+    transaction::{ServerState, ServerTransaction, Timer, TransactionAction, T1, T2, T4},
+    *,
+};
+
+use std::time::Duration;
+
+fn request(method: &str) -> SipMessage {
+    SipMessage::parse(
+        format!(
+            "{} sip:bob@example.com SIP/2.0\r\n\
+            Via: SIP/2.0/UDP pc33.example.com;branch=z9hG4bK776asdhds\r\n\
+            Max-Forwards: 70\r\n\
+            From: <sip:alice@example.com>;tag=1928301774\r\n\
+            To: <sip:bob@example.com>\r\n\
+            Call-ID: a84b4c76e66710\r\n\
+            CSeq: 1 {}\r\n\
+            Content-Length: 0\r\n\r\n",
+            method, method
+        )
+        .as_bytes(),
+    )
+    .unwrap()
+}
+
+fn response(request: &SipMessage, code: u32) -> SipMessage {
+    ResponseGenerator::reply_to(request, code).build().unwrap()
+}
+
+#[test]
+fn invite_trying() {
+    let invite = request("INVITE");
+    let mut transaction = ServerTransaction::new(invite.clone(), false);
+    assert_eq!(ServerState::Proceeding, transaction.state());
+    assert_eq!(
+        vec![
+            TransactionAction::PassToTu(invite.clone()),
+            TransactionAction::StartTimer(Timer::Trying, Duration::from_millis(200)),
+        ],
+        transaction.start()
+    );
+    assert!(transaction.on_request(invite.clone()).is_empty());
+
+    let actions = transaction.on_timer(Timer::Trying);
+    let trying = transaction.last_response().cloned().unwrap();
+    assert_eq!(Some(100), trying.status_code());
+    assert_eq!(None, trying.to().unwrap().tag());
+    assert_eq!(vec![TransactionAction::Send(trying.clone())], actions);
+    assert_eq!(
+        vec![TransactionAction::Send(trying)],
+        transaction.on_request(invite.clone())
+    );
+
+    let ok = response(&invite, 200);
+    assert_eq!(
+        vec![
+            TransactionAction::Send(ok.clone()),
+            TransactionAction::Terminated,
+        ],
+        transaction.send_response(ok)
+    );
+    assert_eq!(ServerState::Terminated, transaction.state());
+}
+
+#[test]
+fn invite_failure() {
+    let invite = request("INVITE");
+    let mut transaction = ServerTransaction::new(invite.clone(), false);
+    transaction.start();
+    let ringing = response(&invite, 180);
+    transaction.send_response(ringing);
+    assert!(transaction.on_timer(Timer::Trying).is_empty());
+
+    let busy = response(&invite, 486);
+    assert_eq!(
+        vec![
+            TransactionAction::Send(busy.clone()),
+            TransactionAction::StartTimer(Timer::G, T1),
+            TransactionAction::StartTimer(Timer::H, T1 * 64),
+        ],
+        transaction.send_response(busy.clone())
+    );
+    assert_eq!(ServerState::Completed, transaction.state());
+    assert_eq!(
+        vec![TransactionAction::Send(busy.clone())],
+        transaction.on_request(invite)
+    );
+    let intervals: Vec<_> = (0..4)
+        .map(|_| match transaction.on_timer(Timer::G).pop() {
+            Some(TransactionAction::StartTimer(Timer::G, interval)) => interval,
+            other => panic!("unexpected action {:?}", other),
+        })
+        .collect();
+    assert_eq!(vec![T1 * 2, T1 * 4, T2, T2], intervals);
+
+    let ack = RequestGenerator::ack_of(transaction.request(), &busy)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        vec![TransactionAction::StartTimer(Timer::I, T4)],
+        transaction.on_request(ack.clone())
+    );
+    assert_eq!(ServerState::Confirmed, transaction.state());
+    assert!(transaction.on_request(ack).is_empty());
+    assert_eq!(
+        vec![TransactionAction::Terminated],
+        transaction.on_timer(Timer::I)
+    );
+}
+
+#[test]
+fn invite_no_ack() {
+    let invite = request("INVITE");
+    let mut transaction = ServerTransaction::new(invite.clone(), true);
+    transaction.start();
+    let decline = response(&invite, 603);
+    assert_eq!(
+        vec![
+            TransactionAction::Send(decline.clone()),
+            TransactionAction::StartTimer(Timer::H, T1 * 64),
+        ],
+        transaction.send_response(decline)
+    );
+    assert_eq!(
+        vec![TransactionAction::Timeout, TransactionAction::Terminated],
+        transaction.on_timer(Timer::H)
+    );
+}
+
+#[test]
+fn non_invite() {
+    let options = request("OPTIONS");
+    let mut transaction = ServerTransaction::new(options.clone(), false);
+    assert_eq!(ServerState::Trying, transaction.state());
+    assert_eq!(
+        vec![TransactionAction::PassToTu(options.clone())],
+        transaction.start()
+    );
+    assert!(transaction.on_request(options.clone()).is_empty());
+    assert!(transaction.on_timer(Timer::Trying).is_empty());
+
+    let trying = response(&options, 100);
+    transaction.send_response(trying.clone());
+    assert_eq!(ServerState::Proceeding, transaction.state());
+    assert_eq!(
+        vec![TransactionAction::Send(trying)],
+        transaction.on_request(options.clone())
+    );
+
+    let ok = response(&options, 200);
+    assert_eq!(
+        vec![
+            TransactionAction::Send(ok.clone()),
+            TransactionAction::StartTimer(Timer::J, T1 * 64),
+        ],
+        transaction.send_response(ok.clone())
+    );
+    assert!(transaction
+        .send_response(response(&options, 500))
+        .is_empty());
+    assert_eq!(
+        vec![TransactionAction::Send(ok)],
+        transaction.on_request(options)
+    );
+    assert_eq!(
+        vec![TransactionAction::Terminated],
+        transaction.on_timer(Timer::J)
+    );
+}
@@ -0,0 +1,73 @@
+use libsip::{
+    transaction::{
+        ClientTransaction, SipTimers, Timer, TimerScheduler, TransactionAction, T1, T2, T4,
+    },
+    SipMessage,
+};
+
+use std::time::{Duration, Instant};
+
+#[test]
+fn timer_durations() {
+    let timers = SipTimers::default();
+    assert_eq!(T1, timers.duration(Timer::A));
+    assert_eq!(T1 * 64, timers.duration(Timer::B));
+    assert_eq!(Duration::from_secs(32), timers.duration(Timer::D));
+    assert_eq!(T1 * 64, timers.duration(Timer::J));
+    assert_eq!(T4, timers.duration(Timer::I));
+
+    let timers = SipTimers::new()
+        .t1(Duration::from_secs(1))
+        .t2(Duration::from_secs(8));
+    assert_eq!(Duration::from_secs(64), timers.duration(Timer::D));
+    assert_eq!(Duration::from_secs(8), timers.t2);
+    assert_ne!(T2, timers.t2);
+}
+
+#[test]
+fn transaction_timers() {
+    let request = SipMessage::parse(b"OPTIONS sip:bob@example.com SIP/2.0\r\n\r\n").unwrap();
+    let timers = SipTimers::new().t1(Duration::from_millis(100));
+    let mut transaction = ClientTransaction::new(request.clone(), false).timers(timers);
+    assert_eq!(
+        vec![
+            TransactionAction::Send(request),
+            TransactionAction::StartTimer(Timer::E, Duration::from_millis(100)),
+            TransactionAction::StartTimer(Timer::F, Duration::from_millis(6400)),
+        ],
+        transaction.start()
+    );
+}
+
+#[test]
+fn scheduler() {
+    let now = Instant::now();
+    let mut scheduler = TimerScheduler::new();
+    assert_eq!(None, scheduler.next_deadline());
+    scheduler.schedule((1, Timer::F), now, Duration::from_secs(32));
+    scheduler.schedule((2, Timer::E), now, Duration::from_secs(1));
+    scheduler.schedule((1, Timer::E), now, Duration::from_secs(2));
+    assert_eq!(3, scheduler.len());
+    assert_eq!(Some(Duration::from_secs(1)), scheduler.next_timeout(now));
+
+    scheduler.schedule((2, Timer::E), now, Duration::from_secs(4));
+    assert_eq!(3, scheduler.len());
+    assert_eq!(
+        Some(now + Duration::from_secs(2)),
+        scheduler.next_deadline()
+    );
+
+    assert!(scheduler.poll(now).is_empty());
+    assert_eq!(
+        vec![(1, Timer::E), (2, Timer::E)],
+        scheduler.poll(now + Duration::from_secs(5))
+    );
+    assert_eq!(
+        Some(Duration::from_secs(0)),
+        scheduler.next_timeout(now + Duration::from_secs(40))
+    );
+
+    assert!(!scheduler.cancel(&(2, Timer::E)));
+    scheduler.cancel_where(|(id, _)| *id == 1);
+    assert!(scheduler.is_empty());
+}
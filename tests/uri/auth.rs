@@ -15,3 +15,28 @@ fn read_auth() {
         parse_uriauth::<VerboseError<&[u8]>>(b"username:password@")
     );
 }
+
+#[test]
+fn escaped_auth() {
+    let remains = vec![];
+    assert_eq!(
+        Ok((remains.as_ref(), uri_auth!("user:name", "pass:word"))),
+        parse_uriauth::<VerboseError<&[u8]>>(b"user%3Aname:pass%3Aword@")
+    );
+    assert_eq!(
+        "user%3Aname:pass%3Aword",
+        format!("{}", uri_auth!("user:name", "pass:word"))
+    );
+}
+
+#[test]
+fn redact_password() {
+    let auth = uri_auth!("alice", "secret").redact_password();
+    assert_eq!("alice", format!("{}", auth));
+    assert_eq!(Some("secret".to_string()), auth.password);
+
+    let uri = Uri::sip(domain!("example.com"))
+        .auth(uri_auth!("alice", "secret"))
+        .redact_password();
+    assert_eq!("sip:alice@example.com", format!("{}", uri));
+}
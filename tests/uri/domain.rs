@@ -1,7 +1,7 @@
 use libsip::{uri::parse_domain, *};
 use nom::error::VerboseError;
 
-use std::net::Ipv4Addr;
+use std::net::{Ipv4Addr, Ipv6Addr};
 
 #[test]
 fn read_domain() {
@@ -54,3 +54,42 @@ fn write_ip_address() {
     let domain = Domain::Ipv4(Ipv4Addr::new(10, 1, 10, 1), Some(8080));
     assert_eq!("10.1.10.1:8080".to_string(), format!("{}", domain));
 }
+
+#[test]
+fn ipv6_address() {
+    let addr: Ipv6Addr = "2001:db8::1".parse().unwrap();
+    let remains = vec![b' '];
+    assert_eq!(
+        Ok((remains.as_ref(), Domain::Ipv6(addr, None))),
+        parse_domain::<VerboseError<&[u8]>>(b"[2001:db8::1] ")
+    );
+    assert_eq!(
+        Ok((remains.as_ref(), Domain::Ipv6(addr, Some(5060)))),
+        parse_domain::<VerboseError<&[u8]>>(b"[2001:db8::1]:5060 ")
+    );
+    assert!(parse_domain::<VerboseError<&[u8]>>(b"[2001:db8::zz] ").is_err());
+
+    assert_eq!("[2001:db8::1]", format!("{}", Domain::Ipv6(addr, None)));
+    assert_eq!(
+        "[2001:db8::1]:5060",
+        format!("{}", Domain::Ipv6(addr, Some(5060)))
+    );
+}
+
+#[test]
+fn host_and_port() {
+    let mut domain = domain!("example.com", 8080);
+    assert_eq!(Host::Domain("example.com".into()), domain.host());
+    assert_eq!(Some(8080), domain.port());
+    domain.set_port(None);
+    assert_eq!(domain!("example.com"), domain);
+
+    let addr: Ipv6Addr = "2001:db8::1".parse().unwrap();
+    let domain = Domain::new(Host::Ipv6(addr), Some(5060));
+    assert_eq!(Domain::Ipv6(addr, Some(5060)), domain);
+    assert_eq!("[2001:db8::1]", format!("{}", domain.host()));
+    assert_eq!(
+        Host::Ipv4(Ipv4Addr::new(10, 1, 10, 1)),
+        ip_domain!(10, 1, 10, 1).host()
+    );
+}
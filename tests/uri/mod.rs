@@ -1,3 +1,4 @@
 mod auth;
 mod domain;
+#[allow(clippy::module_inception)]
 mod uri;
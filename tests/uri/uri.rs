@@ -125,3 +125,241 @@ fn write_uri() {
         format!("{}", uri)
     );
 }
+
+#[test]
+fn uri_headers() {
+    let expected_remains = vec![b'>'];
+    let expected = Uri::sip(domain!("example.org"))
+        .auth(uri_auth!("bob"))
+        .header(
+            "Replaces",
+            "12345@192.168.118.3;to-tag=12345;from-tag=5FFE-3994",
+        );
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected.clone())),
+        parse_uri::<VerboseError<&[u8]>>(
+            b"sip:bob@example.org?Replaces=12345%40192.168.118.3%3Bto-tag%3D12345%3Bfrom-tag%3D5FFE-3994>"
+        )
+    );
+    assert_eq!(
+        "sip:bob@example.org?Replaces=12345%40192.168.118.3%3Bto-tag%3D12345%3Bfrom-tag%3D5FFE-3994",
+        format!("{}", expected)
+    );
+    assert_eq!(
+        Some("12345@192.168.118.3;to-tag=12345;from-tag=5FFE-3994"),
+        expected.header_value("replaces")
+    );
+
+    let expected = Uri::sip(domain!("example.org"))
+        .parameter(UriParam::Transport(Transport::Tcp))
+        .header("subject", "project x")
+        .header("priority", "urgent");
+    assert_eq!(
+        "sip:example.org;transport=TCP?subject=project%20x&priority=urgent",
+        format!("{}", expected)
+    );
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected)),
+        parse_uri::<VerboseError<&[u8]>>(
+            b"sip:example.org;transport=TCP?subject=project%20x&priority=urgent>"
+        )
+    );
+}
+
+#[test]
+fn uri_schemas() {
+    let expected_remains = vec![b' '];
+    let expected = Uri::sips(domain!("example.com")).auth(uri_auth!("alice"));
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected.clone())),
+        parse_uri::<VerboseError<&[u8]>>(b"sips:alice@example.com ")
+    );
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected.clone())),
+        parse_uri::<VerboseError<&[u8]>>(b"SIPS:alice@example.com ")
+    );
+    assert_eq!("sips:alice@example.com", format!("{}", expected));
+
+    let expected = Uri::sip(domain!("example.com")).auth(uri_auth!("alice"));
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected)),
+        parse_uri::<VerboseError<&[u8]>>(b"SIP:alice@example.com ")
+    );
+}
+
+#[test]
+fn tel_uri() {
+    let expected_remains = vec![b'>'];
+    let expected = Uri::tel("+1-212-555-0101");
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected.clone())),
+        parse_uri::<VerboseError<&[u8]>>(b"tel:+1-212-555-0101>")
+    );
+    assert_eq!("tel:+1-212-555-0101", format!("{}", expected));
+    assert_eq!(Some("+12125550101".to_string()), expected.tel_number());
+    assert!(expected.is_global_number());
+
+    let expected = Uri::tel("7042").parameter(UriParam::Other(
+        "phone-context".into(),
+        Some("example.com".into()),
+    ));
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected.clone())),
+        parse_uri::<VerboseError<&[u8]>>(b"TEL:7042;phone-context=example.com>")
+    );
+    assert_eq!(
+        "tel:7042;phone-context=example.com",
+        format!("{}", expected)
+    );
+    assert!(!expected.is_global_number());
+
+    assert!(parse_uri::<VerboseError<&[u8]>>(b"tel:+>").is_err());
+    assert_eq!(None, Uri::sip(domain!("example.com")).tel_number());
+}
+
+#[test]
+fn ipv6_uri() {
+    let expected_remains = vec![b'>'];
+    let host = Domain::Ipv6("2001:db8::1".parse().unwrap(), Some(5060));
+    let expected = Uri::sip(host).auth(uri_auth!("alice"));
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected.clone())),
+        parse_uri::<VerboseError<&[u8]>>(b"sip:alice@[2001:db8::1]:5060>")
+    );
+    assert_eq!("sip:alice@[2001:db8::1]:5060", format!("{}", expected));
+}
+
+#[test]
+fn uri_params() {
+    let expected_remains = vec![b'>'];
+    let expected = Uri::sip(domain!("example.com"))
+        .auth(uri_auth!("alice"))
+        .transport_param(Transport::Tcp)
+        .user_param("phone")
+        .lr_param()
+        .maddr_param(ip_domain!(239, 255, 255, 1))
+        .ttl_param(1)
+        .parameter(UriParam::Other("foo".into(), Some("bar".into())))
+        .header("subject", "Hello")
+        .header("priority", "urgent");
+    let input = b"sip:alice@example.com;transport=tcp;user=phone;lr;maddr=239.255.255.1;ttl=1;foo=bar?subject=Hello&priority=urgent>";
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected.clone())),
+        parse_uri::<VerboseError<&[u8]>>(input)
+    );
+    assert_eq!(
+        "sip:alice@example.com;transport=TCP;user=phone;lr;maddr=239.255.255.1;ttl=1;foo=bar?subject=Hello&priority=urgent",
+        format!("{}", expected)
+    );
+    assert_eq!(Some(Transport::Tcp), expected.transport());
+    assert_eq!(Some("phone"), expected.user_type());
+    assert!(expected.is_loose_route());
+    assert_eq!(Some(&ip_domain!(239, 255, 255, 1)), expected.maddr());
+    assert_eq!(Some(1), expected.ttl());
+    assert_eq!(Some("urgent"), expected.header_value("Priority"));
+
+    let uri = Uri::sip(domain!("example.com"))
+        .transport_param(Transport::Udp)
+        .transport_param(Transport::Tcp);
+    assert_eq!(vec![UriParam::Transport(Transport::Tcp)], uri.parameters);
+
+    let expected = Uri::sip(domain!("example.com"))
+        .parameter(UriParam::Transport(Transport::Sctp))
+        .parameter(UriParam::Other("ttl".into(), Some("300".into())));
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected)),
+        parse_uri::<VerboseError<&[u8]>>(b"sip:example.com;transport=sctp;ttl=300>")
+    );
+
+    let expected = Uri::sip(domain!("example.com"))
+        .parameter(UriParam::Other("transport".into(), Some("quic".into())));
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected)),
+        parse_uri::<VerboseError<&[u8]>>(b"sip:example.com;transport=quic>")
+    );
+}
+
+#[test]
+fn escaped_uri() {
+    let expected_remains = vec![b'>'];
+    let expected = Uri::sip(domain!("host")).auth(uri_auth!("alice;day=tue"));
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected.clone())),
+        parse_uri::<VerboseError<&[u8]>>(b"sip:%61lice;day=tue@host>")
+    );
+    assert_eq!("sip:alice;day=tue@host", format!("{}", expected));
+
+    let expected = Uri::sip(domain!("example.com"))
+        .auth(uri_auth!("+1 212", "p@ss:word"))
+        .parameter(UriParam::Other("x name".into(), Some("a;b".into())));
+    let written = "sip:+1%20212:p%40ss%3Aword@example.com;x%20name=a%3Bb";
+    assert_eq!(written, format!("{}", expected));
+    assert_eq!(
+        Ok((expected_remains.as_ref(), expected)),
+        parse_uri::<VerboseError<&[u8]>>(format!("{}>", written).as_bytes())
+    );
+}
+
+#[test]
+fn uri_port() {
+    assert_eq!(None, Uri::sip(domain!("example.com")).port());
+    assert_eq!(5060, Uri::sip(domain!("example.com")).port_or_default());
+    assert_eq!(5061, Uri::sips(domain!("example.com")).port_or_default());
+    assert_eq!(
+        5080,
+        Uri::sips(domain!("example.com", 5080)).port_or_default()
+    );
+    assert_eq!(
+        5061,
+        Uri::sip(domain!("example.com"))
+            .transport_param(Transport::Tls)
+            .port_or_default()
+    );
+    assert_eq!(
+        5060,
+        ViaHeader::new(domain!("example.com"), Transport::Tcp).port_or_default()
+    );
+    assert_eq!(
+        5061,
+        ViaHeader::new(domain!("example.com"), Transport::Tls).port_or_default()
+    );
+    assert_eq!(5061, Transport::Wss.default_port());
+    assert_eq!(5060, Transport::Ws.default_port());
+}
+
+#[test]
+fn uri_from_str() {
+    let uri: Uri = "sip:alice@example.com:5060;transport=tcp".parse().unwrap();
+    assert_eq!(
+        Uri::sip(domain!("example.com", 5060))
+            .auth(uri_auth!("alice"))
+            .transport_param(Transport::Tcp),
+        uri
+    );
+    assert!("sip:alice@example.com>".parse::<Uri>().is_err());
+}
+
+#[test]
+fn uri_builder() {
+    let uri = UriBuilder::sip("example.com")
+        .user("alice")
+        .password("secret")
+        .port(5061)
+        .parameter("transport", "tcp")
+        .parameter("foo", "bar")
+        .flag("lr")
+        .header("subject", "Hello")
+        .build();
+    assert_eq!(
+        Uri::sip(domain!("example.com", 5061))
+            .auth(uri_auth!("alice", "secret"))
+            .transport_param(Transport::Tcp)
+            .parameter(UriParam::Other("foo".into(), Some("bar".into())))
+            .lr_param()
+            .header("subject", "Hello"),
+        uri
+    );
+
+    let uri = UriBuilder::sips("10.1.10.1:5080").build();
+    assert_eq!(Uri::sips(ip_domain!(10, 1, 10, 1, 5080)), uri);
+}